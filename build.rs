@@ -0,0 +1,19 @@
+use std::process::Command;
+
+/// Makes the commit being built available to the About dialog as
+/// `MODAV_COMMIT`, if the source is a git checkout
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=MODAV_COMMIT={}", commit.trim());
+    }
+}
@@ -1,39 +1,67 @@
 use iced::{
-    application, font,
+    application, event, font,
     keyboard::{self, key, Key},
+    task,
+    theme::Palette,
     widget::{
-        self, button, checkbox, column, container, container::bordered_box, horizontal_space,
-        pick_list, row, text, text_input, vertical_rule, Container, Row, Space,
+        self, button, checkbox, column, container, horizontal_space, pick_list, progress_bar, row,
+        scrollable, slider, stack, text, text_input, vertical_rule, Column, Container, Row, Space,
     },
-    window, Alignment, Element, Font, Length, Size, Subscription, Task, Theme,
+    window, Alignment, Color, Element, Event, Font, Length, Size, Subscription, Task, Theme,
 };
 
 use tracing::{error, info, span, warn, Level};
 use tracing_subscriber::EnvFilter;
 
-use std::{fs::File, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 mod styles;
 use styles::*;
 
 mod utils;
-use utils::{icons, load_file, pick_file, save_file, tooltip, AppError};
+use utils::{
+    csv, icons, load_file, load_file_progress, load_stdin, open_url,
+    pdf::{self, PageSize},
+    pick_file, pick_save_path, png,
+    sampling::{format_count, RowLimit, SamplingStrategy},
+    save_bytes, save_file, svg, tooltip, watch_files, AppError, LoadEvent, Location,
+};
 
 mod views;
 use views::{
-    home_view, BarChartTabData, EditorTabData, LineTabData, Refresh, StackedBarChartTabData, Tabs,
-    TabsMessage, View, ViewType,
+    hex_color, home_view, line_offset,
+    offscreen::{self, Image},
+    render_view, BarChartTabData, BoxPlotTabData, ChartPreview, EditorTabData, FileType,
+    HistogramTabData, LineTabData, RadarTabData, Refresh, Session, SessionTab,
+    StackedBarChartTabData, TableTabData, Tabs, TabsMessage, View, ViewType,
 };
 
 pub mod widgets;
 use widgets::{
     modal::Modal,
     sidemenu::{Context, Menu, MenuSection, SideMenu},
-    style::dialog_container,
-    toast::{self, Status, Toast},
-    wizard::{BarChartConfigState, LineConfigState, StackedBarChartConfigState, Wizard},
+    style::{access_key, dialog_container, FloatingSurface, CONTEXT_LEVEL, MENU_LEVEL},
+    toast::{self, Status, Timeouts, Toast},
+    wizard::{
+        BarChartConfigState, BoxPlotConfigState, HistogramConfigState, LineConfigState,
+        PreviewConfig, RadarConfigState, StackedBarChartConfigState, Wizard,
+    },
 };
 
+/// Where the source of modav is hosted
+const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+/// Where the source of modav_core, the data backend, is hosted
+const CORE_REPOSITORY: &str = "https://github.com/EmmanuelDodoo/modav_core";
+
+/// How long the wizard's configuration has to stay unchanged before its
+/// preview is rebuilt
+const PREVIEW_DELAY: Duration = Duration::from_millis(300);
+
 const THEMES: [Theme; 7] = [
     Theme::TokyoNight,
     Theme::TokyoNightLight,
@@ -44,7 +72,461 @@ const THEMES: [Theme; 7] = [
     Theme::Nightfly,
 ];
 
+/// The settings key the toast timeout of `status` is stored under
+fn timeout_key(status: Status) -> &'static str {
+    match status {
+        Status::Info => "timeout_info",
+        Status::Success => "timeout_success",
+        Status::Warn => "timeout_warn",
+        Status::Error => "timeout_error",
+    }
+}
+
+fn is_custom(theme: &Theme) -> bool {
+    matches!(theme, Theme::Custom(_))
+}
+
+/// The colors of the user defined theme. Success and danger colors are
+/// those of the built in dark or light theme closest to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CustomPalette {
+    background: Color,
+    text: Color,
+    primary: Color,
+}
+
+impl CustomPalette {
+    const NAME: &'static str = "Custom";
+
+    fn theme(self) -> Theme {
+        let base = if is_dark(self.background) {
+            Palette::DARK
+        } else {
+            Palette::LIGHT
+        };
+
+        let palette = Palette {
+            background: self.background,
+            text: self.text,
+            primary: self.primary,
+            ..base
+        };
+
+        Theme::custom(Self::NAME.into(), palette)
+    }
+
+    fn get(&self, part: PaletteColor) -> Color {
+        match part {
+            PaletteColor::Background => self.background,
+            PaletteColor::Text => self.text,
+            PaletteColor::Primary => self.primary,
+        }
+    }
+
+    fn set(&mut self, part: PaletteColor, color: Color) {
+        match part {
+            PaletteColor::Background => self.background = color,
+            PaletteColor::Text => self.text = color,
+            PaletteColor::Primary => self.primary = color,
+        }
+    }
+}
+
+impl Default for CustomPalette {
+    fn default() -> Self {
+        let palette = Theme::TokyoNight.palette();
+
+        Self {
+            background: palette.background,
+            text: palette.text,
+            primary: palette.primary,
+        }
+    }
+}
+
+/// A color of a [`CustomPalette`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteColor {
+    Background,
+    Text,
+    Primary,
+}
+
+impl PaletteColor {
+    const ALL: [Self; 3] = [Self::Background, Self::Text, Self::Primary];
+
+    /// The settings key the color is stored under
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Background => "custom_background",
+            Self::Text => "custom_text",
+            Self::Primary => "custom_primary",
+        }
+    }
+}
+
+impl std::fmt::Display for PaletteColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Background => write!(f, "Background"),
+            Self::Text => write!(f, "Text"),
+            Self::Primary => write!(f, "Primary"),
+        }
+    }
+}
+
+fn is_dark(color: Color) -> bool {
+    0.299 * color.r + 0.587 * color.g + 0.114 * color.b < 0.5
+}
+
+/// The files to open on startup, taken from the command line
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Args {
+    /// Opens the files straight into this view instead of going through the wizard
+    view: Option<ViewType>,
+    files: Vec<PathBuf>,
+    /// Whether to read standard input into an editor tab, asked for with `-`
+    stdin: bool,
+}
+
+impl Args {
+    const USAGE: &'static str =
+        "Usage: modav [--line | --bar | --stacked | --table | --editor] [FILE]...
+       modav render --type TYPE [--x COLUMN] [--y COLUMNS] [--width PIXELS]
+                    [--height PIXELS] --out IMAGE FILE
+
+Opens each FILE on startup. CSV and JSON files go through the chart wizard
+unless a view is given, in which case they are opened with its defaults.
+A FILE of - reads standard input into an unsaved editor tab.
+
+Options:
+    --line       Open the files as line graphs
+    --bar        Open the files as bar charts
+    --stacked    Open the files as stacked bar charts
+    --box        Open the files as box plots
+    --histogram  Open the files as histograms
+    --radar      Open the files as radar charts
+    --table      Open the files as tables
+    --editor     Open the files in the editor
+    -h, --help   Print this message
+
+The render command writes a chart of FILE to IMAGE, a .png or .pdf, and
+exits without showing the app. TYPE is one of line, bar, stacked, box,
+histogram or radar. Columns are numbered from 0. --y takes a list such as
+1,2 or 1:4 for stacked bar and radar charts. --width and --height default
+to the image size in the settings.";
+
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut options = true;
+
+        for arg in args {
+            let view = match arg.as_str() {
+                "--" if options => {
+                    options = false;
+                    continue;
+                }
+                "--line" if options => ViewType::LineGraph,
+                "--bar" if options => ViewType::BarChart,
+                "--stacked" if options => ViewType::StackedBarChart,
+                "--box" if options => ViewType::BoxPlot,
+                "--histogram" if options => ViewType::Histogram,
+                "--radar" if options => ViewType::Radar,
+                "--table" if options => ViewType::Table,
+                "--editor" if options => ViewType::Editor,
+                "-" if options => {
+                    parsed.stdin = true;
+                    continue;
+                }
+                _ if options && arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("Unknown option: {arg}"))
+                }
+                _ => {
+                    parsed.files.push(PathBuf::from(arg));
+                    continue;
+                }
+            };
+
+            if parsed.view.is_some_and(|current| current != view) {
+                return Err("Only one view may be given".into());
+            }
+            parsed.view = Some(view);
+        }
+
+        Ok(parsed)
+    }
+
+    /// The tasks opening each file, run once the app is ready
+    fn open(self) -> Task<Message> {
+        let view = self.view;
+        let stdin = self
+            .stdin
+            .then(|| Task::perform(load_stdin(), Message::StdinLoaded));
+
+        Task::batch(
+            self.files
+                .into_iter()
+                .map(move |file| Task::done(Message::OpenArgument(view, file)))
+                .chain(stdin),
+        )
+    }
+}
+
+/// A chart to write to an image file without showing the app, taken from the
+/// `render` command
+#[derive(Debug, Clone, PartialEq)]
+struct RenderArgs {
+    view: ViewType,
+    x: Option<usize>,
+    y: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    out: PathBuf,
+    file: PathBuf,
+}
+
+impl RenderArgs {
+    const COMMAND: &'static str = "render";
+
+    /// Parses the arguments after the `render` command
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut view = None;
+        let mut x = None;
+        let mut y = None;
+        let mut width = None;
+        let mut height = None;
+        let mut out = None;
+        let mut file = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value =
+                |option: &str| args.next().ok_or_else(|| format!("{option} needs a value"));
+
+            match arg.as_str() {
+                "--type" => {
+                    let kind = value("--type")?;
+                    view = Some(match kind.as_str() {
+                        "line" => ViewType::LineGraph,
+                        "bar" => ViewType::BarChart,
+                        "stacked" => ViewType::StackedBarChart,
+                        "box" => ViewType::BoxPlot,
+                        "histogram" => ViewType::Histogram,
+                        "radar" => ViewType::Radar,
+                        _ => return Err(format!("Unknown chart type: {kind}")),
+                    });
+                }
+                "--x" => {
+                    let column = value("--x")?;
+                    x = Some(
+                        column
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("--x takes a column number, not {column}"))?,
+                    );
+                }
+                "--y" => y = Some(value("--y")?),
+                "--width" | "--height" => {
+                    let pixels = value(&arg)?;
+                    let pixels = pixels
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|pixels| (1..=offscreen::MAX_SIDE).contains(pixels))
+                        .ok_or_else(|| {
+                            format!(
+                                "{arg} takes a number of pixels from 1 to {}, not {pixels}",
+                                offscreen::MAX_SIDE
+                            )
+                        })?;
+
+                    if arg == "--width" {
+                        width = Some(pixels);
+                    } else {
+                        height = Some(pixels);
+                    }
+                }
+                "--out" => out = Some(PathBuf::from(value("--out")?)),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("Unknown option: {arg}"))
+                }
+                _ if file.is_some() => return Err("Only one file may be rendered".into()),
+                _ => file = Some(PathBuf::from(arg)),
+            }
+        }
+
+        let view = view.ok_or("A chart --type is needed")?;
+        let out = out.ok_or("An --out image is needed")?;
+        let file = file.ok_or("A file to render is needed")?;
+
+        if view == ViewType::LineGraph && (x.is_some() || y.is_some()) {
+            return Err("Line graphs plot every column, so they take no --x or --y".into());
+        }
+
+        if view == ViewType::Histogram && x.is_some() {
+            return Err("Histograms only take a --y column".into());
+        }
+
+        let args = Self {
+            view,
+            x,
+            y,
+            width,
+            height,
+            out,
+            file,
+        };
+        args.format()?;
+
+        Ok(args)
+    }
+
+    /// The format of the output, from its extension
+    fn format(&self) -> Result<ImageFormat, String> {
+        match self.out.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("png") => Ok(ImageFormat::Png),
+            Some(ext) if ext.eq_ignore_ascii_case("pdf") => Ok(ImageFormat::Pdf),
+            Some(ext) if ext.eq_ignore_ascii_case("svg") => Ok(ImageFormat::Svg),
+            _ => Err(format!(
+                "Cannot render to {}, only .png, .pdf and .svg files are supported",
+                self.out.display()
+            )),
+        }
+    }
+
+    /// Parses `--y` as a single column
+    fn y_col(&self) -> Result<Option<usize>, AppError> {
+        self.y
+            .as_ref()
+            .map(|column| {
+                column.trim().parse().map_err(|_| {
+                    AppError::Simple(format!("--y takes a single column number, not {column}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Builds the chart, using the default configuration for anything not
+    /// given
+    fn chart(&self) -> Result<View, AppError> {
+        let path = self.file.clone();
+
+        match self.view {
+            ViewType::BarChart => {
+                let mut config = BarChartConfigState::default();
+                config.x_col = self.x.unwrap_or(config.x_col);
+                config.y_col = self.y_col()?.unwrap_or(config.y_col);
+                BarChartTabData::new(path, config).map(View::BarChart)
+            }
+            ViewType::StackedBarChart => {
+                let mut config = StackedBarChartConfigState::default();
+                config.x_col = self.x.unwrap_or(config.x_col);
+                if let Some(columns) = self.y.clone() {
+                    config.acc_cols_str = columns;
+                }
+                StackedBarChartTabData::new(path, config).map(View::StackedBarChart)
+            }
+            ViewType::BoxPlot => {
+                let mut config = BoxPlotConfigState::default();
+                config.category_col = self.x.unwrap_or(config.category_col);
+                config.value_col = self.y_col()?.unwrap_or(config.value_col);
+                BoxPlotTabData::new(path, config).map(View::BoxPlot)
+            }
+            ViewType::Histogram => {
+                let mut config = HistogramConfigState::default();
+                config.column = self.y_col()?.unwrap_or(config.column);
+                HistogramTabData::new(path, config).map(View::Histogram)
+            }
+            ViewType::Radar => {
+                let mut config = RadarConfigState::default();
+                config.label_col = self.x.unwrap_or(config.label_col);
+                if let Some(columns) = self.y.clone() {
+                    config.metric_cols_str = columns;
+                }
+                RadarTabData::new(path, config).map(View::Radar)
+            }
+            _ => LineTabData::new(path, LineConfigState::default()).map(View::LineGraph),
+        }
+    }
+
+    /// Draws the chart offscreen and writes it to `out`, without starting the
+    /// app. The size not given, the theme and the PDF page are taken from the
+    /// settings.
+    fn run(&self) -> Result<(), AppError> {
+        let format = self.format().map_err(AppError::Simple)?;
+
+        let (settings, skipped) = Settings::load().unwrap_or_else(|err| {
+            eprintln!("Could not load settings, using defaults. {}", err.message());
+            (Settings::default(), Vec::new())
+        });
+        for line in skipped {
+            eprintln!("Skipped invalid settings entry: {line}");
+        }
+        let size = Size::new(
+            self.width.unwrap_or(settings.image_size.width),
+            self.height.unwrap_or(settings.image_size.height),
+        );
+
+        offscreen::load_fonts();
+
+        let view = self.chart()?.theme(settings.theme.clone());
+        let image = render_view(view, size)
+            .unwrap_or_else(|| Err(AppError::Simple("Only charts can be rendered".into())))?;
+        let bytes = format.encode(&image, (settings.pdf_page, settings.pdf_dpi));
+
+        std::fs::write(&self.out, bytes).map_err(|err| AppError::FileSaving(err.kind()))
+    }
+}
+
 fn main() -> Result<(), iced::Error> {
+    // Charts are rendered without starting the app, so no window is opened
+    if std::env::args().nth(1).as_deref() == Some(RenderArgs::COMMAND) {
+        let render = match RenderArgs::parse(std::env::args().skip(2)) {
+            Ok(render) => render,
+            Err(err) => {
+                eprintln!("{err}\n\n{}", Args::USAGE);
+                std::process::exit(2);
+            }
+        };
+
+        match render.run() {
+            Ok(()) => {
+                println!("{}", render.out.display());
+                return Ok(());
+            }
+            Err(err) => {
+                eprintln!(
+                    "Could not render {}: {}",
+                    render.file.display(),
+                    err.message()
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if std::env::args()
+        .skip(1)
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == "-h" || arg == "--help")
+    {
+        println!("{}", Args::USAGE);
+        return Ok(());
+    }
+
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", Args::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if args.stdin && std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        eprintln!("Nothing was piped into standard input for -");
+        std::process::exit(2);
+    }
+
     let fallback_log = "./modav.log";
 
     let log = if cfg!(target_os = "windows") {
@@ -81,13 +563,28 @@ fn main() -> Result<(), iced::Error> {
     let span = span!(Level::INFO, "Modav");
     let _guard = span.enter();
 
-    let mut fallback_flag = false;
-    let log_file = File::create(log.clone()).unwrap_or_else(|_| {
-        fallback_flag = true;
-        File::create(fallback_log).unwrap()
-    });
+    let (log, log_file) = match File::create(&log) {
+        Ok(file) => (log, Ok(file)),
+        Err(_) => (PathBuf::from(fallback_log), File::create(fallback_log)),
+    };
 
-    let (non_blocking, _log_writer) = tracing_appender::non_blocking(log_file);
+    // Logs still go to stderr without a log file, and the app says so once
+    // it is up
+    let (non_blocking, _log_writer, log_error) = match log_file {
+        Ok(file) => {
+            let (writer, guard) = tracing_appender::non_blocking(file);
+            (writer, guard, None)
+        }
+        Err(err) => {
+            eprintln!("Could not open a log file, logging to stderr instead: {err}");
+            let (writer, guard) = tracing_appender::non_blocking(std::io::stderr());
+            let error = AppError::Simple(format!(
+                "Could not open the log file {}, so this session is not logged. {err}",
+                log.display()
+            ));
+            (writer, guard, Some(error))
+        }
+    };
 
     let filter = EnvFilter::new("error");
 
@@ -105,18 +602,19 @@ fn main() -> Result<(), iced::Error> {
         .with_env_filter(filter)
         .init();
 
-    let flags = Flags::Prod(if fallback_flag {
-        PathBuf::from(fallback_log)
-    } else {
-        log
-    });
+    let flags = Flags::Prod(log, log_error);
 
     //let flags = Flags::Stacked;
 
+    let window = window::Settings {
+        size: Size::new(1280.0, 800.0),
+        position: window::Position::Centered,
+        ..Default::default()
+    };
+
     application(Modav::title, Modav::update, Modav::view)
-        .centered()
+        .window(window)
         .antialiasing(true)
-        .window_size(Size::new(1280.0, 800.0))
         .subscription(Modav::subscription)
         .exit_on_close_request(false)
         .theme(Modav::theme)
@@ -134,7 +632,7 @@ fn main() -> Result<(), iced::Error> {
 
             let batch = Task::batch(tasks);
 
-            let status = batch.chain(Task::done(Message::Ready));
+            let status = batch.chain(Task::done(Message::Ready)).chain(args.open());
 
             (app, status)
         })
@@ -154,6 +652,9 @@ pub enum FileIOAction {
     NewTab((View, PathBuf)),
     /// Refresh an existing tab
     RefreshTab((ViewType, usize, PathBuf)),
+    /// Replace an existing tab's data with the file's, e.g. after another
+    /// program changed it
+    ReloadTab((ViewType, usize, PathBuf)),
     /// Close a specific tab
     CloseTab(usize),
     /// Exiting main app
@@ -162,12 +663,26 @@ pub enum FileIOAction {
     None,
 }
 
+/// A file being read in the background, shown with its progress until
+/// `action` can be carried out with its contents
+struct FileLoad {
+    id: usize,
+    path: PathBuf,
+    action: FileIOAction,
+    read: u64,
+    total: u64,
+    /// Whether the file has been read and is now being parsed
+    parsing: bool,
+    handle: task::Handle,
+}
+
 impl FileIOAction {
     fn update_path(self, path: PathBuf) -> Self {
         match self {
             Self::None => Self::None,
             Self::NewTab((tidn, _)) => Self::NewTab((tidn, path)),
             Self::RefreshTab((tidn, id, _)) => Self::RefreshTab((tidn, id, path)),
+            Self::ReloadTab((tidn, id, _)) => Self::ReloadTab((tidn, id, path)),
             Self::CloseTab(id) => Self::CloseTab(id),
             Self::Exiting(id) => Self::Exiting(id),
         }
@@ -182,32 +697,557 @@ enum DialogView {
     None,
 }
 
+/// The file formats a captured chart is saved as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Pdf,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Pdf => "pdf",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// Encodes `image` as self. PDFs place it on `page` at `dpi`.
+    fn encode(&self, image: &Image, (page, dpi): (PageSize, u32)) -> Vec<u8> {
+        match self {
+            Self::Png => png::encode(image.width, image.height, &image.rgba),
+            Self::Pdf => pdf::encode(image.width, image.height, &image.rgba, page, dpi),
+            Self::Svg => svg::encode(image.width, image.height, &image.rgba),
+        }
+    }
+}
+
+/// Where a chart is exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTarget {
+    DataFile,
+    /// The data file without the series hidden in the legend
+    VisibleDataFile,
+    DataClipboard,
+    MetadataFile,
+    /// The chart drawn at the export size
+    Image(ImageFormat),
+}
+
+impl ExportTarget {
+    const ALL: [Self; 7] = [
+        Self::Image(ImageFormat::Png),
+        Self::Image(ImageFormat::Pdf),
+        Self::Image(ImageFormat::Svg),
+        Self::DataFile,
+        Self::VisibleDataFile,
+        Self::DataClipboard,
+        Self::MetadataFile,
+    ];
+
+    fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::DataFile | Self::VisibleDataFile => Some("csv"),
+            Self::DataClipboard => None,
+            Self::MetadataFile => Some("json"),
+            Self::Image(format) => Some(format.extension()),
+        }
+    }
+
+    /// Name of self in the settings file
+    fn key(&self) -> &'static str {
+        match self {
+            Self::DataFile => "data",
+            Self::VisibleDataFile => "visible_data",
+            Self::DataClipboard => "data_clipboard",
+            Self::MetadataFile => "metadata",
+            Self::Image(ImageFormat::Png) => "png",
+            Self::Image(ImageFormat::Pdf) => "pdf",
+            Self::Image(ImageFormat::Svg) => "svg",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::DataFile => "Data CSV File",
+            Self::VisibleDataFile => "Visible Data CSV File",
+            Self::DataClipboard => "Data to Clipboard",
+            Self::MetadataFile => "Metadata JSON File",
+            Self::Image(ImageFormat::Png) => "PNG Image",
+            Self::Image(ImageFormat::Pdf) => "PDF Document",
+            Self::Image(ImageFormat::Svg) => "SVG Image",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The last export, repeated by the export button
+#[derive(Debug, Clone, PartialEq)]
+struct LastExport {
+    target: ExportTarget,
+    /// Folder the last file was exported to
+    directory: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct Settings {
     theme: Theme,
-    timeout: u64,
+    /// How long toasts of each status stay on screen
+    timeouts: Timeouts,
     log_file: PathBuf,
     change_graph_theme: bool,
+    row_limit: RowLimit,
+    /// Draws floating surfaces without drop shadows
+    flat_ui: bool,
+    /// Colors editor text based on the file's type
+    highlighting: bool,
+    /// Wraps long lines in editors
+    word_wrap: bool,
+    /// Offers to reload open files when another program changes them
+    watch_files: bool,
+    /// Offers to reopen the tabs of the last session on startup
+    restore_session: bool,
+    /// Saves editor tabs with unsaved changes every `auto_save_interval`
+    /// seconds
+    auto_save: bool,
+    auto_save_interval: u64,
+    /// Shows only the side menu's icons
+    side_menu_collapsed: bool,
+    /// Multiplies the size of the interface's text
+    ui_scale: f32,
+    /// Colors of the custom theme, kept even while another theme is used
+    custom_palette: CustomPalette,
+    last_export: Option<LastExport>,
+    /// Paper the chart is placed on when exported as a PDF
+    pdf_page: PageSize,
+    /// Pixels per inch of charts exported as PDFs
+    pdf_dpi: u32,
+    /// Width and height, in pixels, charts are drawn at when exported
+    image_size: Size<u32>,
 }
 
 impl Settings {
     fn new(theme: Theme, log_file: PathBuf) -> Self {
         Self {
             theme,
-            timeout: 2,
+            timeouts: Timeouts::default(),
             change_graph_theme: true,
             log_file,
+            row_limit: RowLimit::default(),
+            flat_ui: false,
+            highlighting: true,
+            word_wrap: false,
+            watch_files: true,
+            restore_session: true,
+            auto_save: false,
+            auto_save_interval: 30,
+            side_menu_collapsed: false,
+            ui_scale: 1.0,
+            custom_palette: CustomPalette::default(),
+            last_export: None,
+            pdf_page: PageSize::default(),
+            pdf_dpi: 150,
+            image_size: Size::new(1280, 800),
+        }
+    }
+
+    const MIN_UI_SCALE: f32 = 0.75;
+    const MAX_UI_SCALE: f32 = 2.0;
+    const MIN_PDF_DPI: u32 = 72;
+    const MAX_PDF_DPI: u32 = 600;
+    /// DPIs offered in the settings dialog
+    const PDF_DPIS: [u32; 5] = [72, 96, 150, 300, 600];
+
+    /// Where settings are kept between sessions, e.g.
+    /// `~/.config/modav/config.toml` on Linux
+    fn config_path() -> Option<PathBuf> {
+        directories::ProjectDirs::from("", "", "modav")
+            .map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+
+    /// Loads the stored settings, with the entries that could not be read.
+    /// Defaults are returned if there are no stored settings yet.
+    fn load() -> Result<(Self, Vec<String>), AppError> {
+        let Some(path) = Self::config_path() else {
+            return Ok((Self::default(), Vec::new()));
+        };
+
+        match std::fs::read_to_string(path) {
+            Ok(input) => Ok(Self::deserialize(&input)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Ok((Self::default(), Vec::new()))
+            }
+            Err(err) => Err(AppError::FileLoading(err.kind())),
+        }
+    }
+
+    /// Writes self to the config file, creating its folder if needed
+    fn store(&self) -> Result<(), AppError> {
+        let path = Self::config_path()
+            .ok_or_else(|| AppError::Simple("No config folder found for settings".into()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| AppError::FileSaving(err.kind()))?;
+        }
+
+        std::fs::write(path, self.serialize()).map_err(|err| AppError::FileSaving(err.kind()))
+    }
+
+    /// Serializes the persisted settings as TOML `key = value` lines
+    fn serialize(&self) -> String {
+        fn quoted(value: &str) -> String {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        [
+            ("theme", quoted(&self.theme.to_string())),
+            ("log_file", quoted(&self.log_file.to_string_lossy())),
+            ("change_graph_theme", self.change_graph_theme.to_string()),
+            ("flat_ui", self.flat_ui.to_string()),
+            ("highlighting", self.highlighting.to_string()),
+            ("word_wrap", self.word_wrap.to_string()),
+            ("watch_files", self.watch_files.to_string()),
+            ("restore_session", self.restore_session.to_string()),
+            ("auto_save", self.auto_save.to_string()),
+            ("auto_save_interval", self.auto_save_interval.to_string()),
+            ("side_menu_collapsed", self.side_menu_collapsed.to_string()),
+            ("ui_scale", self.ui_scale.to_string()),
+            ("max_rows", self.row_limit.max_rows.to_string()),
+            ("sampling", quoted(&self.row_limit.strategy.to_string())),
+            ("pdf_page", quoted(&self.pdf_page.to_string())),
+            ("pdf_dpi", self.pdf_dpi.to_string()),
+            ("image_width", self.image_size.width.to_string()),
+            ("image_height", self.image_size.height.to_string()),
+        ]
+        .into_iter()
+        .chain(
+            Status::ALL.map(|status| (timeout_key(status), self.timeouts.get(status).to_string())),
+        )
+        .chain(PaletteColor::ALL.map(|part| {
+            let color = hex_color(self.custom_palette.get(part));
+            (part.key(), quoted(&color))
+        }))
+        .chain(self.last_export.iter().flat_map(|last| {
+            let directory = last.directory.as_ref().map(|directory| {
+                (
+                    "last_export_directory",
+                    quoted(&directory.to_string_lossy()),
+                )
+            });
+
+            [("last_export", quoted(last.target.key()))]
+                .into_iter()
+                .chain(directory)
+        }))
+        .map(|(key, value)| format!("{key} = {value}\n"))
+        .collect()
+    }
+
+    /// Parses settings written by [`Settings::serialize`]. Unknown keys are
+    /// ignored, while malformed lines and invalid values are skipped, leaving
+    /// their setting as the default. The skipped lines are returned too.
+    fn deserialize(input: &str) -> (Self, Vec<String>) {
+        fn unquoted(value: &str) -> Option<String> {
+            let value = value.strip_prefix('"')?.strip_suffix('"')?;
+            let mut output = String::with_capacity(value.len());
+            let mut chars = value.chars();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => output.push(chars.next()?),
+                    c => output.push(c),
+                }
+            }
+
+            Some(output)
+        }
+
+        let mut settings = Self::default();
+        let mut skipped = Vec::new();
+        // The custom theme is built once all of its colors are read
+        let mut custom = false;
+        // As is the last export, once its folder is read
+        let mut export_directory = None;
+
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                skipped.push(line.to_string());
+                continue;
+            };
+            let value = value.trim();
+
+            let valid = match key.trim() {
+                "theme" => unquoted(value).and_then(|name| {
+                    custom = name == CustomPalette::NAME;
+
+                    Theme::ALL
+                        .iter()
+                        .find(|theme| theme.to_string() == name)
+                        .map(|theme| settings.theme = theme.clone())
+                        .or(custom.then_some(()))
+                }),
+                // Kept from when every toast shared one timeout
+                "timeout" => value
+                    .parse()
+                    .ok()
+                    .map(|timeout| settings.timeouts = Timeouts::uniform(timeout)),
+                "log_file" => unquoted(value).map(|path| settings.log_file = PathBuf::from(path)),
+                "change_graph_theme" => value
+                    .parse()
+                    .ok()
+                    .map(|flag| settings.change_graph_theme = flag),
+                "flat_ui" => value.parse().ok().map(|flag| settings.flat_ui = flag),
+                "highlighting" => value.parse().ok().map(|flag| settings.highlighting = flag),
+                "word_wrap" => value.parse().ok().map(|flag| settings.word_wrap = flag),
+                "watch_files" => value.parse().ok().map(|flag| settings.watch_files = flag),
+                "restore_session" => value
+                    .parse()
+                    .ok()
+                    .map(|flag| settings.restore_session = flag),
+                "auto_save" => value.parse().ok().map(|flag| settings.auto_save = flag),
+                "auto_save_interval" => value
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .map(|interval| settings.auto_save_interval = interval),
+                "side_menu_collapsed" => value
+                    .parse()
+                    .ok()
+                    .map(|flag| settings.side_menu_collapsed = flag),
+                "ui_scale" => value
+                    .parse()
+                    .ok()
+                    .filter(|scale| (Self::MIN_UI_SCALE..=Self::MAX_UI_SCALE).contains(scale))
+                    .map(|scale| settings.ui_scale = scale),
+                "max_rows" => value
+                    .parse()
+                    .ok()
+                    .map(|max_rows| settings.row_limit.max_rows = max_rows),
+                "sampling" => unquoted(value)
+                    .and_then(|name| {
+                        SamplingStrategy::ALL
+                            .into_iter()
+                            .find(|strategy| strategy.to_string() == name)
+                    })
+                    .map(|strategy| settings.row_limit.strategy = strategy),
+                "pdf_page" => unquoted(value)
+                    .and_then(|name| {
+                        PageSize::ALL
+                            .into_iter()
+                            .find(|page| page.to_string() == name)
+                    })
+                    .map(|page| settings.pdf_page = page),
+                "pdf_dpi" => value
+                    .parse()
+                    .ok()
+                    .filter(|dpi| (Self::MIN_PDF_DPI..=Self::MAX_PDF_DPI).contains(dpi))
+                    .map(|dpi| settings.pdf_dpi = dpi),
+                "image_width" => value
+                    .parse()
+                    .ok()
+                    .filter(|width| (1..=offscreen::MAX_SIDE).contains(width))
+                    .map(|width| settings.image_size.width = width),
+                "image_height" => value
+                    .parse()
+                    .ok()
+                    .filter(|height| (1..=offscreen::MAX_SIDE).contains(height))
+                    .map(|height| settings.image_size.height = height),
+                "last_export" => unquoted(value)
+                    .and_then(|key| {
+                        ExportTarget::ALL
+                            .into_iter()
+                            .find(|target| target.key() == key)
+                    })
+                    .map(|target| {
+                        settings.last_export = Some(LastExport {
+                            target,
+                            directory: None,
+                        })
+                    }),
+                "last_export_directory" => {
+                    unquoted(value).map(|path| export_directory = Some(PathBuf::from(path)))
+                }
+                key => {
+                    let status = Status::ALL
+                        .into_iter()
+                        .find(|status| timeout_key(*status) == key);
+                    let part = PaletteColor::ALL.into_iter().find(|part| part.key() == key);
+
+                    match (status, part) {
+                        (Some(status), _) => value
+                            .parse()
+                            .ok()
+                            .map(|timeout| settings.timeouts.set(status, timeout)),
+                        (_, Some(part)) => unquoted(value)
+                            .and_then(|color| Color::parse(&color))
+                            .map(|color| settings.custom_palette.set(part, color)),
+                        _ => Some(()),
+                    }
+                }
+            };
+
+            if valid.is_none() {
+                skipped.push(line.to_string());
+            }
+        }
+
+        if custom {
+            settings.theme = settings.custom_palette.theme();
+        }
+        if let Some(last) = settings.last_export.as_mut() {
+            last.directory = export_directory;
+        }
+
+        (settings, skipped)
+    }
+}
+
+/// Recently opened files, most recent first
+#[derive(Debug, Clone, PartialEq, Default)]
+struct RecentFiles(Vec<PathBuf>);
+
+impl RecentFiles {
+    const MAX: usize = 10;
+
+    /// Kept in the same folder as the settings file
+    fn config_path() -> Option<PathBuf> {
+        Settings::config_path().map(|path| path.with_file_name("recent"))
+    }
+
+    /// Loads the stored list, one path per line. A missing or unreadable
+    /// file gives an empty list.
+    fn load() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+
+        match std::fs::read_to_string(path) {
+            Ok(input) => Self(
+                input
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(PathBuf::from)
+                    .take(Self::MAX)
+                    .collect(),
+            ),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Could not load recent files: {err}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    fn store(&self) -> Result<(), AppError> {
+        let path = Self::config_path()
+            .ok_or_else(|| AppError::Simple("No config folder found for recent files".into()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| AppError::FileSaving(err.kind()))?;
+        }
+
+        let content = self
+            .0
+            .iter()
+            .map(|path| format!("{}\n", path.display()))
+            .collect::<String>();
+
+        std::fs::write(path, content).map_err(|err| AppError::FileSaving(err.kind()))
+    }
+
+    /// Moves `path` to the front of the list, dropping the oldest entry if
+    /// the list is full
+    fn push(&mut self, path: PathBuf) {
+        self.0.retain(|recent| recent != &path);
+        self.0.insert(0, path);
+        self.0.truncate(Self::MAX);
+    }
+
+    fn remove(&mut self, path: &PathBuf) {
+        self.0.retain(|recent| recent != path);
+    }
+}
+
+/// Where the tabs of the last session are kept, in the same folder as the
+/// settings file
+fn session_path() -> Option<PathBuf> {
+    Settings::config_path().map(|path| path.with_file_name("session.json"))
+}
+
+/// Loads the tabs of the last session. A missing or unreadable file gives
+/// no session.
+fn load_session() -> Option<Session> {
+    let path = session_path()?;
+
+    match std::fs::read_to_string(path) {
+        Ok(input) => match Session::deserialize(&input) {
+            Ok(session) => Some(session),
+            Err(err) => {
+                warn!("Could not load the last session: {}", err.message());
+                None
+            }
+        },
+        Err(err) => {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                warn!("Could not load the last session: {err}");
+            }
+            None
         }
     }
 }
 
+fn store_session(session: &Session) -> Result<(), AppError> {
+    let path = session_path()
+        .ok_or_else(|| AppError::Simple("No config folder found for the session".into()))?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| AppError::FileSaving(err.kind()))?;
+    }
+
+    std::fs::write(path, session.serialize()).map_err(|err| AppError::FileSaving(err.kind()))
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new(Theme::TokyoNight, PathBuf::from("~/.local/share/modav.log"))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SettingsMessage {
     ThemeChange(Theme),
-    TimeoutChange(String),
+    TimeoutChange(Status, String),
+    /// The timeout of a status was submitted, previewing it with a toast
+    TimeoutCommit(Status),
     ReselectLog,
     LogReselect(PathBuf),
     ChangeGraphTheme(bool),
+    MaxRowsChange(String),
+    SamplingChange(SamplingStrategy),
+    FlatUi(bool),
+    Highlighting(bool),
+    WordWrap(bool),
+    WatchFiles(bool),
+    RestoreSession(bool),
+    AutoSave(bool),
+    AutoSaveIntervalChange(String),
+    UiScaleChange(f32),
+    PdfPageChange(PageSize),
+    PdfDpiChange(u32),
+    ImageWidthChange(String),
+    ImageHeightChange(String),
+    /// A color of the custom theme was edited, given as a hex string
+    CustomColorChange(PaletteColor, String),
     Cancel,
     Save,
 }
@@ -217,14 +1257,43 @@ pub struct Modav {
     current_view: ViewType,
     file_path: Option<PathBuf>,
     tabs: Tabs<Theme>,
-    toasts: Vec<Toast>,
+    toasts: Vec<Toast<Message>>,
     dialog_view: DialogView,
     error: AppError,
     settings: Settings,
+    recent: RecentFiles,
+    /// Dropped files waiting for the wizard to show them
+    queued_files: Vec<PathBuf>,
+    /// The tab whose chart the wizard is reconfiguring, and its
+    /// configuration when the wizard opened
+    reconfiguring: Option<(usize, SessionTab)>,
     new_settings: Option<Settings>,
+    /// Hex inputs of the custom theme's colors, which may not be valid yet
+    palette_inputs: [String; 3],
+    /// Toast timeout inputs of each status, which may not be valid yet
+    timeout_inputs: [String; 4],
     main_window_id: Option<window::Id>,
     is_ready: bool,
     context: MenuContext,
+    /// Whether access keys are shown, i.e. while Alt is held
+    show_access_keys: bool,
+    /// The last known scale factor of the main window
+    scale_factor: f32,
+    /// Chart preview of the wizard's configuration, or why it failed
+    preview: Option<Result<ChartPreview, AppError>>,
+    /// Incremented on every preview request so that only the latest is built
+    preview_generation: usize,
+    /// Files being read in the background
+    loads: Vec<FileLoad>,
+    /// Id given to the next file load
+    next_load: usize,
+    /// When each open file was last modified, to notice other programs
+    /// changing it
+    disk_times: HashMap<PathBuf, SystemTime>,
+    /// Line to select once the file's editor opens
+    pending_line: Option<(PathBuf, usize)>,
+    /// Tabs of the last session, until they are reopened
+    last_session: Option<Session>,
 }
 
 #[derive(Debug, Clone)]
@@ -232,15 +1301,44 @@ pub enum Flags {
     Bar,
     Line,
     Stacked,
-    Prod(PathBuf),
+    /// The log file of this session, and why it could not be opened if
+    /// logs only go to stderr
+    Prod(PathBuf, Option<AppError>),
 }
 
 impl Flags {
     fn create(self) -> Modav {
-        let theme = Theme::TokyoNight;
-        let toasts = Vec::default();
-        let title = String::from("Modav");
-        let error = AppError::None;
+        let mut toasts = Vec::default();
+
+        let (mut settings, skipped) = Settings::load().unwrap_or_else(|err| {
+            let body = format!("Could not load settings, using defaults. {}", err.message());
+            error!(body);
+            toasts.push(Toast {
+                body,
+                status: Status::Error,
+                action: None,
+            });
+
+            (Settings::default(), Vec::new())
+        });
+
+        if !skipped.is_empty() {
+            let body = format!(
+                "Skipped invalid settings, using their defaults: {}",
+                skipped.join(", ")
+            );
+            warn!(body);
+            toasts.push(Toast {
+                body,
+                status: Status::Warn,
+                action: None,
+            });
+        }
+
+        let theme = settings.theme.clone();
+        let recent = RecentFiles::load();
+        let title = String::from("Modav");
+        let error = AppError::None;
         let main_window_id = None;
         let is_ready = false;
         let mut tabs = Tabs::new(theme.clone())
@@ -255,29 +1353,58 @@ impl Flags {
             .tab_spacing(2.5)
             .tab_bar_padding(3)
             .tab_padding([5, 7]);
+        tabs.flat_ui(settings.flat_ui);
+        tabs.highlighting(settings.highlighting);
+        tabs.word_wrap(settings.word_wrap);
         let dialog_view = DialogView::default();
-        let default_log_file = PathBuf::from("~/.local/share/modav.log");
         let context = MenuContext::None;
 
-        let mut settings = Settings::new(theme.clone(), default_log_file);
-
         match self {
-            Self::Prod(log_file) => {
+            Self::Prod(log_file, log_error) => {
+                // The log being written this session takes precedence
                 settings.log_file = log_file;
 
+                if let Some(err) = log_error {
+                    toasts.push(Toast {
+                        body: err.message(),
+                        status: Status::Error,
+                        action: None,
+                    });
+                }
+
+                let last_session = settings
+                    .restore_session
+                    .then(load_session)
+                    .flatten()
+                    .filter(|session| !session.is_empty());
+
                 Modav {
                     file_path: None,
                     current_view: ViewType::None,
                     new_settings: None,
+                    palette_inputs: Default::default(),
+                    timeout_inputs: Default::default(),
+                    loads: Vec::default(),
+                    next_load: 0,
+                    disk_times: HashMap::default(),
+                    pending_line: None,
+                    last_session,
                     is_ready,
                     title,
                     toasts,
                     settings,
+                    recent,
+                    queued_files: Vec::default(),
+                    reconfiguring: None,
                     main_window_id,
                     error,
                     tabs,
                     dialog_view,
                     context,
+                    show_access_keys: false,
+                    scale_factor: 1.0,
+                    preview: None,
+                    preview_generation: 0,
                 }
             }
             Self::Line => {
@@ -298,13 +1425,27 @@ impl Flags {
                     current_view,
                     title,
                     settings,
+                    recent,
+                    queued_files: Vec::default(),
+                    reconfiguring: None,
                     new_settings: None,
+                    palette_inputs: Default::default(),
+                    timeout_inputs: Default::default(),
+                    loads: Vec::default(),
+                    next_load: 0,
+                    disk_times: HashMap::default(),
+                    pending_line: None,
+                    last_session: None,
                     main_window_id,
                     toasts,
                     error,
                     tabs,
                     dialog_view,
                     context,
+                    show_access_keys: false,
+                    scale_factor: 1.0,
+                    preview: None,
+                    preview_generation: 0,
                 }
             }
             Self::Bar => {
@@ -335,16 +1476,30 @@ impl Flags {
                 Modav {
                     file_path: Some(file_path),
                     new_settings: None,
+                    palette_inputs: Default::default(),
+                    timeout_inputs: Default::default(),
+                    loads: Vec::default(),
+                    next_load: 0,
+                    disk_times: HashMap::default(),
+                    pending_line: None,
+                    last_session: None,
                     is_ready,
                     current_view,
                     title,
                     main_window_id,
                     settings,
+                    recent,
+                    queued_files: Vec::default(),
+                    reconfiguring: None,
                     toasts,
                     error,
                     tabs,
                     dialog_view,
                     context,
+                    show_access_keys: false,
+                    scale_factor: 1.0,
+                    preview: None,
+                    preview_generation: 0,
                 }
             }
             Self::Stacked => {
@@ -373,16 +1528,30 @@ impl Flags {
                 Modav {
                     file_path: Some(file_path),
                     new_settings: None,
+                    palette_inputs: Default::default(),
+                    timeout_inputs: Default::default(),
+                    loads: Vec::default(),
+                    next_load: 0,
+                    disk_times: HashMap::default(),
+                    pending_line: None,
+                    last_session: None,
                     is_ready,
                     current_view,
                     title,
                     toasts,
                     settings,
+                    recent,
+                    queued_files: Vec::default(),
+                    reconfiguring: None,
                     main_window_id,
                     error,
                     tabs,
                     dialog_view,
                     context,
+                    show_access_keys: false,
+                    scale_factor: 1.0,
+                    preview: None,
+                    preview_generation: 0,
                 }
             }
         }
@@ -397,6 +1566,27 @@ pub enum Message {
     FileSelected(Result<PathBuf, AppError>),
     LoadFile((PathBuf, FileIOAction)),
     FileLoaded((Result<String, AppError>, FileIOAction)),
+    /// Progress of the file load with the id
+    FileLoading(usize, LoadEvent),
+    /// A loaded table finished parsing for the file load with the id
+    TableParsed(usize, TableTabData),
+    /// A chart finished building for the file load with the id
+    ChartParsed(usize, Result<View, AppError>),
+    /// Stops the file load with the id
+    CancelLoad(usize),
+    /// An open file was changed on disk, by modav or another program
+    FileChangedOnDisk(PathBuf),
+    /// Reloads every tab of the file, discarding unsaved edits if true
+    ReloadFromDisk(PathBuf, bool),
+    /// Opens the file in an editor, if not already open, and selects the
+    /// line
+    GoToLine(PathBuf, usize),
+    /// Scrolls the editor with the scrollable id, keeping its cursor in view
+    ScrollEditor(scrollable::Id, scrollable::AbsoluteOffset),
+    /// Saves the editor tabs with unsaved changes to their files
+    AutoSave,
+    /// The file of the tab at the index was auto-saved
+    AutoSaved(usize, Result<(PathBuf, String), AppError>),
     SaveFile((Option<PathBuf>, String, FileIOAction)),
     SaveKeyPressed,
     FileSaved((Result<(PathBuf, String), AppError>, FileIOAction)),
@@ -406,26 +1596,83 @@ pub enum Message {
     SetMainWindowID(window::Id),
     CloseWindow(window::Id),
     KeyPressed(keyboard::Key, keyboard::Modifiers),
+    ModifiersChanged(keyboard::Modifiers),
+    WindowChanged(window::Id),
+    /// Reloads the active tab with every row of its file
+    LoadAllRows,
+    /// Exports the active chart, asking for a destination if needed
+    Export(ExportTarget),
+    /// Repeats the last export
+    ExportAgain,
+    ExportTo(ExportTarget, PathBuf),
+    Exported(ExportTarget, Result<PathBuf, AppError>),
+    /// Asks where to export the active chart's metadata, suggesting a name
+    /// based on the chart's source file
+    ChooseMetadataPath(PathBuf),
+    ExportMetadata(PathBuf),
+    MetadataExported(Result<(PathBuf, String), AppError>),
+    /// Asks where to save the active chart as an image, suggesting a name
+    /// based on the chart's source file
+    ChooseImagePath(PathBuf),
+    ExportImage(PathBuf),
+    /// Asks where to save the active chart as a single page PDF
+    ChoosePdfPath(PathBuf),
+    ExportPdf(PathBuf),
+    ImageExported(Result<PathBuf, AppError>),
+    ScaleFactorChanged(f32),
     CheckExit,
     CanExit,
     OpenTab(Option<PathBuf>, View),
+    /// Builds the chart of the tab in the background and opens it in a new
+    /// tab
+    OpenChart(SessionTab),
+    /// Reopens the tabs of the last session
+    RestoreSession,
+    /// Reopens a file from the recent files list
+    OpenRecent(PathBuf),
+    /// Files dropped onto the window
+    FilesDropped(Vec<PathBuf>),
+    /// A file given on the command line, with the view it was asked to open as
+    OpenArgument(Option<ViewType>, PathBuf),
+    /// Standard input was read to the end, as asked for with `-`
+    StdinLoaded(Result<String, AppError>),
+    /// Reads the clipboard to open its text in a new tab
+    Paste,
+    /// The clipboard's text, if it held any
+    Pasted(Option<String>),
     NewActiveTab,
     TabsMessage(TabsMessage),
-    Debugging,
-    WizardSubmit(PathBuf, View),
+    /// The wizard's configuration, whose chart is built in the background
+    WizardSubmit(SessionTab),
     CloseWizard,
     Settings(SettingsMessage),
+    /// The side menu was collapsed or expanded
+    SideMenuToggled(bool),
     OpenAboutDialog,
     CloseAboutDialog,
     OpenLogFile,
-    AddToast(Toast),
+    /// Opens the link in the system's browser
+    OpenUrl(&'static str),
+    /// The wizard's chart configuration changed
+    PreviewConfig(PathBuf, PreviewConfig),
+    /// Builds the preview if no newer configuration came in meanwhile
+    BuildPreview(usize, PathBuf, PreviewConfig),
+    PreviewBuilt(usize, Result<View, AppError>),
+    AddToast(Toast<Message>),
     CloseToast(usize),
+    /// Closes the toast at the index and sends its action
+    ToastAction(usize),
+    /// Copies the full text of the toast at the index
+    CopyToast(usize),
     Error(AppError, bool),
     MenuContext(MenuContext),
     CloseContext(MenuContext),
     Chain(Box<(Message, Message)>),
     /// Open the editor for the current model
     OpenEditor(Option<PathBuf>),
+    /// Reopens the wizard with the active chart's configuration, to change
+    /// it in place
+    Reconfigure,
 }
 
 #[allow(dead_code)]
@@ -450,7 +1697,7 @@ impl Modav {
                         .and_then(|name| name.to_str())
                         .filter(|name| !name.is_empty())
                         .map(|file| {
-                            let text = text(file.to_owned());
+                            let text = text(file.to_owned()).size(self.scaled(16.0));
                             row!(text).spacing(5)
                         })
                 })
@@ -459,18 +1706,68 @@ impl Modav {
             match (path, &self.current_view) {
                 (None, ViewType::None) => row!(),
                 (None, vt) => {
-                    let txt = text("Untitled");
-                    let txt = txt;
-                    row!(vt.display(), vertical_rule(10), txt)
+                    let source = self
+                        .tabs
+                        .active_source()
+                        .unwrap_or_else(|| "Untitled".into());
+                    let txt = text(source).size(self.scaled(16.0));
+                    row!(vt.display(self.scaled(16.0)), vertical_rule(10), txt)
                 }
                 (Some(p), ViewType::None) => row!(p),
-                (Some(p), m) => row!(m.display(), vertical_rule(10), p),
+                (Some(p), m) => row!(m.display(self.scaled(16.0)), vertical_rule(10), p),
             }
         }
         .align_y(Alignment::Center)
         .spacing(10);
 
-        let row: Row<'_, Message> = row!(horizontal_space(), current)
+        let sample = self.tabs.active_row_sample().map(|(shown, total)| {
+            let label = text(format!(
+                "Showing {} of {} rows",
+                format_count(shown),
+                format_count(total)
+            ))
+            .size(self.scaled(16.0));
+
+            let load = button(text("Load All").size(self.scaled(12.0)))
+                .on_press(Message::LoadAllRows)
+                .padding([2, 6]);
+
+            row!(label, load).spacing(10).align_y(Alignment::Center)
+        });
+
+        let summary = self.tabs.active_status_summary().map(|summary| {
+            let summary = text(summary).size(self.scaled(16.0));
+            row!(vertical_rule(10), summary)
+                .spacing(10)
+                .align_y(Alignment::Center)
+        });
+
+        let export = self.tabs.active_view_state().map(|_| {
+            let last = self.settings.last_export.as_ref().map(|last| last.target);
+
+            let label = match last {
+                Some(target) => format!("Export {target}"),
+                None => String::from("Export"),
+            };
+
+            let again = button(text(label).size(self.scaled(12.0)))
+                .on_press_maybe(last.map(|_| Message::ExportAgain))
+                .padding([2, 6]);
+
+            let targets = pick_list(ExportTarget::ALL, None::<ExportTarget>, Message::Export)
+                .placeholder("Export as...")
+                .text_size(self.scaled(12.0))
+                .padding([2, 6]);
+
+            row!(again, targets).spacing(5).align_y(Alignment::Center)
+        });
+
+        let row: Row<'_, Message> = Row::new()
+            .push_maybe(sample)
+            .push_maybe(export)
+            .spacing(20)
+            .push(horizontal_space())
+            .push(current.push_maybe(summary))
             .height(Length::Fill)
             .align_y(Alignment::Center);
 
@@ -479,6 +1776,48 @@ impl Modav {
         })
     }
 
+    /// Exports the active chart to `target`. File targets are written to
+    /// `directory` if given, otherwise the user is asked for a destination.
+    fn export(&mut self, target: ExportTarget, directory: Option<PathBuf>) -> Task<Message> {
+        let Some(extension) = target.extension() else {
+            let Some(content) = self.tabs.active_csv() else {
+                return Task::none();
+            };
+
+            self.remember_export(LastExport {
+                target,
+                directory: None,
+            });
+
+            let toast = Toast {
+                status: Status::Success,
+                body: String::from("Copied chart data to clipboard"),
+                action: None,
+            };
+            self.push_toast(toast);
+
+            return iced::clipboard::write(content);
+        };
+
+        let name = self
+            .tabs
+            .active_path()
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .and_then(|stem| stem.to_str())
+            .map(|stem| format!("{stem}.{extension}"))
+            .unwrap_or(format!("export.{extension}"));
+
+        match directory {
+            Some(directory) => Task::done(Message::ExportTo(target, directory.join(name))),
+            None => Task::perform(pick_save_path(name), move |res| match res {
+                Ok(path) => Message::ExportTo(target, path),
+                Err(AppError::FileDialogClosed) => Message::None,
+                Err(err) => Message::Error(err, true),
+            }),
+        }
+    }
+
     /// Creates a save message for the current tab. Assumes the path is valid
     /// for current tab. Handles empty tabs situation
     /// Refreshes the current tab
@@ -507,7 +1846,8 @@ impl Modav {
             weight: font::Weight::Semibold,
             ..Default::default()
         };
-        let size = 18;
+        let size = self.scaled(18.0);
+        let flat = self.flat_ui();
 
         match self.context {
             MenuContext::None => Space::with_width(0).into(),
@@ -545,6 +1885,11 @@ impl Modav {
                     .width(Length::Fill)
                     .style(styler);
 
+                let paste = button("Paste as Table")
+                    .on_press(Message::Paste.close_context(MenuContext::File))
+                    .width(Length::Fill)
+                    .style(styler);
+
                 let save = button("Save File")
                     .on_press_maybe(self.tabs.active_tab_can_save().then(|| {
                         self.save_helper(self.tabs.active_path())
@@ -561,13 +1906,80 @@ impl Modav {
                     .width(Length::Fill)
                     .style(styler);
 
+                let split = {
+                    let label = if self.tabs.is_split() {
+                        "Single View"
+                    } else {
+                        "Split View"
+                    };
+
+                    button(label)
+                        // Each pane shows its own tab, so splitting takes two
+                        .on_press_maybe((self.tabs.is_split() || self.tabs.len() > 1).then(|| {
+                            Message::TabsMessage(TabsMessage::ToggleSplit)
+                                .close_context(MenuContext::File)
+                        }))
+                        .width(Length::Fill)
+                        .style(styler)
+                };
+
+                let recent = {
+                    let header = text("Recent Files")
+                        .font(header_font)
+                        .size(self.scaled(15.0));
+
+                    let entries = self.recent.0.iter().map(|path| {
+                        let exists = path.is_file();
+                        let name = path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| path.display().to_string());
+
+                        let label =
+                            text(name)
+                                .size(self.scaled(14.0))
+                                .style(move |theme: &Theme| {
+                                    let palette = theme.extended_palette();
+                                    text::Style {
+                                        color: (!exists).then_some(palette.background.strong.color),
+                                    }
+                                });
+
+                        button(label)
+                            .on_press(
+                                Message::OpenRecent(path.clone()).close_context(MenuContext::File),
+                            )
+                            .width(Length::Fill)
+                            .style(styler)
+                            .into()
+                    });
+
+                    let empty = self
+                        .recent
+                        .0
+                        .is_empty()
+                        .then(|| text("No recent files").size(self.scaled(14.0)));
+
+                    column![header]
+                        .extend(entries)
+                        .push_maybe(empty)
+                        .spacing(6.0)
+                };
+
                 let context =
-                    context!(Space::with_height(0.0), header, Space::with_height(28.0), open, new, save, save_new ; Message::CloseContext(MenuContext::File))
+                    context!(Space::with_height(0.0), header, Space::with_height(28.0), open, new, paste, save, save_new, split, recent ; Message::CloseContext(MenuContext::File))
                     .width(130)
                     .spacing(20.0)
                 .height(Length::Fill);
 
-                container(context).style(bordered_box).into()
+                container(context)
+                    .style(move |theme| {
+                        <FloatingSurface as container::Catalog>::style(
+                            &FloatingSurface::new(CONTEXT_LEVEL, flat),
+                            theme,
+                        )
+                    })
+                    .into()
             }
             MenuContext::Settings => {
                 let header = text("Settings Menu").size(size).font(header_font);
@@ -575,37 +1987,132 @@ impl Modav {
                 let theme = {
                     let label = text("Change Theme:");
 
-                    let pick_list = pick_list(THEMES, Some(self.theme()), |theme| {
+                    let custom = self
+                        .new_settings
+                        .as_ref()
+                        .map(|settings| settings.custom_palette)
+                        .unwrap_or(self.settings.custom_palette);
+
+                    let themes = THEMES
+                        .into_iter()
+                        .chain(std::iter::once(custom.theme()))
+                        .collect::<Vec<_>>();
+
+                    let pick_list = pick_list(themes, Some(self.theme()), |theme| {
                         Message::Settings(SettingsMessage::ThemeChange(theme))
                     });
 
-                    row!(label, pick_list)
+                    let tip = tooltip(
+                        "Hold Ctrl and scroll over the list to preview themes without opening it.",
+                    );
+
+                    let theme = row!(label, pick_list, tip)
                         .spacing(10)
-                        .align_y(Alignment::Center)
+                        .align_y(Alignment::Center);
+
+                    let colors = is_custom(self.theme_ref()).then(|| {
+                        let colors = PaletteColor::ALL.map(|part| -> Element<'_, Message> {
+                            let swatch = container(Space::new(20, 20)).style(move |_| {
+                                container::Style::default().background(custom.get(part))
+                            });
+
+                            let input = text_input("#rrggbb", &self.palette_inputs[part as usize])
+                                .on_input(move |input| {
+                                    Message::Settings(SettingsMessage::CustomColorChange(
+                                        part, input,
+                                    ))
+                                })
+                                .on_submit(
+                                    Message::Settings(SettingsMessage::Save)
+                                        .close_context(MenuContext::Settings),
+                                )
+                                .padding([0, 5])
+                                .width(80.0);
+
+                            row!(text(format!("{part}:")).width(90.0), input, swatch)
+                                .spacing(10)
+                                .align_y(Alignment::Center)
+                                .into()
+                        });
+
+                        column(colors).spacing(8.0)
+                    });
+
+                    column!(theme).push_maybe(colors).spacing(12.0)
                 };
 
                 let timeout = {
-                    let value = self.timeout();
-
-                    let label = text("Toast timeout:");
+                    let inputs = Status::ALL.map(|status| -> Element<'_, Message> {
+                        let input = text_input("", &self.timeout_inputs[status as usize])
+                            .on_input(move |timeout| {
+                                Message::Settings(SettingsMessage::TimeoutChange(status, timeout))
+                            })
+                            .on_submit(Message::Settings(SettingsMessage::TimeoutCommit(status)))
+                            .padding([0, 5])
+                            .width(44.0);
+
+                        row!(
+                            text(format!("{status}:")).width(70.0),
+                            input,
+                            text("seconds")
+                        )
+                        .spacing(5)
+                        .align_y(Alignment::Center)
+                        .into()
+                    });
 
-                    let input = text_input("", &value.to_string())
-                        .on_input(|timeout| {
-                            Message::Settings(SettingsMessage::TimeoutChange(timeout))
+                    let feedback = Status::ALL
+                        .into_iter()
+                        .filter_map(|status| {
+                            let input = self.timeout_inputs[status as usize].trim();
+
+                            match Timeouts::parse(input) {
+                                None => Some(format!("{status}: only whole seconds are allowed")),
+                                Some(Timeouts::MAX)
+                                    if input.parse::<u64>() != Ok(Timeouts::MAX) =>
+                                {
+                                    Some(format!("{status}: capped at {} seconds", Timeouts::MAX))
+                                }
+                                Some(_) => None,
+                            }
                         })
-                        .padding([0, 5])
-                        .width(44.0);
+                        .map(|msg| text(msg).size(self.scaled(13.0)).style(text::danger).into())
+                        .collect::<Vec<Element<'_, Message>>>();
+
+                    let feedback = (!feedback.is_empty()).then(|| column(feedback).spacing(2));
+
+                    column!(
+                        text("Toast timeouts (0 = never auto-close):"),
+                        column(inputs).spacing(5)
+                    )
+                    .push_maybe(feedback)
+                    .spacing(10.0)
+                };
+
+                let ui_scale = {
+                    let scale = self.ui_scale();
+
+                    let slider = slider(
+                        Settings::MIN_UI_SCALE..=Settings::MAX_UI_SCALE,
+                        scale,
+                        |scale| Message::Settings(SettingsMessage::UiScaleChange(scale)),
+                    )
+                    .step(0.05)
+                    .width(120.0);
+
+                    let tip = tooltip("Scales the text of menus, dialogs and the status bar.");
 
                     row!(
-                        label,
-                        row!(input, text("seconds"))
-                            .align_y(Alignment::Center)
-                            .spacing(5)
+                        text("Text size:"),
+                        slider,
+                        text(format!("{:.0}%", scale * 100.0)),
+                        tip
                     )
-                    .spacing(20.0)
+                    .spacing(10.0)
+                    .align_y(Alignment::Center)
                 };
 
-                let log = button(text("Open Log File").size(15.0))
+                let log = button(text("Open Log File").size(self.scaled(15.0)))
                     .on_press(Message::OpenLogFile.close_context(MenuContext::Settings));
 
                 let change_graph_theme = {
@@ -619,13 +2126,190 @@ impl Modav {
                     row!(check, tip).spacing(10.0)
                 };
 
+                let flat_ui = {
+                    let check = checkbox("Flat interface", self.flat_ui())
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::FlatUi(flag)));
+
+                    let tip =
+                        tooltip("When enabled, menus and dialogs are drawn without drop shadows.");
+
+                    row!(check, tip).spacing(10.0)
+                };
+
+                let highlighting = {
+                    let check = checkbox("Syntax highlighting", self.highlighting())
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::Highlighting(flag)));
+
+                    let tip = tooltip(if cfg!(feature = "highlighter") {
+                        "When disabled, editors show plain monospace text."
+                    } else {
+                        "This build of modav was made without syntax highlighting, so editors always show plain text."
+                    });
+
+                    row!(check, tip).spacing(10.0)
+                };
+
+                let word_wrap = {
+                    let check = checkbox("Word wrap", self.word_wrap())
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::WordWrap(flag)));
+
+                    let tip = tooltip(
+                        "When enabled, editors wrap long lines. Toggle it with Ctrl+Alt+W.",
+                    );
+
+                    row!(check, tip).spacing(10.0)
+                };
+
+                let watch_files = {
+                    let check = checkbox("Watch open files", self.watch_files())
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::WatchFiles(flag)));
+
+                    let tip = tooltip("When enabled, files changed by other programs while open can be reloaded from a notification.");
+
+                    row!(check, tip).spacing(10.0)
+                };
+
+                let restore_session = {
+                    let check = checkbox("Reopen last session", self.restore_session())
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::RestoreSession(flag)));
+
+                    let tip = tooltip("When enabled, the tabs open when modav closed can be reopened from the home screen on the next start.");
+
+                    row!(check, tip).spacing(10.0)
+                };
+
+                let auto_save = {
+                    let (enabled, interval) = self.auto_save();
+
+                    let check = checkbox("Auto-save every", enabled)
+                        .on_toggle(|flag| Message::Settings(SettingsMessage::AutoSave(flag)));
+
+                    let input = text_input("", &interval.to_string())
+                        .on_input(|interval| {
+                            Message::Settings(SettingsMessage::AutoSaveIntervalChange(interval))
+                        })
+                        .on_submit(
+                            Message::Settings(SettingsMessage::Save)
+                                .close_context(MenuContext::Settings),
+                        )
+                        .padding([0, 5])
+                        .width(44.0);
+
+                    let tip = tooltip("When enabled, editor tabs of files with unsaved changes are saved on this interval. Tabs that have never been saved are left alone.");
+
+                    row!(check, input, text("seconds"), tip)
+                        .spacing(10.0)
+                        .align_y(Alignment::Center)
+                };
+
+                let row_limit = {
+                    let limit = self.row_limit();
+
+                    let value = if limit.is_limited() {
+                        limit.max_rows.to_string()
+                    } else {
+                        String::default()
+                    };
+
+                    let input = text_input("All", &value)
+                        .on_input(|max_rows| {
+                            Message::Settings(SettingsMessage::MaxRowsChange(max_rows))
+                        })
+                        .on_submit(
+                            Message::Settings(SettingsMessage::Save)
+                                .close_context(MenuContext::Settings),
+                        )
+                        .padding([0, 5])
+                        .width(80.0);
+
+                    let strategy =
+                        pick_list(SamplingStrategy::ALL, Some(limit.strategy), |strategy| {
+                            Message::Settings(SettingsMessage::SamplingChange(strategy))
+                        })
+                        .text_size(self.scaled(13.0))
+                        .padding([2, 5]);
+
+                    let tip = tooltip("Charts of files with more rows than this only show a sample of the rows, picked using the chosen strategy. Leave empty to always load every row.");
+
+                    column!(
+                        row!(text("Max rows:"), input, tip)
+                            .spacing(10.0)
+                            .align_y(Alignment::Center),
+                        row!(text("Sampling:"), strategy)
+                            .spacing(10.0)
+                            .align_y(Alignment::Center)
+                    )
+                    .spacing(10.0)
+                };
+
+                let pdf_export = {
+                    let (page, dpi) = self.pdf_export();
+
+                    let page = pick_list(PageSize::ALL, Some(page), |page| {
+                        Message::Settings(SettingsMessage::PdfPageChange(page))
+                    })
+                    .text_size(self.scaled(13.0))
+                    .padding([2, 5]);
+
+                    let dpi = pick_list(Settings::PDF_DPIS, Some(dpi), |dpi| {
+                        Message::Settings(SettingsMessage::PdfDpiChange(dpi))
+                    })
+                    .text_size(self.scaled(13.0))
+                    .padding([2, 5]);
+
+                    let tip = tooltip("Charts exported as PDFs are printed at this many pixels per inch, and shrunk to fit the page if needed. Higher values print the chart smaller and sharper.");
+
+                    row!(text("PDF page:"), page, dpi, text("DPI"), tip)
+                        .spacing(10.0)
+                        .align_y(Alignment::Center)
+                };
+
+                let image_size = {
+                    let size = self.image_size();
+
+                    let width = text_input("", &size.width.to_string())
+                        .on_input(|width| {
+                            Message::Settings(SettingsMessage::ImageWidthChange(width))
+                        })
+                        .on_submit(
+                            Message::Settings(SettingsMessage::Save)
+                                .close_context(MenuContext::Settings),
+                        )
+                        .padding([0, 5])
+                        .width(60.0);
+
+                    let height = text_input("", &size.height.to_string())
+                        .on_input(|height| {
+                            Message::Settings(SettingsMessage::ImageHeightChange(height))
+                        })
+                        .on_submit(
+                            Message::Settings(SettingsMessage::Save)
+                                .close_context(MenuContext::Settings),
+                        )
+                        .padding([0, 5])
+                        .width(60.0);
+
+                    let tip = tooltip("Charts exported as images and PDFs are drawn at this width and height, whatever the size of the window.");
+
+                    row!(
+                        text("Export size:"),
+                        width,
+                        text("×"),
+                        height,
+                        text("px"),
+                        tip
+                    )
+                    .spacing(10.0)
+                    .align_y(Alignment::Center)
+                };
+
                 let actions = {
-                    let cancel = button(text("Cancel").size(13.0)).on_press(
+                    let cancel = button(text("Cancel").size(self.scaled(13.0))).on_press(
                         Message::Settings(SettingsMessage::Cancel)
                             .close_context(MenuContext::Settings),
                     );
 
-                    let submit = button(text("Save").size(13.0)).on_press(
+                    let submit = button(text("Save").size(self.scaled(13.0))).on_press(
                         Message::Settings(SettingsMessage::Save)
                             .close_context(MenuContext::Settings),
                     );
@@ -636,25 +2320,66 @@ impl Modav {
                 let msg =
                     Message::Settings(SettingsMessage::Cancel).close_context(MenuContext::Settings);
 
-                let context = context!(
-                        Space::with_height(0.0),
-                        header,
-                        Space::with_height(28.0),
+                // Grows with the text size, so it scrolls rather than pushing
+                // the actions off screen
+                let options = scrollable(
+                    column!(
                         theme,
                         timeout,
+                        ui_scale,
                         log,
                         change_graph_theme,
-                        Space::with_height(Length::Fill),
+                        flat_ui,
+                        highlighting,
+                        word_wrap,
+                        watch_files,
+                        restore_session,
+                        auto_save,
+                        row_limit,
+                        image_size,
+                        pdf_export
+                    )
+                    .spacing(25.0)
+                    .padding(iced::padding::right(12.0)),
+                )
+                .height(Length::Fill);
+
+                let context = context!(
+                        Space::with_height(0.0),
+                        header,
+                        Space::with_height(28.0),
+                        options,
                         actions;
                         msg)
                 .spacing(25.0)
                 .height(Length::Fill);
 
-                container(context).style(bordered_box).into()
+                container(context)
+                    .style(move |theme| {
+                        <FloatingSurface as container::Catalog>::style(
+                            &FloatingSurface::new(CONTEXT_LEVEL, flat),
+                            theme,
+                        )
+                    })
+                    .into()
             }
         }
     }
 
+    /// Label of a side menu entry, badged with its access key while access keys
+    /// are shown
+    fn menu_label<'a>(&self, label: &'a str, key: Option<char>, size: f32) -> Element<'a, Message> {
+        let label = text(label).size(size);
+
+        match key {
+            Some(key) if self.show_access_keys => row!(label, access_key(key))
+                .spacing(6)
+                .align_y(Alignment::Center)
+                .into(),
+            _ => label.into(),
+        }
+    }
+
     fn side_menu(&self) -> Element<'_, Message> {
         let font = Font {
             family: font::Family::Cursive,
@@ -662,18 +2387,18 @@ impl Modav {
             ..Default::default()
         };
 
-        let size = 18.0;
+        let size = self.scaled(18.0);
         let icon_size = f32::max(size * 0.9, 18.0);
 
         let header = Menu::new(
-            icons::icon(icons::SETTINGS).size(24),
-            text("modav").font(font).size(24),
+            icons::icon(icons::SETTINGS).size(self.scaled(24.0)),
+            text("modav").font(font).size(self.scaled(24.0)),
         );
 
-        let file = Menu::new(
-            icons::icon(icons::FILE).size(icon_size),
-            text("File").size(size),
-        )
+        let file = Menu::from_slice([
+            icons::icon(icons::FILE).size(icon_size).into(),
+            self.menu_label("File", Some('f'), size),
+        ])
         .width(Length::Fill)
         .message(Message::MenuContext(MenuContext::File));
 
@@ -683,10 +2408,10 @@ impl Modav {
         )
         .width(Length::Fill);
 
-        let about = Menu::new(
-            icons::icon(icons::INFO).size(icon_size),
-            text("Information").size(size),
-        )
+        let about = Menu::from_slice([
+            icons::icon(icons::INFO).size(icon_size).into(),
+            self.menu_label("Information", Some('i'), size),
+        ])
         .message(Message::OpenAboutDialog);
 
         let help = Menu::new(
@@ -694,10 +2419,10 @@ impl Modav {
             text("Help").size(size),
         );
 
-        let settings = Menu::new(
-            icons::icon(icons::SETTINGS).size(icon_size),
-            text("Settings").size(size),
-        )
+        let settings = Menu::from_slice([
+            icons::icon(icons::SETTINGS).size(icon_size).into(),
+            self.menu_label("Settings", Some('s'), size),
+        ])
         .width(Length::Fill)
         .message(Message::MenuContext(MenuContext::Settings));
 
@@ -706,13 +2431,178 @@ impl Modav {
             section!(file, models).width(Length::Fill).spacing(20.0),
             section!(about, help, settings),
         )
-        .height(Length::Fill);
-
-        let content = container(menu).style(bordered_box);
+        .height(Length::Fill)
+        .collapsed(self.settings.side_menu_collapsed)
+        .on_toggle(Message::SideMenuToggled);
+
+        let flat = self.flat_ui();
+        let content = container(menu).style(move |theme| {
+            <FloatingSurface as container::Catalog>::style(
+                &FloatingSurface::new(MENU_LEVEL, flat),
+                theme,
+            )
+        });
 
         content.into()
     }
 
+    /// Selects `line` of the active editor and scrolls to it
+    fn go_to_line(&mut self, line: usize) -> Task<Message> {
+        match self.tabs.go_to_line(line) {
+            Some(id) => {
+                let offset = scrollable::AbsoluteOffset {
+                    x: 0.0,
+                    y: line_offset(line),
+                };
+                scrollable::scroll_to(id, offset)
+            }
+            None => Task::none(),
+        }
+    }
+
+    /// Reads `path` in the background, reporting its progress until `action`
+    /// is carried out with the contents
+    fn start_load(&mut self, path: PathBuf, action: FileIOAction) -> Task<Message> {
+        self.next_load += 1;
+        let id = self.next_load;
+
+        let (task, handle) = Task::run(load_file_progress(path.clone()), move |event| {
+            Message::FileLoading(id, event)
+        })
+        .abortable();
+
+        self.loads.push(FileLoad {
+            id,
+            path,
+            action,
+            read: 0,
+            total: 0,
+            parsing: false,
+            handle,
+        });
+
+        task
+    }
+
+    /// The chart `action` opens or refreshes, configured as a new chart of
+    /// its file and sampled at the current row limit. None if `action` is
+    /// not for a chart.
+    fn chart_session(&self, action: &FileIOAction) -> Option<SessionTab> {
+        let session = match action.clone() {
+            FileIOAction::NewTab((View::LineGraph(_), path)) => {
+                SessionTab::LineGraph(path, LineConfigState::default())
+            }
+            FileIOAction::NewTab((View::BarChart(_), path)) => {
+                SessionTab::BarChart(path, BarChartConfigState::default())
+            }
+            FileIOAction::NewTab((View::StackedBarChart(_), path)) => {
+                SessionTab::StackedBarChart(path, StackedBarChartConfigState::default())
+            }
+            FileIOAction::NewTab((View::BoxPlot(_), path)) => {
+                SessionTab::BoxPlot(path, BoxPlotConfigState::default())
+            }
+            FileIOAction::NewTab((View::Histogram(_), path)) => {
+                SessionTab::Histogram(path, HistogramConfigState::default())
+            }
+            FileIOAction::NewTab((View::Radar(_), path)) => {
+                SessionTab::Radar(path, RadarConfigState::default())
+            }
+            // Refreshed charts are rebuilt with the options they were made
+            // with, only sampled at the current row limit
+            FileIOAction::RefreshTab((_, tidx, _)) => match self.tabs.tab_session(tidx)? {
+                SessionTab::Editor(_) | SessionTab::Table(_) => return None,
+                session => session,
+            },
+            _ => return None,
+        };
+
+        Some(session.with_row_limit(self.settings.row_limit))
+    }
+
+    /// Builds the chart of `session` in the background, showing it with the
+    /// files being loaded until `action` can be carried out with it
+    fn parse_chart(&mut self, session: SessionTab, action: FileIOAction) -> Task<Message> {
+        self.next_load += 1;
+        let id = self.next_load;
+        let path = session.path().clone();
+
+        let (task, handle) = Task::perform(async move { session.view() }, move |view| {
+            Message::ChartParsed(id, view)
+        })
+        .abortable();
+
+        self.loads.push(FileLoad {
+            id,
+            path,
+            action,
+            read: 0,
+            total: 0,
+            parsing: true,
+            handle,
+        });
+
+        task
+    }
+
+    /// Progress of the files being loaded, each with a button cancelling it
+    fn loads_view(&self) -> Option<Element<'_, Message>> {
+        if self.loads.is_empty() {
+            return None;
+        }
+
+        let loads = self.loads.iter().map(|load| {
+            let name = load
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+
+            let (status, progress) = if load.parsing {
+                (String::from("Parsing..."), 1.0)
+            } else if load.total == 0 {
+                (String::from("Reading..."), 0.0)
+            } else {
+                let progress = load.read as f32 / load.total as f32;
+                (format!("{:.0}%", progress * 100.0), progress)
+            };
+
+            let cancel = button(text("Cancel").size(self.scaled(12.0)))
+                .on_press(Message::CancelLoad(load.id))
+                .padding([2, 6]);
+
+            row!(
+                text(format!("Loading {name}"))
+                    .size(self.scaled(14.0))
+                    .width(Length::Fill),
+                text(status).size(self.scaled(12.0)),
+                progress_bar(0.0..=1.0, progress).width(120).height(6),
+                cancel
+            )
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+        });
+
+        let flat = self.flat_ui();
+        let panel = container(Column::with_children(loads).spacing(8))
+            .padding([10, 15])
+            .max_width(520)
+            .style(move |theme| {
+                <FloatingSurface as container::Catalog>::style(
+                    &FloatingSurface::new(CONTEXT_LEVEL, flat),
+                    theme,
+                )
+            });
+
+        Some(
+            container(panel)
+                .align_bottom(Length::Fill)
+                .center_x(Length::Fill)
+                .padding([40, 20])
+                .into(),
+        )
+    }
+
     fn update_tabs(&mut self, tsg: TabsMessage) -> Task<Message> {
         if let Some(response) = self.tabs.update(tsg) {
             Task::perform(async { response }, |response| response)
@@ -721,6 +2611,25 @@ impl Modav {
         }
     }
 
+    /// Selects the tab after the active one, or the one before it when
+    /// `forward` is false, wrapping around at the ends
+    fn cycle_tabs(&mut self, forward: bool) -> Task<Message> {
+        let count = self.tabs.len();
+
+        if count == 0 {
+            return Task::none();
+        }
+
+        let idx = match (self.tabs.active_tab_idx(), forward) {
+            (Some(idx), true) => (idx + 1) % count,
+            (Some(idx), false) => (idx + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+
+        self.update_tabs(TabsMessage::TabSelected(idx))
+    }
+
     fn file_io_action_handler(&mut self, action: FileIOAction, content: String) -> Task<Message> {
         match action {
             FileIOAction::NewTab((View::Editor(data), path)) => {
@@ -728,50 +2637,10 @@ impl Modav {
                 let idr = View::Editor(data);
                 self.update_tabs(TabsMessage::AddTab(idr))
             }
-            FileIOAction::NewTab((View::LineGraph(_), path)) => {
-                let data = LineTabData::new(path, LineConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-
-                    Ok(data) => {
-                        let data = data.theme(self.theme());
-                        let idr = View::LineGraph(data);
-                        self.update_tabs(TabsMessage::AddTab(idr))
-                    }
-                }
-            }
-            FileIOAction::NewTab((View::BarChart(_), path)) => {
-                let data = BarChartTabData::new(path, BarChartConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-
-                    Ok(data) => {
-                        let data = data.theme(self.theme());
-                        let idr = View::BarChart(data);
-                        self.update_tabs(TabsMessage::AddTab(idr))
-                    }
-                }
-            }
-            FileIOAction::NewTab((View::StackedBarChart(_), path)) => {
-                let data = StackedBarChartTabData::new(path, StackedBarChartConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-
-                    Ok(data) => {
-                        let data = data.theme(self.theme());
-                        let idr = View::StackedBarChart(data);
-                        self.update_tabs(TabsMessage::AddTab(idr))
-                    }
-                }
+            FileIOAction::NewTab((View::Table(data), path)) => {
+                let data = data.path(path).data(content);
+                let idr = View::Table(data);
+                self.update_tabs(TabsMessage::AddTab(idr))
             }
             FileIOAction::NewTab((View::None, _)) => self.update_tabs(TabsMessage::None),
             FileIOAction::RefreshTab((ViewType::Editor, tidx, path)) => {
@@ -779,46 +2648,42 @@ impl Modav {
                 let rsh = Refresh::Editor(data);
                 self.update_tabs(TabsMessage::RefreshTab(tidx, rsh))
             }
-            FileIOAction::RefreshTab((ViewType::LineGraph, tidx, path)) => {
-                let data = LineTabData::new(path, LineConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-                    Ok(data) => {
-                        let rsh = Refresh::LineGraph(data);
-                        self.update_tabs(TabsMessage::RefreshTab(tidx, rsh))
-                    }
-                }
+            FileIOAction::RefreshTab((ViewType::Table, tidx, path)) => {
+                // Charts of the saved file are reloaded so they show the edits
+                let linked = self.tabs.linked_charts(&path);
+
+                let data = TableTabData::new(Some(path.clone()), content);
+                let rsh = Refresh::Table(data);
+                let table = self.update_tabs(TabsMessage::RefreshTab(tidx, rsh));
+
+                // Each chart is rebuilt with its own options
+                let row_limit = self.settings.row_limit;
+                let charts = linked
+                    .into_iter()
+                    .map(|(idx, kind, session)| {
+                        let action = FileIOAction::RefreshTab((kind, idx, path.clone()));
+                        self.parse_chart(session.with_row_limit(row_limit), action)
+                    })
+                    .collect::<Vec<_>>();
+
+                Task::batch(std::iter::once(table).chain(charts))
             }
-            FileIOAction::RefreshTab((ViewType::BarChart, tidx, path)) => {
-                let data = BarChartTabData::new(path, BarChartConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-                    Ok(data) => {
-                        let rsh = Refresh::BarChart(data);
-                        self.update_tabs(TabsMessage::RefreshTab(tidx, rsh))
-                    }
+            FileIOAction::RefreshTab((ViewType::None, _, _)) => self.update_tabs(TabsMessage::None),
+            // Charts read their file themselves, off the UI thread
+            action @ (FileIOAction::NewTab(_) | FileIOAction::RefreshTab(_)) => {
+                match self.chart_session(&action) {
+                    Some(session) => self.parse_chart(session, action),
+                    None => Task::none(),
                 }
             }
-            FileIOAction::RefreshTab((ViewType::StackedBarChart, tidx, path)) => {
-                let data = StackedBarChartTabData::new(path, StackedBarChartConfigState::default());
-                match data {
-                    Err(err) => {
-                        let msg = Message::Error(err, true);
-                        Task::perform(async { msg }, |msg| msg)
-                    }
-                    Ok(data) => {
-                        let rsh = Refresh::StackedBarChart(data);
-                        self.update_tabs(TabsMessage::RefreshTab(tidx, rsh))
-                    }
-                }
+            FileIOAction::ReloadTab((ViewType::Editor, tidx, path)) => {
+                let data = EditorTabData::new(Some(path), content).reload(true);
+                let rsh = Refresh::Editor(data);
+                self.update_tabs(TabsMessage::RefreshTab(tidx, rsh))
+            }
+            FileIOAction::ReloadTab((kind, tidx, path)) => {
+                self.file_io_action_handler(FileIOAction::RefreshTab((kind, tidx, path)), content)
             }
-            FileIOAction::RefreshTab((ViewType::None, _, _)) => self.update_tabs(TabsMessage::None),
             FileIOAction::CloseTab(idx) => {
                 let tsg = TabsMessage::CloseTab(idx, true);
                 self.update_tabs(tsg)
@@ -832,7 +2697,37 @@ impl Modav {
         }
     }
 
-    fn push_toast(&mut self, toast: Toast) {
+    /// Draws the active chart at the export size, encoded as `format`
+    fn chart_bytes(&self, format: ImageFormat) -> Result<Vec<u8>, AppError> {
+        let image = self
+            .tabs
+            .render_active(self.image_size())
+            .unwrap_or_else(|| Err(AppError::Simple("There is no chart to export".into())))?;
+
+        Ok(format.encode(&image, self.pdf_export()))
+    }
+
+    /// Draws the active chart at the export size and saves it at `path`
+    fn export_chart(&self, path: PathBuf, format: ImageFormat) -> Task<Message> {
+        match self.chart_bytes(format) {
+            Ok(bytes) => Task::perform(save_bytes(path, bytes), Message::ImageExported),
+            Err(err) => Task::done(Message::Error(err, true)),
+        }
+    }
+
+    /// Keeps `last` for the export button, storing it with the settings
+    fn remember_export(&mut self, last: LastExport) {
+        if let Some(settings) = self.new_settings.as_mut() {
+            settings.last_export = Some(last.clone());
+        }
+        self.settings.last_export = Some(last);
+
+        if let Err(err) = self.settings.store() {
+            warn!("Could not store settings: {}", err.message());
+        }
+    }
+
+    fn push_toast(&mut self, toast: Toast<Message>) {
         match toast.status {
             Status::Info => info!(toast.body),
             Status::Warn => warn!(toast.body),
@@ -843,20 +2738,229 @@ impl Modav {
         self.toasts.push(toast);
     }
 
+    /// Shows `err` from making a chart of `path`, with a button to pick a
+    /// different configuration in the wizard
+    fn configuration_error(&mut self, err: AppError, path: PathBuf) -> Task<Message> {
+        let action = match err.location() {
+            Some(location) => located_action(location),
+            None => (
+                "Reconfigure".into(),
+                Box::new(Message::FileSelected(Ok(path))),
+            ),
+        };
+        let toast = Toast {
+            status: Status::Error,
+            body: err.message(),
+            action: Some(action),
+        };
+        self.push_toast(toast);
+        Task::none()
+    }
+
+    /// Warns about the values of `view` left out of a logarithmic axis, if
+    /// there are any
+    fn warn_log_skipped(&mut self, view: &View) {
+        let skipped = view.log_skipped();
+        if skipped > 0 {
+            let toast = Toast {
+                body: format!(
+                    "Left out {skipped} values that are not positive from the logarithmic axis"
+                ),
+                status: Status::Warn,
+                action: None,
+            };
+            self.push_toast(toast);
+        }
+    }
+
+    /// Drops the wizard's preview and any preview still being built
+    fn clear_preview(&mut self) {
+        self.preview = None;
+        self.preview_generation += 1;
+    }
+
+    /// Shows the wizard for `file`, or queues it if the wizard is already
+    /// showing another file
+    fn select_file(&mut self, file: PathBuf) {
+        if self.dialog_view == DialogView::Wizard {
+            self.queued_files.push(file);
+            return;
+        }
+
+        self.info_log(format!(
+            "{} file selected",
+            file.file_name()
+                .map(|name| name.to_str().unwrap_or("None"))
+                .unwrap_or("None")
+        ));
+        self.remember_recent(file.clone());
+        self.file_path = Some(file);
+        self.dialog_view = DialogView::Wizard;
+    }
+
+    /// Opens `path` through the wizard if it can be charted, otherwise
+    /// straight in the editor
+    fn open_file(&mut self, path: PathBuf) -> Task<Message> {
+        if matches!(FileType::new(&path), FileType::CSV | FileType::JSON) {
+            self.select_file(path);
+            Task::none()
+        } else {
+            self.remember_recent(path.clone());
+            Task::done(Message::OpenTab(
+                Some(path),
+                View::Editor(EditorTabData::default()),
+            ))
+        }
+    }
+
+    /// Opens `path` straight into `view`. Charts use their default
+    /// configuration, falling back to the wizard if it does not fit the file
+    fn open_as(&mut self, view: ViewType, path: PathBuf) -> Task<Message> {
+        let chart = match view {
+            ViewType::LineGraph => {
+                LineTabData::new(path.clone(), LineConfigState::default()).map(View::LineGraph)
+            }
+            ViewType::BarChart => {
+                BarChartTabData::new(path.clone(), BarChartConfigState::default())
+                    .map(View::BarChart)
+            }
+            ViewType::StackedBarChart => {
+                StackedBarChartTabData::new(path.clone(), StackedBarChartConfigState::default())
+                    .map(View::StackedBarChart)
+            }
+            ViewType::BoxPlot => {
+                BoxPlotTabData::new(path.clone(), BoxPlotConfigState::default()).map(View::BoxPlot)
+            }
+            ViewType::Histogram => {
+                HistogramTabData::new(path.clone(), HistogramConfigState::default())
+                    .map(View::Histogram)
+            }
+            ViewType::Radar => {
+                RadarTabData::new(path.clone(), RadarConfigState::default()).map(View::Radar)
+            }
+            ViewType::Table => Ok(View::Table(TableTabData::default())),
+            ViewType::Editor | ViewType::None => Ok(View::Editor(EditorTabData::default())),
+        };
+
+        match chart {
+            Ok(view) => {
+                self.remember_recent(path.clone());
+                Task::done(Message::OpenTab(Some(path), view))
+            }
+            Err(err) => {
+                let toast = Toast {
+                    body: format!(
+                        "Could not open {} as a {}: {}",
+                        path.display(),
+                        view.name(),
+                        err.message()
+                    ),
+                    status: Status::Warn,
+                    action: None,
+                };
+                self.push_toast(toast);
+                self.select_file(path);
+                Task::none()
+            }
+        }
+    }
+
+    /// Shows the wizard for the next queued file, if any
+    fn select_queued_file(&mut self) {
+        if !self.queued_files.is_empty() {
+            let file = self.queued_files.remove(0);
+            self.select_file(file);
+        }
+    }
+
+    /// Adds `path` to the recent files and stores the list
+    fn remember_recent(&mut self, path: PathBuf) {
+        self.recent.push(path);
+        self.store_recent();
+    }
+
+    fn store_recent(&self) {
+        if let Err(err) = self.recent.store() {
+            warn!("Could not store recent files: {}", err.message());
+        }
+    }
+
+    /// Stores the open tabs to be reopened on the next start. A last
+    /// session that was never reopened is kept if no tabs are open.
+    fn store_session(&self) {
+        let session = match (self.settings.restore_session, &self.last_session) {
+            (false, _) => Session::default(),
+            (true, Some(last)) if self.tabs.is_empty() => last.clone(),
+            (true, _) => self.tabs.session(),
+        };
+
+        if let Err(err) = store_session(&session) {
+            warn!("Could not store the session: {}", err.message());
+        }
+    }
+
     fn info_log(&mut self, message: impl Into<String>) {
         let message: String = message.into();
         info!(message);
     }
 
     fn about(&self) -> Element<'_, Message> {
-        let text = text(
+        let size = self.scaled(15.0);
+
+        let description = text(
             "Yet another one of my projects. I started this particular one to grow more familiar with Rust. I hope to be able to continue this is it for now. 
 
-This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 parts. This is the Iced GUI frontend. The backend, modav_core, can be found on my Github profile.
-            ",
-        );
+This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 parts. This is the Iced GUI frontend. The backend is modav_core.",
+        )
+        .size(self.scaled(16.0));
 
-        dialog_container(text).height(Length::Shrink).into()
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        let version = text(format!(
+            "Version {} ({}, {profile} build)",
+            env!("CARGO_PKG_VERSION"),
+            option_env!("MODAV_COMMIT").unwrap_or("unknown commit"),
+        ))
+        .size(size);
+
+        let link = |label: &'static str, url: &'static str| {
+            button(text(label).size(size))
+                .on_press(Message::OpenUrl(url))
+                .style(button::text)
+                .padding(0)
+        };
+
+        let links = row!(
+            link("modav", REPOSITORY),
+            link("modav_core", CORE_REPOSITORY)
+        )
+        .spacing(20.0);
+
+        let log = {
+            let path = button(text(self.log_file().display().to_string()).size(size))
+                .on_press(Message::OpenLogFile)
+                .style(button::text)
+                .padding(0);
+
+            let tip = tooltip("Opens the log file. Attaching it to bug reports helps a lot");
+
+            column!(
+                row!(text("Log file").size(size), tip)
+                    .spacing(10.0)
+                    .align_y(Alignment::Center),
+                path
+            )
+            .spacing(4.0)
+        };
+
+        let content = column!(description, version, links, log).spacing(15.0);
+
+        dialog_container(content, self.flat_ui())
+            .height(Length::Shrink)
+            .into()
     }
 
     fn title(&self) -> String {
@@ -877,11 +2981,11 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             .unwrap_or(&self.settings.theme)
     }
 
-    fn timeout(&self) -> u64 {
+    fn timeouts(&self) -> Timeouts {
         self.new_settings
             .as_ref()
-            .map(|settings| settings.timeout)
-            .unwrap_or(self.settings.timeout)
+            .map(|settings| settings.timeouts)
+            .unwrap_or(self.settings.timeouts)
     }
 
     fn log_file(&self) -> &PathBuf {
@@ -891,6 +2995,80 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             .unwrap_or(&self.settings.log_file)
     }
 
+    fn flat_ui(&self) -> bool {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.flat_ui)
+            .unwrap_or(self.settings.flat_ui)
+    }
+
+    fn highlighting(&self) -> bool {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.highlighting)
+            .unwrap_or(self.settings.highlighting)
+    }
+
+    /// Whether auto-save is enabled, and its interval in seconds
+    fn auto_save(&self) -> (bool, u64) {
+        let settings = self.new_settings.as_ref().unwrap_or(&self.settings);
+        (settings.auto_save, settings.auto_save_interval)
+    }
+
+    fn word_wrap(&self) -> bool {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.word_wrap)
+            .unwrap_or(self.settings.word_wrap)
+    }
+
+    fn watch_files(&self) -> bool {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.watch_files)
+            .unwrap_or(self.settings.watch_files)
+    }
+
+    fn restore_session(&self) -> bool {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.restore_session)
+            .unwrap_or(self.settings.restore_session)
+    }
+
+    fn ui_scale(&self) -> f32 {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.ui_scale)
+            .unwrap_or(self.settings.ui_scale)
+    }
+
+    /// Scales a text `size` by the interface scale
+    fn scaled(&self, size: f32) -> f32 {
+        size * self.ui_scale()
+    }
+
+    fn row_limit(&self) -> RowLimit {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.row_limit)
+            .unwrap_or(self.settings.row_limit)
+    }
+
+    /// The page size and DPI charts are exported as PDFs with
+    fn pdf_export(&self) -> (PageSize, u32) {
+        let settings = self.new_settings.as_ref().unwrap_or(&self.settings);
+        (settings.pdf_page, settings.pdf_dpi)
+    }
+
+    /// The size, in pixels, charts are drawn at when exported
+    fn image_size(&self) -> Size<u32> {
+        self.new_settings
+            .as_ref()
+            .map(|settings| settings.image_size)
+            .unwrap_or(self.settings.image_size)
+    }
+
     fn change_graph_theme(&self) -> bool {
         self.new_settings
             .as_ref()
@@ -905,23 +3083,96 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
 
                 SettingsMessage::ChangeGraphTheme(flag) => settings.change_graph_theme = flag,
 
-                SettingsMessage::TimeoutChange(mut timeout) => {
-                    if !timeout.is_empty() {
-                        if let Some(first) = timeout.chars().next() {
-                            if settings.timeout == 0 && first != '0' {
-                                timeout.pop();
-                            }
+                SettingsMessage::MaxRowsChange(max_rows) => {
+                    if max_rows.is_empty() {
+                        settings.row_limit.max_rows = 0;
+                    } else if let Ok(max_rows) = max_rows.parse() {
+                        settings.row_limit.max_rows = max_rows;
+                    }
+                }
+
+                SettingsMessage::SamplingChange(strategy) => settings.row_limit.strategy = strategy,
+
+                SettingsMessage::FlatUi(flat) => settings.flat_ui = flat,
+
+                SettingsMessage::Highlighting(flag) => settings.highlighting = flag,
+
+                SettingsMessage::WordWrap(flag) => settings.word_wrap = flag,
+
+                SettingsMessage::WatchFiles(flag) => settings.watch_files = flag,
+
+                SettingsMessage::RestoreSession(flag) => settings.restore_session = flag,
+
+                SettingsMessage::AutoSave(flag) => settings.auto_save = flag,
+
+                SettingsMessage::AutoSaveIntervalChange(interval) => {
+                    if let Some(interval) = interval.parse().ok().filter(|interval| *interval > 0) {
+                        settings.auto_save_interval = interval;
+                    }
+                }
+
+                SettingsMessage::UiScaleChange(scale) => settings.ui_scale = scale,
+
+                SettingsMessage::PdfPageChange(page) => settings.pdf_page = page,
+
+                SettingsMessage::PdfDpiChange(dpi) => settings.pdf_dpi = dpi,
+
+                SettingsMessage::ImageWidthChange(width) => {
+                    if let Some(width) = width
+                        .parse()
+                        .ok()
+                        .filter(|width| (1..=offscreen::MAX_SIDE).contains(width))
+                    {
+                        settings.image_size.width = width;
+                    }
+                }
+
+                SettingsMessage::ImageHeightChange(height) => {
+                    if let Some(height) = height
+                        .parse()
+                        .ok()
+                        .filter(|height| (1..=offscreen::MAX_SIDE).contains(height))
+                    {
+                        settings.image_size.height = height;
+                    }
+                }
+
+                SettingsMessage::CustomColorChange(part, input) => {
+                    if let Some(color) = Color::parse(input.trim()) {
+                        settings.custom_palette.set(part, color);
+
+                        if is_custom(&settings.theme) {
+                            settings.theme = settings.custom_palette.theme();
                         }
-                        settings.timeout = timeout.parse().unwrap_or(self.settings.timeout);
+                    }
 
-                        let toast = Toast {
-                            body: format!("Toast with {timeout} second timeout."),
-                            status: Status::Info,
-                        };
-                        self.push_toast(toast);
-                    } else {
-                        settings.timeout = 0;
+                    self.palette_inputs[part as usize] = input;
+                }
+
+                SettingsMessage::TimeoutChange(status, input) => {
+                    if let Some(timeout) = Timeouts::parse(&input) {
+                        settings.timeouts.set(status, timeout);
                     }
+
+                    self.timeout_inputs[status as usize] = input;
+                }
+
+                SettingsMessage::TimeoutCommit(status) => {
+                    let timeout = settings.timeouts.get(status);
+                    self.timeout_inputs[status as usize] = timeout.to_string();
+
+                    // Previews the new timeout with a toast of the status
+                    let body = match timeout {
+                        0 => format!("{status} toasts stay until closed."),
+                        timeout => format!("{status} toasts close after {timeout} seconds."),
+                    };
+
+                    let toast = Toast {
+                        body,
+                        status,
+                        action: None,
+                    };
+                    self.push_toast(toast);
                 }
 
                 SettingsMessage::Cancel => {
@@ -935,13 +3186,24 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                         if settings.change_graph_theme {
                             self.tabs.set_theme(settings.theme.clone());
                         }
+                        self.tabs.flat_ui(settings.flat_ui);
+                        self.tabs.highlighting(settings.highlighting);
+                        self.tabs.word_wrap(settings.word_wrap);
                         self.settings = settings;
                     }
                     self.dialog_view = DialogView::None;
 
-                    let toast = Toast {
-                        body: "Settings Saved".into(),
-                        status: Status::Success,
+                    let toast = match self.settings.store() {
+                        Ok(()) => Toast {
+                            body: "Settings Saved".into(),
+                            status: Status::Success,
+                            action: None,
+                        },
+                        Err(err) => Toast {
+                            body: format!("Settings applied but not stored. {}", err.message()),
+                            status: Status::Error,
+                            action: None,
+                        },
                     };
                     self.push_toast(toast);
                 }
@@ -956,64 +3218,333 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                     settings.log_file = log;
                 }
             }
-        }
-        Task::none()
-    }
+        }
+        Task::none()
+    }
+
+    fn new(flags: Flags) -> Self {
+        flags.create()
+    }
+
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Error(err, show_toast) => {
+                if show_toast {
+                    let toast = Toast {
+                        status: Status::Error,
+                        body: err.message(),
+                        action: err.location().map(located_action),
+                    };
+                    self.push_toast(toast);
+                } else {
+                    error!("{}", err.message());
+                }
+                Task::none()
+            }
+            Message::IconLoaded(Ok(_)) => {
+                self.info_log("Icon Loaded Successfully");
+                Task::none()
+            }
+            Message::IconLoaded(Err(e)) => {
+                let error = AppError::FontLoading(e);
+                Task::perform(async { error }, |error| Message::Error(error, true))
+            }
+            Message::SelectFile => {
+                self.error = AppError::None;
+                Task::perform(pick_file(), Message::FileSelected)
+            }
+            Message::FileSelected(Ok(file)) => {
+                self.error = AppError::None;
+                self.select_file(file);
+                Task::none()
+            }
+            Message::FilesDropped(paths) => {
+                let tasks = paths
+                    .into_iter()
+                    .filter(|path| path.is_file())
+                    .map(|path| self.open_file(path))
+                    .collect::<Vec<_>>();
+
+                Task::batch(tasks)
+            }
+            Message::OpenArgument(view, path) => {
+                if !path.is_file() {
+                    let toast = Toast {
+                        body: format!("Could not open {}, no such file", path.display()),
+                        status: Status::Error,
+                        action: None,
+                    };
+                    self.push_toast(toast);
+
+                    return Task::none();
+                }
+
+                match view {
+                    Some(view) => self.open_as(view, path),
+                    None => self.open_file(path),
+                }
+            }
+            Message::StdinLoaded(Ok(data)) => {
+                let data = EditorTabData::new(None, data).name("stdin");
+                Task::done(Message::OpenTab(None, View::Editor(data)))
+            }
+            Message::StdinLoaded(Err(error)) => Task::done(Message::Error(error, true)),
+            Message::Paste => iced::clipboard::read().map(Message::Pasted),
+            Message::Pasted(content) => {
+                let Some(content) = content.filter(|content| !content.trim().is_empty()) else {
+                    let toast = Toast {
+                        body: String::from("The clipboard has no text to open"),
+                        status: Status::Warn,
+                        action: None,
+                    };
+                    self.push_toast(toast);
+
+                    return Task::none();
+                };
+
+                // Text that splits into even columns is shown as a table,
+                // anything else in the editor
+                let view = match csv::sniff_delimiter(&content) {
+                    Some(delimiter) => {
+                        let data = TableTabData::new(None, csv::to_csv(&content, delimiter))
+                            .name("clipboard");
+                        View::Table(data)
+                    }
+                    None => View::Editor(EditorTabData::new(None, content).name("clipboard")),
+                };
+
+                Task::done(Message::OpenTab(None, view))
+            }
+            Message::OpenRecent(path) => {
+                if !path.is_file() {
+                    self.recent.remove(&path);
+                    self.store_recent();
+
+                    let toast = Toast {
+                        body: format!(
+                            "{} no longer exists and was removed from recent files",
+                            path.display()
+                        ),
+                        status: Status::Warn,
+                        action: Some(("Choose File".into(), Box::new(Message::SelectFile))),
+                    };
+                    self.push_toast(toast);
+
+                    return Task::none();
+                }
+
+                self.open_file(path)
+            }
+            Message::FileSelected(Err(error)) => Task::done(Message::Error(error, true)),
+            Message::LoadFile((path, action)) => {
+                self.error = AppError::None;
+                self.start_load(path, action)
+            }
+            Message::FileLoading(id, LoadEvent::Progress { read, total }) => {
+                if let Some(load) = self.loads.iter_mut().find(|load| load.id == id) {
+                    load.read = read;
+                    load.total = total;
+                }
+                Task::none()
+            }
+            Message::FileLoading(id, LoadEvent::Loaded(res)) => {
+                let Some(idx) = self.loads.iter().position(|load| load.id == id) else {
+                    return Task::none();
+                };
+
+                let content = match res {
+                    Ok(content) => content,
+                    Err(err) => {
+                        self.loads.remove(idx);
+                        return Task::done(Message::Error(err, true));
+                    }
+                };
+                self.info_log("File loaded");
+
+                let load = &mut self.loads[idx];
+                match load.action.clone() {
+                    FileIOAction::NewTab((View::Table(data), path)) => {
+                        let (task, handle) = Task::perform(
+                            async move { data.path(path).data(content).parse() },
+                            move |data| Message::TableParsed(id, data),
+                        )
+                        .abortable();
+
+                        // Cancelling now stops the parsing instead
+                        load.parsing = true;
+                        load.handle = handle;
+                        task
+                    }
+                    action => {
+                        self.loads.remove(idx);
+                        self.file_io_action_handler(action, content)
+                    }
+                }
+            }
+            Message::TableParsed(id, data) => {
+                let Some(idx) = self.loads.iter().position(|load| load.id == id) else {
+                    return Task::none();
+                };
+                self.loads.remove(idx);
+
+                self.update_tabs(TabsMessage::AddTab(View::Table(data)))
+            }
+            Message::ChartParsed(id, view) => {
+                let Some(idx) = self.loads.iter().position(|load| load.id == id) else {
+                    return Task::none();
+                };
+                let load = self.loads.remove(idx);
+
+                if let Ok(view) = &view {
+                    self.warn_log_skipped(view);
+                }
+
+                match (view, load.action) {
+                    (Err(err), _) => self.configuration_error(err, load.path),
+                    (Ok(view), FileIOAction::NewTab(_)) => {
+                        let view = view.theme(self.theme());
+                        self.update_tabs(TabsMessage::AddTab(view))
+                    }
+                    (Ok(view), FileIOAction::RefreshTab((kind, tidx, _))) => {
+                        match Refresh::matching(Some(kind), view) {
+                            Some(rsh) => self.update_tabs(TabsMessage::RefreshTab(tidx, rsh)),
+                            None => Task::none(),
+                        }
+                    }
+                    _ => Task::none(),
+                }
+            }
+            Message::ScrollEditor(id, offset) => scrollable::scroll_to(id, offset),
+            Message::GoToLine(path, line) => {
+                if self.dialog_view == DialogView::Wizard {
+                    self.dialog_view = DialogView::None;
+                    self.clear_preview();
+                }
+
+                let editor = self
+                    .tabs
+                    .tabs_with_path(&path)
+                    .into_iter()
+                    .find(|(_, kind, _)| *kind == ViewType::Editor);
+
+                match editor {
+                    Some((idx, _, _)) => {
+                        let select = self.update_tabs(TabsMessage::TabSelected(idx));
+                        select.chain(self.go_to_line(line))
+                    }
+                    None => {
+                        self.pending_line = Some((path.clone(), line));
+                        let data = EditorTabData::default();
+                        Task::done(Message::OpenTab(Some(path), View::Editor(data)))
+                    }
+                }
+            }
+            Message::AutoSave => {
+                let saves = self
+                    .tabs
+                    .unsaved_editors()
+                    .into_iter()
+                    .map(|(idx, path, content)| {
+                        Task::perform(save_file(Some(path), content), move |res| {
+                            Message::AutoSaved(idx, res)
+                        })
+                    });
 
-    fn new(flags: Flags) -> Self {
-        flags.create()
-    }
+                Task::batch(saves)
+            }
+            Message::AutoSaved(idx, Ok((path, content))) => {
+                if let Some(modified) = modified_time(&path) {
+                    self.disk_times.insert(path.clone(), modified);
+                }
 
-    fn update(&mut self, message: Message) -> Task<Message> {
-        match message {
-            Message::Error(err, show_toast) => {
-                if show_toast {
-                    let toast = Toast {
-                        status: Status::Error,
-                        body: err.message(),
-                    };
-                    self.push_toast(toast);
+                // The tab may have been closed or moved while saving
+                let open = self
+                    .tabs
+                    .tabs_with_path(&path)
+                    .iter()
+                    .any(|(tab, _, _)| *tab == idx);
+
+                if open {
+                    let action = FileIOAction::RefreshTab((ViewType::Editor, idx, path));
+                    self.file_io_action_handler(action, content)
                 } else {
-                    error!("{}", err.message());
+                    Task::none()
                 }
-                Task::none()
             }
-            Message::IconLoaded(Ok(_)) => {
-                self.info_log("Icon Loaded Successfully");
+            Message::AutoSaved(_, Err(err)) => {
+                let toast = Toast {
+                    body: format!("Auto-save failed: {}", err.message()),
+                    status: Status::Error,
+                    action: None,
+                };
+                self.push_toast(toast);
                 Task::none()
             }
-            Message::IconLoaded(Err(e)) => {
-                let error = AppError::FontLoading(e);
-                Task::perform(async { error }, |error| Message::Error(error, true))
+            Message::FileChangedOnDisk(path) => {
+                // Saves made by modav record their time, so they are not
+                // mistaken for another program's
+                let Some(modified) = modified_time(&path) else {
+                    return Task::none();
+                };
+                if self.disk_times.insert(path.clone(), modified) == Some(modified) {
+                    return Task::none();
+                }
+
+                let toast = Toast {
+                    body: format!("{} was changed by another program", path.display()),
+                    status: Status::Warn,
+                    action: Some((
+                        "Reload".into(),
+                        Box::new(Message::ReloadFromDisk(path, false)),
+                    )),
+                };
+                self.push_toast(toast);
+                Task::none()
             }
-            Message::SelectFile => {
-                self.error = AppError::None;
-                Task::perform(pick_file(), Message::FileSelected)
+            Message::ReloadFromDisk(path, force) => {
+                let tabs = self.tabs.tabs_with_path(&path);
+
+                if !force && tabs.iter().any(|(_, _, dirty)| *dirty) {
+                    let toast = Toast {
+                        body: format!(
+                            "{} has unsaved changes that reloading would discard",
+                            path.display()
+                        ),
+                        status: Status::Warn,
+                        action: Some((
+                            "Reload Anyway".into(),
+                            Box::new(Message::ReloadFromDisk(path, true)),
+                        )),
+                    };
+                    self.push_toast(toast);
+                    return Task::none();
+                }
+
+                Task::batch(tabs.into_iter().map(|(idx, kind, _)| {
+                    let action = FileIOAction::ReloadTab((kind, idx, path.clone()));
+                    Task::done(Message::LoadFile((path.clone(), action)))
+                }))
             }
-            Message::FileSelected(Ok(file)) => {
-                self.error = AppError::None;
-                self.info_log(format!(
-                    "{} file selected",
-                    file.file_name()
-                        .map(|name| name.to_str().unwrap_or("None"))
-                        .unwrap_or("None")
-                ));
-                self.file_path = Some(file);
-                self.dialog_view = DialogView::Wizard;
+            Message::CancelLoad(id) => {
+                if let Some(idx) = self.loads.iter().position(|load| load.id == id) {
+                    let load = self.loads.remove(idx);
+                    load.handle.abort();
+                    self.info_log(format!("Stopped loading {}", load.path.display()));
+                }
                 Task::none()
             }
-            Message::FileSelected(Err(error)) => Task::done(Message::Error(error, true)),
-            Message::LoadFile((path, action)) => {
-                self.error = AppError::None;
-                Task::perform(
-                    async { (load_file(path).await, action) },
-                    |((res, _), action)| Message::FileLoaded((res, action)),
-                )
-            }
             Message::FileLoaded((Ok(res), action)) => {
                 self.info_log("File loaded");
                 self.file_io_action_handler(action, res)
             }
+            Message::OpenUrl(url) => {
+                self.info_log(format!("Opening {url}"));
+
+                match open_url(url) {
+                    Ok(()) => Task::none(),
+                    Err(err) => Task::perform(async { err }, |error| Message::Error(error, true)),
+                }
+            }
             Message::OpenLogFile => {
                 self.dialog_view = DialogView::None;
                 self.info_log("Opening Log file");
@@ -1032,33 +3563,23 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             Message::FileLoaded((Err(err), _)) => {
                 Task::perform(async { err }, |error| Message::Error(error, true))
             }
+            Message::OpenChart(session) => {
+                let action = FileIOAction::NewTab((View::None, session.path().clone()));
+                self.parse_chart(session, action)
+            }
             Message::OpenTab(path, tidr) => {
                 self.info_log("Tab opened");
                 let path = path.filter(|path| path.is_file());
 
                 match (path, tidr.should_load()) {
-                    (Some(path), true) => Task::perform(
-                        async move { (load_file(path.clone()).await, tidr) },
-                        |((res, path), tidr)| {
-                            Message::FileLoaded((res, FileIOAction::NewTab((tidr, path))))
-                        },
-                    ),
+                    (Some(path), true) => {
+                        let action = FileIOAction::NewTab((tidr, path.clone()));
+                        self.start_load(path, action)
+                    }
                     (Some(_path), false) => {
                         let idr = match tidr {
-                            View::Editor(_) => View::None,
-                            View::LineGraph(data) => {
-                                let data = data.theme(self.theme());
-                                View::LineGraph(data)
-                            }
-                            View::BarChart(data) => {
-                                let data = data.theme(self.theme());
-                                View::BarChart(data)
-                            }
-                            View::StackedBarChart(data) => {
-                                let data = data.theme(self.theme());
-                                View::StackedBarChart(data)
-                            }
-                            View::None => View::None,
+                            View::Editor(_) | View::Table(_) | View::None => View::None,
+                            chart => chart.theme(self.theme()),
                         };
                         self.update_tabs(TabsMessage::AddTab(idr))
                     }
@@ -1070,6 +3591,10 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                             View::LineGraph(_) => View::None,
                             View::BarChart(_) => View::None,
                             View::StackedBarChart(_) => View::None,
+                            View::BoxPlot(_) => View::None,
+                            View::Histogram(_) => View::None,
+                            View::Radar(_) => View::None,
+                            View::Table(_) => View::None,
                             View::None => View::None,
                         };
                         self.update_tabs(TabsMessage::AddTab(idr))
@@ -1105,10 +3630,16 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                 let save_message = self.save_helper(self.tabs.active_path());
                 Task::perform(async { save_message }, |msg| msg)
             }
-            Message::FileSaved((Ok((_path, content)), action)) => {
+            Message::FileSaved((Ok((path, content)), action)) => {
+                // Our own save should not look like another program's change
+                if let Some(modified) = modified_time(&path) {
+                    self.disk_times.insert(path, modified);
+                }
+
                 let toast = Toast {
                     status: Status::Success,
                     body: "Save Successful!".into(),
+                    action: None,
                 };
                 self.push_toast(toast);
                 self.file_io_action_handler(action, content)
@@ -1116,7 +3647,56 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             Message::FileSaved((Err(e), _)) => {
                 Task::perform(async { e }, |error| Message::Error(error, true))
             }
-            Message::CheckExit => self.update_tabs(TabsMessage::Exit),
+            Message::CheckExit => {
+                // Stored before any unsaved tabs are saved and closed
+                self.store_session();
+                self.update_tabs(TabsMessage::Exit)
+            }
+            Message::RestoreSession => {
+                let Some(session) = self.last_session.take() else {
+                    return Task::none();
+                };
+                self.info_log("Restoring last session");
+
+                let mut tasks = Vec::new();
+                let mut failed = Vec::new();
+
+                for (tab, view_state) in session.tabs() {
+                    let path = tab.path().clone();
+
+                    if !path.is_file() {
+                        failed.push(format!("{} no longer exists", path.display()));
+                        continue;
+                    }
+
+                    match tab.view() {
+                        Ok(view) if view.should_load() => {
+                            tasks.push(Task::done(Message::OpenTab(Some(path), view)));
+                        }
+                        Ok(view) => {
+                            // Charts are added right away so their toolbar
+                            // options can be restored on the new tab
+                            tasks.push(self.update(Message::OpenTab(Some(path), view)));
+
+                            if let Some(view_state) = view_state {
+                                self.tabs.restore_active_view_state(view_state);
+                            }
+                        }
+                        Err(err) => failed.push(format!("{}: {}", path.display(), err.message())),
+                    }
+                }
+
+                if !failed.is_empty() {
+                    let toast = Toast {
+                        body: format!("Some tabs could not be reopened. {}", failed.join(". ")),
+                        status: Status::Warn,
+                        action: None,
+                    };
+                    self.push_toast(toast);
+                }
+
+                Task::batch(tasks)
+            }
             Message::SetMainWindowID(id) => {
                 self.main_window_id = Some(id);
                 Task::none()
@@ -1132,23 +3712,193 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                 self.info_log("New active tab");
                 self.current_view = self.tabs.active_tab_type().unwrap_or(ViewType::None);
                 self.file_path = self.tabs.active_path();
-                Task::none()
+
+                match self.pending_line.take() {
+                    Some((path, line))
+                        if self.file_path.as_ref() == Some(&path)
+                            && self.current_view == ViewType::Editor =>
+                    {
+                        self.go_to_line(line)
+                    }
+                    pending => {
+                        self.pending_line = pending;
+                        Task::none()
+                    }
+                }
             }
             Message::Convert => Task::none(),
             Message::None => Task::none(),
-            Message::Debugging => {
-                dbg!("Debugging Message sent!");
-                Task::none()
-            }
             Message::CloseWizard => {
                 self.dialog_view = DialogView::None;
+                self.reconfiguring = None;
+                self.clear_preview();
+                self.select_queued_file();
                 Task::perform(async {}, |_| Message::NewActiveTab)
             }
-            Message::WizardSubmit(path, view) => {
+            Message::WizardSubmit(session) => {
                 self.dialog_view = DialogView::None;
                 self.info_log("Wizard Submitted");
-                Task::perform(async { Message::OpenTab(Some(path), view) }, |msg| msg)
+                self.clear_preview();
+                self.select_queued_file();
+
+                let path = session.path().clone();
+
+                let action = match session {
+                    SessionTab::Editor(_) => {
+                        let view = View::Editor(EditorTabData::default());
+                        return Task::done(Message::OpenTab(Some(path), view));
+                    }
+                    SessionTab::Table(_) => {
+                        let view = View::Table(TableTabData::default());
+                        return Task::done(Message::OpenTab(Some(path), view));
+                    }
+                    // A reconfigured chart replaces the tab it came from,
+                    // unless it was changed to a different kind of chart
+                    _ => match self.reconfiguring.take() {
+                        Some((idx, _)) if self.tabs.tab_kind(idx) == Some(session.kind()) => {
+                            FileIOAction::RefreshTab((session.kind(), idx, path))
+                        }
+                        _ => FileIOAction::NewTab((View::None, path)),
+                    },
+                };
+
+                self.parse_chart(session, action)
+            }
+            Message::PreviewConfig(path, config) => {
+                self.preview_generation += 1;
+                let generation = self.preview_generation;
+
+                Task::perform(tokio::time::sleep(PREVIEW_DELAY), move |_| {
+                    Message::BuildPreview(generation, path, config)
+                })
+            }
+            Message::BuildPreview(generation, path, config) => {
+                if generation != self.preview_generation {
+                    return Task::none();
+                }
+
+                Task::perform(async move { config.build(path) }, move |result| {
+                    Message::PreviewBuilt(generation, result)
+                })
+            }
+            Message::PreviewBuilt(generation, result) => {
+                if generation == self.preview_generation {
+                    let theme = self.theme();
+
+                    self.preview = result
+                        .map(|view| ChartPreview::new(view, theme))
+                        .transpose();
+                }
+
+                Task::none()
+            }
+            Message::Export(target) => self.export(target, None),
+            Message::ExportAgain => match self.settings.last_export.clone() {
+                Some(LastExport { target, directory }) => self.export(target, directory),
+                None => Task::none(),
+            },
+            Message::ExportTo(target, path) => {
+                let content = match target {
+                    ExportTarget::Image(format) => {
+                        return match self.chart_bytes(format) {
+                            Ok(bytes) => Task::perform(save_bytes(path, bytes), move |res| {
+                                Message::Exported(target, res)
+                            }),
+                            Err(err) => Task::done(Message::Error(err, true)),
+                        };
+                    }
+                    ExportTarget::MetadataFile => self.tabs.active_metadata(),
+                    ExportTarget::VisibleDataFile => self.tabs.active_visible_csv(),
+                    _ => self.tabs.active_csv(),
+                };
+
+                match content {
+                    Some(content) => Task::perform(save_file(Some(path), content), move |res| {
+                        Message::Exported(target, res.map(|(path, _)| path))
+                    }),
+                    None => Task::none(),
+                }
+            }
+            Message::Exported(target, Ok(path)) => {
+                self.remember_export(LastExport {
+                    target,
+                    directory: path.parent().map(|dir| dir.to_path_buf()),
+                });
+
+                let toast = Toast {
+                    status: Status::Success,
+                    body: format!("Exported to {}", path.display()),
+                    action: None,
+                };
+                self.push_toast(toast);
+                Task::none()
+            }
+            Message::Exported(_, Err(err)) => {
+                Task::perform(async { err }, |error| Message::Error(error, true))
+            }
+            Message::LoadAllRows => match self.tabs.load_all_active_rows() {
+                Ok(()) => Task::none(),
+                Err(err) => Task::done(Message::Error(err, true)),
+            },
+            Message::ChooseMetadataPath(source) => {
+                let name = source
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| format!("{stem}.json"))
+                    .unwrap_or(String::from("metadata.json"));
+
+                Task::perform(pick_save_path(name), |res| match res {
+                    Ok(path) => Message::ExportMetadata(path),
+                    Err(AppError::FileDialogClosed) => Message::None,
+                    Err(err) => Message::Error(err, true),
+                })
+            }
+            Message::ExportMetadata(path) => match self.tabs.active_metadata() {
+                Some(content) => {
+                    Task::perform(save_file(Some(path), content), Message::MetadataExported)
+                }
+                None => Task::none(),
+            },
+            Message::MetadataExported(Ok((path, _))) => {
+                let toast = Toast {
+                    status: Status::Success,
+                    body: format!("Metadata exported to {}", path.display()),
+                    action: None,
+                };
+                self.push_toast(toast);
+                Task::none()
+            }
+            Message::MetadataExported(Err(err)) => {
+                Task::perform(async { err }, |error| Message::Error(error, true))
+            }
+            Message::ChooseImagePath(source) => Task::perform(
+                pick_save_path(image_name(&source, ImageFormat::Png)),
+                |res| match res {
+                    Ok(path) => Message::ExportImage(path),
+                    Err(AppError::FileDialogClosed) => Message::None,
+                    Err(err) => Message::Error(err, true),
+                },
+            ),
+            Message::ExportImage(path) => self.export_chart(path, ImageFormat::Png),
+            Message::ChoosePdfPath(source) => Task::perform(
+                pick_save_path(image_name(&source, ImageFormat::Pdf)),
+                |res| match res {
+                    Ok(path) => Message::ExportPdf(path),
+                    Err(AppError::FileDialogClosed) => Message::None,
+                    Err(err) => Message::Error(err, true),
+                },
+            ),
+            Message::ExportPdf(path) => self.export_chart(path, ImageFormat::Pdf),
+            Message::ImageExported(Ok(path)) => {
+                let toast = Toast {
+                    status: Status::Success,
+                    body: format!("Chart exported to {}", path.display()),
+                    action: None,
+                };
+                self.push_toast(toast);
+                Task::none()
             }
+            Message::ImageExported(Err(err)) => Task::done(Message::Error(err, true)),
             Message::AddToast(toast) => {
                 self.push_toast(toast);
                 Task::none()
@@ -1157,6 +3907,25 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                 self.toasts.remove(index);
                 Task::none()
             }
+            Message::ToastAction(index) => match self.toasts.remove(index).action {
+                Some((_, message)) => Task::done(*message),
+                None => Task::none(),
+            },
+            Message::CopyToast(index) => match self.toasts.get(index) {
+                Some(toast) => iced::clipboard::write(toast.body.clone()),
+                None => Task::none(),
+            },
+            Message::SideMenuToggled(collapsed) => {
+                self.settings.side_menu_collapsed = collapsed;
+                if let Some(settings) = self.new_settings.as_mut() {
+                    settings.side_menu_collapsed = collapsed;
+                }
+
+                if let Err(err) = self.settings.store() {
+                    warn!("Could not store settings: {}", err.message());
+                }
+                Task::none()
+            }
             Message::OpenAboutDialog => {
                 self.dialog_view = DialogView::About;
                 self.info_log("About dialog open");
@@ -1176,6 +3945,25 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                 }
                 None => Task::none(),
             },
+            Message::Reconfigure => {
+                if self.dialog_view == DialogView::Wizard {
+                    return Task::none();
+                }
+
+                let Some(idx) = self.tabs.active_tab_idx() else {
+                    return Task::none();
+                };
+
+                match self.tabs.tab_session(idx) {
+                    Some(SessionTab::Editor(_) | SessionTab::Table(_)) | None => Task::none(),
+                    Some(session) => {
+                        self.file_path = Some(session.path().clone());
+                        self.reconfiguring = Some((idx, session));
+                        self.dialog_view = DialogView::Wizard;
+                        Task::none()
+                    }
+                }
+            }
             Message::CloseWindow(id) => {
                 self.info_log(format!("Closing window with Id: {id}"));
                 window::close(id)
@@ -1196,6 +3984,64 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                     let save_message = self.save_helper(self.tabs.active_path());
                     Task::perform(async { save_message }, |msg| msg)
                 }
+                Key::Named(key::Named::Tab) if modifiers.command() => {
+                    self.cycle_tabs(!modifiers.shift())
+                }
+                Key::Character(s) if modifiers.command() && tab_number(s.as_str()).is_some() => {
+                    match tab_number(s.as_str()) {
+                        Some(idx) if idx < self.tabs.len() => {
+                            self.update_tabs(TabsMessage::TabSelected(idx))
+                        }
+                        _ => Task::none(),
+                    }
+                }
+                Key::Character(s) if modifiers.command() && is_undo_key(s.as_str()) => {
+                    let redo = s.as_str() == "y" || modifiers.shift();
+                    self.tabs.undo(redo);
+                    Task::none()
+                }
+                // Pasting anywhere else is left to the focused widget
+                Key::Character(s)
+                    if modifiers.command()
+                        && s.as_str() == "v"
+                        && self.tabs.is_empty()
+                        && self.dialog_view == DialogView::None =>
+                {
+                    Task::done(Message::Paste)
+                }
+                Key::Character(s) if modifiers.command() && matches!(s.as_str(), "f" | "h") => {
+                    match self.tabs.open_find(s.as_str() == "h") {
+                        Some(id) => text_input::focus(id),
+                        None => Task::none(),
+                    }
+                }
+                Key::Named(key::Named::Escape) => match (&self.dialog_view, &self.context) {
+                    (DialogView::Wizard, _) => Task::done(Message::CloseWizard),
+                    (DialogView::About, _) => Task::done(Message::CloseAboutDialog),
+                    (DialogView::None, MenuContext::Settings) => Task::done(
+                        Message::Settings(SettingsMessage::Cancel)
+                            .close_context(MenuContext::Settings),
+                    ),
+                    (DialogView::None, MenuContext::File) => {
+                        Task::done(Message::CloseContext(MenuContext::File))
+                    }
+                    (DialogView::None, MenuContext::None) => {
+                        if self.tabs.close_find() {
+                            widget::focus_previous()
+                        } else {
+                            Task::none()
+                        }
+                    }
+                },
+                Key::Named(key::Named::Enter)
+                    if self.dialog_view == DialogView::None
+                        && self.context == MenuContext::Settings =>
+                {
+                    Task::done(
+                        Message::Settings(SettingsMessage::Save)
+                            .close_context(MenuContext::Settings),
+                    )
+                }
                 Key::Named(key::Named::Tab) => {
                     if modifiers.shift() {
                         widget::focus_previous()
@@ -1203,8 +4049,56 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                         widget::focus_next()
                     }
                 }
+                Key::Character(s)
+                    if modifiers.command() && modifiers.alt() && s.as_str() == "w" =>
+                {
+                    let word_wrap = !self.settings.word_wrap;
+                    self.settings.word_wrap = word_wrap;
+                    self.tabs.word_wrap(word_wrap);
+
+                    // An open settings menu would otherwise undo it on save
+                    if let Some(settings) = self.new_settings.as_mut() {
+                        settings.word_wrap = word_wrap;
+                    }
+
+                    if let Err(err) = self.settings.store() {
+                        warn!("Could not store settings: {}", err.message());
+                    }
+
+                    Task::none()
+                }
+                Key::Character(s) if modifiers.alt() => {
+                    self.show_access_keys = false;
+                    self.tabs.show_access_keys(false);
+
+                    match s.as_str() {
+                        "f" => Task::done(Message::MenuContext(MenuContext::File)),
+                        "i" => Task::done(Message::OpenAboutDialog),
+                        "s" => Task::done(Message::MenuContext(MenuContext::Settings)),
+                        "t" if self.tabs.active_tab_has_config() => {
+                            self.update_tabs(TabsMessage::ToggleConfig)
+                        }
+                        _ => Task::none(),
+                    }
+                }
                 _ => Task::none(),
             },
+            Message::ModifiersChanged(modifiers) => {
+                self.show_access_keys = modifiers.alt();
+                self.tabs.show_access_keys(self.show_access_keys);
+                Task::none()
+            }
+            Message::WindowChanged(id) => {
+                window::get_scale_factor(id).map(Message::ScaleFactorChanged)
+            }
+            Message::ScaleFactorChanged(scale_factor) => {
+                if self.scale_factor != scale_factor {
+                    self.info_log(format!("Scale factor changed to {scale_factor}"));
+                    self.scale_factor = scale_factor;
+                    self.tabs.set_scale_factor(scale_factor);
+                }
+                Task::none()
+            }
             Message::Ready => {
                 self.is_ready = true;
                 self.tabs.set_theme(self.theme());
@@ -1214,6 +4108,10 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                 self.context = context;
                 if context == MenuContext::Settings {
                     self.new_settings = Some(self.settings.clone());
+                    self.palette_inputs = PaletteColor::ALL
+                        .map(|part| hex_color(self.settings.custom_palette.get(part)));
+                    self.timeout_inputs =
+                        Status::ALL.map(|status| self.settings.timeouts.get(status).to_string());
                     self.info_log("Settings Dialog Opened");
                 }
                 Task::none()
@@ -1243,15 +4141,21 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             return content.into();
         }
 
-        let status_bar = self.status_bar().height(30.0);
+        let status_bar = self.status_bar().height(self.scaled(30.0));
 
         let content = container(if self.tabs.is_empty() {
-            Into::<Element<'_, Message>>::into(home_view())
+            let session_tabs = self.last_session.as_ref().map(Session::len);
+            Into::<Element<'_, Message>>::into(home_view(session_tabs))
         } else {
             column!(self.tabs.view(Message::TabsMessage), status_bar).into()
         })
         .height(Length::Fill);
 
+        let content: Element<'_, Message> = match self.loads_view() {
+            Some(loads) => stack!(content, loads).into(),
+            None => content.into(),
+        };
+
         let cross_axis = row!(self.side_menu(), self.handle_context(), content);
 
         let main_axis = column!(cross_axis);
@@ -1264,11 +4168,15 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
                     .clone()
                     .expect("File path was empty for Wizard")
                     .clone();
-                let wizard = Wizard::new(file, Message::WizardSubmit, |error| {
-                    Message::Error(error, false)
-                })
-                .on_reselect(Message::SelectFile)
-                .on_cancel(Message::CloseWizard);
+                let wizard = Wizard::new(file, Message::WizardSubmit)
+                    .initial(self.reconfiguring.clone().map(|(_, session)| session))
+                    .on_preview(Message::PreviewConfig)
+                    .preview(self.preview.as_ref())
+                    .row_limit(self.settings.row_limit)
+                    .flat(self.flat_ui())
+                    .on_reselect(Message::SelectFile)
+                    .on_locate(Message::GoToLine)
+                    .on_cancel(Message::CloseWizard);
                 Modal::new(main_axis, wizard)
                     .on_blur(Message::CloseWizard)
                     .into()
@@ -1282,9 +4190,11 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
             content,
             &self.toasts,
             Message::CloseToast,
+            Message::ToastAction,
+            Message::CopyToast,
             &self.theme_ref(),
         )
-        .timeout(self.timeout());
+        .timeouts(self.timeouts());
 
         container(content).height(Length::Fill).into()
     }
@@ -1293,16 +4203,322 @@ This app is meant to be a MOdern Data Visualisation (MODAV) tool split into 2 pa
         let close_window = window::close_requests().map(Message::WindowCloseRequested);
 
         let key_press = keyboard::on_key_press(|key, modifiers| match key {
-            Key::Named(key::Named::Save) | Key::Named(key::Named::Tab) => {
+            Key::Named(key::Named::Save)
+            | Key::Named(key::Named::Tab)
+            | Key::Named(key::Named::Enter) => Some(Message::KeyPressed(key, modifiers)),
+            Key::Character(ref s) if s.as_str() == "s" => Some(Message::KeyPressed(key, modifiers)),
+            Key::Character(_) if modifiers.alt() => Some(Message::KeyPressed(key, modifiers)),
+            Key::Character(ref s) if modifiers.command() && tab_number(s.as_str()).is_some() => {
                 Some(Message::KeyPressed(key, modifiers))
             }
-            Key::Character(ref s) if s.as_str() == "s" => Some(Message::KeyPressed(key, modifiers)),
+            Key::Character(ref s) if modifiers.command() && is_undo_key(s.as_str()) => {
+                Some(Message::KeyPressed(key, modifiers))
+            }
+            Key::Character(ref s)
+                if modifiers.command() && matches!(s.as_str(), "f" | "h" | "v") =>
+            {
+                Some(Message::KeyPressed(key, modifiers))
+            }
+            _ => None,
+        });
+
+        let modifiers = event::listen_with(|event, _status, window| match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            // Text inputs capture Escape, so it is listened for here instead
+            // of with the other shortcuts
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: key @ Key::Named(key::Named::Escape),
+                modifiers,
+                ..
+            }) => Some(Message::KeyPressed(key, modifiers)),
+            // Moving between monitors shows up as a move or a resize
+            Event::Window(window::Event::Moved(_)) | Event::Window(window::Event::Resized(_)) => {
+                Some(Message::WindowChanged(window))
+            }
+            Event::Window(window::Event::FileDropped(path)) => {
+                Some(Message::FilesDropped(vec![path]))
+            }
             _ => None,
         });
 
-        Subscription::batch(vec![close_window, key_press])
+        let disk_changes = if self.settings.watch_files {
+            let mut paths = self.tabs.paths();
+            paths.sort();
+            paths.dedup();
+
+            // The watcher restarts whenever the open files change
+            Subscription::run_with_id(paths.clone(), watch_files(paths))
+                .map(Message::FileChangedOnDisk)
+        } else {
+            Subscription::none()
+        };
+
+        let auto_save = if self.settings.auto_save {
+            iced::time::every(Duration::from_secs(self.settings.auto_save_interval))
+                .map(|_| Message::AutoSave)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch(vec![
+            close_window,
+            key_press,
+            modifiers,
+            disk_changes,
+            auto_save,
+        ])
         //event::listen()
         //    .with(self.main_window_id.clone())
         //    .map(|id, event| Message::Event(id, event))
     }
 }
+
+/// Suggested name of a chart of `source` exported as `format`
+fn image_name(source: &Path, format: ImageFormat) -> String {
+    let stem = source
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("chart");
+
+    format!("{stem}.{}", format.extension())
+}
+
+/// Toast action showing where in a file an error is
+fn located_action(location: &Location) -> (String, Box<Message>) {
+    (
+        format!("Go to line {}", location.line),
+        Box::new(Message::GoToLine(location.file.clone(), location.line)),
+    )
+}
+
+/// When the file at `path` was last modified, if it can be read
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Whether `key` undoes or redoes with the command modifier held. Shift
+/// may give an uppercase Z
+fn is_undo_key(key: &str) -> bool {
+    matches!(key, "z" | "Z" | "y")
+}
+
+/// Returns the index of the tab a Ctrl+1..9 shortcut jumps to
+fn tab_number(key: &str) -> Option<usize> {
+    match key.parse::<usize>() {
+        Ok(n @ 1..=9) => Some(n - 1),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_settings_round_trip() {
+    let mut settings = Settings::new(
+        Theme::GruvboxLight,
+        PathBuf::from("C:\\logs\\\"modav\".log"),
+    );
+    settings.timeouts.set(Status::Error, 0);
+    settings.timeouts.set(Status::Info, 5);
+    settings.flat_ui = true;
+    settings.highlighting = false;
+    settings.word_wrap = false;
+    settings.watch_files = false;
+    settings.restore_session = false;
+    settings.auto_save = true;
+    settings.auto_save_interval = 90;
+    settings.side_menu_collapsed = true;
+    settings.ui_scale = 1.25;
+    settings.row_limit.max_rows = 200;
+    settings.row_limit.strategy = SamplingStrategy::EveryNth;
+    settings.pdf_page = PageSize::Letter;
+    settings.pdf_dpi = 300;
+    settings.image_size = Size::new(1920, 1080);
+    settings.last_export = Some(LastExport {
+        target: ExportTarget::Image(ImageFormat::Svg),
+        directory: Some(PathBuf::from("/home/user/charts")),
+    });
+
+    assert_eq!(
+        Settings::deserialize(&settings.serialize()),
+        (settings, Vec::new())
+    );
+
+    assert_eq!(
+        Settings::deserialize("timeout = 7").0.timeouts,
+        Timeouts::uniform(7)
+    );
+
+    // Invalid entries are skipped, keeping the settings around them
+    let (stored, skipped) = Settings::deserialize(
+        "timeout_warn = soon\ntheme\nflat_ui = true\nui_scale = 10\npdf_dpi = 5\nimage_width = 0",
+    );
+    assert!(stored.flat_ui);
+    assert_eq!(stored.ui_scale, Settings::default().ui_scale);
+    assert_eq!(
+        skipped,
+        [
+            "timeout_warn = soon",
+            "theme",
+            "ui_scale = 10",
+            "pdf_dpi = 5",
+            "image_width = 0"
+        ]
+    );
+
+    // The custom theme is rebuilt from its colors, in whatever order
+    let mut custom = Settings::default();
+    custom.custom_palette.primary = Color::from_rgb8(0xff, 0x88, 0x00);
+    custom.theme = custom.custom_palette.theme();
+    let (stored, _) = Settings::deserialize(&custom.serialize());
+    assert!(is_custom(&stored.theme));
+    assert_eq!(
+        stored.theme.palette().primary,
+        custom.custom_palette.primary
+    );
+    assert_eq!(
+        Settings::deserialize("custom_text = \"not a color\"").1,
+        ["custom_text = \"not a color\""]
+    );
+    assert_eq!(
+        Settings::deserialize("# comment\nunknown = 1\n"),
+        (Settings::default(), Vec::new())
+    );
+}
+
+#[test]
+fn test_args_parse() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|arg| arg.to_string()));
+
+    assert_eq!(args(&[]), Ok(Args::default()));
+    assert_eq!(
+        args(&["data.csv", "--bar", "notes.txt"]),
+        Ok(Args {
+            view: Some(ViewType::BarChart),
+            files: vec![PathBuf::from("data.csv"), PathBuf::from("notes.txt")],
+            stdin: false,
+        })
+    );
+    assert_eq!(
+        args(&["--", "--line", "-"]),
+        Ok(Args {
+            view: None,
+            files: vec![PathBuf::from("--line"), PathBuf::from("-")],
+            stdin: false,
+        })
+    );
+    assert_eq!(
+        args(&["-", "--table"]),
+        Ok(Args {
+            view: Some(ViewType::Table),
+            files: vec![],
+            stdin: true,
+        })
+    );
+    assert!(args(&["--line", "--bar"]).is_err());
+    assert!(args(&["--unknown"]).is_err());
+}
+
+#[test]
+fn test_render_args_parse() {
+    let args = |list: &[&str]| RenderArgs::parse(list.iter().map(|arg| arg.to_string()));
+
+    assert_eq!(
+        args(&[
+            "--type",
+            "bar",
+            "--x",
+            "0",
+            "--y",
+            "2",
+            "--out",
+            "chart.png",
+            "input.csv"
+        ]),
+        Ok(RenderArgs {
+            view: ViewType::BarChart,
+            x: Some(0),
+            y: Some("2".into()),
+            width: None,
+            height: None,
+            out: PathBuf::from("chart.png"),
+            file: PathBuf::from("input.csv"),
+        })
+    );
+    assert_eq!(
+        args(&[
+            "input.csv",
+            "--out",
+            "chart.PDF",
+            "--type",
+            "stacked",
+            "--y",
+            "1:4"
+        ])
+        .and_then(|render| render.format()),
+        Ok(ImageFormat::Pdf)
+    );
+
+    assert_eq!(
+        args(&[
+            "--type",
+            "histogram",
+            "--width",
+            "640",
+            "--height",
+            " 480",
+            "--out",
+            "chart.png",
+            "input.csv"
+        ])
+        .map(|render| (render.width, render.height)),
+        Ok((Some(640), Some(480)))
+    );
+    assert!(args(&[
+        "--type",
+        "bar",
+        "--width",
+        "0",
+        "--out",
+        "chart.png",
+        "input.csv"
+    ])
+    .is_err());
+    assert!(args(&[
+        "--type",
+        "bar",
+        "--height",
+        "a",
+        "--out",
+        "chart.png",
+        "input.csv"
+    ])
+    .is_err());
+
+    assert!(args(&["--type", "bar", "--out", "chart.png"]).is_err());
+    assert!(args(&["--type", "pie", "--out", "chart.png", "input.csv"]).is_err());
+    assert!(args(&["--type", "bar", "--out", "chart.jpg", "input.csv"]).is_err());
+    assert!(args(&[
+        "--type",
+        "bar",
+        "--x",
+        "a",
+        "--out",
+        "chart.png",
+        "input.csv"
+    ])
+    .is_err());
+    assert!(args(&[
+        "--type",
+        "line",
+        "--x",
+        "0",
+        "--out",
+        "chart.png",
+        "input.csv"
+    ])
+    .is_err());
+    assert!(args(&["--type", "bar", "--out"]).is_err());
+}
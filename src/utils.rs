@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use modav_core::repr::sheet::error::Error;
 
+pub use formatting::{format_data, AxisFormat, NotationMode};
 pub use tooltip::tooltip;
 
 #[allow(dead_code)]
@@ -241,128 +242,2588 @@ pub mod coloring {
         assert_eq!(rbg.b, 0.375);
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    struct HSL {
+        /// The hue expressed as a fraction of 360 degrees
+        h: F2,
+        /// The saturation expressed as a fraction of 100
+        s: F2,
+        /// The lightness expressed as a fraction of 100
+        l: F2,
+    }
+
+    impl HSL {
+        fn new(hue: impl Into<F2>, saturation: impl Into<F2>, lightness: impl Into<F2>) -> Self {
+            Self {
+                h: hue.into(),
+                s: saturation.into(),
+                l: lightness.into(),
+            }
+        }
+    }
+
+    impl From<Color> for HSL {
+        fn from(value: Color) -> Self {
+            let cmin = f32::min(value.r, value.g.min(value.b));
+            let cmax = f32::max(value.r, value.g.max(value.b));
+            let cdiff = cmax - cmin;
+
+            let h = HSV::from(value).h;
+            let l = (cmax + cmin) / 2.0;
+
+            let s = if cdiff == 0.0 {
+                0.0
+            } else {
+                cdiff / (1.0 - f32::abs(2.0 * l - 1.0))
+            };
+
+            HSL::new(h, s, l)
+        }
+    }
+
+    impl From<HSL> for Color {
+        fn from(value: HSL) -> Self {
+            let s: f32 = value.s.into();
+            let l: f32 = value.l.into();
+
+            // HSL maps onto HSV with the same hue
+            let v = l + s * f32::min(l, 1.0 - l);
+            let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+
+            HSV::new(value.h, s, v).into()
+        }
+    }
+
+    #[test]
+    fn test_hsl_conversions() {
+        let hsl: HSL = color!(255, 0, 0).into();
+
+        assert_eq!(hsl.h, F2(0.0));
+        assert_eq!(hsl.s, F2(1.0));
+        assert_eq!(hsl.l, F2(0.5));
+
+        let rgb: Color = HSL::new(1.0 / 3.0, 1.0, 0.25).into();
+
+        assert_eq!(rgb.r, 0.0);
+        assert_eq!(rgb.g, 0.5);
+        assert_eq!(rgb.b, 0.0);
+
+        let rgb: Color = HSL::new(0.5, 0.0, 1.0).into();
+
+        assert_eq!(rgb, Color::WHITE);
+    }
+
     #[derive(Debug, Default, Clone, Copy)]
     enum ColoringMode {
-        /// Generated colors can vary wildly from each other
+        /// Generated colors are spread across the whole hue wheel
         #[default]
         Normal,
-        /// Generated colors grow gradually darker or lighter depending on the
-        /// theme
+        /// Generated colors walk gradually along a portion of the hue wheel,
+        /// so their order can still be told apart
         Gradual,
     }
 
-    #[derive(Clone, Copy, Debug)]
-    pub struct ColorEngine {
-        seed: HSV,
-        is_dark: bool,
-        random: f32,
-        mode: ColoringMode,
-        stable_h: f32,
-        count: u32,
+    /// Generates distinguishable colors for the series of a chart.
+    ///
+    /// Colors share a saturation and lightness derived from the theme and
+    /// differ in hue. When the number of series is given with
+    /// [`ColorEngine::count`], the hues are evenly spaced.
+    #[derive(Clone, Copy, Debug)]
+    pub struct ColorEngine {
+        /// The hue generated colors start from
+        base: f32,
+        saturation: f32,
+        lightness: f32,
+        is_dark: bool,
+        random: f32,
+        mode: ColoringMode,
+        count: Option<u32>,
+        accent: Option<f32>,
+        generated: u32,
+    }
+
+    impl ColorEngine {
+        /// Hue step used when the number of colors is unknown. Never lands
+        /// on a previous hue.
+        const GOLDEN_RATIO: f32 = 0.618034;
+        const SATURATION: f32 = 0.65;
+        /// How far the lightness of generated colors is from the theme's
+        /// background
+        const LIGHTNESS_OFFSET: f32 = 0.45;
+        /// Lightness shift of each lap around the hue wheel, once more colors
+        /// than the count are generated
+        const LAP_SHIFT: f32 = 0.12;
+        /// Portion of the hue wheel walked by gradual colors
+        const GRADUAL_ARC: f32 = 0.5;
+        /// How much the lightness of gradual colors changes from first to last
+        const GRADUAL_LIGHTNESS: f32 = 0.2;
+        /// How far, in hue, accented colors may stray from the accent
+        const ACCENT_SPREAD: f32 = 0.16;
+
+        pub fn new_with_seed<'a>(theme: &'a Theme, seed: f32) -> Self {
+            let rng = {
+                let mult = 10000.0;
+
+                (seed * mult).trunc() / mult
+            };
+            let palette = theme.extended_palette();
+            let is_dark = palette.is_dark;
+
+            let base = {
+                let seed: HSV = Theme::default()
+                    .extended_palette()
+                    .secondary
+                    .base
+                    .color
+                    .into();
+                let seed: f32 = seed.h.into();
+                (rng + seed) % 1.0
+            };
+
+            let lightness = {
+                let background: HSL = palette.background.base.color.into();
+                let background: f32 = background.l.into();
+
+                if is_dark {
+                    (background + Self::LIGHTNESS_OFFSET).min(0.7)
+                } else {
+                    (background - Self::LIGHTNESS_OFFSET).max(0.35)
+                }
+            };
+
+            Self {
+                base,
+                saturation: Self::SATURATION,
+                lightness,
+                is_dark,
+                random: rng,
+                mode: ColoringMode::Normal,
+                count: None,
+                accent: None,
+                generated: 0,
+            }
+        }
+
+        pub fn new<'a>(theme: &'a Theme) -> Self {
+            let seed: f32 = thread_rng().gen();
+            Self::new_with_seed(theme, seed)
+        }
+
+        /// Sets the number of colors expected, so their hues can be evenly
+        /// spaced. Has no effect if `count` is 0.
+        pub fn count(mut self, count: u32) -> Self {
+            if count > 0 {
+                self.count = Some(count);
+            }
+
+            self
+        }
+
+        pub fn gradual(mut self, gradual: bool) -> Self {
+            self.mode = if gradual {
+                ColoringMode::Gradual
+            } else {
+                ColoringMode::Normal
+            };
+
+            self
+        }
+
+        /// Generates colors around the `hue` family, in the range [0, 1],
+        /// rather than around the theme. Has no effect if `hue` is None.
+        pub fn accent(mut self, hue: Option<f32>) -> Self {
+            if let Some(hue) = hue {
+                let hue = hue.rem_euclid(1.0);
+                self.accent = Some(hue);
+                self.base = hue;
+            }
+
+            self
+        }
+
+        pub fn seed(&self) -> f32 {
+            self.random
+        }
+
+        /// Position, in the range [0, 1), of the `index`th color along the
+        /// hues available to the engine, and the lap around them it is on
+        fn position(&self, index: u32) -> (f32, u32) {
+            match self.count {
+                Some(count) => {
+                    let lap = index / count;
+                    let offset = (lap as f32 * Self::GOLDEN_RATIO) % 1.0;
+                    (((index % count) as f32 + offset) / count as f32, lap)
+                }
+                None => ((self.random + index as f32 * Self::GOLDEN_RATIO) % 1.0, 0),
+            }
+        }
+
+        /// Generates a Color taking into consideration previously generated colors
+        fn generate(&mut self) -> Color {
+            let (position, lap) = self.position(self.generated);
+            self.generated += 1;
+
+            let h = match (self.accent, self.mode) {
+                (Some(accent), _) => accent + (position - 0.5) * Self::ACCENT_SPREAD,
+                (None, ColoringMode::Normal) => self.base + position,
+                (None, ColoringMode::Gradual) => self.base + position * Self::GRADUAL_ARC,
+            }
+            .rem_euclid(1.0);
+
+            let lap_shift = match lap % 3 {
+                0 => 0.0,
+                1 => Self::LAP_SHIFT,
+                _ => -Self::LAP_SHIFT,
+            };
+
+            let gradual_shift = match self.mode {
+                ColoringMode::Normal => 0.0,
+                ColoringMode::Gradual => {
+                    let shift = (position - 0.5) * Self::GRADUAL_LIGHTNESS;
+                    if self.is_dark {
+                        shift
+                    } else {
+                        -shift
+                    }
+                }
+            };
+
+            let l = (self.lightness + lap_shift + gradual_shift).clamp(0.2, 0.8);
+
+            HSL::new(h, self.saturation, l).into()
+        }
+    }
+
+    impl Iterator for ColorEngine {
+        type Item = Color;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            Some(self.generate())
+        }
+    }
+
+    #[test]
+    fn test_evenly_spaced_hues() {
+        let hues = ColorEngine::new_with_seed(&Theme::Dark, 0.3)
+            .count(4)
+            .take(4)
+            .map(|color| f32::from(HSL::from(color).h))
+            .collect::<Vec<_>>();
+
+        for (one, two) in hues.iter().zip(hues.iter().skip(1)) {
+            let step = (two - one).rem_euclid(1.0);
+            assert!((step - 0.25).abs() < 0.01, "{hues:?}");
+        }
+    }
+}
+
+pub mod formatting {
+    use std::fmt::{self, Display};
+
+    use modav_core::repr::Data;
+
+    /// How numeric values are rendered on charts
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub enum NotationMode {
+        /// Switches between plain, scientific and SI notation depending on the
+        /// magnitude of the value
+        #[default]
+        Auto,
+        Plain,
+        Scientific,
+        SI,
+    }
+
+    impl NotationMode {
+        pub const ALL: [Self; 4] = [Self::Auto, Self::Plain, Self::Scientific, Self::SI];
+
+        /// Resolves [`NotationMode::Auto`] into a concrete mode for `value`.
+        fn resolve(self, value: f64, thresholds: Thresholds) -> Self {
+            match self {
+                Self::Auto => {
+                    let magnitude = value.abs();
+
+                    if magnitude == 0.0 || !magnitude.is_finite() {
+                        Self::Plain
+                    } else if magnitude >= thresholds.large {
+                        Self::SI
+                    } else if magnitude < thresholds.small {
+                        Self::Scientific
+                    } else {
+                        Self::Plain
+                    }
+                }
+                mode => mode,
+            }
+        }
+    }
+
+    impl Display for NotationMode {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Auto => "Auto",
+                Self::Plain => "Plain",
+                Self::Scientific => "Scientific",
+                Self::SI => "SI Prefix",
+            }
+            .fmt(f)
+        }
+    }
+
+    /// How the numbers along a chart axis are written
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct AxisFormat {
+        pub notation: NotationMode,
+        /// Fixed number of decimal places. Numbers are written as short as
+        /// possible if unset.
+        pub decimals: Option<usize>,
+        /// Groups the digits of plain numbers in thousands
+        pub thousands: bool,
+        /// Where [`NotationMode::Auto`] switches away from plain numbers
+        pub thresholds: Thresholds,
+    }
+
+    impl AxisFormat {
+        /// Most decimal places an axis can be given
+        pub const MAX_DECIMALS: usize = 10;
+    }
+
+    impl From<NotationMode> for AxisFormat {
+        fn from(notation: NotationMode) -> Self {
+            Self {
+                notation,
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Magnitudes at which [`NotationMode::Auto`] stops using plain notation
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Thresholds {
+        /// Non-zero magnitudes below this use scientific notation
+        pub small: f64,
+        /// Magnitudes at or above this use SI prefixes
+        pub large: f64,
+    }
+
+    impl Thresholds {
+        /// Magnitudes offered for [`Thresholds::small`]
+        pub const SMALL: [f64; 4] = [1e-2, 1e-3, 1e-4, 1e-6];
+        /// Magnitudes offered for [`Thresholds::large`]
+        pub const LARGE: [f64; 4] = [1e3, 1e4, 1e6, 1e9];
+    }
+
+    impl Default for Thresholds {
+        fn default() -> Self {
+            Self {
+                small: 1e-3,
+                large: 1e6,
+            }
+        }
+    }
+
+    const PREFIXES: [(i32, &str); 9] = [
+        (12, "T"),
+        (9, "G"),
+        (6, "M"),
+        (3, "k"),
+        (0, ""),
+        (-3, "m"),
+        (-6, "µ"),
+        (-9, "n"),
+        (-12, "p"),
+    ];
+
+    /// Removes trailing zeros, and a dangling decimal point, from a decimal
+    /// number string.
+    fn trim_zeros(number: &str) -> &str {
+        if number.contains('.') {
+            number.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            number
+        }
+    }
+
+    /// Separates the digits before the decimal point of a plain number string
+    /// into groups of three.
+    fn group_thousands(number: &str) -> String {
+        let (sign, number) = match number.strip_prefix('-') {
+            Some(number) => ("-", number),
+            None => ("", number),
+        };
+        let (integer, fraction) = number.split_at(number.find('.').unwrap_or(number.len()));
+
+        let mut grouped = String::new();
+        for (idx, digit) in integer.chars().enumerate() {
+            if idx > 0 && (integer.len() - idx) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+
+        format!("{sign}{grouped}{fraction}")
+    }
+
+    fn scientific(value: f64) -> String {
+        let formatted = format!("{value:.3e}");
+
+        match formatted.split_once('e') {
+            Some((mantissa, exponent)) => format!("{}e{}", trim_zeros(mantissa), exponent),
+            None => formatted,
+        }
+    }
+
+    fn si(value: f64) -> String {
+        if value == 0.0 || !value.is_finite() {
+            return value.to_string();
+        }
+
+        let mut exponent = ((value.abs().log10() / 3.0).floor() as i32 * 3).clamp(-12, 12);
+
+        // Rounding can carry the number up to the next prefix, as 999999
+        // does to 1000k, so the prefix is picked again from the rounded value
+        let formatted = loop {
+            let formatted = format!("{:.2}", value / 10f64.powi(exponent));
+
+            match formatted.parse::<f64>() {
+                Ok(rounded) if rounded.abs() >= 1000.0 && exponent < 12 => exponent += 3,
+                _ => break formatted,
+            }
+        };
+
+        let prefix = PREFIXES
+            .iter()
+            .find(|(exp, _)| *exp == exponent)
+            .map(|(_, prefix)| *prefix)
+            .unwrap_or_default();
+
+        format!("{}{}", trim_zeros(&formatted), prefix)
+    }
+
+    /// Formats `value` according to `mode`, using `thresholds` to resolve
+    /// [`NotationMode::Auto`].
+    pub fn format_number(value: f64, mode: NotationMode, thresholds: Thresholds) -> String {
+        match mode.resolve(value, thresholds) {
+            NotationMode::Scientific => scientific(value),
+            NotationMode::SI => si(value),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Returns the numeric value of a [`Data`] point, if any.
+    pub fn numeric_value(data: &Data) -> Option<f64> {
+        match data {
+            Data::Integer(value) => Some(*value as f64),
+            Data::Number(value) => Some(*value as f64),
+            Data::Float(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Formats a [`Data`] point for display. Non-numeric data is displayed
+    /// as is. Fixed decimal places apply to plain and scientific notation.
+    pub fn format_data(data: &Data, format: &AxisFormat) -> String {
+        let Some(value) = numeric_value(data) else {
+            return data.to_string();
+        };
+
+        match (
+            format.notation.resolve(value, format.thresholds),
+            format.decimals,
+        ) {
+            (NotationMode::Scientific, Some(decimals)) => format!("{value:.decimals$e}"),
+            (NotationMode::Scientific, None) => scientific(value),
+            (NotationMode::SI, _) => si(value),
+            (_, decimals) => {
+                let plain = match decimals {
+                    Some(decimals) => format!("{value:.decimals$}"),
+                    // Using the data's own representation avoids the noise
+                    // of widening floats
+                    None => data.to_string(),
+                };
+
+                if format.thousands {
+                    group_thousands(&plain)
+                } else {
+                    plain
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plain_notation() {
+        let mode = NotationMode::Plain;
+
+        assert_eq!(
+            format_data(&Data::Integer(12000000), &mode.into()),
+            "12000000"
+        );
+        assert_eq!(format_data(&Data::Float(0.5), &mode.into()), "0.5");
+        assert_eq!(format_number(-42.0, mode, Thresholds::default()), "-42");
+    }
+
+    #[test]
+    fn test_scientific_notation() {
+        let mode = NotationMode::Scientific;
+        let thresholds = Thresholds::default();
+
+        assert_eq!(format_number(0.0000023, mode, thresholds), "2.3e-6");
+        assert_eq!(format_number(12000000.0, mode, thresholds), "1.2e7");
+        assert_eq!(format_number(-1500.0, mode, thresholds), "-1.5e3");
+        assert_eq!(format_number(1.0, mode, thresholds), "1e0");
+    }
+
+    #[test]
+    fn test_si_notation() {
+        let mode = NotationMode::SI;
+        let thresholds = Thresholds::default();
+
+        assert_eq!(format_number(12000000.0, mode, thresholds), "12M");
+        assert_eq!(format_number(1500.0, mode, thresholds), "1.5k");
+        assert_eq!(format_number(-2500000000.0, mode, thresholds), "-2.5G");
+        assert_eq!(format_number(0.0000023, mode, thresholds), "2.3µ");
+        assert_eq!(format_number(42.0, mode, thresholds), "42");
+        assert_eq!(format_number(0.0, mode, thresholds), "0");
+
+        // Rounding up to the next prefix
+        assert_eq!(format_number(999999.0, mode, thresholds), "1M");
+        assert_eq!(format_number(-999.999, mode, thresholds), "-1k");
+        assert_eq!(format_number(0.000999999, mode, thresholds), "1m");
+        assert_eq!(format_number(999.994, mode, thresholds), "999.99");
+    }
+
+    #[test]
+    fn test_auto_notation() {
+        let mode = NotationMode::Auto;
+        let thresholds = Thresholds::default();
+
+        assert_eq!(format_number(0.0000023, mode, thresholds), "2.3e-6");
+        assert_eq!(format_number(12000000.0, mode, thresholds), "12M");
+        assert_eq!(format_number(0.25, mode, thresholds), "0.25");
+        assert_eq!(format_number(999999.0, mode, thresholds), "999999");
+        assert_eq!(format_number(0.0, mode, thresholds), "0");
+
+        assert_eq!(format_data(&Data::Integer(1000000), &mode.into()), "1M");
+        assert_eq!(format_data(&Data::Integer(350), &mode.into()), "350");
+
+        let thresholds = Thresholds {
+            small: 1e-1,
+            large: 1e3,
+        };
+
+        assert_eq!(format_number(0.05, mode, thresholds), "5e-2");
+        assert_eq!(format_number(4500.0, mode, thresholds), "4.5k");
+    }
+
+    #[test]
+    fn test_axis_format() {
+        let format = AxisFormat {
+            notation: NotationMode::Plain,
+            decimals: Some(2),
+            thousands: true,
+            ..Default::default()
+        };
+
+        assert_eq!(format_data(&Data::Float(12.339999), &format), "12.34");
+        assert_eq!(
+            format_data(&Data::Integer(1234567), &format),
+            "1,234,567.00"
+        );
+        assert_eq!(format_data(&Data::Float(-9876.5), &format), "-9,876.50");
+        assert_eq!(format_data(&Data::Text("a".into()), &format), "a");
+
+        let format = AxisFormat {
+            decimals: None,
+            ..format
+        };
+        assert_eq!(format_data(&Data::Integer(999), &format), "999");
+        assert_eq!(format_data(&Data::Integer(-1000), &format), "-1,000");
+
+        let format = AxisFormat {
+            notation: NotationMode::Scientific,
+            decimals: Some(1),
+            thousands: false,
+            ..Default::default()
+        };
+        assert_eq!(format_data(&Data::Integer(1500), &format), "1.5e3");
+
+        let format = AxisFormat {
+            thresholds: Thresholds {
+                small: 1e-2,
+                large: 1e3,
+            },
+            ..NotationMode::Auto.into()
+        };
+        assert_eq!(format_data(&Data::Integer(4500), &format), "4.5k");
+        assert_eq!(format_data(&Data::Float(0.005), &format), "5e-3");
+        assert_eq!(format_data(&Data::Integer(450), &format), "450");
+    }
+}
+
+pub mod stats {
+    /// Summary statistics of a series of values
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Stats {
+        pub count: usize,
+        pub sum: f64,
+        pub mean: f64,
+        pub median: f64,
+        pub min: f64,
+        pub max: f64,
+        /// Population standard deviation
+        pub std_dev: f64,
+    }
+
+    /// Computes the summary statistics of `values`. Non-finite values are
+    /// ignored. All fields are zero if there are no values.
+    pub fn series_stats(values: &[f64]) -> Stats {
+        let mut values = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return Stats::default();
+        }
+
+        values.sort_by(f64::total_cmp);
+
+        let count = values.len();
+        let sum: f64 = values.iter().sum();
+        let mean = sum / count as f64;
+
+        let median = if count % 2 == 0 {
+            (values[count / 2 - 1] + values[count / 2]) / 2.0
+        } else {
+            values[count / 2]
+        };
+
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Stats {
+            count,
+            sum,
+            mean,
+            median,
+            min: values[0],
+            max: values[count - 1],
+            std_dev: variance.sqrt(),
+        }
+    }
+
+    /// The spread of a series as drawn by a box plot. Whiskers reach the
+    /// furthest values within 1.5 times the interquartile range of the box,
+    /// values beyond them are outliers.
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct BoxSummary {
+        pub lower: f64,
+        pub q1: f64,
+        pub median: f64,
+        pub q3: f64,
+        pub upper: f64,
+        pub outliers: Vec<f64>,
+    }
+
+    /// Summarizes `values` for a box plot. Non-finite values are ignored.
+    /// Returns `None` if there are no values.
+    pub fn box_summary(values: &[f64]) -> Option<BoxSummary> {
+        let mut values = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_by(f64::total_cmp);
+
+        // Linearly interpolates between the values around the quantile
+        let quantile = |fraction: f64| {
+            let position = fraction * (values.len() - 1) as f64;
+            let below = values[position.floor() as usize];
+            let above = values[position.ceil() as usize];
+
+            below + (above - below) * position.fract()
+        };
+
+        let (q1, median, q3) = (quantile(0.25), quantile(0.5), quantile(0.75));
+        let reach = 1.5 * (q3 - q1);
+        let (low_fence, high_fence) = (q1 - reach, q3 + reach);
+
+        let inside = values
+            .iter()
+            .copied()
+            .filter(|value| (low_fence..=high_fence).contains(value));
+        let lower = inside.clone().fold(f64::INFINITY, f64::min);
+        let upper = inside.fold(f64::NEG_INFINITY, f64::max);
+
+        let outliers = values
+            .iter()
+            .copied()
+            .filter(|value| !(low_fence..=high_fence).contains(value))
+            .collect();
+
+        Some(BoxSummary {
+            lower,
+            q1,
+            median,
+            q3,
+            upper,
+            outliers,
+        })
+    }
+
+    /// A range of values, closed at `start` and open at `end`, and how many
+    /// values fall in it. The last bin of a histogram also holds its `end`.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Bin {
+        pub start: f64,
+        pub end: f64,
+        pub count: usize,
+    }
+
+    /// Most bins a histogram is split into
+    pub const MAX_BINS: usize = 100;
+
+    /// Picks a bin count for `values` using the Freedman–Diaconis rule. Falls
+    /// back to Sturges' rule when the values have no spread to measure.
+    pub fn auto_bins(values: &[f64]) -> usize {
+        let Some(summary) = box_summary(values) else {
+            return 1;
+        };
+
+        let count = values.iter().filter(|value| value.is_finite()).count() as f64;
+        let min = summary
+            .outliers
+            .iter()
+            .copied()
+            .fold(summary.lower, f64::min);
+        let max = summary
+            .outliers
+            .iter()
+            .copied()
+            .fold(summary.upper, f64::max);
+
+        let width = 2.0 * (summary.q3 - summary.q1) / count.cbrt();
+        let bins = if width > 0.0 && max > min {
+            ((max - min) / width).ceil()
+        } else {
+            count.log2().ceil() + 1.0
+        };
+
+        (bins as usize).clamp(1, MAX_BINS)
+    }
+
+    /// Splits the range of `values` into `bins` equal bins and counts the
+    /// values in each. Non-finite values are ignored.
+    pub fn histogram(values: &[f64], bins: usize) -> Vec<Bin> {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+
+        let Some(min) = values.iter().copied().reduce(f64::min) else {
+            return Vec::new();
+        };
+        let max = values.iter().copied().fold(min, f64::max);
+
+        // Values that are all the same share one bin around them
+        if max == min {
+            return vec![Bin {
+                start: min - 0.5,
+                end: max + 0.5,
+                count: values.len(),
+            }];
+        }
+
+        let bins = bins.clamp(1, MAX_BINS);
+        let width = (max - min) / bins as f64;
+
+        let mut histogram = (0..bins)
+            .map(|idx| Bin {
+                start: min + idx as f64 * width,
+                end: min + (idx + 1) as f64 * width,
+                count: 0,
+            })
+            .collect::<Vec<Bin>>();
+
+        for value in values {
+            let idx = (((value - min) / width) as usize).min(bins - 1);
+            histogram[idx].count += 1;
+        }
+
+        histogram
+    }
+
+    /// How the values of rows grouped together are combined into one
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Aggregation {
+        /// Rows are not grouped
+        #[default]
+        None,
+        Sum,
+        Mean,
+        Min,
+        Max,
+        Count,
+    }
+
+    impl Aggregation {
+        pub const ALL: [Self; 6] = [
+            Self::None,
+            Self::Sum,
+            Self::Mean,
+            Self::Min,
+            Self::Max,
+            Self::Count,
+        ];
+
+        /// Combines `values`, out of `rows` grouped rows, into one value
+        pub fn apply(self, values: &[f64], rows: usize) -> f64 {
+            let stats = series_stats(values);
+
+            match self {
+                Self::None | Self::Sum => stats.sum,
+                Self::Mean => stats.mean,
+                Self::Min => stats.min,
+                Self::Max => stats.max,
+                Self::Count => rows as f64,
+            }
+        }
+    }
+
+    impl std::fmt::Display for Aggregation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let name = match self {
+                Self::None => "None",
+                Self::Sum => "Sum",
+                Self::Mean => "Mean",
+                Self::Min => "Min",
+                Self::Max => "Max",
+                Self::Count => "Count",
+            };
+
+            write!(f, "{name}")
+        }
+    }
+
+    #[test]
+    fn test_series_stats() {
+        let stats = series_stats(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.sum, 40.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.median, 4.5);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.std_dev, 2.0);
+
+        let stats = series_stats(&[3.0, -1.0, f64::NAN, 10.0]);
+
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.min, -1.0);
+        assert_eq!(stats.max, 10.0);
+    }
+
+    #[test]
+    fn test_box_summary() {
+        let values = [9.0, 2.0, 100.0, 4.0, 1.0, 6.0, 3.0, 8.0, 5.0, 7.0];
+        let summary = box_summary(&values).unwrap();
+
+        assert_eq!(summary.q1, 3.25);
+        assert_eq!(summary.median, 5.5);
+        assert_eq!(summary.q3, 7.75);
+        assert_eq!(summary.lower, 1.0);
+        assert_eq!(summary.upper, 9.0);
+        assert_eq!(summary.outliers, vec![100.0]);
+
+        let summary = box_summary(&[4.0]).unwrap();
+        assert_eq!(
+            (summary.lower, summary.median, summary.upper),
+            (4.0, 4.0, 4.0)
+        );
+        assert!(box_summary(&[f64::NAN]).is_none());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let values = (0..=10).map(f64::from).collect::<Vec<f64>>();
+
+        let bins = histogram(&values, 5);
+        let counts = bins.iter().map(|bin| bin.count).collect::<Vec<usize>>();
+        // The largest value is counted in the last bin
+        assert_eq!(counts, vec![2, 2, 2, 2, 3]);
+        assert_eq!(bins[0].start, 0.0);
+        assert_eq!(bins[4].end, 10.0);
+
+        let bins = histogram(&[4.0, 4.0, f64::NAN], 10);
+        assert_eq!(
+            bins,
+            vec![Bin {
+                start: 3.5,
+                end: 4.5,
+                count: 2
+            }]
+        );
+
+        assert!(histogram(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_auto_bins() {
+        let values = (1..=100).map(f64::from).collect::<Vec<f64>>();
+        assert_eq!(auto_bins(&values), 5);
+
+        // No spread falls back to Sturges' rule
+        assert_eq!(auto_bins(&[3.0; 8]), 4);
+        assert_eq!(auto_bins(&[]), 1);
+    }
+
+    #[test]
+    fn test_empty_series_stats() {
+        assert_eq!(series_stats(&[]), Stats::default());
+    }
+}
+
+pub mod sampling {
+    use std::fmt::{self, Display};
+
+    use rand::{rngs::StdRng, seq::index, SeedableRng};
+
+    /// How rows are picked when a file has more rows than allowed
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum SamplingStrategy {
+        #[default]
+        First,
+        Random,
+        EveryNth,
+    }
+
+    impl SamplingStrategy {
+        pub const ALL: [Self; 3] = [Self::First, Self::Random, Self::EveryNth];
+    }
+
+    impl Display for SamplingStrategy {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Self::First => "First N",
+                Self::Random => "Random Sample",
+                Self::EveryNth => "Every Nth",
+            };
+
+            write!(f, "{name}")
+        }
+    }
+
+    /// The maximum number of rows charted from a file. A `max_rows` of 0
+    /// means there is no limit.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct RowLimit {
+        pub max_rows: usize,
+        pub strategy: SamplingStrategy,
+        /// Seed used for random sampling so the same rows are picked each time
+        pub seed: u64,
+    }
+
+    /// How many rows of a file a [`RowLimit`] kept
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Sample {
+        pub shown: usize,
+        pub total: usize,
+    }
+
+    impl RowLimit {
+        pub fn is_limited(&self) -> bool {
+            self.max_rows > 0
+        }
+
+        /// Returns whether self leaves out some of `total` rows
+        pub fn limits(&self, total: usize) -> bool {
+            self.is_limited() && total > self.max_rows
+        }
+
+        /// Returns the indices, in ascending order, of the rows out of
+        /// `total` kept by self
+        pub fn kept(&self, total: usize) -> Vec<usize> {
+            if !self.limits(total) {
+                return (0..total).collect();
+            }
+
+            match self.strategy {
+                SamplingStrategy::First => (0..self.max_rows).collect(),
+                SamplingStrategy::Random => {
+                    let mut rng = StdRng::seed_from_u64(self.seed);
+                    let mut kept = index::sample(&mut rng, total, self.max_rows).into_vec();
+                    kept.sort_unstable();
+
+                    kept
+                }
+                SamplingStrategy::EveryNth => {
+                    let step = total.div_ceil(self.max_rows);
+
+                    (0..total).step_by(step).collect()
+                }
+            }
+        }
+    }
+
+    /// Formats `count` with commas between groups of thousands
+    pub fn format_count(count: usize) -> String {
+        let digits = count.to_string();
+        let mut output = String::with_capacity(digits.len() + digits.len() / 3);
+
+        for (idx, digit) in digits.chars().enumerate() {
+            if idx > 0 && (digits.len() - idx) % 3 == 0 {
+                output.push(',');
+            }
+            output.push(digit);
+        }
+
+        output
+    }
+
+    #[test]
+    fn test_row_limit_kept() {
+        let limit = RowLimit::default();
+        assert_eq!(limit.kept(50), (0..50).collect::<Vec<_>>());
+
+        let limit = RowLimit {
+            max_rows: 10,
+            ..Default::default()
+        };
+        assert_eq!(limit.kept(50), (0..10).collect::<Vec<_>>());
+        assert_eq!(limit.kept(8).len(), 8);
+
+        let limit = RowLimit {
+            max_rows: 10,
+            strategy: SamplingStrategy::EveryNth,
+            seed: 0,
+        };
+        let kept = limit.kept(50);
+        assert_eq!(kept.len(), 10);
+        assert!(kept.contains(&45));
+
+        let limit = RowLimit {
+            max_rows: 10,
+            strategy: SamplingStrategy::Random,
+            seed: 7,
+        };
+        let kept = limit.kept(50);
+        assert_eq!(kept.len(), 10);
+        assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(kept, limit.kept(50));
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(10000), "10,000");
+        assert_eq!(format_count(2000000), "2,000,000");
+    }
+}
+
+pub mod csv {
+    use std::{
+        collections::HashSet,
+        fmt::{self, Display},
+        io::Write,
+        ops::Deref,
+        path::{Path, PathBuf},
+    };
+
+    use tempfile::TempPath;
+
+    use super::{
+        sampling::{format_count, RowLimit, Sample},
+        stats::Aggregation,
+        AppError, Location,
+    };
+
+    /// Rows passed from one step of reading a file to the next. Rows
+    /// rewritten to a temporary file are deleted with it once dropped.
+    #[derive(Debug)]
+    pub enum Source {
+        File(PathBuf),
+        Temporary(TempPath),
+    }
+
+    impl Source {
+        fn file(file: &Path) -> Self {
+            Self::File(file.to_path_buf())
+        }
+    }
+
+    impl Deref for Source {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            match self {
+                Self::File(file) => file,
+                Self::Temporary(temp) => temp,
+            }
+        }
+    }
+
+    impl AsRef<Path> for Source {
+        fn as_ref(&self) -> &Path {
+            self
+        }
+    }
+
+    /// Character separating the fields of a record in a source file
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Delimiter {
+        /// Tabs for `.tsv` files, commas otherwise
+        #[default]
+        Auto,
+        Comma,
+        Tab,
+        Semicolon,
+        Pipe,
+    }
+
+    impl Delimiter {
+        pub const ALL: [Self; 5] = [
+            Self::Auto,
+            Self::Comma,
+            Self::Tab,
+            Self::Semicolon,
+            Self::Pipe,
+        ];
+
+        /// Returns the character delimiting `file`
+        pub fn resolve(self, file: &Path) -> char {
+            match self {
+                Self::Auto => {
+                    let is_tsv = file
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"));
+
+                    if is_tsv {
+                        '\t'
+                    } else {
+                        ','
+                    }
+                }
+                Self::Comma => ',',
+                Self::Tab => '\t',
+                Self::Semicolon => ';',
+                Self::Pipe => '|',
+            }
+        }
+    }
+
+    impl Display for Delimiter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Self::Auto => "Auto",
+                Self::Comma => "Comma (,)",
+                Self::Tab => "Tab",
+                Self::Semicolon => "Semicolon (;)",
+                Self::Pipe => "Pipe (|)",
+            };
+
+            write!(f, "{name}")
+        }
+    }
+
+    /// The kind of values a column holds, inferred from its cells
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ColumnType {
+        Integer,
+        Float,
+        Date,
+        Text,
+        /// Every cell of the column is blank
+        Empty,
+    }
+
+    impl ColumnType {
+        /// Infers the type of a column from its cells. Blank cells are
+        /// ignored and a mix of integers and floats is a float column.
+        pub fn infer<'a>(cells: impl IntoIterator<Item = &'a str>) -> Self {
+            let mut kind = Self::Empty;
+
+            for cell in cells
+                .into_iter()
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+            {
+                let cell = if cell.parse::<i64>().is_ok() {
+                    Self::Integer
+                } else if cell.parse::<f64>().is_ok() {
+                    Self::Float
+                } else if is_date(cell) {
+                    Self::Date
+                } else {
+                    Self::Text
+                };
+
+                kind = match (kind, cell) {
+                    (Self::Empty, cell) => cell,
+                    (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+                    (kind, cell) if kind == cell => kind,
+                    _ => return Self::Text,
+                };
+            }
+
+            kind
+        }
+
+        pub fn is_numeric(self) -> bool {
+            matches!(self, Self::Integer | Self::Float)
+        }
+    }
+
+    impl Display for ColumnType {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Self::Integer => "Integer",
+                Self::Float => "Decimal",
+                Self::Date => "Date",
+                Self::Text => "Text",
+                Self::Empty => "Empty",
+            };
+
+            write!(f, "{name}")
+        }
+    }
+
+    /// Returns whether `cell` starts with a date written as `yyyy-mm-dd` or
+    /// `dd/mm/yyyy`, with either separator
+    fn is_date(cell: &str) -> bool {
+        let date = cell.split(['T', ' ']).next().unwrap_or(cell);
+        let parts = date.split(['-', '/']).collect::<Vec<_>>();
+
+        if !parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        {
+            return false;
+        }
+
+        let lengths = parts.iter().map(|part| part.len()).collect::<Vec<_>>();
+        matches!(lengths.as_slice(), [4, 1..=2, 1..=2] | [1..=2, 1..=2, 4])
+    }
+
+    /// Writes `fields` as a single CSV record, quoting fields as needed
+    pub fn record<I, S>(fields: I) -> String
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let field = field.as_ref();
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect::<Vec<String>>();
+
+        fields.join(",")
+    }
+
+    /// Splits CSV `content` into records of fields. Quoted fields may hold
+    /// delimiters, escaped quotes and line breaks.
+    pub fn parse(content: &str) -> Vec<Vec<String>> {
+        parse_delimited(content, ',')
+    }
+
+    /// Splits `content` into records of fields separated by `delimiter`
+    pub fn parse_delimited(content: &str, delimiter: char) -> Vec<Vec<String>> {
+        parse_lines(content, delimiter)
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    /// Guesses the delimiter of pasted or piped `content`. The delimiter
+    /// splitting its first rows into the most fields, and the same number
+    /// of fields on each, is picked. Returns `None` if none splits them.
+    pub fn sniff_delimiter(content: &str) -> Option<Delimiter> {
+        /// Rows looked at when guessing
+        const SAMPLE: usize = 20;
+
+        let sample = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(SAMPLE)
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        // Tabs come first, as spreadsheets copy cells separated by them
+        [
+            Delimiter::Tab,
+            Delimiter::Comma,
+            Delimiter::Semicolon,
+            Delimiter::Pipe,
+        ]
+        .into_iter()
+        .filter_map(|delimiter| {
+            let records = parse_delimited(&sample, delimiter.resolve(Path::new("")));
+            let width = records.first().map(Vec::len)?;
+
+            let even = records.iter().all(|record| record.len() == width);
+            (width > 1 && even).then_some((delimiter, width))
+        })
+        .fold(
+            None,
+            |best: Option<(Delimiter, usize)>, (delimiter, width)| match best {
+                Some((_, most)) if most >= width => best,
+                _ => Some((delimiter, width)),
+            },
+        )
+        .map(|(delimiter, _)| delimiter)
+    }
+
+    /// Rewrites `content`, whose fields are separated by `delimiter`, as
+    /// CSV. Blank rows are left out.
+    pub fn to_csv(content: &str, delimiter: Delimiter) -> String {
+        parse_delimited(content, delimiter.resolve(Path::new("")))
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()))
+            .map(record)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Splits `content` as [`parse_delimited`] does, pairing each record
+    /// with the line it starts on, counting from 1
+    pub fn parse_lines(content: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = content.chars().peekable();
+        let mut line = 1;
+        let mut start = line;
+
+        while let Some(ch) = chars.next() {
+            if ch == '\n' {
+                line += 1;
+            }
+
+            match (ch, quoted) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                ('"', true) => quoted = false,
+                ('"', false) if field.is_empty() => quoted = true,
+                (ch, false) if ch == delimiter => record.push(std::mem::take(&mut field)),
+                ('\r', false) => {}
+                ('\n', false) => {
+                    record.push(std::mem::take(&mut field));
+                    records.push((start, std::mem::take(&mut record)));
+                    start = line;
+                }
+                (ch, _) => field.push(ch),
+            }
+        }
+
+        if !field.is_empty() || !record.is_empty() {
+            record.push(field);
+            records.push((start, record));
+        }
+
+        records
+    }
+
+    /// Returns whether `file` holds JSON rather than delimited text
+    fn is_json(file: &Path) -> bool {
+        file.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    }
+
+    /// Checks that `file` splits cleanly on `delimiter` and returns a path
+    /// to its contents as CSV. Files delimited by commas are returned as is,
+    /// others are rewritten to a temporary file. Rows with a different
+    /// number of fields than the first are an error unless `flexible`.
+    ///
+    /// JSON files, holding a list of objects, are read with
+    /// [`json::records`](super::json::records) instead and always rewritten.
+    pub fn normalize(
+        file: &Path,
+        delimiter: Delimiter,
+        flexible: bool,
+    ) -> Result<Source, AppError> {
+        let content =
+            std::fs::read_to_string(file).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        if is_json(file) {
+            let records = super::json::records(&content).map_err(AppError::Simple)?;
+            return write_temporary(records);
+        }
+
+        let delimiter = delimiter.resolve(file);
+        let (lines, records): (Vec<_>, Vec<_>) = parse_lines(&content, delimiter)
+            .into_iter()
+            .filter(|(_, record)| record.iter().any(|field| !field.trim().is_empty()))
+            .unzip();
+
+        let name = match delimiter {
+            '\t' => String::from("tab"),
+            delimiter => format!("`{delimiter}`"),
+        };
+
+        if records.iter().all(|record| record.len() <= 1) && records.len() > 1 {
+            let other = [',', '\t', ';', '|']
+                .into_iter()
+                .any(|other| other != delimiter && content.contains(other));
+
+            if other {
+                return Err(AppError::Simple(format!(
+                    "No row is split by a {name}. Try another delimiter"
+                )));
+            }
+        }
+
+        if !flexible {
+            let width = records.first().map(Vec::len).unwrap_or_default();
+            let uneven = records
+                .iter()
+                .enumerate()
+                .find(|(_, record)| record.len() != width);
+
+            if let Some((row, record)) = uneven {
+                let message = format!(
+                    "Row {} on line {} has {} cells split by a {name} but the first row has {width}. Check the delimiter or allow flexible rows",
+                    row + 1,
+                    lines[row],
+                    record.len()
+                );
+                let location = Location {
+                    file: file.to_path_buf(),
+                    line: lines[row],
+                };
+
+                return Err(AppError::Located(message, location));
+            }
+        }
+
+        if delimiter == ',' {
+            return Ok(Source::file(file));
+        }
+
+        write_temporary(records)
+    }
+
+    /// Returns the name, if `has_header`, and inferred type of each column
+    /// of `file`, read as [`normalize`] would
+    pub fn column_types(
+        file: &Path,
+        delimiter: Delimiter,
+        has_header: bool,
+    ) -> Result<Vec<(String, ColumnType)>, AppError> {
+        let source = normalize(file, delimiter, true)?;
+        let content =
+            std::fs::read_to_string(source).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header {
+            records.next().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let rows = records.collect::<Vec<_>>();
+
+        let width = rows
+            .iter()
+            .map(Vec::len)
+            .chain([header.len()])
+            .max()
+            .unwrap_or_default();
+
+        let columns = (0..width)
+            .map(|column| {
+                let name = header.get(column).cloned().unwrap_or_default();
+                let kind = ColumnType::infer(
+                    rows.iter()
+                        .filter_map(|row| row.get(column).map(String::as_str)),
+                );
+
+                (name, kind)
+            })
+            .collect();
+
+        Ok(columns)
+    }
+
+    /// The size of a sheet, read from a normalized source file
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct Shape {
+        /// Rows not counting the header
+        pub rows: usize,
+        /// Header label of each column, empty if the sheet has no header
+        pub columns: Vec<String>,
+    }
+
+    impl Shape {
+        pub fn read(source: &Path, has_header: bool) -> Result<Self, AppError> {
+            let content =
+                std::fs::read_to_string(source).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+            let records = parse(&content)
+                .into_iter()
+                .filter(|record| record.iter().any(|field| !field.trim().is_empty()))
+                .collect::<Vec<_>>();
+
+            let width = records.iter().map(Vec::len).max().unwrap_or_default();
+            let (header, rows) = match records.split_first() {
+                Some((header, rows)) if has_header => (header.clone(), rows.len()),
+                _ => (Vec::new(), records.len()),
+            };
+
+            let columns = (0..width)
+                .map(|column| header.get(column).cloned().unwrap_or_default())
+                .collect();
+
+            Ok(Self { rows, columns })
+        }
+
+        /// The header label of `column`, or its number if it has none
+        pub fn column_name(&self, column: usize) -> String {
+            match self.columns.get(column).map(|name| name.trim()) {
+                Some(name) if !name.is_empty() => name.to_string(),
+                _ => format!("column {column}"),
+            }
+        }
+
+        /// Describes the size of the sheet, e.g. `1,240 rows × 6 cols`
+        pub fn summary(&self) -> String {
+            size_summary(self.rows, self.columns.len())
+        }
+    }
+
+    /// Describes a sheet of `rows` by `columns`
+    pub fn size_summary(rows: usize, columns: usize) -> String {
+        let plural = |count: usize, one: &str, many: &str| {
+            let unit = if count == 1 { one } else { many };
+            format!("{} {unit}", format_count(count))
+        };
+
+        format!(
+            "{} × {}",
+            plural(rows, "row", "rows"),
+            plural(columns, "col", "cols")
+        )
     }
 
-    impl ColorEngine {
-        const RATIO: f32 = 0.60;
-        const DEFAULT_COUNT: u32 = 5;
+    /// How a [`RowFilter`] compares a cell with its value
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum FilterOp {
+        #[default]
+        Equals,
+        NotEquals,
+        Contains,
+        Greater,
+        Less,
+    }
 
-        pub fn new_with_seed<'a>(theme: &'a Theme, seed: f32) -> Self {
-            let rng = {
-                let mult = 10000.0;
+    impl FilterOp {
+        pub const ALL: [Self; 5] = [
+            Self::Equals,
+            Self::NotEquals,
+            Self::Contains,
+            Self::Greater,
+            Self::Less,
+        ];
+    }
 
-                (seed * mult).trunc() / mult
+    impl Display for FilterOp {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Self::Equals => "=",
+                Self::NotEquals => "!=",
+                Self::Contains => "contains",
+                Self::Greater => ">",
+                Self::Less => "<",
             };
-            let is_dark = theme.extended_palette().is_dark;
-            let seed = Theme::default();
-            let seed: HSV = seed.extended_palette().secondary.base.color.into();
 
-            let stable_h = {
-                let seed: f32 = seed.h.into();
-                (rng + Self::RATIO + seed) % 1.0
-            };
+            write!(f, "{name}")
+        }
+    }
 
-            Self {
-                seed,
-                is_dark,
-                stable_h,
-                count: Self::DEFAULT_COUNT,
-                random: rng,
-                mode: ColoringMode::Normal,
+    /// Keeps the rows whose cell in `column` compares to `value` by `op`.
+    /// Cells are compared as numbers when both sides are numbers, as text
+    /// otherwise.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct RowFilter {
+        pub column: usize,
+        pub op: FilterOp,
+        pub value: String,
+    }
+
+    impl RowFilter {
+        pub fn matches(&self, record: &[String]) -> bool {
+            let cell = record
+                .get(self.column)
+                .map(|cell| cell.trim())
+                .unwrap_or_default();
+            let value = self.value.trim();
+
+            let numbers = cell.parse::<f64>().ok().zip(value.parse::<f64>().ok());
+
+            match (self.op, numbers) {
+                (FilterOp::Equals, Some((cell, value))) => cell == value,
+                (FilterOp::Equals, None) => cell == value,
+                (FilterOp::NotEquals, Some((cell, value))) => cell != value,
+                (FilterOp::NotEquals, None) => cell != value,
+                (FilterOp::Contains, _) => cell.to_lowercase().contains(&value.to_lowercase()),
+                (FilterOp::Greater, Some((cell, value))) => cell > value,
+                (FilterOp::Greater, None) => cell > value,
+                (FilterOp::Less, Some((cell, value))) => cell < value,
+                (FilterOp::Less, None) => cell < value,
             }
         }
+    }
 
-        pub fn new<'a>(theme: &'a Theme) -> Self {
-            let seed: f32 = thread_rng().gen();
-            Self::new_with_seed(theme, seed)
+    impl Display for RowFilter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "column {} {} \"{}\"", self.column, self.op, self.value)
         }
+    }
 
-        pub fn count(mut self, count: u32) -> Self {
-            if count > 0 {
-                self.count = count;
-            }
+    /// Returns a path to the rows of CSV `file` matching every filter, with
+    /// the header kept if `has_header`. `file` is returned as is when there
+    /// are no filters.
+    pub fn filter(
+        file: &Path,
+        has_header: bool,
+        filters: &[RowFilter],
+    ) -> Result<Source, AppError> {
+        if filters.is_empty() {
+            return Ok(Source::file(file));
+        }
 
-            self
+        let content =
+            std::fs::read_to_string(file).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header { records.next() } else { None };
+        let rows = records.collect::<Vec<_>>();
+
+        let width = rows
+            .iter()
+            .chain(header.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        if let Some(filter) = filters.iter().find(|filter| filter.column >= width) {
+            return Err(AppError::Simple(format!(
+                "Cannot filter on column {}. The file has {width} columns",
+                filter.column
+            )));
         }
 
-        pub fn gradual(mut self, gradual: bool) -> Self {
-            if gradual {
-                if self.is_dark {
-                    self.seed.v = (0.15).into()
-                } else {
-                    self.seed.v = (0.85).into()
+        let total = rows.len();
+        let rows = rows
+            .into_iter()
+            .filter(|row| filters.iter().all(|filter| filter.matches(row)))
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() {
+            let filters = filters
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(" and ");
+
+            return Err(AppError::Simple(format!(
+                "None of the {total} rows match {filters}. Loosen the filters to chart some rows"
+            )));
+        }
+
+        write_temporary(header.into_iter().chain(rows).collect())
+    }
+
+    /// Returns a path to the rows of CSV `file` kept by `limit`, with the
+    /// header kept if `has_header`, and how many rows were kept. Rows in
+    /// `row_exclude` are dropped before sampling, and `row_exclude` is
+    /// cleared as its indices no longer match the sampled file. `file` is
+    /// returned as is when no row is left out.
+    pub fn sample(
+        file: &Path,
+        has_header: bool,
+        limit: RowLimit,
+        row_exclude: &mut HashSet<usize>,
+    ) -> Result<(Source, Option<Sample>), AppError> {
+        if !limit.is_limited() {
+            return Ok((Source::file(file), None));
+        }
+
+        let content =
+            std::fs::read_to_string(file).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header { records.next() } else { None };
+        let mut rows = records
+            .enumerate()
+            .filter(|(row, _)| !row_exclude.contains(row))
+            .map(|(_, record)| Some(record))
+            .collect::<Vec<_>>();
+
+        let total = rows.len();
+        if !limit.limits(total) {
+            return Ok((Source::file(file), None));
+        }
+
+        let kept = limit
+            .kept(total)
+            .into_iter()
+            .filter_map(|row| rows[row].take())
+            .collect::<Vec<_>>();
+        let sample = Sample {
+            shown: kept.len(),
+            total,
+        };
+
+        let target = write_temporary(header.into_iter().chain(kept).collect())?;
+        row_exclude.clear();
+
+        Ok((target, Some(sample)))
+    }
+
+    /// Groups the rows of CSV `file` not in `row_exclude` by their cell in
+    /// `x_col` and combines the numbers in `y_col` of each group by
+    /// `aggregation`. Returns a path to the groups, as a column of keys and
+    /// a column of values, and a description of the grouping.
+    pub fn aggregate(
+        file: &Path,
+        has_header: bool,
+        x_col: usize,
+        y_col: usize,
+        aggregation: Aggregation,
+        row_exclude: &HashSet<usize>,
+    ) -> Result<(Source, String), AppError> {
+        let content =
+            std::fs::read_to_string(file).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header { records.next() } else { None };
+        let name = |column: usize| {
+            header
+                .as_ref()
+                .and_then(|header| header.get(column))
+                .filter(|name| !name.trim().is_empty())
+                .cloned()
+                .unwrap_or_else(|| format!("column {column}"))
+        };
+
+        let mut groups: Vec<(String, Vec<f64>, usize)> = Vec::new();
+        for (_, record) in records
+            .enumerate()
+            .filter(|(row, _)| !row_exclude.contains(row))
+        {
+            let key = record
+                .get(x_col)
+                .map(|cell| cell.trim())
+                .unwrap_or_default();
+            let value = record
+                .get(y_col)
+                .and_then(|cell| cell.trim().parse::<f64>().ok());
+
+            let idx = match groups.iter().position(|(group, ..)| group == key) {
+                Some(idx) => idx,
+                None => {
+                    groups.push((key.to_string(), Vec::new(), 0));
+                    groups.len() - 1
                 }
-                self.mode = ColoringMode::Gradual;
+            };
+
+            let (_, values, rows) = &mut groups[idx];
+            values.extend(value);
+            *rows += 1;
+        }
+
+        if groups.is_empty() {
+            return Err(AppError::Simple(String::from("There are no rows to group")));
+        }
+
+        let description = match aggregation {
+            Aggregation::Count => format!("Count of rows by {}", name(x_col)),
+            aggregation => format!("{aggregation} of {} by {}", name(y_col), name(x_col)),
+        };
+
+        let header = header.map(|_| {
+            let values = match aggregation {
+                Aggregation::Count => String::from("Count"),
+                aggregation => format!("{aggregation} of {}", name(y_col)),
+            };
+
+            vec![name(x_col), values]
+        });
+
+        let rows = groups.into_iter().map(|(key, values, rows)| {
+            let value = aggregation.apply(&values, rows);
+            let value = match aggregation {
+                Aggregation::Count => rows.to_string(),
+                // Written as decimals so every value is read as the same type
+                _ => format!("{value:?}"),
+            };
+
+            vec![key, value]
+        });
+
+        let target = write_temporary(header.into_iter().chain(rows).collect())?;
+
+        Ok((target, description))
+    }
+
+    /// Writes `records` as CSV to a new temporary file, removed once the
+    /// returned [`Source`] is dropped
+    fn write_temporary(records: Vec<Vec<String>>) -> Result<Source, AppError> {
+        let content = records
+            .into_iter()
+            .map(record)
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let failed = |err: std::io::Error| AppError::FileLoading(err.kind());
+
+        let mut target = tempfile::Builder::new()
+            .prefix("modav-")
+            .suffix(".csv")
+            .tempfile()
+            .map_err(failed)?;
+        target.write_all(content.as_bytes()).map_err(failed)?;
+
+        Ok(Source::Temporary(target.into_temp_path()))
+    }
+
+    #[test]
+    fn test_record() {
+        assert_eq!(record(["a", "b", "1.5"]), "a,b,1.5");
+        assert_eq!(
+            record(["x, y", "say \"hi\""]),
+            "\"x, y\",\"say \"\"hi\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("a,b\r\n1,\"x, \"\"y\"\"\"\n,\"two\nlines\"\n"),
+            vec![
+                vec!["a", "b"],
+                vec!["1", "x, \"y\""],
+                vec!["", "two\nlines"]
+            ]
+        );
+        assert_eq!(parse("a,b"), vec![vec!["a", "b"]]);
+        assert!(parse("").is_empty());
+        assert_eq!(
+            parse_lines("a\n\"b\nc\"\n\nd\n", ',')
+                .into_iter()
+                .map(|(line, _)| line)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            parse_delimited("a\tb,c\n1\t2\n", '\t'),
+            vec![vec!["a", "b,c"], vec!["1", "2"]]
+        );
+    }
+
+    #[test]
+    fn test_sniff_delimiter() {
+        assert_eq!(
+            sniff_delimiter("a\tb\tc\n1\t2,5\t3\n"),
+            Some(Delimiter::Tab)
+        );
+        assert_eq!(sniff_delimiter("a,b\n1,2\n\n3,4"), Some(Delimiter::Comma));
+        assert_eq!(
+            sniff_delimiter("a;b;c\n1,5;2;3"),
+            Some(Delimiter::Semicolon)
+        );
+        assert_eq!(sniff_delimiter("a|b\n1|2"), Some(Delimiter::Pipe));
+        assert_eq!(sniff_delimiter("just text"), None);
+        assert_eq!(sniff_delimiter("a,b\n1,2,3"), None);
+
+        assert_eq!(
+            to_csv("name\tnote\nAda\tsays, hi\n\n", Delimiter::Tab),
+            "name,note\nAda,\"says, hi\""
+        );
+    }
+
+    #[test]
+    fn test_column_type() {
+        assert_eq!(ColumnType::infer(["1", " 2", ""]), ColumnType::Integer);
+        assert_eq!(ColumnType::infer(["1", "2.5"]), ColumnType::Float);
+        assert_eq!(
+            ColumnType::infer(["2024-01-31", "1/2/2023 10:00"]),
+            ColumnType::Date
+        );
+        assert_eq!(ColumnType::infer(["1", "n/a"]), ColumnType::Text);
+        assert_eq!(ColumnType::infer(["", " "]), ColumnType::Empty);
+        assert!(!is_date("1-2-3"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let file = tempfile::Builder::new()
+            .suffix(".csv")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(&file, "name,value\na,1\nb,2.5\nab,10\n").unwrap();
+
+        let filters = [RowFilter {
+            column: 1,
+            op: FilterOp::Greater,
+            value: "2".into(),
+        }];
+        let filtered = filter(&file, true, &filters).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(filtered).unwrap(),
+            "name,value\nb,2.5\nab,10"
+        );
+
+        let filters = [
+            RowFilter {
+                column: 0,
+                op: FilterOp::Contains,
+                value: "A".into(),
+            },
+            RowFilter {
+                column: 1,
+                op: FilterOp::Equals,
+                value: "1.0".into(),
+            },
+        ];
+        let filtered = filter(&file, true, &filters).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&filtered).unwrap(),
+            "name,value\na,1"
+        );
+
+        let temporary = filtered.to_path_buf();
+        assert_ne!(temporary, file.to_path_buf());
+        drop(filtered);
+        assert!(!temporary.exists());
+
+        let filters = [RowFilter {
+            column: 0,
+            op: FilterOp::Equals,
+            value: "z".into(),
+        }];
+        assert!(filter(&file, true, &filters).is_err());
+        assert_eq!(&*filter(&file, true, &[]).unwrap(), &*file);
+    }
+
+    #[test]
+    fn test_sample() {
+        let file = tempfile::Builder::new()
+            .suffix(".csv")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(&file, "name,note\na,\"two\nlines\"\n\nb,x\nc,y\nd,z\n").unwrap();
+
+        let limit = RowLimit {
+            max_rows: 2,
+            ..Default::default()
+        };
+
+        let (sampled, kept) = sample(&file, true, limit, &mut HashSet::new()).unwrap();
+        assert_eq!(kept, Some(Sample { shown: 2, total: 4 }));
+        assert_eq!(
+            std::fs::read_to_string(sampled).unwrap(),
+            "name,note\na,\"two\nlines\"\nb,x"
+        );
+
+        let mut row_exclude = HashSet::from([0]);
+        let (sampled, kept) = sample(&file, true, limit, &mut row_exclude).unwrap();
+        assert_eq!(kept, Some(Sample { shown: 2, total: 3 }));
+        assert!(row_exclude.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(sampled).unwrap(),
+            "name,note\nb,x\nc,y"
+        );
+
+        let limit = RowLimit {
+            max_rows: 4,
+            ..Default::default()
+        };
+        let mut row_exclude = HashSet::from([1]);
+        let (sampled, kept) = sample(&file, true, limit, &mut row_exclude).unwrap();
+        assert_eq!((&*sampled, kept), (&*file, None));
+        assert_eq!(row_exclude, HashSet::from([1]));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let file = tempfile::Builder::new()
+            .suffix(".csv")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(
+            &file,
+            "region,sales\nnorth,1\nsouth,4\nnorth,2\nsouth,n/a\n",
+        )
+        .unwrap();
+
+        let (grouped, description) =
+            aggregate(&file, true, 0, 1, Aggregation::Sum, &HashSet::new()).unwrap();
+        assert_eq!(description, "Sum of sales by region");
+        assert_eq!(
+            std::fs::read_to_string(grouped).unwrap(),
+            "region,Sum of sales\nnorth,3.0\nsouth,4.0"
+        );
+
+        let (grouped, _) =
+            aggregate(&file, true, 0, 1, Aggregation::Count, &HashSet::from([0])).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(grouped).unwrap(),
+            "region,Count\nsouth,2\nnorth,1"
+        );
+
+        assert!(aggregate(
+            &file,
+            true,
+            0,
+            1,
+            Aggregation::Max,
+            &HashSet::from([0, 1, 2, 3])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let file = tempfile::Builder::new()
+            .suffix(".tsv")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(&file, "name\tvalue\na, b\t1\n\nc\t2\n").unwrap();
+
+        let normalized = normalize(&file, Delimiter::Auto, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(normalized).unwrap(),
+            "name,value\n\"a, b\",1\nc,2"
+        );
+
+        assert!(normalize(&file, Delimiter::Comma, false).is_err());
+        assert!(normalize(&file, Delimiter::Semicolon, false).is_err());
+
+        std::fs::write(&file, "name\tvalue\na\t1\textra\n").unwrap();
+        let err = normalize(&file, Delimiter::Tab, false).unwrap_err();
+        assert_eq!(err.location().map(|location| location.line), Some(2));
+        assert!(normalize(&file, Delimiter::Tab, true).is_ok());
+    }
+
+    #[test]
+    fn test_shape() {
+        let file = tempfile::Builder::new()
+            .suffix(".csv")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(&file, "name,,value\na,1,2\n,,\nb,3,4\n").unwrap();
+
+        let shape = Shape::read(&file, true).unwrap();
+        assert_eq!(shape.rows, 2);
+        assert_eq!(shape.summary(), "2 rows × 3 cols");
+        assert_eq!(shape.column_name(0), "name");
+        assert_eq!(shape.column_name(1), "column 1");
+
+        let shape = Shape::read(&file, false).unwrap();
+        assert_eq!(shape.rows, 3);
+        assert_eq!(shape.column_name(2), "column 2");
+        assert_eq!(size_summary(1, 1), "1 row × 1 col");
+    }
+}
+
+pub mod json {
+    use serde_json::Map;
+    pub use serde_json::{json, Value};
+
+    /// Returns `value` as an index or count, if it is a whole number that is
+    /// not negative
+    pub fn as_usize(value: &Value) -> Option<usize> {
+        value.as_u64().and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// Adds the fields of `object` to `row`, naming nested fields by their
+    /// dotted path
+    fn flatten(
+        prefix: &str,
+        object: &Map<String, Value>,
+        row: &mut Vec<(String, String)>,
+    ) -> Result<(), String> {
+        for (key, value) in object {
+            let key = if prefix.is_empty() {
+                key.clone()
             } else {
-                self.mode = ColoringMode::Normal;
+                format!("{prefix}.{key}")
+            };
+
+            let cell = match value {
+                Value::Object(fields) => {
+                    flatten(&key, fields, row)?;
+                    continue;
+                }
+                Value::Array(_) => {
+                    return Err(format!(
+                        "`{key}` is a list, which cannot be made into a column"
+                    ))
+                }
+                Value::Null => String::new(),
+                Value::Bool(value) => value.to_string(),
+                Value::Number(value) => value.to_string(),
+                Value::String(value) => value.clone(),
+            };
+
+            row.push((key, cell));
+        }
+
+        Ok(())
+    }
+
+    /// Reads a JSON array of objects into rows of cells, headed by the keys
+    /// of the objects. Nested objects are flattened into dotted keys.
+    pub fn records(input: &str) -> Result<Vec<Vec<String>>, String> {
+        let value: Value = serde_json::from_str(input).map_err(|err| err.to_string())?;
+
+        let Value::Array(values) = value else {
+            return Err(String::from("Expected a list of objects"));
+        };
+
+        let mut header: Vec<String> = Vec::new();
+        let mut rows = Vec::with_capacity(values.len());
+
+        for (idx, value) in values.iter().enumerate() {
+            let Value::Object(fields) = value else {
+                return Err(format!("Item {} of the list is not an object", idx + 1));
+            };
+
+            let mut row = Vec::new();
+            flatten("", fields, &mut row).map_err(|err| format!("Item {}: {err}", idx + 1))?;
+
+            for (key, _) in &row {
+                if !header.contains(key) {
+                    header.push(key.clone());
+                }
             }
 
-            self
+            rows.push(row);
         }
 
-        pub fn seed(&self) -> f32 {
-            self.random
+        let rows = rows.into_iter().map(|row| {
+            header
+                .iter()
+                .map(|key| {
+                    row.iter()
+                        .find(|(field, _)| field == key)
+                        .map(|(_, cell)| cell.clone())
+                        .unwrap_or_default()
+                })
+                .collect()
+        });
+
+        Ok(std::iter::once(header.clone()).chain(rows).collect())
+    }
+
+    #[test]
+    fn test_as_usize() {
+        assert_eq!(as_usize(&json!(3)), Some(3));
+        assert_eq!(as_usize(&json!(0.5)), None);
+        assert_eq!(as_usize(&json!(-1)), None);
+        assert_eq!(as_usize(&json!("3")), None);
+    }
+
+    #[test]
+    fn test_json_records() {
+        let records =
+            records(r#"[{"name": "a", "pos": {"x": 1, "y": 2.5}}, {"name": "b", "extra": true}]"#);
+
+        assert_eq!(
+            records,
+            Ok(vec![
+                vec!["name", "pos.x", "pos.y", "extra"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                vec!["a", "1", "2.5", ""]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                vec!["b", "", "", "true"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<String>>(),
+            ])
+        );
+
+        assert!(records(r#"[{"tags": [1]}]"#).is_err());
+        assert!(records(r#"{"name": "a"}"#).is_err());
+        assert!(records("[1, 2").is_err());
+
+        // Surrogate pairs are combined into a single character
+        assert_eq!(
+            records(r#"[{"emoji": "\ud83d\ude00"}]"#).map(|records| records[1][0].clone()),
+            Ok(String::from("\u{1f600}"))
+        );
+
+        // Deeply nested input is rejected instead of overflowing the stack
+        let deep = format!("[{}1{}]", "{\"a\":".repeat(10_000), "}".repeat(10_000));
+        assert!(records(&deep).is_err());
+    }
+}
+
+/// A minimal PNG encoder for RGBA8 images. Image data is stored without
+/// compression, which is fine for the size of chart exports.
+pub mod png {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+    /// Encodes `rgba`, rows of `width` pixels of 4 bytes each, as a PNG
+    pub fn encode(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+        let row = width as usize * 4;
+
+        let mut raw = Vec::with_capacity((row + 1) * height as usize);
+        for line in rgba.chunks(row).take(height as usize) {
+            // Filter type None
+            raw.push(0);
+            raw.extend_from_slice(line);
         }
 
-        /// Generates a Color taking into consideration previously generated colors
-        fn generate(&mut self) -> Color {
-            let seed: f32 = self.seed.h.into();
-            let h = (self.random + Self::RATIO + seed) % 1.0;
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        // 8 bit depth, RGBA, deflate, adaptive filtering, no interlacing
+        header.extend([8, 6, 0, 0, 0]);
 
-            match self.mode {
-                ColoringMode::Normal => {
-                    let generated = if self.is_dark {
-                        HSV::new(h, 0.8, 0.5)
-                    } else {
-                        HSV::new(h, 0.69, 0.85)
-                    };
+        let mut png = SIGNATURE.to_vec();
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        chunk(&mut png, b"IEND", &[]);
 
-                    self.seed = generated;
+        png
+    }
 
-                    generated.into()
-                }
-                ColoringMode::Gradual => {
-                    let diff = 0.85 / (self.count as f32);
-                    let generated = if self.is_dark {
-                        let v = { self.seed.v + diff }.min(0.925);
-                        HSV::new(self.stable_h, 0.8, v)
-                    } else {
-                        let v = { self.seed.v - diff }.max(0.125);
-                        HSV::new(self.stable_h, 0.8, v)
-                    };
+    fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend((data.len() as u32).to_be_bytes());
+        png.extend(kind);
+        png.extend(data);
+        png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+    }
 
-                    self.seed = generated;
+    /// Wraps `data` in a zlib stream of uncompressed deflate blocks
+    pub(super) fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut output = vec![0x78, 0x01];
 
-                    generated.into()
+        if data.is_empty() {
+            output.extend([1, 0, 0, 0xFF, 0xFF]);
+        }
+
+        let mut blocks = data.chunks(u16::MAX as usize).peekable();
+        while let Some(block) = blocks.next() {
+            let is_last = blocks.peek().is_none();
+            let len = block.len() as u16;
+
+            output.push(is_last as u8);
+            output.extend(len.to_le_bytes());
+            output.extend((!len).to_le_bytes());
+            output.extend(block);
+        }
+
+        output.extend(adler32(data).to_be_bytes());
+        output
+    }
+
+    fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+        let crc = bytes.fold(0xFFFF_FFFF_u32, |crc, byte| {
+            (0..8).fold(crc ^ *byte as u32, |crc, _| {
+                if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
                 }
+            })
+        });
+
+        !crc
+    }
+
+    fn adler32(bytes: &[u8]) -> u32 {
+        let (a, b) = bytes.iter().fold((1_u32, 0_u32), |(a, b), byte| {
+            let a = (a + *byte as u32) % 65521;
+            (a, (b + a) % 65521)
+        });
+
+        (b << 16) | a
+    }
+
+    #[test]
+    fn test_png_encode() {
+        assert_eq!(crc32(b"IEND".iter()), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+
+        let png = encode(2, 1, &[255, 0, 0, 255, 0, 0, 255, 128]);
+
+        assert_eq!(png[..8], SIGNATURE);
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+}
+
+pub mod pdf {
+    use std::fmt;
+
+    use super::png::zlib_stored;
+
+    /// Paper sizes a chart can be exported on
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum PageSize {
+        #[default]
+        A4,
+        Letter,
+    }
+
+    impl PageSize {
+        pub const ALL: [Self; 2] = [Self::A4, Self::Letter];
+
+        /// Width and height of the page, in portrait, in points
+        pub fn points(&self) -> (f32, f32) {
+            match self {
+                Self::A4 => (595.0, 842.0),
+                Self::Letter => (612.0, 792.0),
             }
         }
     }
 
-    impl Iterator for ColorEngine {
-        type Item = Color;
+    impl fmt::Display for PageSize {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Self::A4 => "A4",
+                Self::Letter => "Letter",
+            };
 
-        fn next(&mut self) -> Option<Self::Item> {
-            Some(self.generate())
+            write!(f, "{name}")
+        }
+    }
+
+    /// Space left around the image, in points
+    const MARGIN: f32 = 36.0;
+
+    /// Returns the width and height an image of `width` by `height` pixels
+    /// is printed at, in points, on a page of `page_width` by `page_height`.
+    /// Images are printed at `dpi` pixels per inch, shrinking them if they
+    /// do not fit within the margins.
+    pub fn printed_size(
+        width: u32,
+        height: u32,
+        dpi: u32,
+        page_width: f32,
+        page_height: f32,
+    ) -> (f32, f32) {
+        let width = width as f32 * 72.0 / dpi.max(1) as f32;
+        let height = height as f32 * 72.0 / dpi.max(1) as f32;
+
+        let available_width = (page_width - 2.0 * MARGIN).max(1.0);
+        let available_height = (page_height - 2.0 * MARGIN).max(1.0);
+        let scale = (available_width / width)
+            .min(available_height / height)
+            .min(1.0);
+
+        (width * scale, height * scale)
+    }
+
+    /// Encodes `rgba`, rows of `width` pixels of 4 bytes each, as a single
+    /// page PDF with the image centered on it. The page is turned to
+    /// landscape for images wider than they are tall. Alpha is dropped.
+    pub fn encode(width: u32, height: u32, rgba: &[u8], page: PageSize, dpi: u32) -> Vec<u8> {
+        let (page_width, page_height) = match page.points() {
+            (short, long) if width > height => (long, short),
+            (short, long) => (short, long),
+        };
+
+        let (image_width, image_height) = printed_size(width, height, dpi, page_width, page_height);
+        let x = (page_width - image_width) / 2.0;
+        let y = (page_height - image_height) / 2.0;
+
+        let rgb = rgba
+            .chunks(4)
+            .take(width as usize * height as usize)
+            .flat_map(|pixel| pixel.iter().take(3).copied())
+            .collect::<Vec<u8>>();
+        let image = zlib_stored(&rgb);
+
+        let content =
+            format!("q {image_width:.2} 0 0 {image_height:.2} {x:.2} {y:.2} cm /Im0 Do Q");
+
+        let objects: [Vec<u8>; 5] = [
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {page_width} {page_height}] \
+                 /Resources << /XObject << /Im0 5 0 R >> >> /Contents 4 0 R >>"
+            )
+            .into_bytes(),
+            stream(
+                format!("<< /Length {} >>", content.len()),
+                content.as_bytes(),
+            ),
+            stream(
+                format!(
+                    "<< /Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                     /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode \
+                     /Length {} >>",
+                    image.len()
+                ),
+                &image,
+            ),
+        ];
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+
+        for (idx, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", idx + 1).as_bytes());
+            pdf.extend(object);
+            pdf.extend(b"\nendobj\n");
+        }
+
+        let xref = pdf.len();
+        pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            pdf.extend(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                objects.len() + 1
+            )
+            .as_bytes(),
+        );
+
+        pdf
+    }
+
+    fn stream(dictionary: String, data: &[u8]) -> Vec<u8> {
+        let mut output = dictionary.into_bytes();
+        output.extend(b"\nstream\n");
+        output.extend(data);
+        output.extend(b"\nendstream");
+        output
+    }
+
+    #[test]
+    fn test_pdf_encode() {
+        assert_eq!(printed_size(300, 150, 300, 595.0, 842.0), (72.0, 36.0));
+        let (width, height) = printed_size(6000, 3000, 72, 842.0, 595.0);
+        assert!((width - (842.0 - 2.0 * MARGIN)).abs() < 1e-3);
+        assert!((height - width / 2.0).abs() < 1e-3);
+
+        let pdf = encode(2, 1, &[255, 0, 0, 255, 0, 0, 255, 128], PageSize::A4, 150);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(text.contains("/MediaBox [0 0 842 595]"));
+
+        let xref = text.rfind("xref\n").unwrap();
+        let startxref = text
+            .split("startxref\n")
+            .nth(1)
+            .and_then(|rest| rest.lines().next())
+            .and_then(|offset| offset.parse::<usize>().ok());
+        assert_eq!(startxref, Some(xref));
+
+        let page = text.find("3 0 obj").unwrap();
+        assert!(text.contains(&format!("{page:010} 00000 n ")));
+    }
+}
+
+pub mod svg {
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Encodes `rgba`, rows of `width` pixels of 4 bytes each, as an SVG of
+    /// the same size. Charts are drawn as pixels, so the SVG holds them as
+    /// an embedded PNG rather than as shapes.
+    pub fn encode(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+        let png = base64(&super::png::encode(width, height, rgba));
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n\
+             <image width=\"{width}\" height=\"{height}\" href=\"data:image/png;base64,{png}\"/>\n\
+             </svg>\n"
+        )
+        .into_bytes()
+    }
+
+    fn base64(bytes: &[u8]) -> String {
+        let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+        for chunk in bytes.chunks(3) {
+            let group = chunk.iter().enumerate().fold(0_u32, |group, (idx, byte)| {
+                group | ((*byte as u32) << (16 - idx * 8))
+            });
+
+            for idx in 0..4 {
+                if idx <= chunk.len() {
+                    let sextet = (group >> (18 - idx * 6)) & 0x3F;
+                    output.push(BASE64[sextet as usize] as char);
+                } else {
+                    output.push('=');
+                }
+            }
         }
+
+        output
+    }
+
+    #[test]
+    fn test_svg_encode() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Many"), "TWFueQ==");
+
+        let svg = encode(2, 1, &[255, 0, 0, 255, 0, 0, 255, 128]);
+        let text = String::from_utf8(svg).unwrap();
+
+        assert!(text.starts_with("<svg "));
+        assert!(text.contains("width=\"2\" height=\"1\""));
+        assert!(text.contains("href=\"data:image/png;base64,iVBORw0KGgo"));
+        assert!(text.ends_with("</svg>\n"));
     }
 }
 
@@ -450,10 +2911,19 @@ pub enum AppError {
     FileSaving(io::ErrorKind),
     CSVError(Error),
     Simple(String),
+    /// An error at a known place in a file
+    Located(String, Location),
     #[default]
     None,
 }
 
+/// A line of a file, counting from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+}
+
 impl Clone for AppError {
     fn clone(&self) -> Self {
         match self {
@@ -462,6 +2932,7 @@ impl Clone for AppError {
             Self::FileDialogClosed => Self::FileDialogClosed,
             Self::FontLoading(err) => Self::FontLoading(err.clone()),
             Self::Simple(s) => Self::Simple(s.clone()),
+            Self::Located(s, location) => Self::Located(s.clone(), location.clone()),
             Self::CSVError(err) => AppError::Simple(err.to_string()),
             Self::None => Self::None,
         }
@@ -476,10 +2947,19 @@ impl AppError {
             Self::FileLoading(err) => err.to_string(),
             Self::FileSaving(err) => err.to_string(),
             Self::Simple(s) => s.clone(),
+            Self::Located(s, _) => s.clone(),
             Self::CSVError(err) => err.to_string(),
             Self::None => String::new(),
         }
     }
+
+    /// Where in a file the error is, if known
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Located(_, location) => Some(location),
+            _ => None,
+        }
+    }
 }
 
 impl Display for AppError {
@@ -514,6 +2994,38 @@ pub async fn pick_file() -> Result<PathBuf, AppError> {
     Ok(handle.path().into())
 }
 
+/// Asks the user where to save a file, suggesting `file_name`
+pub async fn pick_save_path(file_name: String) -> Result<PathBuf, AppError> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Choose File name")
+        .set_file_name(file_name)
+        .save_file()
+        .await
+        .ok_or(AppError::FileDialogClosed)?;
+
+    Ok(handle.path().into())
+}
+
+/// Opens `url` in the system's default browser
+pub fn open_url(url: &str) -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|err| AppError::Simple(format!("Could not open {url}: {err}")))
+}
+
 pub async fn load_file(path: PathBuf) -> (Result<String, AppError>, PathBuf) {
     let res = tokio::fs::read_to_string(path.clone())
         .await
@@ -522,6 +3034,134 @@ pub async fn load_file(path: PathBuf) -> (Result<String, AppError>, PathBuf) {
     (res, path)
 }
 
+/// Bytes read between progress updates while loading a file
+const LOAD_CHUNK: usize = 256 * 1024;
+
+/// Progress of a file being read with [`load_file_progress`]
+#[derive(Debug, Clone)]
+pub enum LoadEvent {
+    /// `read` of `total` bytes have been read so far
+    Progress {
+        read: u64,
+        total: u64,
+    },
+    Loaded(Result<String, AppError>),
+}
+
+/// Reads the file at `path` in chunks, reporting progress after each one
+/// so large files can show how far along they are
+pub fn load_file_progress(path: PathBuf) -> impl iced::futures::Stream<Item = LoadEvent> {
+    use iced::futures::SinkExt;
+    use tokio::io::AsyncReadExt;
+
+    iced::stream::channel(4, move |mut output| async move {
+        let result = async {
+            let mut file = tokio::fs::File::open(&path).await?;
+            let total = file.metadata().await?.len();
+            let mut bytes = Vec::with_capacity(total as usize);
+            let mut chunk = vec![0; LOAD_CHUNK];
+
+            loop {
+                let read = file.read(&mut chunk).await?;
+                if read == 0 {
+                    break;
+                }
+
+                bytes.extend_from_slice(&chunk[..read]);
+                let progress = LoadEvent::Progress {
+                    read: bytes.len() as u64,
+                    total,
+                };
+                let _ = output.send(progress).await;
+            }
+
+            String::from_utf8(bytes).map_err(|_| io::ErrorKind::InvalidData.into())
+        }
+        .await
+        .map_err(|err: io::Error| AppError::FileLoading(err.kind()));
+
+        let _ = output.send(LoadEvent::Loaded(result)).await;
+    })
+}
+
+/// How long changes to watched files are gathered before being reported,
+/// as programs often write a file in several steps
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Reports the files among `paths` that change on disk, for as long as the
+/// stream is polled. Their folders are watched rather than the files, so
+/// files replaced on save are still followed.
+pub fn watch_files(paths: Vec<PathBuf>) -> impl iced::futures::Stream<Item = PathBuf> {
+    use iced::futures::{channel::mpsc, SinkExt, StreamExt};
+    use notify::{EventKind, RecursiveMode, Watcher};
+    use std::collections::{HashMap, HashSet};
+
+    iced::stream::channel(4, move |mut output| async move {
+        // Changes are reported with the paths given, not the canonical ones
+        // the watcher uses
+        let watched = paths
+            .into_iter()
+            .filter_map(|path| Some((path.canonicalize().ok()?, path)))
+            .collect::<HashMap<PathBuf, PathBuf>>();
+
+        let (sender, mut events) = mpsc::unbounded();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let _ = sender.unbounded_send(event);
+        });
+
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                tracing::warn!("Could not watch open files: {err}");
+                return;
+            }
+        };
+
+        let folders = watched
+            .keys()
+            .filter_map(|path| path.parent())
+            .collect::<HashSet<_>>();
+
+        for folder in folders {
+            if let Err(err) = watcher.watch(folder, RecursiveMode::NonRecursive) {
+                tracing::warn!("Could not watch {}: {err}", folder.display());
+            }
+        }
+
+        while let Some(event) = events.next().await {
+            tokio::time::sleep(WATCH_DEBOUNCE).await;
+
+            let pending = std::iter::from_fn(|| events.try_next().ok().flatten());
+
+            let changed = std::iter::once(event)
+                .chain(pending)
+                .filter_map(Result::ok)
+                .filter(|event| matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)))
+                .flat_map(|event| event.paths)
+                .filter_map(|path| watched.get(&path).cloned())
+                .collect::<HashSet<_>>();
+
+            for path in changed {
+                let _ = output.send(path).await;
+            }
+        }
+    })
+}
+
+/// Reads standard input until it is closed. Reading happens off the UI
+/// thread, so input that is slow to arrive does not freeze the window
+pub async fn load_stdin() -> Result<String, AppError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut data = String::new();
+    tokio::io::stdin()
+        .read_to_string(&mut data)
+        .await
+        .map_err(|err| AppError::FileLoading(err.kind()))?;
+
+    Ok(data)
+}
+
 pub async fn save_file(
     path: Option<PathBuf>,
     content: String,
@@ -544,6 +3184,15 @@ pub async fn save_file(
     Ok((path, content))
 }
 
+/// Writes `bytes` to `path`, for content that is not text
+pub async fn save_bytes(path: PathBuf, bytes: Vec<u8>) -> Result<PathBuf, AppError> {
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|err| AppError::FileSaving(err.kind()))?;
+
+    Ok(path)
+}
+
 /// Represents singular/multiple selection of Rows/Columns
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
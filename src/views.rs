@@ -5,17 +5,17 @@ use std::{
 
 use iced::{
     widget::{button, column, container, horizontal_space, row, text, Button, Container, Row},
-    Element, Length, Renderer, Theme,
+    Element, Length, Renderer, Size, Theme,
 };
 
 use crate::Message;
 
 pub mod tabs;
-pub use tabs::Refresh;
+pub use tabs::{render_view, Refresh};
 use tabs::{TabBarMessage, TabLabel, TabsState};
 
 mod editor;
-pub use editor::EditorTabData;
+pub use editor::{line_offset, EditorTabData};
 
 mod line;
 pub use line::LineTabData;
@@ -23,12 +23,36 @@ pub use line::LineTabData;
 mod barchart;
 pub use barchart::BarChartTabData;
 
+mod boxplot;
+pub use boxplot::BoxPlotTabData;
+
+mod histogram;
+pub use histogram::HistogramTabData;
+
+mod radar;
+pub use radar::RadarTabData;
+
 mod shared;
+use shared::offscreen::Image;
+use shared::state::ChartViewState;
+pub use shared::{graph::hex_color, offscreen};
 
 mod stacked_barchart;
 pub use stacked_barchart::StackedBarChartTabData;
 
-use crate::utils::icons;
+mod table;
+pub use table::TableTabData;
+
+use crate::utils::{
+    icons,
+    json::{self, json, Value},
+    sampling::RowLimit,
+    AppError,
+};
+use crate::widgets::wizard::{
+    BarChartConfigState, BoxPlotConfigState, HistogramConfigState, LineConfigState,
+    RadarConfigState, StackedBarChartConfigState,
+};
 
 #[derive(Debug, Clone, PartialEq, Default, Copy)]
 pub enum FileType {
@@ -43,7 +67,7 @@ pub enum FileType {
 impl FileType {
     fn create<'a>(ext: &'a str) -> Self {
         match ext {
-            "csv" => Self::CSV,
+            "csv" | "tsv" => Self::CSV,
             "json" => Self::JSON,
             "txt" => Self::TXT,
             _ => Self::Other,
@@ -64,6 +88,10 @@ pub enum View {
     LineGraph(LineTabData),
     BarChart(BarChartTabData),
     StackedBarChart(StackedBarChartTabData),
+    BoxPlot(BoxPlotTabData),
+    Histogram(HistogramTabData),
+    Radar(RadarTabData),
+    Table(TableTabData),
     #[default]
     None,
 }
@@ -76,9 +104,46 @@ impl View {
             Self::LineGraph(_) => false,
             Self::BarChart(_) => false,
             Self::StackedBarChart(_) => false,
+            Self::BoxPlot(_) => false,
+            Self::Histogram(_) => false,
+            Self::Radar(_) => false,
+            Self::Table(_) => true,
             Self::None => false,
         }
     }
+
+    /// Sets the theme charts are drawn with. Other views are unchanged.
+    pub fn theme(self, theme: Theme) -> Self {
+        match self {
+            Self::LineGraph(data) => Self::LineGraph(data.theme(theme)),
+            Self::BarChart(data) => Self::BarChart(data.theme(theme)),
+            Self::StackedBarChart(data) => Self::StackedBarChart(data.theme(theme)),
+            Self::BoxPlot(data) => Self::BoxPlot(data.theme(theme)),
+            Self::Histogram(data) => Self::Histogram(data.theme(theme)),
+            Self::Radar(data) => Self::Radar(data.theme(theme)),
+            view => view,
+        }
+    }
+
+    /// Number of values left out of a chart for not being positive on a
+    /// logarithmic axis
+    pub fn log_skipped(&self) -> usize {
+        match self {
+            Self::LineGraph(data) => data.log_skipped(),
+            Self::BarChart(data) => data.log_skipped(),
+            _ => 0,
+        }
+    }
+}
+
+/// Whether closing a view first asks the user to save their changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClosePolicy {
+    /// Always closes without asking. Used by read-only and view-only views
+    #[default]
+    Never,
+    /// Asks before closing if the view has unsaved changes
+    PromptIfDirty,
 }
 
 #[allow(unused_variables)]
@@ -109,6 +174,39 @@ pub trait Viewable {
 
     fn theme_changed(&mut self, _theme: &Theme) {}
 
+    /// Returns the data plotted by self as CSV, if self is a chart
+    fn to_csv(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the data plotted by self as CSV, leaving out hidden series,
+    /// if self is a chart
+    fn to_visible_csv(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the number of rows shown and the total number of rows if
+    /// only a sample of the source file's rows was loaded
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Returns a short description of the data behind self, such as its
+    /// size and the columns a chart maps, for the status bar
+    fn status_summary(&self) -> Option<String> {
+        None
+    }
+
+    /// Reloads self with every row of the source file, if only a sample
+    /// was loaded
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Called when the window's scale factor changes so any cached geometry
+    /// can be redrawn at the new scale
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {}
+
     fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, Theme, Renderer>
     where
         F: 'a + Fn(Self::Event) -> Message,
@@ -130,6 +228,37 @@ pub trait Viewable {
         None
     }
 
+    /// Returns the toolbar driven view options of self, if any
+    fn view_state(&self) -> Option<ChartViewState> {
+        None
+    }
+
+    /// Reapplies previously saved view options
+    fn restore_view_state(&mut self, state: ChartViewState) {}
+
+    /// Draws the chart of self into an image of `size` pixels, without a
+    /// window. Returns None if self is not a chart.
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        None
+    }
+
+    /// Returns a JSON description of self, if self is a chart. See
+    /// [`shared::metadata`] for the schema.
+    fn to_metadata_json(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns what is needed to reopen self in a later session, if self
+    /// can be reopened
+    fn session(&self) -> Option<SessionTab> {
+        None
+    }
+
+    /// Returns whether closing self should ask the user to save first
+    fn close_policy(&self) -> ClosePolicy {
+        ClosePolicy::default()
+    }
+
     /// Returns true if self can be saved. Unlike [`is_dirty`], this represents
     /// the logical reasoning of whether a view can be saved.
     fn can_save(&self) -> bool {
@@ -143,6 +272,10 @@ pub enum ViewType {
     LineGraph,
     BarChart,
     StackedBarChart,
+    BoxPlot,
+    Histogram,
+    Radar,
+    Table,
     #[default]
     None,
 }
@@ -156,6 +289,10 @@ impl ViewType {
         Self::LineGraph,
         Self::BarChart,
         Self::StackedBarChart,
+        Self::BoxPlot,
+        Self::Histogram,
+        Self::Radar,
+        Self::Table,
     ];
 
     pub fn name(&self) -> String {
@@ -165,26 +302,47 @@ impl ViewType {
             Self::LineGraph => "Line Graph".into(),
             Self::BarChart => "Bar Chart".into(),
             Self::StackedBarChart => "Stacked Bar Chart".into(),
+            Self::BoxPlot => "Box Plot".into(),
+            Self::Histogram => "Histogram".into(),
+            Self::Radar => "Radar Chart".into(),
+            Self::Table => "Table".into(),
         }
     }
 
-    pub fn display(&self) -> Row<'_, Message, Theme, Renderer> {
-        let txt = text(self.name());
+    /// The icon and name of the view type, with text of `size`
+    pub fn display(&self, size: f32) -> Row<'_, Message, Theme, Renderer> {
+        let txt = text(self.name()).size(size);
         match self {
             Self::Editor => {
-                let icon = icons::icon(icons::EDITOR);
+                let icon = icons::icon(icons::EDITOR).size(size);
                 row!(icon, txt).spacing(5)
             }
             Self::LineGraph => {
-                let icon = icons::icon(icons::CHART);
+                let icon = icons::icon(icons::CHART).size(size);
                 row!(icon, txt).spacing(5)
             }
             Self::BarChart => {
-                let icon = icons::icon(icons::BARCHART);
+                let icon = icons::icon(icons::BARCHART).size(size);
                 row!(icon, txt).spacing(5)
             }
             Self::StackedBarChart => {
-                let icon = icons::icon(icons::BARCHART);
+                let icon = icons::icon(icons::BARCHART).size(size);
+                row!(icon, txt).spacing(5)
+            }
+            Self::BoxPlot => {
+                let icon = icons::icon(icons::BARCHART).size(size);
+                row!(icon, txt).spacing(5)
+            }
+            Self::Histogram => {
+                let icon = icons::icon(icons::BARCHART).size(size);
+                row!(icon, txt).spacing(5)
+            }
+            Self::Radar => {
+                let icon = icons::icon(icons::CHART).size(size);
+                row!(icon, txt).spacing(5)
+            }
+            Self::Table => {
+                let icon = icons::icon(icons::CONFIG).size(size);
                 row!(icon, txt).spacing(5)
             }
             Self::None => Row::new(),
@@ -198,6 +356,10 @@ impl ViewType {
             Self::LineGraph => true,
             Self::BarChart => true,
             Self::StackedBarChart => true,
+            Self::BoxPlot => true,
+            Self::Histogram => true,
+            Self::Radar => true,
+            Self::Table => false,
             Self::None => false,
         }
     }
@@ -205,14 +367,30 @@ impl ViewType {
     pub fn is_supported_filetype(&self, extn: &FileType) -> bool {
         match self {
             Self::LineGraph => match extn {
-                FileType::CSV => true,
+                FileType::CSV | FileType::JSON => true,
                 _ => false,
             },
             Self::BarChart => match extn {
-                FileType::CSV => true,
+                FileType::CSV | FileType::JSON => true,
                 _ => false,
             },
             Self::StackedBarChart => match extn {
+                FileType::CSV | FileType::JSON => true,
+                _ => false,
+            },
+            Self::BoxPlot => match extn {
+                FileType::CSV | FileType::JSON => true,
+                _ => false,
+            },
+            Self::Histogram => match extn {
+                FileType::CSV | FileType::JSON => true,
+                _ => false,
+            },
+            Self::Radar => match extn {
+                FileType::CSV | FileType::JSON => true,
+                _ => false,
+            },
+            Self::Table => match extn {
                 FileType::CSV => true,
                 _ => false,
             },
@@ -228,7 +406,227 @@ impl fmt::Display for ViewType {
     }
 }
 
-pub fn home_view<'a>() -> Container<'a, Message, Theme, Renderer> {
+/// A chart shown small and without interaction while it is configured in
+/// the wizard
+#[derive(Debug)]
+pub enum ChartPreview {
+    BarChart(barchart::BarChartTab),
+    StackedBarChart(stacked_barchart::StackedBarChartTab),
+}
+
+impl ChartPreview {
+    /// Returns None if `view` is not a chart that can be previewed
+    pub fn new(view: View, theme: Theme) -> Option<Self> {
+        match view {
+            View::BarChart(data) => Some(Self::BarChart(barchart::BarChartTab::new(
+                data.theme(theme),
+            ))),
+            View::StackedBarChart(data) => Some(Self::StackedBarChart(
+                stacked_barchart::StackedBarChartTab::new(data.theme(theme)),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        match self {
+            Self::BarChart(tab) => tab.preview(),
+            Self::StackedBarChart(tab) => tab.preview(),
+        }
+    }
+}
+
+/// A tab to reopen in a later session. Charts are rebuilt from their file
+/// and configuration, other tabs only keep their file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionTab {
+    Editor(PathBuf),
+    Table(PathBuf),
+    LineGraph(PathBuf, LineConfigState),
+    BarChart(PathBuf, BarChartConfigState),
+    StackedBarChart(PathBuf, StackedBarChartConfigState),
+    BoxPlot(PathBuf, BoxPlotConfigState),
+    Histogram(PathBuf, HistogramConfigState),
+    Radar(PathBuf, RadarConfigState),
+}
+
+impl SessionTab {
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Self::Editor(path)
+            | Self::Table(path)
+            | Self::LineGraph(path, _)
+            | Self::BarChart(path, _)
+            | Self::StackedBarChart(path, _)
+            | Self::BoxPlot(path, _)
+            | Self::Histogram(path, _)
+            | Self::Radar(path, _) => path,
+        }
+    }
+
+    /// The kind of tab self reopens as
+    pub fn kind(&self) -> ViewType {
+        match self {
+            Self::Editor(_) => ViewType::Editor,
+            Self::Table(_) => ViewType::Table,
+            Self::LineGraph(..) => ViewType::LineGraph,
+            Self::BarChart(..) => ViewType::BarChart,
+            Self::StackedBarChart(..) => ViewType::StackedBarChart,
+            Self::BoxPlot(..) => ViewType::BoxPlot,
+            Self::Histogram(..) => ViewType::Histogram,
+            Self::Radar(..) => ViewType::Radar,
+        }
+    }
+
+    /// Samples charts that keep a limited number of rows at `row_limit`, as
+    /// the setting may have changed since the chart was made
+    pub fn with_row_limit(mut self, row_limit: RowLimit) -> Self {
+        match &mut self {
+            Self::LineGraph(_, config) => config.row_limit = row_limit,
+            Self::BarChart(_, config) => config.row_limit = row_limit,
+            Self::BoxPlot(_, config) => config.row_limit = row_limit,
+            Self::Histogram(_, config) => config.row_limit = row_limit,
+            Self::Radar(_, config) => config.row_limit = row_limit,
+            Self::StackedBarChart(_, config) => config.row_limit = row_limit,
+            Self::Editor(_) | Self::Table(_) => {}
+        }
+
+        self
+    }
+
+    /// Serializes self with the toolbar options of the tab, if it is a chart
+    pub fn to_json(&self, view_state: Option<&ChartViewState>) -> Value {
+        let (kind, config) = match self {
+            Self::Editor(_) => ("editor", Value::Null),
+            Self::Table(_) => ("table", Value::Null),
+            Self::LineGraph(_, config) => ("line", config.to_json()),
+            Self::BarChart(_, config) => ("bar", config.to_json()),
+            Self::StackedBarChart(_, config) => ("stacked_bar", config.to_json()),
+            Self::BoxPlot(_, config) => ("box_plot", config.to_json()),
+            Self::Histogram(_, config) => ("histogram", config.to_json()),
+            Self::Radar(_, config) => ("radar", config.to_json()),
+        };
+
+        json!({
+            "kind": kind,
+            "path": self.path().to_string_lossy(),
+            "config": config,
+            "view_state": view_state.map(ChartViewState::serialize),
+        })
+    }
+
+    /// Reads a tab written by [`SessionTab::to_json`] with its toolbar
+    /// options. Returns None if the kind or path is missing.
+    pub fn from_json(json: &Value) -> Option<(Self, Option<ChartViewState>)> {
+        let path = PathBuf::from(json.get("path").and_then(Value::as_str)?);
+        let config = json.get("config").unwrap_or(&Value::Null);
+
+        let tab = match json.get("kind").and_then(Value::as_str)? {
+            "editor" => Self::Editor(path),
+            "table" => Self::Table(path),
+            "line" => Self::LineGraph(path, LineConfigState::from_json(config)),
+            "bar" => Self::BarChart(path, BarChartConfigState::from_json(config)),
+            "stacked_bar" => {
+                Self::StackedBarChart(path, StackedBarChartConfigState::from_json(config))
+            }
+            "box_plot" => Self::BoxPlot(path, BoxPlotConfigState::from_json(config)),
+            "histogram" => Self::Histogram(path, HistogramConfigState::from_json(config)),
+            "radar" => Self::Radar(path, RadarConfigState::from_json(config)),
+            _ => return None,
+        };
+
+        let view_state = json
+            .get("view_state")
+            .and_then(Value::as_str)
+            .map(ChartViewState::deserialize);
+
+        Some((tab, view_state))
+    }
+
+    /// Rebuilds the view of self. Editors and tables are empty until their
+    /// file is loaded.
+    pub fn view(self) -> Result<View, AppError> {
+        match self {
+            Self::Editor(_) => Ok(View::Editor(EditorTabData::default())),
+            Self::Table(_) => Ok(View::Table(TableTabData::default())),
+            Self::LineGraph(path, config) => LineTabData::new(path, config).map(View::LineGraph),
+            Self::BarChart(path, config) => BarChartTabData::new(path, config).map(View::BarChart),
+            Self::StackedBarChart(path, config) => {
+                StackedBarChartTabData::new(path, config).map(View::StackedBarChart)
+            }
+            Self::BoxPlot(path, config) => BoxPlotTabData::new(path, config).map(View::BoxPlot),
+            Self::Histogram(path, config) => {
+                HistogramTabData::new(path, config).map(View::Histogram)
+            }
+            Self::Radar(path, config) => RadarTabData::new(path, config).map(View::Radar),
+        }
+    }
+}
+
+/// The tabs open when modav was last closed, with the toolbar options of
+/// the charts among them
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Session(Vec<(SessionTab, Option<ChartViewState>)>);
+
+impl Session {
+    /// Version of the stored format, increased on incompatible changes
+    const VERSION: usize = 1;
+
+    pub fn new(tabs: Vec<(SessionTab, Option<ChartViewState>)>) -> Self {
+        Self(tabs)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn tabs(self) -> Vec<(SessionTab, Option<ChartViewState>)> {
+        self.0
+    }
+
+    pub fn serialize(&self) -> String {
+        let tabs = self
+            .0
+            .iter()
+            .map(|(tab, view_state)| tab.to_json(view_state.as_ref()))
+            .collect::<Vec<_>>();
+
+        json!({
+            "version": Self::VERSION,
+            "tabs": tabs,
+        })
+        .to_string()
+    }
+
+    /// Parses a session written by [`Session::serialize`]. Tabs that cannot
+    /// be read are skipped.
+    pub fn deserialize(input: &str) -> Result<Self, AppError> {
+        let json: Value =
+            serde_json::from_str(input).map_err(|err| AppError::Simple(err.to_string()))?;
+
+        if json.get("version").and_then(json::as_usize) != Some(Self::VERSION) {
+            return Err(AppError::Simple("Unsupported session version".into()));
+        }
+
+        let tabs = json
+            .get("tabs")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(SessionTab::from_json)
+            .collect();
+
+        Ok(Self(tabs))
+    }
+}
+
+/// The screen shown when no tabs are open. `session_tabs` is the number of
+/// tabs of the last session, if it can be reopened.
+pub fn home_view<'a>(session_tabs: Option<usize>) -> Container<'a, Message, Theme, Renderer> {
     let new_btn: Button<'_, Message, Theme, Renderer> = button("New File")
         .on_press(Message::OpenTab(
             None,
@@ -241,9 +639,22 @@ pub fn home_view<'a>() -> Container<'a, Message, Theme, Renderer> {
     let recents_btn: Button<'_, Message, Theme, Renderer> = button("Recent Files")
         .on_press(Message::None)
         .style(button::text);
+    let restore_btn = session_tabs.map(|count| {
+        let label = if count == 1 {
+            String::from("Reopen Last Session (1 tab)")
+        } else {
+            format!("Reopen Last Session ({count} tabs)")
+        };
+
+        button(text(label))
+            .on_press(Message::RestoreSession)
+            .style(button::text)
+    });
     let options: Row<'_, Message, Theme, Renderer> = row!(
         horizontal_space(),
-        column!(new_btn, open_btn, recents_btn).spacing(8),
+        column!(new_btn, open_btn, recents_btn)
+            .push_maybe(restore_btn)
+            .spacing(8),
         horizontal_space()
     )
     .width(Length::Fill);
@@ -280,3 +691,66 @@ pub fn parse_seed(mut input: String, compare: bool) -> Option<f32> {
 
 pub type Tabs<Theme> = TabsState<Theme>;
 pub type TabsMessage = TabBarMessage;
+
+#[test]
+fn test_session_round_trip() {
+    use crate::utils::csv::{FilterOp, RowFilter};
+    use modav_core::repr::sheet::utils::BarChartAxisLabelStrategy;
+
+    let bar = BarChartConfigState {
+        title: "Sales \"2024\"".into(),
+        x_col: 1,
+        y_col: 3,
+        caption: Some("By region".into()),
+        row_exclude: [2, 5].into_iter().collect(),
+        axis_label: BarChartAxisLabelStrategy::Provided {
+            x: "Region".into(),
+            y: "Total".into(),
+        },
+        is_horizontal: true,
+        filters: vec![RowFilter {
+            column: 0,
+            op: FilterOp::Contains,
+            value: "north".into(),
+        }],
+        ..Default::default()
+    };
+
+    let line = LineConfigState {
+        facet: Some(2),
+        log_y: true,
+        secondary: vec!["Rate".into()],
+        secondary_label: "Percent".into(),
+        ..Default::default()
+    };
+
+    let view_state = ChartViewState {
+        crosshair: true,
+        ..Default::default()
+    };
+
+    let session = Session::new(vec![
+        (SessionTab::Editor(PathBuf::from("notes.txt")), None),
+        (
+            SessionTab::BarChart(PathBuf::from("sales.csv"), bar),
+            Some(view_state),
+        ),
+        (
+            SessionTab::LineGraph(PathBuf::from("rates.csv"), line),
+            Some(ChartViewState::default()),
+        ),
+    ]);
+
+    assert_eq!(
+        Session::deserialize(&session.serialize()).ok(),
+        Some(session)
+    );
+
+    assert!(Session::deserialize(r#"{"version": 99, "tabs": []}"#).is_err());
+    assert_eq!(
+        Session::deserialize(r#"{"version": 1, "tabs": [{"kind": "pie", "path": "a.csv"}]}"#)
+            .map(|session| session.len())
+            .ok(),
+        Some(0)
+    );
+}
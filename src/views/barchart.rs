@@ -3,8 +3,8 @@ use std::{fmt::Debug, path::PathBuf};
 use iced::{
     alignment,
     widget::{
-        button, canvas, checkbox, column, container, horizontal_space, row, text, text_input,
-        Canvas, Tooltip,
+        button, canvas, checkbox, column, container, horizontal_space, pick_list, row, text,
+        text_input, Canvas, Tooltip,
     },
     Alignment, Color, Element, Font, Length, Padding, Point, Renderer, Size, Theme,
 };
@@ -12,16 +12,28 @@ use iced::{
 use modav_core::{
     models::{
         bar::{Bar, BarChart},
-        Point as GraphPoint, Scale,
+        AxisPoints, Point as GraphPoint, Scale,
+    },
+    repr::sheet::{
+        builders::SheetBuilder,
+        utils::{BarChartAxisLabelStrategy, BarChartBarLabels, Data, HeaderLabelStrategy},
     },
-    repr::sheet::{builders::SheetBuilder, utils::Data},
 };
 use tracing::warn;
 
 use crate::{
-    utils::{coloring::ColorEngine, icons, tooltip, AppError},
+    utils::{
+        coloring::ColorEngine,
+        csv,
+        formatting::{numeric_value, Thresholds},
+        icons,
+        sampling::RowLimit,
+        stats::Aggregation,
+        tooltip, AppError, AxisFormat, NotationMode,
+    },
     widgets::{
         modal::Modal,
+        toast::{Status, Toast},
         toolbar::{ToolBarOrientation, ToolbarMenu},
         wizard::BarChartConfigState,
     },
@@ -31,11 +43,23 @@ use crate::{
 use super::{
     parse_seed,
     shared::{
-        graph::{create_axis, Axis, DrawnOutput, Graph, Graphable, LegendPosition},
-        ContentAreaContainer, EditorButtonStyle,
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            create_axis, legend_row, log_points, muted, AspectRatio, Axis, ChartColor, DrawnOutput,
+            Graph, Graphable, GridStyle, HoverPoint, LegendEntry, LegendPosition, LegendSort,
+            LEGEND_ROW_HEIGHT,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{self, Image},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, EditorButtonStyle, StyleInputs,
     },
     tabs::TabLabel,
-    Viewable,
+    SessionTab, Viewable,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,23 +67,34 @@ pub struct GraphBar {
     point: GraphPoint,
     label: Option<String>,
     color: Color,
+    /// Hidden bars are not drawn and are greyed out in the legend
+    hidden: bool,
+    /// Fraction of the space between axis points the bar spans
+    width: f32,
 }
 
 impl GraphBar {
-    fn new(point: GraphPoint<Data, Data>, label: Option<String>, color: Color) -> Self {
+    pub(super) fn new(point: GraphPoint<Data, Data>, label: Option<String>, color: Color) -> Self {
         Self {
             point,
             label,
             color,
+            hidden: false,
+            width: 0.5,
         }
     }
 
-    fn color(mut self, color: Color) -> Self {
+    pub(super) fn color(mut self, color: Color) -> Self {
         self.color = color;
         self
     }
 
-    fn set_color(&mut self, color: impl Into<Color>) {
+    pub(super) fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub(super) fn set_color(&mut self, color: impl Into<Color>) {
         self.color = color.into();
     }
 }
@@ -83,6 +118,10 @@ impl Graphable for GraphBar {
         self.label.is_some()
     }
 
+    fn legend_value(&self, _data: &Self::Data<'_>) -> Option<f64> {
+        numeric_value(&self.point.y)
+    }
+
     fn draw_legend(
         &self,
         frame: &mut canvas::Frame,
@@ -91,20 +130,21 @@ impl Graphable for GraphBar {
         idx: usize,
         _data: &Self::Data<'_>,
     ) {
-        if idx > 4 {
-            return;
-        }
-
-        let y_padding = bounds.height / (5.0);
         let spacing = 5.0;
         let text_size = 12.0;
         let color_size = Size::new(12.0, 12.0);
 
         let x = bounds.x;
-        let y = bounds.position().y + (idx as f32 * y_padding);
+        let y = bounds.position().y + (idx as f32 * LEGEND_ROW_HEIGHT);
         let position = Point::new(x, y);
 
-        frame.fill_rectangle(position, color_size, self.color);
+        let (swatch, color) = if self.hidden {
+            (muted(self.color), muted(color))
+        } else {
+            (self.color, color)
+        };
+
+        frame.fill_rectangle(position, color_size, swatch);
 
         let position = Point::new(x + spacing + color_size.width, y + 0.5 * color_size.height);
 
@@ -120,6 +160,56 @@ impl Graphable for GraphBar {
         frame.fill_text(label);
     }
 
+    fn legend_entry_at(
+        &self,
+        bounds: iced::Rectangle,
+        idx: usize,
+        point: Point,
+        _data: &Self::Data<'_>,
+    ) -> Option<usize> {
+        legend_row(bounds, idx).contains(point).then_some(0)
+    }
+
+    fn hover_points(
+        &self,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        data: &Self::Data<'_>,
+    ) -> Vec<HoverPoint> {
+        if self.hidden {
+            return Vec::new();
+        }
+
+        let is_horizontal = *data;
+
+        let (x_output, y_output) = if is_horizontal {
+            (y_output, x_output)
+        } else {
+            (x_output, y_output)
+        };
+
+        let Some((x, y)) = x_output
+            .get_closest(&self.point.x, true)
+            .zip(y_output.get_closest(&self.point.y, false))
+        else {
+            return Vec::new();
+        };
+
+        // The point sits at the end of the bar
+        let position = if is_horizontal {
+            Point::new(y, x)
+        } else {
+            Point::new(x, y)
+        };
+
+        vec![HoverPoint {
+            position,
+            label: self.label.clone(),
+            x: self.point.x.clone(),
+            y: self.point.y.clone(),
+        }]
+    }
+
     fn draw(
         &self,
         frame: &mut canvas::Frame,
@@ -127,6 +217,10 @@ impl Graphable for GraphBar {
         y_output: &DrawnOutput,
         data: &Self::Data<'_>,
     ) {
+        if self.hidden {
+            return;
+        }
+
         let is_horizontal = *data;
 
         let mut x_output = x_output;
@@ -161,7 +255,7 @@ impl Graphable for GraphBar {
         } = x_output;
 
         if is_horizontal {
-            let height = x_spacing / 2.0;
+            let height = x_spacing * self.width;
 
             let base = *x_axis;
 
@@ -170,7 +264,7 @@ impl Graphable for GraphBar {
 
             frame.fill_rectangle(top_left, size, self.color)
         } else {
-            let width = x_spacing / 2.0;
+            let width = x_spacing * self.width;
             let base = *x_axis;
 
             let top_left = Point::new(x - (width / 2.0), f32::min(y, base));
@@ -190,6 +284,10 @@ pub struct BarChartTabData {
     order: bool,
     is_horizontal: bool,
     caption: Option<String>,
+    config: BarChartConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
 }
 
 impl BarChartTabData {
@@ -198,20 +296,51 @@ impl BarChartTabData {
             title,
             trim,
             flexible,
+            delimiter,
             header_types,
             header_labels,
-            row_exclude,
-            bar_label,
+            mut row_exclude,
+            mut bar_label,
             axis_label,
-            x_col,
-            y_col,
+            mut x_col,
+            mut y_col,
             order,
-            caption,
+            mut caption,
             is_horizontal,
+            row_limit,
+            filters,
+            aggregation,
             ..
-        } = config;
+        } = config.clone();
 
-        let sht = SheetBuilder::new(file.clone().into())
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
+
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut row_exclude)?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let source = if aggregation == Aggregation::None {
+            source
+        } else {
+            let (grouped, description) =
+                csv::aggregate(&source, has_header, x_col, y_col, aggregation, &row_exclude)?;
+
+            caption = Some(match caption {
+                Some(caption) => format!("{caption} ({description})"),
+                None => description,
+            });
+            if let BarChartBarLabels::FromColumn(_) = bar_label {
+                bar_label = BarChartBarLabels::FromColumn(0);
+            }
+            row_exclude.clear();
+            (x_col, y_col) = (0, 1);
+
+            grouped
+        };
+
+        let sht = SheetBuilder::new(source.to_path_buf().into())
             .trim(trim)
             .flexible(flexible)
             .labels(header_labels)
@@ -230,6 +359,9 @@ impl BarChartTabData {
             order,
             caption,
             is_horizontal,
+            config,
+            sample,
+            shape,
             theme: Theme::default(),
         })
     }
@@ -238,6 +370,20 @@ impl BarChartTabData {
         self.theme = theme;
         self
     }
+
+    /// Number of bars left out for not being positive on a logarithmic
+    /// value axis
+    pub fn log_skipped(&self) -> usize {
+        if !self.config.log_scale {
+            return 0;
+        }
+
+        self.barchart
+            .bars
+            .iter()
+            .filter(|bar| numeric_value(&bar.point.y).is_some_and(|value| value <= 0.0))
+            .count()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -248,14 +394,35 @@ pub enum BarChartMessage {
     SequentialX(bool),
     SequentialY(bool),
     Clean(bool),
+    Crosshair(bool),
+    RotateLabels(bool),
     Horizontal(bool),
     CaptionChange(String),
     XLabelChanged(String),
     YLabelChanged(String),
     Legend(LegendPosition),
+    LegendSort(LegendSort),
+    /// Hides or shows the clicked legend entry's bar
+    ToggleSeries(LegendEntry),
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    XFormat(AxisFormat),
+    YFormat(AxisFormat),
     ChangeSeed(String),
+    AccentChanged(String),
     ApplySeed,
     RandomSeed,
+    SwapAxes,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
 }
 
 #[derive(Debug)]
@@ -268,16 +435,16 @@ pub struct BarChartTab {
     y_label: Option<String>,
     bars: Vec<GraphBar>,
     caption: Option<String>,
-    is_horizontal: bool,
     config_shown: bool,
-    sequential_x: bool,
-    sequential_y: bool,
-    clean: bool,
     color_seed: f32,
     cache: canvas::Cache,
-    legend: LegendPosition,
     theme: Theme,
-    order: bool,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
+    config: BarChartConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
 }
 
 impl BarChartTab {
@@ -324,7 +491,8 @@ impl BarChartTab {
 
         let ranged_x = {
             let check = {
-                let check = checkbox("", self.sequential_x).on_toggle(BarChartMessage::SequentialX);
+                let check = checkbox("", self.view_state.sequential_x)
+                    .on_toggle(BarChartMessage::SequentialX);
                 let label = text("Ranged X axis");
 
                 row!(label, check).spacing(8.0).align_y(Alignment::Center)
@@ -337,7 +505,8 @@ impl BarChartTab {
 
         let ranged_y = {
             let check = {
-                let check = checkbox("", self.sequential_y).on_toggle(BarChartMessage::SequentialY);
+                let check = checkbox("", self.view_state.sequential_y)
+                    .on_toggle(BarChartMessage::SequentialY);
                 let label = text("Ranged Y axis");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -350,7 +519,7 @@ impl BarChartTab {
 
         let clean = {
             let check = {
-                let check = checkbox("", self.clean).on_toggle(BarChartMessage::Clean);
+                let check = checkbox("", self.view_state.clean).on_toggle(BarChartMessage::Clean);
                 let label = text("Clean graph");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -361,6 +530,37 @@ impl BarChartTab {
             row!(check, tip).spacing(spacing)
         };
 
+        let crosshair = {
+            let check = {
+                let check =
+                    checkbox("", self.view_state.crosshair).on_toggle(BarChartMessage::Crosshair);
+                let label = text("Crosshair");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Draws guide lines from the cursor to the axes, labelled with their values",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let rotate_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.rotate_labels)
+                    .on_toggle(BarChartMessage::RotateLabels);
+                let label = text("Rotate X labels");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip =
+                tooltip("Slants the X axis labels. Labels that would overlap are always slanted");
+
+            row!(check, tip).spacing(spacing)
+        };
+
         let seed = {
             let value = self.color_seed;
             let value = format!("{value:.4}");
@@ -395,7 +595,8 @@ impl BarChartTab {
 
         let horizontal = {
             let check = {
-                let check = checkbox("", self.is_horizontal).on_toggle(BarChartMessage::Horizontal);
+                let check =
+                    checkbox("", self.view_state.horizontal).on_toggle(BarChartMessage::Horizontal);
                 let label = text("Horizontal bars");
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
             };
@@ -410,7 +611,7 @@ impl BarChartTab {
 
             let menu = ToolbarMenu::new(
                 LegendPosition::ALL,
-                self.legend,
+                self.view_state.legend,
                 BarChartMessage::Legend,
                 icons,
             )
@@ -444,6 +645,66 @@ impl BarChartTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let legend_sort = {
+            let label = text("Legend Order");
+
+            let pick_list = pick_list(
+                LegendSort::ALL,
+                Some(self.view_state.legend_sort),
+                BarChartMessage::LegendSort,
+            )
+            .padding([2, 5]);
+
+            let tip = tooltip("Reorders the legend entries without changing how bars are drawn");
+
+            row!(label, pick_list, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(BarChartMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let x_format = axis_format_row(
+            "X Axis Numbers",
+            self.view_state.x_format,
+            BarChartMessage::XFormat,
+        );
+
+        let y_format = axis_format_row(
+            "Y Axis Numbers",
+            self.view_state.y_format,
+            BarChartMessage::YFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the axes are written",
+            BarChartMessage::Notation,
+            BarChartMessage::Thresholds,
+        );
+
         let editor = {
             let font = Font::with_name(icons::NAME);
 
@@ -480,58 +741,228 @@ impl BarChartTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let swap = {
+            let btn = button(text("Duplicate").size(13.0))
+                .on_press(BarChartMessage::SwapAxes)
+                .padding([4, 8]);
+
+            let text = text("Swap Axes");
+
+            let tip =
+                tooltip("Opens a copy of this graph in a new tab with its x and y axes swapped");
+
+            row!(text, btn, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            BarChartMessage::ToggleStyle,
+            BarChartMessage::StyleColor,
+            BarChartMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, BarChartMessage::Aspect);
+        let reconfigure = reconfigure_row(BarChartMessage::Reconfigure);
+        let export = export_metadata_row(BarChartMessage::ExportMetadata);
+        let image = export_image_row(BarChartMessage::ExportImage);
+        let pdf = export_pdf_row(BarChartMessage::ExportPdf);
+
         column!(
-            header, title, x_label, y_label, caption, ranged_x, ranged_y, clean, horizontal, seed,
-            legend, editor,
+            header,
+            title,
+            x_label,
+            y_label,
+            caption,
+            ranged_x,
+            ranged_y,
+            clean,
+            crosshair,
+            rotate_labels,
+            horizontal,
+            seed,
+            accent,
+            notation,
+            x_format,
+            y_format,
+            legend,
+            legend_sort,
+            style,
+            swap,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor,
         )
         .spacing(25.0)
         .into()
     }
 
-    fn create_axis(&self) -> (Axis, Axis) {
-        let (x_scale, y_scale) = if self.is_horizontal {
+    /// Returns the options of a copy of this graph with its x and y columns
+    /// swapped. Fails if the x axis cannot hold bar values.
+    fn swapped(&self) -> Result<SessionTab, AppError> {
+        if let AxisPoints::Categorical(_) = self.x_axis.axis_points(false) {
+            return Err(AppError::Simple(String::from(
+                "Cannot swap axes: the x axis is categorical so it cannot be used for bar values",
+            )));
+        }
+
+        let mut config = self.config.clone();
+        (config.x_col, config.y_col) = (config.y_col, config.x_col);
+        if let BarChartAxisLabelStrategy::Provided { x, y } = config.axis_label {
+            config.axis_label = BarChartAxisLabelStrategy::Provided { x: y, y: x };
+        }
+        config.order = self.view_state.order;
+        config.is_horizontal = self.view_state.horizontal;
+
+        Ok(SessionTab::BarChart(self.file.clone(), config))
+    }
+
+    fn create_axis(&self) -> Result<(Axis, Axis), AppError> {
+        let (x_scale, y_scale) = if self.view_state.horizontal {
             (&self.y_axis, &self.x_axis)
         } else {
             (&self.x_axis, &self.y_axis)
         };
 
+        let log = self
+            .config
+            .log_scale
+            .then(|| {
+                log_points(
+                    self.bars
+                        .iter()
+                        .filter_map(|bar| numeric_value(&bar.point.y)),
+                )
+            })
+            .flatten();
+
+        let (log_x, log_y) = if self.view_state.horizontal {
+            (log, None)
+        } else {
+            (None, log)
+        };
+
         let (x_axis, y_axis) = create_axis(
             x_scale,
             y_scale,
-            self.sequential_x,
-            self.sequential_y,
-            self.clean,
-        );
-
-        let (x_label, y_label) = if self.is_horizontal {
+            self.view_state.sequential_x,
+            self.view_state.sequential_y,
+            self.view_state.clean,
+            log_x,
+            log_y,
+        )?;
+
+        let (x_label, y_label) = if self.view_state.horizontal {
             (self.y_label.clone(), self.x_label.clone())
         } else {
             (self.x_label.clone(), self.y_label.clone())
         };
 
-        return (x_axis.label(x_label), y_axis.label(y_label));
+        Ok((
+            x_axis
+                .label(x_label)
+                .format(self.view_state.x_format)
+                .rotate_labels(self.view_state.rotate_labels),
+            y_axis.label(y_label).format(self.view_state.y_format),
+        ))
+    }
+
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(
+        &'a self,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphBar, BarChartMessage>, AppError> {
+        let (x_axis, y_axis) = self.create_axis()?;
+
+        Ok(Graph::new(
+            x_axis,
+            y_axis,
+            &self.bars,
+            &self.theme,
+            cache,
+            self.view_state.horizontal,
+        )
+        .title(Some(self.title.as_str()).filter(|title| !title.is_empty()))
+        .caption(self.caption.as_ref())
+        .labels_len(self.bars.iter().filter(|bar| bar.label.is_some()).count())
+        .legend(self.view_state.legend)
+        .legend_sort(self.view_state.legend_sort)
+        .on_legend_toggle(BarChartMessage::ToggleSeries)
+        .notation(self.view_state.notation)
+        .crosshair(self.view_state.crosshair)
+        .style(self.view_state.style))
     }
 
     fn graph(&self) -> Element<'_, BarChartMessage> {
-        let (x_axis, y_axis) = self.create_axis();
+        let content: Element<'_, BarChartMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
 
-        let content = Canvas::new(
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    /// The chart alone, without a legend or interaction, for the wizard's
+    /// live preview
+    pub fn preview<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        let (x_axis, y_axis) = match self.create_axis() {
+            Ok(axes) => axes,
+            Err(err) => return empty_chart(&err),
+        };
+
+        Canvas::new(
             Graph::new(
                 x_axis,
                 y_axis,
                 &self.bars,
                 &self.theme,
                 &self.cache,
-                self.is_horizontal,
+                self.view_state.horizontal,
             )
-            .caption(self.caption.as_ref())
             .labels_len(self.bars.iter().filter(|bar| bar.label.is_some()).count())
-            .legend(self.legend),
+            .legend(LegendPosition::None)
+            .notation(self.view_state.notation)
+            .style(self.view_state.style),
         )
-        .width(Length::FillPortion(24))
-        .height(Length::Fill);
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
 
-        content.into()
+    /// Returns the plotted data as CSV, leaving out hidden series if
+    /// `visible_only`
+    fn csv(&self, visible_only: bool) -> Option<String> {
+        let header = csv::record([
+            "label",
+            self.x_label.as_deref().unwrap_or("x"),
+            self.y_label.as_deref().unwrap_or("y"),
+        ]);
+
+        let bars = self.bars.iter().filter(|bar| !(visible_only && bar.hidden));
+
+        let rows = bars.map(|bar| {
+            csv::record([
+                bar.label.clone().unwrap_or_default(),
+                bar.point.x.to_string(),
+                bar.point.y.to_string(),
+            ])
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
     }
 
     fn redraw(&mut self) {
@@ -539,6 +970,8 @@ impl BarChartTab {
     }
 
     fn recolor(&mut self, colors: ColorEngine) {
+        let colors = colors.count(self.bars.len() as u32);
+
         self.bars.iter_mut().zip(colors).for_each(|(bar, color)| {
             bar.set_color(color);
         });
@@ -560,6 +993,9 @@ impl Viewable for BarChartTab {
             order,
             caption,
             is_horizontal,
+            config,
+            sample,
+            shape,
         } = data;
 
         let BarChart {
@@ -574,7 +1010,9 @@ impl Viewable for BarChartTab {
             bars.sort_by(|one, two| one.point.x.cmp(&two.point.x))
         };
 
-        let colors = ColorEngine::new(&theme).gradual(order);
+        let colors = ColorEngine::new(&theme)
+            .gradual(order)
+            .count(bars.len() as u32);
         let seed = colors.seed();
 
         let bars = bars
@@ -592,16 +1030,20 @@ impl Viewable for BarChartTab {
             y_label,
             caption,
             bars,
-            is_horizontal,
             theme,
-            order,
             color_seed: seed,
             config_shown: false,
-            sequential_x: false,
-            sequential_y: false,
-            clean: false,
-            legend: LegendPosition::default(),
             cache: canvas::Cache::default(),
+            view_state: ChartViewState {
+                horizontal: is_horizontal,
+                order,
+                ..Default::default()
+            },
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+            config,
+            sample,
+            shape,
         }
     }
 
@@ -633,6 +1075,10 @@ impl Viewable for BarChartTab {
         Some(self.file.clone())
     }
 
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::BarChart(self.file.clone(), self.config.clone()))
+    }
+
     fn can_save(&self) -> bool {
         false
     }
@@ -643,8 +1089,45 @@ impl Viewable for BarChartTab {
 
     fn refresh(&mut self, data: Self::Data) {
         let new = <Self as Viewable>::new(data);
+        let view_state = self.view_state.clone();
+        let style_shown = self.style_shown;
 
         *self = new;
+        self.style_shown = style_shown;
+        self.restore_view_state(view_state);
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let shape = &self.shape;
+        let mapped = [
+            ("X", shape.column_name(self.config.x_col)),
+            ("Y", shape.column_name(self.config.y_col)),
+        ];
+
+        Some(mapped_summary(shape, &mapped))
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = BarChartConfigState {
+            row_limit: RowLimit::default(),
+            ..self.config.clone()
+        };
+        let data = BarChartTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.cache.clear();
     }
 
     fn theme_changed(&mut self, theme: &Theme) {
@@ -653,12 +1136,79 @@ impl Viewable for BarChartTab {
         }
         self.theme = theme.clone();
 
-        let colors = ColorEngine::new(&self.theme).gradual(self.order);
+        let colors = ColorEngine::new(&self.theme)
+            .accent(self.view_state.accent)
+            .gradual(self.view_state.order);
         self.color_seed = colors.seed();
 
         self.recolor(colors);
     }
 
+    fn to_csv(&self) -> Option<String> {
+        self.csv(false)
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv(true)
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        let cache = canvas::Cache::new();
+
+        Some(
+            self.chart(&cache)
+                .and_then(|chart| offscreen::render(&chart, size, &self.theme)),
+        )
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let metadata = ChartMetadata {
+            kind: "bar",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(
+                self.x_label.as_ref(),
+                self.bars.iter().map(|bar| &bar.point.x),
+            ),
+            y_axis: AxisMetadata::new(
+                self.y_label.as_ref(),
+                self.bars.iter().map(|bar| &bar.point.y),
+            ),
+            series: self
+                .bars
+                .iter()
+                .map(|bar| {
+                    SeriesMetadata::new(bar.label.as_ref(), bar.color, [&bar.point.y].into_iter())
+                })
+                .collect(),
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = ChartViewState {
+            order: self.view_state.order,
+            ..state
+        };
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent)
+                .gradual(self.view_state.order);
+            self.recolor(colors);
+        }
+
+        self.cache.clear();
+    }
+
     fn has_config(&self) -> bool {
         true
     }
@@ -674,31 +1224,75 @@ impl Viewable for BarChartTab {
     fn update(&mut self, message: Self::Event) -> Option<Message> {
         match message {
             BarChartMessage::OpenEditor => Some(Message::OpenEditor(Some(self.file.clone()))),
+            BarChartMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::Reconfigure => Some(Message::Reconfigure),
+            BarChartMessage::ExportMetadata => Some(Message::ChooseMetadataPath(self.file.clone())),
+            BarChartMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            BarChartMessage::ExportImage => Some(Message::ChooseImagePath(self.file.clone())),
+            BarChartMessage::SwapAxes => match self.swapped() {
+                Ok(session) => Some(Message::OpenChart(session)),
+                Err(err) => Some(Message::AddToast(Toast {
+                    status: Status::Error,
+                    body: err.message(),
+                    action: None,
+                })),
+            },
             BarChartMessage::ToggleConfig => {
                 self.config_shown = !self.config_shown;
                 None
             }
+            BarChartMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            BarChartMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.cache.clear();
+                }
+                None
+            }
             BarChartMessage::TitleChanged(title) => {
                 self.title = title;
                 None
             }
             BarChartMessage::SequentialX(seq) => {
-                self.sequential_x = seq;
+                self.view_state.sequential_x = seq;
                 self.cache.clear();
                 None
             }
             BarChartMessage::SequentialY(seq) => {
-                self.sequential_y = seq;
+                self.view_state.sequential_y = seq;
                 self.cache.clear();
                 None
             }
             BarChartMessage::Clean(clean) => {
-                self.clean = clean;
+                self.view_state.clean = clean;
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::Crosshair(crosshair) => {
+                self.view_state.crosshair = crosshair;
+                None
+            }
+            BarChartMessage::RotateLabels(rotate) => {
+                self.view_state.rotate_labels = rotate;
                 self.cache.clear();
                 None
             }
             BarChartMessage::Horizontal(is_horizontal) => {
-                self.is_horizontal = is_horizontal;
+                self.view_state.horizontal = is_horizontal;
                 self.cache.clear();
                 None
             }
@@ -721,8 +1315,56 @@ impl Viewable for BarChartTab {
                 self.cache.clear();
                 None
             }
+            BarChartMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::XFormat(format) => {
+                self.view_state.x_format = format;
+                self.cache.clear();
+                None
+            }
+            BarChartMessage::YFormat(format) => {
+                self.view_state.y_format = format;
+                self.cache.clear();
+                None
+            }
             BarChartMessage::Legend(legend) => {
-                self.legend = legend;
+                self.view_state.legend = legend;
+                None
+            }
+            BarChartMessage::ToggleSeries(LegendEntry { series, .. }) => {
+                if let Some(bar) = self.bars.get_mut(series) {
+                    bar.hidden = !bar.hidden;
+                    self.cache.clear();
+                }
+                None
+            }
+            BarChartMessage::LegendSort(sort) => {
+                self.view_state.legend_sort = sort;
+                None
+            }
+            BarChartMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
+                self.recolor(colors);
                 None
             }
             BarChartMessage::ChangeSeed(seed) => {
@@ -733,7 +1375,9 @@ impl Viewable for BarChartTab {
                 None
             }
             BarChartMessage::ApplySeed => {
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
                 self.recolor(colors);
                 None
             }
@@ -742,7 +1386,9 @@ impl Viewable for BarChartTab {
                 let seed: f32 = thread_rng().gen();
                 self.color_seed = seed;
 
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
                 self.recolor(colors);
                 None
             }
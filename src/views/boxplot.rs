@@ -0,0 +1,1276 @@
+use std::{collections::HashSet, fmt::Debug, path::PathBuf};
+
+use iced::{
+    alignment,
+    widget::{
+        button, canvas, checkbox, column, container, horizontal_space, pick_list, row, text,
+        text_input, Canvas, Tooltip,
+    },
+    Alignment, Color, Element, Font, Length, Padding, Point, Renderer, Size, Theme,
+};
+
+use modav_core::repr::sheet::utils::{Data, HeaderLabelStrategy};
+use tracing::warn;
+
+use crate::{
+    utils::{
+        coloring::ColorEngine,
+        csv,
+        formatting::Thresholds,
+        icons,
+        sampling::RowLimit,
+        stats::{box_summary, BoxSummary},
+        tooltip, AppError, AxisFormat, NotationMode,
+    },
+    widgets::{
+        modal::Modal,
+        toolbar::{ToolBarOrientation, ToolbarMenu},
+        wizard::BoxPlotConfigState,
+    },
+    Message, ToolTipContainerStyle,
+};
+
+use super::{
+    parse_seed,
+    shared::{
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            legend_row, muted, value_points, AspectRatio, Axis, AxisKind, ChartColor, DrawnOutput,
+            Graph, Graphable, GridStyle, HoverPoint, LegendEntry, LegendPosition, LegendSort,
+            LEGEND_ROW_HEIGHT,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{self, Image},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, EditorButtonStyle, StyleInputs,
+    },
+    tabs::TabLabel,
+    SessionTab, Viewable,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphBox {
+    category: Data,
+    label: Option<String>,
+    summary: BoxSummary,
+    color: Color,
+    /// Hidden boxes are not drawn and are greyed out in the legend
+    hidden: bool,
+}
+
+impl GraphBox {
+    fn new(category: String, summary: BoxSummary, color: Color) -> Self {
+        Self {
+            category: Data::Text(category.clone()),
+            label: Some(category),
+            summary,
+            color,
+            hidden: false,
+        }
+    }
+
+    fn set_color(&mut self, color: impl Into<Color>) {
+        self.color = color.into();
+    }
+
+    /// The five numbers of the box, from lowest to highest, with their names
+    fn numbers(&self) -> [(&'static str, f64); 5] {
+        let BoxSummary {
+            lower,
+            q1,
+            median,
+            q3,
+            upper,
+            ..
+        } = self.summary;
+
+        [
+            ("lower whisker", lower),
+            ("first quartile", q1),
+            ("median", median),
+            ("third quartile", q3),
+            ("upper whisker", upper),
+        ]
+    }
+}
+
+impl Graphable for GraphBox {
+    type Data<'a> = ();
+
+    fn label(&self) -> Option<&String> {
+        self.label.as_ref()
+    }
+
+    fn legend_value(&self, _data: &Self::Data<'_>) -> Option<f64> {
+        Some(self.summary.median)
+    }
+
+    fn draw_legend(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: iced::Rectangle,
+        color: Color,
+        idx: usize,
+        _data: &Self::Data<'_>,
+    ) {
+        let spacing = 5.0;
+        let text_size = 12.0;
+        let color_size = Size::new(12.0, 12.0);
+
+        let x = bounds.x;
+        let y = bounds.position().y + (idx as f32 * LEGEND_ROW_HEIGHT);
+        let position = Point::new(x, y);
+
+        let (swatch, color) = if self.hidden {
+            (muted(self.color), muted(color))
+        } else {
+            (self.color, color)
+        };
+
+        frame.fill_rectangle(position, color_size, swatch);
+
+        let position = Point::new(x + spacing + color_size.width, y + 0.5 * color_size.height);
+
+        let label = canvas::Text {
+            content: self.label.clone().unwrap_or_default(),
+            position,
+            color,
+            size: text_size.into(),
+            vertical_alignment: alignment::Vertical::Center,
+            ..Default::default()
+        };
+
+        frame.fill_text(label);
+    }
+
+    fn legend_entry_at(
+        &self,
+        bounds: iced::Rectangle,
+        idx: usize,
+        point: Point,
+        _data: &Self::Data<'_>,
+    ) -> Option<usize> {
+        legend_row(bounds, idx).contains(point).then_some(0)
+    }
+
+    fn hover_points(
+        &self,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        _data: &Self::Data<'_>,
+    ) -> Vec<HoverPoint> {
+        if self.hidden {
+            return Vec::new();
+        }
+
+        let Some(x) = x_output.get_closest(&self.category, true) else {
+            return Vec::new();
+        };
+
+        let numbers = self
+            .numbers()
+            .into_iter()
+            .map(|(name, value)| (format!("{} {name}", self.category), value));
+        let outliers = self
+            .summary
+            .outliers
+            .iter()
+            .map(|value| (format!("{} outlier", self.category), *value));
+
+        numbers
+            .chain(outliers)
+            .filter_map(|(label, value)| {
+                let y = Data::Float(value as f32);
+
+                y_output.get_closest(&y, false).map(|position| HoverPoint {
+                    position: Point::new(x, position),
+                    label: Some(label),
+                    x: self.category.clone(),
+                    y,
+                })
+            })
+            .collect()
+    }
+
+    fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        _data: &Self::Data<'_>,
+    ) {
+        if self.hidden {
+            return;
+        }
+
+        let x = match x_output.get_closest(&self.category, true) {
+            Some(x) => x,
+            None => {
+                warn!("BoxPlot category, {} not found", &self.category);
+                return;
+            }
+        };
+
+        let Some([lower, q1, median, q3, upper]) = self
+            .numbers()
+            .into_iter()
+            .map(|(_, value)| y_output.get_closest(&Data::Float(value as f32), false))
+            .collect::<Option<Vec<f32>>>()
+            .and_then(|values| <[f32; 5]>::try_from(values).ok())
+        else {
+            warn!("BoxPlot values of {} not found", &self.category);
+            return;
+        };
+
+        let width = x_output.spacing.abs() / 2.0;
+        let left = x - (width / 2.0);
+        let right = x + (width / 2.0);
+
+        let stroke = canvas::Stroke::default()
+            .with_width(1.5)
+            .with_color(self.color);
+
+        let outline = canvas::Path::rectangle(
+            Point::new(left, f32::min(q1, q3)),
+            Size::new(width, f32::abs(q1 - q3)),
+        );
+        frame.fill(
+            &outline,
+            Color {
+                a: self.color.a * 0.35,
+                ..self.color
+            },
+        );
+        frame.stroke(&outline, stroke);
+
+        frame.stroke(
+            &canvas::Path::line(Point::new(left, median), Point::new(right, median)),
+            stroke.with_width(2.5),
+        );
+
+        // Whiskers run from the box to caps half as wide as it
+        let cap = width / 4.0;
+        for (end, whisker) in [(q1, lower), (q3, upper)] {
+            frame.stroke(
+                &canvas::Path::line(Point::new(x, end), Point::new(x, whisker)),
+                stroke,
+            );
+            frame.stroke(
+                &canvas::Path::line(Point::new(x - cap, whisker), Point::new(x + cap, whisker)),
+                stroke,
+            );
+        }
+
+        for outlier in &self.summary.outliers {
+            let Some(y) = y_output.get_closest(&Data::Float(*outlier as f32), false) else {
+                continue;
+            };
+
+            frame.stroke(&canvas::Path::circle(Point::new(x, y), 3.0), stroke);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlotTabData {
+    file: PathBuf,
+    title: String,
+    /// Categories and the summary of their values, in the order the
+    /// categories first appear
+    boxes: Vec<(String, BoxSummary)>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    theme: Theme,
+    caption: Option<String>,
+    config: BoxPlotConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl BoxPlotTabData {
+    pub fn new(file: PathBuf, config: BoxPlotConfigState) -> Result<Self, AppError> {
+        let BoxPlotConfigState {
+            title,
+            category_col,
+            value_col,
+            caption,
+            trim,
+            flexible,
+            delimiter,
+            header_labels,
+            row_limit,
+            filters,
+            ..
+        } = config.clone();
+
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
+
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut HashSet::new())?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let content =
+            std::fs::read_to_string(&source).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = csv::parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header { records.next() } else { None };
+        let name = |column: usize| {
+            header
+                .as_ref()
+                .and_then(|header| header.get(column))
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+        };
+
+        let mut groups: Vec<(String, Vec<f64>)> = Vec::new();
+        for record in records {
+            let category = record.get(category_col).cloned().unwrap_or_default();
+            let category = if trim {
+                category.trim().to_string()
+            } else {
+                category
+            };
+            let value = record
+                .get(value_col)
+                .and_then(|cell| cell.trim().parse::<f64>().ok());
+
+            match groups.iter_mut().find(|(group, _)| *group == category) {
+                Some((_, values)) => values.extend(value),
+                None => groups.push((category, value.into_iter().collect())),
+            }
+        }
+
+        let boxes = groups
+            .into_iter()
+            .filter_map(|(category, values)| {
+                box_summary(&values).map(|summary| (category, summary))
+            })
+            .collect::<Vec<(String, BoxSummary)>>();
+
+        if boxes.is_empty() {
+            return Err(AppError::Simple(format!(
+                "Column {value_col} has no numbers to summarize"
+            )));
+        }
+
+        Ok(Self {
+            file,
+            title,
+            boxes,
+            x_label: name(category_col),
+            y_label: name(value_col),
+            caption,
+            config,
+            sample,
+            shape,
+            theme: Theme::default(),
+        })
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BoxPlotMessage {
+    OpenEditor,
+    ToggleConfig,
+    TitleChanged(String),
+    Clean(bool),
+    Crosshair(bool),
+    RotateLabels(bool),
+    CaptionChange(String),
+    XLabelChanged(String),
+    YLabelChanged(String),
+    Legend(LegendPosition),
+    LegendSort(LegendSort),
+    /// Hides or shows the clicked legend entry's box
+    ToggleSeries(LegendEntry),
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    YFormat(AxisFormat),
+    ChangeSeed(String),
+    AccentChanged(String),
+    ApplySeed,
+    RandomSeed,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
+}
+
+#[derive(Debug)]
+pub struct BoxPlotTab {
+    file: PathBuf,
+    title: String,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    boxes: Vec<GraphBox>,
+    caption: Option<String>,
+    config_shown: bool,
+    color_seed: f32,
+    cache: canvas::Cache,
+    theme: Theme,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
+    config: BoxPlotConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl BoxPlotTab {
+    fn tools(&self) -> Element<'_, BoxPlotMessage> {
+        let spacing = 10.0;
+
+        let header = {
+            let header = text("Model Config").size(17.0);
+
+            row!(horizontal_space(), header, horizontal_space())
+                .padding([2, 0])
+                .align_y(Alignment::Center)
+        };
+
+        let title =
+            text_input("Graph Title", self.title.as_str()).on_input(BoxPlotMessage::TitleChanged);
+
+        let x_label = text_input(
+            "X axis label",
+            self.x_label
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(BoxPlotMessage::XLabelChanged);
+
+        let y_label = text_input(
+            "Y axis label",
+            self.y_label
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(BoxPlotMessage::YLabelChanged);
+
+        let caption = text_input(
+            "Graph Caption",
+            self.caption
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(BoxPlotMessage::CaptionChange);
+
+        let clean = {
+            let check = {
+                let check = checkbox("", self.view_state.clean).on_toggle(BoxPlotMessage::Clean);
+                let label = text("Clean graph");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip("Only points on the axes have their outline drawn");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let crosshair = {
+            let check = {
+                let check =
+                    checkbox("", self.view_state.crosshair).on_toggle(BoxPlotMessage::Crosshair);
+                let label = text("Crosshair");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Draws guide lines from the cursor to the axes, labelled with their values",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let rotate_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.rotate_labels)
+                    .on_toggle(BoxPlotMessage::RotateLabels);
+                let label = text("Rotate X labels");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip =
+                tooltip("Slants the X axis labels. Labels that would overlap are always slanted");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let seed = {
+            let value = self.color_seed;
+            let value = format!("{value:.4}");
+
+            let label = text("Coloring Seed");
+
+            let tip = tooltip("Sets the seed used to generate graph colors");
+
+            let btn = button(
+                icons::icon(icons::REDO)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .padding([4, 8])
+            .on_press(BoxPlotMessage::ApplySeed);
+
+            let rand = button(icons::icon(icons::SHUFFLE).align_y(alignment::Vertical::Center))
+                .padding([4, 8])
+                .style(button::secondary)
+                .on_press(BoxPlotMessage::RandomSeed);
+
+            let input = text_input("", &value)
+                .on_input(BoxPlotMessage::ChangeSeed)
+                .padding([2, 5])
+                .width(67.0);
+
+            row!(label, input, btn, rand, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let legend = {
+            let icons = Font::with_name("legend-icons");
+
+            let menu = ToolbarMenu::new(
+                LegendPosition::ALL,
+                self.view_state.legend,
+                BoxPlotMessage::Legend,
+                icons,
+            )
+            .orientation(ToolBarOrientation::Both)
+            .padding([4, 4])
+            .menu_padding(Padding {
+                top: 4.,
+                right: 10.,
+                bottom: 4.,
+                left: 8.,
+            })
+            .spacing(5.0);
+
+            let tooltip = container(text("Legend Position").size(12.0))
+                .max_width(200.0)
+                .padding([6, 8])
+                .style(|theme| {
+                    <ToolTipContainerStyle as container::Catalog>::style(
+                        &ToolTipContainerStyle,
+                        theme,
+                    )
+                })
+                .height(Length::Shrink);
+
+            let menu = Tooltip::new(menu, tooltip, iced::widget::tooltip::Position::Bottom)
+                .gap(2.0)
+                .snap_within_viewport(true);
+
+            let text = text("Legend Position");
+
+            row!(text, menu).spacing(spacing).align_y(Alignment::Center)
+        };
+
+        let legend_sort = {
+            let label = text("Legend Order");
+
+            let pick_list = pick_list(
+                LegendSort::ALL,
+                Some(self.view_state.legend_sort),
+                BoxPlotMessage::LegendSort,
+            )
+            .padding([2, 5]);
+
+            let tip = tooltip(
+                "Reorders the legend entries, by median when sorting by value, without changing how boxes are drawn",
+            );
+
+            row!(label, pick_list, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(BoxPlotMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let y_format = axis_format_row(
+            "Y Axis Numbers",
+            self.view_state.y_format,
+            BoxPlotMessage::YFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the axes are written",
+            BoxPlotMessage::Notation,
+            BoxPlotMessage::Thresholds,
+        );
+
+        let editor = {
+            let font = Font::with_name(icons::NAME);
+
+            let btn = button(
+                text(icons::EDITOR)
+                    .font(font)
+                    .width(16.0)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .on_press(BoxPlotMessage::OpenEditor)
+            .style(|theme, status| {
+                <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+            })
+            .padding([4, 4]);
+
+            let tooltip = container(text("Open in Editor").size(12.0))
+                .max_width(200.0)
+                .padding([6, 8])
+                .style(|theme| {
+                    <ToolTipContainerStyle as container::Catalog>::style(
+                        &ToolTipContainerStyle,
+                        theme,
+                    )
+                })
+                .height(Length::Shrink);
+
+            let menu = Tooltip::new(btn, tooltip, iced::widget::tooltip::Position::Bottom)
+                .gap(2.0)
+                .snap_within_viewport(true);
+
+            let text = text("Open in Editor");
+
+            row!(text, menu).spacing(spacing).align_y(Alignment::Center)
+        };
+
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            BoxPlotMessage::ToggleStyle,
+            BoxPlotMessage::StyleColor,
+            BoxPlotMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, BoxPlotMessage::Aspect);
+        let reconfigure = reconfigure_row(BoxPlotMessage::Reconfigure);
+        let export = export_metadata_row(BoxPlotMessage::ExportMetadata);
+        let image = export_image_row(BoxPlotMessage::ExportImage);
+        let pdf = export_pdf_row(BoxPlotMessage::ExportPdf);
+
+        column!(
+            header,
+            title,
+            x_label,
+            y_label,
+            caption,
+            clean,
+            crosshair,
+            rotate_labels,
+            seed,
+            accent,
+            notation,
+            y_format,
+            legend,
+            legend_sort,
+            style,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor,
+        )
+        .spacing(25.0)
+        .into()
+    }
+
+    fn create_axis(&self) -> Result<(Axis, Axis), AppError> {
+        let categories = self
+            .boxes
+            .iter()
+            .map(|graph_box| graph_box.category.clone())
+            .collect::<Vec<Data>>();
+
+        let (min, max) = self
+            .boxes
+            .iter()
+            .flat_map(|graph_box| {
+                let BoxSummary {
+                    lower,
+                    upper,
+                    outliers,
+                    ..
+                } = &graph_box.summary;
+
+                [*lower, *upper].into_iter().chain(outliers.iter().copied())
+            })
+            .fold(None, |range: Option<(f64, f64)>, value| match range {
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+                None => Some((value, value)),
+            })
+            .ok_or_else(|| {
+                AppError::Simple(String::from("There is no data to place on the Y axis"))
+            })?;
+
+        let x_axis = Axis::new(AxisKind::BaseHorizontal(categories), 1.0, 1.0)
+            .clean(self.view_state.clean)
+            .label(self.x_label.clone())
+            .rotate_labels(self.view_state.rotate_labels);
+        let y_axis = Axis::new(AxisKind::BaseVertical(value_points(min, max)), 1.0, 1.0)
+            .clean(self.view_state.clean)
+            .label(self.y_label.clone())
+            .format(self.view_state.y_format);
+
+        Ok((x_axis, y_axis))
+    }
+
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(
+        &'a self,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphBox, BoxPlotMessage>, AppError> {
+        let (x_axis, y_axis) = self.create_axis()?;
+
+        Ok(
+            Graph::new(x_axis, y_axis, &self.boxes, &self.theme, cache, ())
+                .title(Some(self.title.as_str()).filter(|title| !title.is_empty()))
+                .caption(self.caption.as_ref())
+                .labels_len(self.boxes.len())
+                .legend(self.view_state.legend)
+                .legend_sort(self.view_state.legend_sort)
+                .on_legend_toggle(BoxPlotMessage::ToggleSeries)
+                .notation(self.view_state.notation)
+                .crosshair(self.view_state.crosshair)
+                .style(self.view_state.style),
+        )
+    }
+
+    fn graph(&self) -> Element<'_, BoxPlotMessage> {
+        let content: Element<'_, BoxPlotMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
+
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    /// Returns the plotted summaries as CSV, leaving out hidden boxes if
+    /// `visible_only`
+    fn csv(&self, visible_only: bool) -> Option<String> {
+        let header = csv::record([
+            self.x_label.as_deref().unwrap_or("category"),
+            "lower whisker",
+            "first quartile",
+            "median",
+            "third quartile",
+            "upper whisker",
+            "outliers",
+        ]);
+
+        let boxes = self
+            .boxes
+            .iter()
+            .filter(|graph_box| !(visible_only && graph_box.hidden));
+
+        let rows = boxes.map(|graph_box| {
+            let outliers = graph_box
+                .summary
+                .outliers
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            std::iter::once(graph_box.category.to_string())
+                .chain(
+                    graph_box
+                        .numbers()
+                        .into_iter()
+                        .map(|(_, value)| value.to_string()),
+                )
+                .chain(std::iter::once(outliers))
+                .collect::<Vec<String>>()
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows.map(csv::record))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn redraw(&mut self) {
+        self.cache.clear()
+    }
+
+    fn recolor(&mut self, colors: ColorEngine) {
+        let colors = colors.count(self.boxes.len() as u32);
+
+        self.boxes
+            .iter_mut()
+            .zip(colors)
+            .for_each(|(graph_box, color)| {
+                graph_box.set_color(color);
+            });
+
+        self.redraw()
+    }
+}
+
+impl Viewable for BoxPlotTab {
+    type Event = BoxPlotMessage;
+    type Data = BoxPlotTabData;
+
+    fn new(data: Self::Data) -> Self {
+        let BoxPlotTabData {
+            file,
+            title,
+            boxes,
+            x_label,
+            y_label,
+            theme,
+            caption,
+            config,
+            sample,
+            shape,
+        } = data;
+
+        let colors = ColorEngine::new(&theme).count(boxes.len() as u32);
+        let seed = colors.seed();
+
+        let boxes = boxes
+            .into_iter()
+            .zip(colors)
+            .map(|((category, summary), color)| GraphBox::new(category, summary, color))
+            .collect();
+
+        Self {
+            file,
+            title,
+            x_label,
+            y_label,
+            caption,
+            boxes,
+            theme,
+            color_seed: seed,
+            config_shown: false,
+            cache: canvas::Cache::default(),
+            view_state: ChartViewState::default(),
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+            config,
+            sample,
+            shape,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn label(&self) -> TabLabel {
+        let file_name = self
+            .file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("New File");
+
+        let font = Font::with_name(icons::NAME);
+
+        TabLabel::new(icons::BARCHART, format!("{} - {}", self.title, file_name)).icon_font(font)
+    }
+
+    fn content(&self) -> Option<String> {
+        None
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.file.clone())
+    }
+
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::BoxPlot(self.file.clone(), self.config.clone()))
+    }
+
+    fn can_save(&self) -> bool {
+        false
+    }
+
+    fn modal_msg(&self) -> String {
+        "Seeing this means a logic error occurred".into()
+    }
+
+    fn refresh(&mut self, data: Self::Data) {
+        let new = <Self as Viewable>::new(data);
+        let view_state = self.view_state.clone();
+        let style_shown = self.style_shown;
+
+        *self = new;
+        self.style_shown = style_shown;
+        self.restore_view_state(view_state);
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let shape = &self.shape;
+        let mapped = [
+            ("Category", shape.column_name(self.config.category_col)),
+            ("Value", shape.column_name(self.config.value_col)),
+        ];
+
+        Some(mapped_summary(shape, &mapped))
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = BoxPlotConfigState {
+            row_limit: RowLimit::default(),
+            ..self.config.clone()
+        };
+        let data = BoxPlotTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.cache.clear();
+    }
+
+    fn theme_changed(&mut self, theme: &Theme) {
+        if &self.theme == theme {
+            return;
+        }
+        self.theme = theme.clone();
+
+        let colors = ColorEngine::new(&self.theme).accent(self.view_state.accent);
+        self.color_seed = colors.seed();
+
+        self.recolor(colors);
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        self.csv(false)
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv(true)
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        let cache = canvas::Cache::new();
+
+        Some(
+            self.chart(&cache)
+                .and_then(|chart| offscreen::render(&chart, size, &self.theme)),
+        )
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let metadata = ChartMetadata {
+            kind: "box_plot",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(
+                self.x_label.as_ref(),
+                self.boxes.iter().map(|graph_box| &graph_box.category),
+            ),
+            y_axis: AxisMetadata::new(self.y_label.as_ref(), std::iter::empty()),
+            series: self
+                .boxes
+                .iter()
+                .map(|graph_box| {
+                    let values = graph_box
+                        .numbers()
+                        .into_iter()
+                        .map(|(_, value)| value)
+                        .collect();
+
+                    SeriesMetadata::with_values(graph_box.label.as_ref(), graph_box.color, values)
+                })
+                .collect(),
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = state;
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent);
+            self.recolor(colors);
+        }
+
+        self.cache.clear();
+    }
+
+    fn has_config(&self) -> bool {
+        true
+    }
+
+    fn config<'a, Message, F>(&'a self, map: F) -> Option<Element<'a, Message, Theme, Renderer>>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        Some(self.tools().map(map))
+    }
+
+    fn update(&mut self, message: Self::Event) -> Option<Message> {
+        match message {
+            BoxPlotMessage::OpenEditor => Some(Message::OpenEditor(Some(self.file.clone()))),
+            BoxPlotMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::Reconfigure => Some(Message::Reconfigure),
+            BoxPlotMessage::ExportMetadata => Some(Message::ChooseMetadataPath(self.file.clone())),
+            BoxPlotMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            BoxPlotMessage::ExportImage => Some(Message::ChooseImagePath(self.file.clone())),
+            BoxPlotMessage::ToggleConfig => {
+                self.config_shown = !self.config_shown;
+                None
+            }
+            BoxPlotMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            BoxPlotMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.cache.clear();
+                }
+                None
+            }
+            BoxPlotMessage::TitleChanged(title) => {
+                self.title = title;
+                None
+            }
+            BoxPlotMessage::Clean(clean) => {
+                self.view_state.clean = clean;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::Crosshair(crosshair) => {
+                self.view_state.crosshair = crosshair;
+                None
+            }
+            BoxPlotMessage::RotateLabels(rotate) => {
+                self.view_state.rotate_labels = rotate;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::CaptionChange(caption) => {
+                self.caption = if caption.is_empty() {
+                    None
+                } else {
+                    Some(caption)
+                };
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::XLabelChanged(label) => {
+                self.x_label = if label.is_empty() { None } else { Some(label) };
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::YLabelChanged(label) => {
+                self.y_label = if label.is_empty() { None } else { Some(label) };
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::YFormat(format) => {
+                self.view_state.y_format = format;
+                self.cache.clear();
+                None
+            }
+            BoxPlotMessage::Legend(legend) => {
+                self.view_state.legend = legend;
+                None
+            }
+            BoxPlotMessage::ToggleSeries(LegendEntry { series, .. }) => {
+                if let Some(graph_box) = self.boxes.get_mut(series) {
+                    graph_box.hidden = !graph_box.hidden;
+                    self.cache.clear();
+                }
+                None
+            }
+            BoxPlotMessage::LegendSort(sort) => {
+                self.view_state.legend_sort = sort;
+                None
+            }
+            BoxPlotMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            BoxPlotMessage::ChangeSeed(seed) => {
+                if let Some(seed) = parse_seed(seed, self.color_seed == 0.0) {
+                    self.color_seed = seed;
+                }
+
+                None
+            }
+            BoxPlotMessage::ApplySeed => {
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            BoxPlotMessage::RandomSeed => {
+                use rand::{thread_rng, Rng};
+                let seed: f32 = thread_rng().gen();
+                self.color_seed = seed;
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+        }
+    }
+
+    fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, Theme, Renderer>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        let title = {
+            let text = text(format!("{} - Box Plot", self.title));
+            row!(horizontal_space(), text, horizontal_space())
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+        }
+        .height(Length::Shrink);
+
+        let content_area = container(self.graph())
+            .max_width(1450)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|theme| {
+                <ContentAreaContainer as container::Catalog>::style(&ContentAreaContainer, theme)
+            });
+
+        let content = column!(title, content_area)
+            .align_x(Alignment::Center)
+            .spacing(20)
+            .height(Length::Fill)
+            .width(Length::Fill);
+
+        let content: Element<Self::Event, Theme, Renderer> = if self.config_shown {
+            Modal::new(content, self.tools())
+                .on_blur(BoxPlotMessage::ToggleConfig)
+                .into()
+        } else {
+            content.into()
+        };
+
+        let content: Element<Self::Event, Theme, Renderer> = container(content)
+            .padding(Padding {
+                top: 10.,
+                right: 30.,
+                bottom: 30.,
+                left: 15.,
+            })
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+
+        content.map(map)
+    }
+}
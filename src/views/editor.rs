@@ -1,18 +1,104 @@
+#[cfg(feature = "highlighter")]
+use iced::highlighter::{self, Highlighter};
 use iced::{
-    highlighter::{self, Highlighter},
-    widget::text_editor,
-    Element, Font, Length, Renderer, Theme,
+    advanced::{
+        graphics,
+        text::{
+            self as core_text,
+            highlighter::{Format, PlainText},
+            Paragraph as _,
+        },
+    },
+    alignment, font,
+    widget::{
+        button, checkbox, column, horizontal_space, row, scrollable, text,
+        text::{LineHeight, Shaping, Wrapping},
+        text_editor::{self, Motion},
+        text_input, Column, TextEditor,
+    },
+    Alignment, Element, Font, Length, Rectangle, Renderer, Size, Theme,
 };
-use std::path::PathBuf;
+use std::{collections::VecDeque, fmt::Debug, ops::Range, path::PathBuf, sync::Arc};
 
-use super::{TabLabel, Viewable};
-use crate::{utils::icons, Message};
+use super::{ClosePolicy, SessionTab, TabLabel, Viewable};
+use crate::{
+    utils::{icons, tooltip},
+    Message,
+};
+
+/// Text size shared by the editor and its line number gutter so both stay
+/// aligned
+const TEXT_SIZE: f32 = 16.0;
+
+/// Padding around the editor's text
+const EDITOR_PADDING: [f32; 2] = [4.0, 8.0];
+
+/// Horizontal padding either side of the line numbers
+const GUTTER_PADDING: f32 = 4.0;
+
+/// Most undo steps kept per editor
+const MAX_HISTORY: usize = 200;
+
+/// How far down the unwrapped editor `line` is, leaving a few lines above it
+/// in view
+pub fn line_offset(line: usize) -> f32 {
+    line.saturating_sub(4) as f32 * line_height()
+}
+
+fn line_height() -> f32 {
+    LineHeight::default().to_absolute(TEXT_SIZE.into()).0
+}
+
+/// Width of `line` laid out as the editor lays out its text, so tabs and
+/// wide characters take the room they are drawn with
+fn line_width(line: &str) -> f32 {
+    graphics::text::Paragraph::with_text(core_text::Text {
+        content: line,
+        bounds: Size::INFINITY,
+        size: TEXT_SIZE.into(),
+        line_height: LineHeight::default(),
+        font: Font::MONOSPACE,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        shaping: Shaping::Advanced,
+        wrapping: Wrapping::None,
+    })
+    .min_width()
+}
+
+/// Returns the offset that brings `cursor` fully into the `visible` area of a
+/// scrollable scrolled to `offset`, if it is not already
+fn follow(
+    offset: scrollable::AbsoluteOffset,
+    visible: Size,
+    cursor: Rectangle,
+) -> Option<scrollable::AbsoluteOffset> {
+    let axis = |offset: f32, visible: f32, start: f32, length: f32| {
+        if start < offset {
+            start
+        } else if start + length > offset + visible {
+            (start + length - visible).max(0.0)
+        } else {
+            offset
+        }
+    };
+
+    let next = scrollable::AbsoluteOffset {
+        x: axis(offset.x, visible.width, cursor.x, cursor.width),
+        y: axis(offset.y, visible.height, cursor.y, cursor.height),
+    };
+
+    (next != offset).then_some(next)
+}
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct EditorTabData {
     path: Option<PathBuf>,
     data: String,
     read_only: bool,
+    plain: bool,
+    name: Option<String>,
+    reload: bool,
 }
 
 impl EditorTabData {
@@ -21,6 +107,9 @@ impl EditorTabData {
             path,
             data,
             read_only: false,
+            plain: false,
+            name: None,
+            reload: false,
         }
     }
 
@@ -38,21 +127,858 @@ impl EditorTabData {
         self.read_only = read_only;
         self
     }
+
+    /// Shows the text in plain monospace, without syntax highlighting
+    pub fn plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+
+    /// Names where the text came from, such as `stdin`, for a tab without a
+    /// path. Named tabs start out unsaved so closing them prompts first
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Replaces the shown text on refresh, for files reloaded from disk.
+    /// Otherwise the shown text is kept, along with edits made since
+    pub fn reload(mut self, reload: bool) -> Self {
+        self.reload = reload;
+        self
+    }
 }
 
 #[derive(Debug)]
 pub struct EditorTab {
     is_dirty: bool,
     file_path: Option<PathBuf>,
+    /// Shown instead of "Untitled" while the tab has no path
+    name: Option<String>,
     content: text_editor::Content,
+    /// The text of `content`, kept to find what each edit changed
+    text: String,
     is_empty: bool,
     read_only: bool,
+    word_wrap: bool,
+    line_numbers: bool,
+    /// Whether syntax highlighting is used. Always off in builds without the
+    /// `highlighter` feature.
+    highlighting: bool,
+    /// Content as of the last load or save
+    saved: String,
+    /// Ignore trailing whitespace and newlines when checking for changes
+    ignore_whitespace: bool,
+    find_shown: bool,
+    find: Find,
+    history: History,
+    /// Width of each line of the text. The longest sets the width of the
+    /// editor while lines are not wrapped
+    line_widths: Vec<f32>,
+    /// Last known viewport of the scrollable holding the unwrapped editor
+    viewport: Option<scrollable::Viewport>,
+    /// Id of the find field. Each editor has its own, as two can be shown
+    /// side by side
+    find_id: text_input::Id,
+    /// Id of the scrollable holding the unwrapped editor and its line
+    /// numbers, unique as `find_id` is
+    scroll_id: scrollable::Id,
+}
+
+/// One step of an editor's history. Only the replaced part of the text is
+/// kept, so steps stay small however long the text is
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    /// Byte offset of the replaced text
+    at: usize,
+    removed: String,
+    inserted: String,
+    /// Line and column of the cursor before the change
+    before: (usize, usize),
+    /// Line and column of the cursor after the change
+    after: (usize, usize),
+}
+
+impl Change {
+    /// The change turning `old` into `new`, or None if they are the same
+    fn between(
+        old: &str,
+        new: &str,
+        before: (usize, usize),
+        after: (usize, usize),
+    ) -> Option<Self> {
+        let mut prefix = old
+            .bytes()
+            .zip(new.bytes())
+            .take_while(|(old, new)| old == new)
+            .count();
+        while !old.is_char_boundary(prefix) || !new.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+
+        let mut suffix = old[prefix..]
+            .bytes()
+            .rev()
+            .zip(new[prefix..].bytes().rev())
+            .take_while(|(old, new)| old == new)
+            .count();
+        while !old.is_char_boundary(old.len() - suffix) || !new.is_char_boundary(new.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        let removed = &old[prefix..old.len() - suffix];
+        let inserted = &new[prefix..new.len() - suffix];
+
+        (!removed.is_empty() || !inserted.is_empty()).then(|| Self {
+            at: prefix,
+            removed: removed.to_string(),
+            inserted: inserted.to_string(),
+            before,
+            after,
+        })
+    }
+
+    /// The change reverting self
+    fn inverse(&self) -> Self {
+        Self {
+            at: self.at,
+            removed: self.inserted.clone(),
+            inserted: self.removed.clone(),
+            before: self.after,
+            after: self.before,
+        }
+    }
+
+    /// Applies self to `text`
+    fn apply(&self, text: &mut String) {
+        text.replace_range(self.at..self.at + self.removed.len(), &self.inserted);
+    }
+}
+
+/// Undo and redo stacks of an editor
+#[derive(Debug, Default)]
+struct History {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+    /// Whether the last edit typed part of a word, so that typing the rest
+    /// of it is undone along with it
+    typing: bool,
+}
+
+impl History {
+    /// Records `change`. Typing joins the step of the edit before it if
+    /// that was typing too, right before where it left off
+    fn record(&mut self, change: Change, typing: bool) {
+        match self.undo.back_mut() {
+            Some(last)
+                if typing
+                    && self.typing
+                    && change.removed.is_empty()
+                    && change.at == last.at + last.inserted.len() =>
+            {
+                last.inserted.push_str(&change.inserted);
+                last.after = change.after;
+            }
+            _ => {
+                self.undo.push_back(change);
+
+                if self.undo.len() > MAX_HISTORY {
+                    self.undo.pop_front();
+                }
+            }
+        }
+
+        self.redo.clear();
+        self.typing = typing;
+    }
+
+    /// Takes the last change, keeping it to redo. Returns the change
+    /// reverting it
+    fn undo(&mut self) -> Option<Change> {
+        let change = self.undo.pop_back()?;
+        self.typing = false;
+
+        let inverse = change.inverse();
+        self.redo.push(change);
+        Some(inverse)
+    }
+
+    /// Takes the last undone change, to be applied again
+    fn redo(&mut self) -> Option<Change> {
+        let change = self.redo.pop()?;
+        self.typing = false;
+
+        self.undo.push_back(change.clone());
+        Some(change)
+    }
+}
+
+/// State of the find and replace bar. Kept while the bar is hidden so the
+/// last query is still there when it is opened again
+#[derive(Debug, Default)]
+struct Find {
+    query: String,
+    replacement: String,
+    case_sensitive: bool,
+    /// Whether the replace field is shown
+    replace: bool,
+    /// Character offset of every match into the text
+    matches: Vec<usize>,
+    /// Index into `matches` of the selected match
+    current: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
 pub enum EditorMessage {
     Action(text_editor::Action),
     Refresh(EditorTabData),
+    WordWrap(bool),
+    LineNumbers(bool),
+    Highlighting(bool),
+    IgnoreWhitespace(bool),
+    /// Shows the find bar, with the replace field if true
+    OpenFind(bool),
+    CloseFind,
+    FindQuery(String),
+    ReplaceQuery(String),
+    CaseSensitive(bool),
+    NextMatch,
+    PreviousMatch,
+    ReplaceAll,
+    Undo,
+    Redo,
+    /// Selects the line, counting from 1
+    GoToLine(usize),
+    /// The unwrapped editor was scrolled
+    Scrolled(scrollable::Viewport),
+}
+
+/// Returns the byte length of `query` if `text` starts with it
+fn match_at(text: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+
+    for expected in query.chars() {
+        let (_, found) = chars.next()?;
+
+        let same = if case_sensitive {
+            found == expected
+        } else {
+            found.to_lowercase().eq(expected.to_lowercase())
+        };
+
+        if !same {
+            return None;
+        }
+    }
+
+    Some(chars.next().map_or(text.len(), |(idx, _)| idx))
+}
+
+/// Returns the byte ranges of the non-overlapping matches of `query` in `line`
+fn line_matches(line: &str, query: &str, case_sensitive: bool) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+
+    while let Some(next) = line[start..].chars().next() {
+        match match_at(&line[start..], query, case_sensitive) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += next.len_utf8(),
+        }
+    }
+
+    matches
+}
+
+/// Returns the character offset into `text` of byte `column` of `line`
+fn char_offset(text: &str, line: usize, column: usize) -> usize {
+    let mut lines = text.split('\n');
+    let before = lines
+        .by_ref()
+        .take(line)
+        .map(|line| line.chars().count() + 1)
+        .sum::<usize>();
+
+    let line = lines.next().unwrap_or_default();
+    let mut column = column.min(line.len());
+    while !line.is_char_boundary(column) {
+        column -= 1;
+    }
+
+    before + line[..column].chars().count()
+}
+
+/// Returns the character offset of every match of `query` in `text`. Matches
+/// do not span lines.
+fn find_matches(text: &str, query: &str, case_sensitive: bool) -> Vec<usize> {
+    let mut offset = 0;
+    let mut matches = Vec::new();
+
+    for line in text.split('\n') {
+        matches.extend(
+            line_matches(line, query, case_sensitive)
+                .into_iter()
+                .map(|range| offset + line[..range.start].chars().count()),
+        );
+
+        offset += line.chars().count() + 1;
+    }
+
+    matches
+}
+
+/// Replaces every match of `query` in `text` with `replacement`
+fn replace_matches(text: &str, query: &str, replacement: &str, case_sensitive: bool) -> String {
+    text.split('\n')
+        .map(|line| {
+            let mut replaced = String::with_capacity(line.len());
+            let mut last = 0;
+
+            for range in line_matches(line, query, case_sensitive) {
+                replaced.push_str(&line[last..range.start]);
+                replaced.push_str(replacement);
+                last = range.end;
+            }
+
+            replaced.push_str(&line[last..]);
+            replaced
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MatchSettings {
+    query: String,
+    case_sensitive: bool,
+}
+
+/// Marks the matches of the find query in each line
+struct MatchHighlighter {
+    settings: MatchSettings,
+    current_line: usize,
+}
+
+impl core_text::Highlighter for MatchHighlighter {
+    type Settings = MatchSettings;
+    type Highlight = ();
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, ())>;
+
+    fn new(settings: &Self::Settings) -> Self {
+        Self {
+            settings: settings.clone(),
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.settings = new_settings.clone();
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line;
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        self.current_line += 1;
+
+        line_matches(line, &self.settings.query, self.settings.case_sensitive)
+            .into_iter()
+            .map(|range| (range, ()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+/// Returns true if `current` differs from the `saved` snapshot. When
+/// `ignore_whitespace` is set, differences only in trailing whitespace of lines
+/// or trailing newlines of the text are not counted.
+fn is_modified(saved: &str, current: &str, ignore_whitespace: bool) -> bool {
+    if ignore_whitespace {
+        let normalize = |text: &str| {
+            text.trim_end()
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+        };
+
+        normalize(saved) != normalize(current)
+    } else {
+        // The editor always ends its text with a newline
+        let strip = |text: &str| text.strip_suffix('\n').unwrap_or(text).to_string();
+
+        strip(saved) != strip(current)
+    }
+}
+
+impl EditorTab {
+    #[cfg(feature = "highlighter")]
+    fn highlight<'a>(
+        &self,
+        editor: TextEditor<'a, PlainText, EditorMessage>,
+    ) -> Element<'a, EditorMessage> {
+        if let Some(settings) = self.match_settings() {
+            return Self::highlight_matches(editor, settings);
+        }
+
+        if !self.highlighting {
+            return editor.into();
+        }
+
+        let extension = self
+            .path()
+            .as_ref()
+            .and_then(|path| path.extension()?.to_str())
+            .unwrap_or("txt")
+            .to_string();
+        let highlighter_settings = highlighter::Settings {
+            token: extension,
+            theme: highlighter::Theme::SolarizedDark,
+        };
+
+        editor
+            .highlight_with::<Highlighter>(highlighter_settings, |hl, _theme| hl.to_format())
+            .into()
+    }
+
+    #[cfg(not(feature = "highlighter"))]
+    fn highlight<'a>(
+        &self,
+        editor: TextEditor<'a, PlainText, EditorMessage>,
+    ) -> Element<'a, EditorMessage> {
+        match self.match_settings() {
+            Some(settings) => Self::highlight_matches(editor, settings),
+            None => editor.into(),
+        }
+    }
+
+    /// Returns what to highlight while the find bar has a query. Matches take
+    /// the place of syntax highlighting until the bar is closed.
+    fn match_settings(&self) -> Option<MatchSettings> {
+        if self.find_shown && !self.find.query.is_empty() {
+            Some(MatchSettings {
+                query: self.find.query.clone(),
+                case_sensitive: self.find.case_sensitive,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn highlight_matches<'a>(
+        editor: TextEditor<'a, PlainText, EditorMessage>,
+        settings: MatchSettings,
+    ) -> Element<'a, EditorMessage> {
+        editor
+            .highlight_with::<MatchHighlighter>(settings, |_, theme| Format {
+                color: Some(theme.extended_palette().primary.strong.color),
+                font: Some(Font {
+                    weight: font::Weight::Bold,
+                    ..Font::MONOSPACE
+                }),
+            })
+            .into()
+    }
+
+    fn refresh_matches(&mut self) {
+        self.find.matches = find_matches(&self.text, &self.find.query, self.find.case_sensitive);
+        self.find.current = None;
+    }
+
+    /// Id of the find field
+    pub fn find_id(&self) -> text_input::Id {
+        self.find_id.clone()
+    }
+
+    /// Id of the scrollable holding the unwrapped editor
+    pub fn scroll_id(&self) -> scrollable::Id {
+        self.scroll_id.clone()
+    }
+
+    /// Where the text came from, while the tab has no path
+    pub fn source(&self) -> Option<&str> {
+        self.file_path
+            .is_none()
+            .then_some(self.name.as_deref())
+            .flatten()
+    }
+
+    /// Makes `change` of the history in the text. Only the replaced part
+    /// is changed in the editor, and the cursor is left where it was after
+    /// `change`
+    fn restore(&mut self, change: &Change) {
+        self.move_to_offset(self.text[..change.at].chars().count());
+
+        for _ in change.removed.chars() {
+            self.content
+                .perform(text_editor::Action::Select(Motion::Right));
+        }
+        self.content
+            .perform(text_editor::Action::Edit(text_editor::Edit::Paste(
+                Arc::new(change.inserted.clone()),
+            )));
+
+        change.apply(&mut self.text);
+        self.remeasure(change.at, &change.removed, &change.inserted);
+
+        let (line, column) = change.after;
+        self.move_to(line, column);
+
+        self.is_empty = self.text.is_empty();
+        self.is_dirty = is_modified(&self.saved, &self.text, self.ignore_whitespace);
+
+        if self.find_shown {
+            self.refresh_matches();
+        }
+    }
+
+    /// Moves the cursor to byte `column` of `line`, both counting from 0
+    fn move_to(&mut self, line: usize, column: usize) {
+        self.move_to_offset(char_offset(&self.text, line, column));
+    }
+
+    /// Moves the cursor to character `offset` of the text
+    fn move_to_offset(&mut self, offset: usize) {
+        // Moving right ends a selection at its end without moving further
+        if self.content.selection().is_some() {
+            self.content
+                .perform(text_editor::Action::Move(Motion::Right));
+        }
+
+        let total = self.text.chars().count();
+        let offset = offset.min(total);
+        let (line, column) = self.content.cursor_position();
+        let current = char_offset(&self.text, line, column);
+
+        // The editor has no way to place the cursor at an offset, so it is
+        // walked there from the closest of the start, the end and where it
+        // is now. Moving down would count wrapped lines
+        let from = if current.abs_diff(offset) <= offset.min(total - offset) {
+            current
+        } else if offset <= total - offset {
+            self.content
+                .perform(text_editor::Action::Move(Motion::DocumentStart));
+            0
+        } else {
+            self.content
+                .perform(text_editor::Action::Move(Motion::DocumentEnd));
+            total
+        };
+
+        let motion = if offset < from {
+            Motion::Left
+        } else {
+            Motion::Right
+        };
+
+        for _ in 0..from.abs_diff(offset) {
+            self.content.perform(text_editor::Action::Move(motion));
+        }
+    }
+
+    /// Moves the cursor to the match at `idx` and selects it
+    fn select_match(&mut self, idx: usize) {
+        let Some(&offset) = self.find.matches.get(idx) else {
+            return;
+        };
+        let len = self.find.query.chars().count();
+
+        self.move_to_offset(offset);
+
+        for _ in 0..len {
+            self.content
+                .perform(text_editor::Action::Select(Motion::Right));
+        }
+
+        self.find.current = Some(idx);
+    }
+
+    fn find_bar(&self) -> Element<'_, EditorMessage> {
+        let icon_button = |icon: char, on_press: EditorMessage| {
+            button(
+                text(icon)
+                    .font(Font::with_name(icons::NAME))
+                    .size(14.0)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .on_press(on_press)
+            .style(button::text)
+            .padding([2, 6])
+        };
+
+        let count = match (self.find.current, self.find.matches.len()) {
+            _ if self.find.query.is_empty() => String::default(),
+            (_, 0) => "No matches".into(),
+            (Some(current), total) => format!("{} of {total}", current + 1),
+            (None, 1) => "1 match".into(),
+            (None, total) => format!("{total} matches"),
+        };
+
+        let find = row!(
+            text_input("Find", &self.find.query)
+                .id(self.find_id.clone())
+                .on_input(EditorMessage::FindQuery)
+                .on_submit(EditorMessage::NextMatch)
+                .size(14.0)
+                .padding([3, 6])
+                .width(260.0),
+            text(count).size(13.0).width(90.0),
+            icon_button(icons::ANGLE_UP, EditorMessage::PreviousMatch),
+            icon_button(icons::ANGLE_DOWN, EditorMessage::NextMatch),
+            checkbox("Match case", self.find.case_sensitive)
+                .on_toggle(EditorMessage::CaseSensitive)
+                .size(14.0)
+                .text_size(13.0),
+            horizontal_space(),
+            icon_button(icons::CLOSE, EditorMessage::CloseFind),
+        )
+        .spacing(8.0)
+        .align_y(Alignment::Center);
+
+        let mut bar = column!(find).spacing(4.0).padding([4, 8]);
+
+        if self.find.replace {
+            let replace_all = button(text("Replace All").size(13.0))
+                .on_press_maybe(
+                    (!self.read_only && !self.find.matches.is_empty())
+                        .then_some(EditorMessage::ReplaceAll),
+                )
+                .padding([3, 8]);
+
+            let replace = row!(
+                text_input("Replace", &self.find.replacement)
+                    .on_input(EditorMessage::ReplaceQuery)
+                    .on_submit(EditorMessage::ReplaceAll)
+                    .size(14.0)
+                    .padding([3, 6])
+                    .width(260.0),
+                replace_all,
+            )
+            .spacing(8.0)
+            .align_y(Alignment::Center);
+
+            bar = bar.push(replace);
+        }
+
+        bar.into()
+    }
+
+    /// Width of the numbers in the gutter
+    fn gutter_width(&self) -> f32 {
+        line_width(&self.content.line_count().max(1).to_string()) + 8.0
+    }
+
+    /// Whether line numbers are shown. Numbers are per line of text, so they
+    /// are only shown while lines are not wrapped and stay beside their line
+    fn shows_gutter(&self) -> bool {
+        self.line_numbers && !self.word_wrap
+    }
+
+    /// Width of everything left of the editor's text in the scrollable
+    fn text_left(&self) -> f32 {
+        let gutter = if self.shows_gutter() {
+            self.gutter_width() + GUTTER_PADDING * 2.0
+        } else {
+            0.0
+        };
+
+        gutter + EDITOR_PADDING[1]
+    }
+
+    /// Measures every line of the text
+    fn measure(&mut self) {
+        self.line_widths = self.text.split('\n').map(line_width).collect();
+    }
+
+    /// Measures again the lines of the text where `removed` was replaced
+    /// by `inserted` at byte offset `at`
+    fn remeasure(&mut self, at: usize, removed: &str, inserted: &str) {
+        let first = self.text[..at].matches('\n').count();
+        let start = self.text[..at].rfind('\n').map_or(0, |idx| idx + 1);
+        let removed = removed.matches('\n').count() + 1;
+        let inserted = inserted.matches('\n').count() + 1;
+
+        let widths = self.text[start..]
+            .split('\n')
+            .take(inserted)
+            .map(line_width)
+            .collect::<Vec<_>>();
+        let end = (first + removed).min(self.line_widths.len());
+
+        self.line_widths.splice(first.min(end)..end, widths);
+    }
+
+    /// Width of the longest line
+    fn text_width(&self) -> f32 {
+        self.line_widths.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Scrolls the unwrapped editor so the cursor is in view
+    fn follow_cursor(&self) -> Option<Message> {
+        if self.word_wrap {
+            return None;
+        }
+
+        let viewport = self.viewport?;
+        let (line, column) = self.content.cursor_position();
+        let height = line_height();
+
+        // The column is a byte offset into the line
+        let text = self.text.split('\n').nth(line).unwrap_or_default();
+        let mut column = column.min(text.len());
+        while !text.is_char_boundary(column) {
+            column -= 1;
+        }
+        let (before, after) = text.split_at(column);
+        let width = after
+            .chars()
+            .next()
+            .map_or_else(|| line_width("0"), |next| line_width(&next.to_string()));
+
+        let cursor = Rectangle {
+            x: self.text_left() + line_width(before),
+            y: EDITOR_PADDING[0] + line as f32 * height,
+            width,
+            height,
+        };
+
+        follow(viewport.absolute_offset(), viewport.bounds().size(), cursor)
+            .map(|offset| Message::ScrollEditor(self.scroll_id.clone(), offset))
+    }
+
+    fn gutter(&self) -> Element<'_, EditorMessage> {
+        let current = self.content.cursor_position().0;
+        let count = self.content.line_count().max(1);
+        let width = self.gutter_width();
+
+        let numbers = (0..count).map(|line| {
+            text(line + 1)
+                .size(TEXT_SIZE)
+                .line_height(LineHeight::default())
+                .width(width)
+                .align_x(alignment::Horizontal::Right)
+                .style(move |theme: &Theme| {
+                    let palette = theme.extended_palette();
+
+                    let color = if line == current {
+                        palette.primary.strong.color
+                    } else {
+                        palette.background.strong.color
+                    };
+
+                    text::Style { color: Some(color) }
+                })
+        });
+
+        Column::with_children(numbers)
+            .padding([EDITOR_PADDING[0], GUTTER_PADDING])
+            .width(Length::Shrink)
+            .into()
+    }
+
+    fn tools(&self) -> Element<'_, EditorMessage> {
+        let spacing = 10.0;
+
+        let header = {
+            let header = text("Editor Config").size(17.0);
+
+            row!(horizontal_space(), header, horizontal_space())
+                .padding([2, 0])
+                .align_y(Alignment::Center)
+        };
+
+        let word_wrap = {
+            let check = {
+                let check = checkbox("", self.word_wrap).on_toggle(EditorMessage::WordWrap);
+                let label = text("Word wrap");
+
+                row!(label, check).spacing(8.0).align_y(Alignment::Center)
+            };
+
+            let tip = tooltip("Wrap long lines instead of scrolling sideways to see them");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let line_numbers = {
+            let check = {
+                let check = checkbox("", self.line_numbers)
+                    .on_toggle_maybe((!self.word_wrap).then_some(EditorMessage::LineNumbers));
+                let label = text("Line numbers");
+
+                row!(label, check).spacing(8.0).align_y(Alignment::Center)
+            };
+
+            let tip = if self.word_wrap {
+                tooltip("Line numbers are shown while word wrap is off")
+            } else {
+                tooltip("Show line numbers beside the text")
+            };
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let highlighting = {
+            let check = {
+                let check = checkbox("", self.highlighting);
+                let check = if cfg!(feature = "highlighter") {
+                    check.on_toggle(EditorMessage::Highlighting)
+                } else {
+                    check
+                };
+                let label = text("Syntax highlighting");
+
+                row!(label, check).spacing(8.0).align_y(Alignment::Center)
+            };
+
+            let tip = if cfg!(feature = "highlighter") {
+                tooltip("Color the text based on the file's type. When off, the text is plain monospace")
+            } else {
+                tooltip("This build of modav was made without syntax highlighting")
+            };
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let ignore_whitespace = {
+            let check = {
+                let check =
+                    checkbox("", self.ignore_whitespace).on_toggle(EditorMessage::IgnoreWhitespace);
+                let label = text("Ignore trailing whitespace");
+
+                row!(label, check).spacing(8.0).align_y(Alignment::Center)
+            };
+
+            let tip =
+                tooltip("Changes to only trailing spaces or newlines are not unsaved changes");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        column!(
+            header,
+            word_wrap,
+            line_numbers,
+            highlighting,
+            ignore_whitespace
+        )
+        .spacing(spacing)
+        .width(Length::Fill)
+        .into()
+    }
 }
 
 impl Viewable for EditorTab {
@@ -64,16 +990,36 @@ impl Viewable for EditorTab {
             path,
             data,
             read_only,
+            plain,
+            name,
+            reload: _,
         } = data;
         let is_empty = data.is_empty();
         let content = text_editor::Content::with_text(data.as_str());
-        Self {
+        let mut tab = Self {
+            text: data.clone(),
+            saved: data,
             content,
             is_empty,
             read_only,
-            is_dirty: false,
+            is_dirty: name.is_some() && !read_only,
             file_path: path,
-        }
+            name,
+            word_wrap: false,
+            line_numbers: true,
+            highlighting: cfg!(feature = "highlighter") && !plain,
+            ignore_whitespace: true,
+            find_shown: false,
+            find: Find::default(),
+            history: History::default(),
+            line_widths: Vec::new(),
+            viewport: None,
+            find_id: text_input::Id::unique(),
+            scroll_id: scrollable::Id::unique(),
+        };
+        tab.measure();
+
+        tab
     }
 
     fn is_dirty(&self) -> bool {
@@ -106,20 +1052,33 @@ impl Viewable for EditorTab {
             .unwrap_or("");
 
         if path.is_empty() {
-            "Untitled".into()
+            self.name.clone().unwrap_or_else(|| "Untitled".into())
         } else {
             path.into()
         }
     }
 
     fn update(&mut self, message: Self::Event) -> Option<Message> {
+        // Messages which can move the cursor out of view
+        let moves_cursor = matches!(
+            message,
+            EditorMessage::Action(
+                text_editor::Action::Edit(_)
+                    | text_editor::Action::Move(_)
+                    | text_editor::Action::Select(_)
+            ) | EditorMessage::NextMatch
+                | EditorMessage::PreviousMatch
+                | EditorMessage::GoToLine(_)
+                | EditorMessage::Undo
+                | EditorMessage::Redo
+        );
+
         match message {
             EditorMessage::Action(text_editor::Action::Edit(edit)) => {
                 if self.read_only {
                     return None;
                 }
 
-                self.is_dirty = true;
                 match &edit {
                     text_editor::Edit::Insert(_) => {
                         self.is_empty = false;
@@ -130,22 +1089,167 @@ impl Viewable for EditorTab {
                     _ => {}
                 };
 
+                let typing = matches!(&edit, text_editor::Edit::Insert(c) if !c.is_whitespace());
+                let before = self.content.cursor_position();
+
                 self.content.perform(text_editor::Action::Edit(edit));
+                let text = self.content.text();
+                let after = self.content.cursor_position();
+
+                let change = Change::between(&self.text, &text, before, after);
+                self.text = text;
+
+                if let Some(change) = change {
+                    self.remeasure(change.at, &change.removed, &change.inserted);
+                    self.history.record(change, typing);
+                }
+                self.is_dirty = is_modified(&self.saved, &self.text, self.ignore_whitespace);
+
+                if self.find_shown {
+                    self.refresh_matches();
+                }
+            }
+            // Lines are not wrapped while the editor is in a scrollable, and
+            // an editor sized to its text has nothing to scroll by itself
+            EditorMessage::Action(text_editor::Action::Scroll { lines }) if !self.word_wrap => {
+                let viewport = self.viewport?;
+                let offset = viewport.absolute_offset();
+
+                return Some(Message::ScrollEditor(
+                    self.scroll_id.clone(),
+                    scrollable::AbsoluteOffset {
+                        x: offset.x,
+                        y: (offset.y + lines as f32 * line_height()).max(0.0),
+                    },
+                ));
             }
             EditorMessage::Action(act) => {
+                // Moving the cursor ends the word being typed
+                if matches!(
+                    act,
+                    text_editor::Action::Move(_) | text_editor::Action::Click(_)
+                ) {
+                    self.history.typing = false;
+                }
                 self.content.perform(act);
             }
             EditorMessage::Refresh(data) => {
                 self.is_dirty = false;
                 self.refresh(data);
             }
+            EditorMessage::WordWrap(word_wrap) => {
+                self.word_wrap = word_wrap;
+            }
+            EditorMessage::LineNumbers(line_numbers) => {
+                self.line_numbers = line_numbers;
+            }
+            EditorMessage::Highlighting(highlighting) => {
+                self.highlighting = cfg!(feature = "highlighter") && highlighting;
+            }
+            EditorMessage::IgnoreWhitespace(ignore) => {
+                self.ignore_whitespace = ignore;
+                self.is_dirty = is_modified(&self.saved, &self.text, ignore);
+            }
+            EditorMessage::OpenFind(replace) => {
+                self.find_shown = true;
+                self.find.replace = replace && !self.read_only;
+                self.refresh_matches();
+            }
+            EditorMessage::CloseFind => {
+                self.find_shown = false;
+            }
+            EditorMessage::FindQuery(query) => {
+                self.find.query = query;
+                self.refresh_matches();
+            }
+            EditorMessage::ReplaceQuery(replacement) => {
+                self.find.replacement = replacement;
+            }
+            EditorMessage::CaseSensitive(case_sensitive) => {
+                self.find.case_sensitive = case_sensitive;
+                self.refresh_matches();
+            }
+            EditorMessage::NextMatch => {
+                let total = self.find.matches.len();
+
+                if total > 0 {
+                    let next = self.find.current.map_or(0, |current| (current + 1) % total);
+                    self.select_match(next);
+                }
+            }
+            EditorMessage::PreviousMatch => {
+                let total = self.find.matches.len();
+
+                if total > 0 {
+                    let previous = self
+                        .find
+                        .current
+                        .map_or(total - 1, |current| (current + total - 1) % total);
+                    self.select_match(previous);
+                }
+            }
+            EditorMessage::ReplaceAll => {
+                if self.read_only || self.find.matches.is_empty() {
+                    return None;
+                }
+
+                let replaced = replace_matches(
+                    &self.text,
+                    &self.find.query,
+                    &self.find.replacement,
+                    self.find.case_sensitive,
+                );
+
+                // The new text starts with the cursor at its beginning
+                let before = self.content.cursor_position();
+                if let Some(change) = Change::between(&self.text, &replaced, before, (0, 0)) {
+                    self.history.record(change, false);
+                }
+
+                self.content = text_editor::Content::with_text(&replaced);
+                self.text = replaced;
+                self.measure();
+                self.is_dirty = is_modified(&self.saved, &self.text, self.ignore_whitespace);
+                self.refresh_matches();
+            }
+            EditorMessage::GoToLine(line) => {
+                self.history.typing = false;
+                self.move_to(line.saturating_sub(1), 0);
+                self.content
+                    .perform(text_editor::Action::Select(Motion::End));
+            }
+            EditorMessage::Undo => {
+                if self.read_only {
+                    return None;
+                }
+
+                if let Some(change) = self.history.undo() {
+                    self.restore(&change);
+                }
+            }
+            EditorMessage::Redo => {
+                if self.read_only {
+                    return None;
+                }
+
+                if let Some(change) = self.history.redo() {
+                    self.restore(&change);
+                }
+            }
+            EditorMessage::Scrolled(viewport) => {
+                self.viewport = Some(viewport);
+            }
         }
 
-        None
+        if moves_cursor {
+            self.follow_cursor()
+        } else {
+            None
+        }
     }
 
     fn content(&self) -> Option<String> {
-        self.content.text().into()
+        self.text.clone().into()
     }
 
     fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, iced::Theme, iced::Renderer>
@@ -153,27 +1257,79 @@ impl Viewable for EditorTab {
         F: 'a + Fn(Self::Event) -> Message,
         Message: 'a,
     {
-        let extension = self
-            .path()
-            .as_ref()
-            .and_then(|path| path.extension()?.to_str())
-            .unwrap_or("txt")
-            .to_string();
-        let highlighter_settings = highlighter::Settings {
-            token: extension,
-            theme: highlighter::Theme::SolarizedDark,
+        let wrapping = if self.word_wrap {
+            Wrapping::Word
+        } else {
+            Wrapping::None
         };
 
-        let content: Element<EditorMessage, Theme, Renderer> = text_editor(&self.content)
+        let editor = text_editor(&self.content)
             .on_action(EditorMessage::Action)
-            .height(Length::Fill)
-            .padding([4, 8])
-            .highlight_with::<Highlighter>(highlighter_settings, |hl, _theme| hl.to_format())
-            .into();
+            .font(Font::MONOSPACE)
+            .size(TEXT_SIZE)
+            .line_height(LineHeight::default())
+            .wrapping(wrapping)
+            .padding(EDITOR_PADDING);
+
+        let content: Element<EditorMessage, Theme, Renderer> = if self.word_wrap {
+            // Wrapped lines have no width to scroll across, and the editor
+            // keeps its own cursor in view
+            self.highlight(editor.height(Length::Fill))
+        } else {
+            // Unwrapped, the editor is sized to its text and scrolls both
+            // ways along with the gutter, so numbers stay beside their line
+            let text_width = self.text_width() + EDITOR_PADDING[1] * 2.0;
+            let visible = self
+                .viewport
+                .map(|viewport| viewport.bounds().width - self.text_left() + EDITOR_PADDING[1])
+                .unwrap_or_default();
+
+            let editor = editor
+                .height(Length::Shrink)
+                .width(text_width.max(visible) + line_width("0"));
+            let editor = self.highlight(editor);
+
+            let body = if self.shows_gutter() {
+                row!(self.gutter(), editor)
+            } else {
+                row!(editor)
+            };
+
+            scrollable(body)
+                .id(self.scroll_id.clone())
+                .direction(scrollable::Direction::Both {
+                    vertical: scrollable::Scrollbar::default(),
+                    horizontal: scrollable::Scrollbar::default(),
+                })
+                .on_scroll(EditorMessage::Scrolled)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        };
+
+        // The bar comes before the editor so that the editor is the last
+        // focusable widget of the tab, which is where focus returns on close
+        let content = if self.find_shown {
+            column!(self.find_bar(), content).into()
+        } else {
+            content
+        };
 
         content.map(map)
     }
 
+    fn has_config(&self) -> bool {
+        true
+    }
+
+    fn config<'a, Message, F>(&'a self, map: F) -> Option<Element<'a, Message, Theme, Renderer>>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        Some(self.tools().map(map))
+    }
+
     fn modal_msg(&self) -> String {
         if self.is_dirty {
             format!("Do you want to save changes to {}?", self.title())
@@ -183,16 +1339,214 @@ impl Viewable for EditorTab {
     }
 
     fn refresh(&mut self, data: Self::Data) {
+        if data.reload {
+            self.content = text_editor::Content::with_text(&data.data);
+            self.text = data.data.clone();
+            self.measure();
+            self.history = History::default();
+            self.refresh_matches();
+        }
         self.is_empty = data.data.is_empty();
         self.file_path = data.path;
-        self.is_dirty = false;
+        self.saved = data.data;
+        // Edits made while a save was in flight are still unsaved
+        self.is_dirty = is_modified(&self.saved, &self.text, self.ignore_whitespace);
     }
 
     fn path(&self) -> Option<PathBuf> {
         self.file_path.clone()
     }
 
+    fn session(&self) -> Option<SessionTab> {
+        self.file_path.clone().map(SessionTab::Editor)
+    }
+
     fn can_save(&self) -> bool {
         !self.read_only
     }
+
+    fn close_policy(&self) -> ClosePolicy {
+        if self.read_only {
+            ClosePolicy::Never
+        } else {
+            ClosePolicy::PromptIfDirty
+        }
+    }
+}
+
+#[test]
+fn test_trailing_newline_not_modified() {
+    // Loading a file without a trailing newline and saving it immediately
+    assert!(!is_modified("a,b,c", "a,b,c\n", true));
+    assert!(!is_modified("a,b,c", "a,b,c\n", false));
+    assert!(!is_modified("a,b,c\n", "a,b,c\n", false));
+}
+
+#[test]
+fn test_trailing_whitespace_modifications() {
+    let saved = "first line\nsecond line\n";
+
+    assert!(!is_modified(saved, "first line  \nsecond line\n\n\n", true));
+    assert!(is_modified(saved, "first line  \nsecond line\n\n\n", false));
+
+    assert!(is_modified(saved, "first line\nsecond  line\n", true));
+    assert!(is_modified(saved, "first line\n\nsecond line\n", true));
+    assert!(is_modified(saved, "first line\n", true));
+}
+
+#[test]
+fn test_find_and_replace() {
+    let text = "Apple pie\napple tart\nbanana";
+
+    assert_eq!(find_matches(text, "apple", true), vec![10]);
+    assert_eq!(find_matches(text, "apple", false), vec![0, 10]);
+    assert_eq!(find_matches(text, "an", true), vec![22, 24]);
+    assert_eq!(find_matches(text, "", false), Vec::<usize>::new());
+    assert_eq!(find_matches("aaaa", "aa", true), vec![0, 2]);
+    assert_eq!(find_matches("é x\né", "É", false), vec![0, 4]);
+
+    assert_eq!(
+        replace_matches(text, "apple", "plum", false),
+        "plum pie\nplum tart\nbanana"
+    );
+    assert_eq!(
+        replace_matches(text, "apple", "plum", true),
+        "Apple pie\nplum tart\nbanana"
+    );
+    assert_eq!(replace_matches(text, "kiwi", "plum", true), text);
+}
+
+#[test]
+fn test_undo_redo() {
+    use text_editor::{Action, Edit};
+
+    let mut editor = EditorTab::new(EditorTabData::new(None, String::new()));
+    let type_text = |editor: &mut EditorTab, text: &str| {
+        for c in text.chars() {
+            editor.update(EditorMessage::Action(Action::Edit(Edit::Insert(c))));
+        }
+    };
+
+    type_text(&mut editor, "one two");
+    assert_eq!(editor.content.text(), "one two");
+    assert!(editor.is_dirty());
+
+    // Each word is its own step, as is the space between them
+    editor.update(EditorMessage::Undo);
+    assert_eq!(editor.content.text(), "one ");
+    editor.update(EditorMessage::Undo);
+    assert_eq!(editor.content.text(), "one");
+    editor.update(EditorMessage::Undo);
+    assert_eq!(editor.content.text(), "");
+    assert!(!editor.is_dirty());
+
+    editor.update(EditorMessage::Redo);
+    assert_eq!(editor.content.text(), "one");
+
+    // A new edit drops what could have been redone
+    type_text(&mut editor, "s");
+    editor.update(EditorMessage::Redo);
+    assert_eq!(editor.content.text(), "ones");
+
+    // Undoing a replace brings back what it replaced
+    editor.update(EditorMessage::OpenFind(true));
+    editor.update(EditorMessage::FindQuery("one".into()));
+    editor.update(EditorMessage::ReplaceQuery("two".into()));
+    editor.update(EditorMessage::ReplaceAll);
+    assert_eq!(editor.content.text(), "twos");
+    editor.update(EditorMessage::Undo);
+    assert_eq!(editor.content.text(), "ones");
+    editor.update(EditorMessage::Redo);
+    assert_eq!(editor.content.text(), "twos");
+
+    let mut history = History::default();
+    let mut text = String::new();
+    for idx in 0..MAX_HISTORY + 10 {
+        let change = Change::between(&text, &format!("{text}{idx},"), (0, 0), (0, 0));
+        text = format!("{text}{idx},");
+        history.record(change.unwrap(), false);
+    }
+    assert_eq!(history.undo.len(), MAX_HISTORY);
+    assert_eq!(history.undo[0].inserted, "10,");
+}
+
+#[test]
+fn test_remeasure() {
+    use text_editor::{Action, Edit};
+
+    let mut editor = EditorTab::new(EditorTabData::new(None, "ab\n\tc\n".into()));
+    let edits = [
+        Edit::Insert('字'),
+        Edit::Enter,
+        Edit::Paste(std::sync::Arc::new("x\ny".into())),
+        Edit::Backspace,
+        Edit::Delete,
+    ];
+    for edit in edits {
+        editor.update(EditorMessage::Action(Action::Edit(edit)));
+    }
+
+    // Only the lines an edit touched are measured again, and they end up
+    // as if every line was
+    let widths = editor.line_widths.clone();
+    assert_eq!(widths.len(), editor.content.line_count());
+    editor.measure();
+    assert_eq!(widths, editor.line_widths);
+}
+
+#[test]
+fn test_change_between() {
+    let change = Change::between("one two", "one 2 two", (0, 4), (0, 6)).unwrap();
+    assert_eq!((change.at, change.removed.as_str()), (4, ""));
+    assert_eq!(change.inserted, "2 ");
+
+    // Changes never split a character
+    let change = Change::between("café", "cafè", (0, 0), (0, 0)).unwrap();
+    assert_eq!(
+        (change.removed.as_str(), change.inserted.as_str()),
+        ("é", "è")
+    );
+
+    // Each change undoes and redoes back to the text it was made from
+    for (old, new) in [("one two", "on two"), ("on two", ""), ("", "ünïcode")] {
+        let change = Change::between(old, new, (0, 0), (0, 0)).unwrap();
+        let mut text = old.to_string();
+
+        change.apply(&mut text);
+        assert_eq!(text, new);
+        change.inverse().apply(&mut text);
+        assert_eq!(text, old);
+    }
+
+    assert_eq!(Change::between("same", "same", (0, 0), (0, 0)), None);
+
+    assert_eq!(char_offset("ab\ncdé\nf", 1, 2), 5);
+    assert_eq!(char_offset("ab\ncdé\nf", 1, 3), 5);
+    assert_eq!(char_offset("ab\ncdé\nf", 2, 9), 8);
+}
+
+#[test]
+fn test_follow_cursor() {
+    let offset = scrollable::AbsoluteOffset { x: 0.0, y: 100.0 };
+    let visible = Size::new(200.0, 100.0);
+    let cursor = |x: f32, y: f32| Rectangle {
+        x,
+        y,
+        width: 10.0,
+        height: 20.0,
+    };
+
+    assert_eq!(follow(offset, visible, cursor(50.0, 120.0)), None);
+    assert_eq!(
+        follow(offset, visible, cursor(50.0, 40.0)),
+        Some(scrollable::AbsoluteOffset { x: 0.0, y: 40.0 })
+    );
+    assert_eq!(
+        follow(offset, visible, cursor(50.0, 190.0)),
+        Some(scrollable::AbsoluteOffset { x: 0.0, y: 110.0 })
+    );
+    assert_eq!(
+        follow(offset, visible, cursor(300.0, 150.0)),
+        Some(scrollable::AbsoluteOffset { x: 110.0, y: 100.0 })
+    );
 }
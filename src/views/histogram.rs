@@ -0,0 +1,965 @@
+use std::{collections::HashSet, fmt::Debug, path::PathBuf};
+
+use iced::{
+    alignment,
+    widget::{
+        button, canvas, checkbox, column, container, horizontal_space, row, slider, text,
+        text_input, Canvas, Tooltip,
+    },
+    Alignment, Color, Element, Font, Length, Padding, Renderer, Size, Theme,
+};
+
+use modav_core::{
+    models::Point as GraphPoint,
+    repr::sheet::utils::{Data, HeaderLabelStrategy},
+};
+
+use crate::{
+    utils::{
+        coloring::ColorEngine,
+        csv,
+        formatting::Thresholds,
+        icons,
+        sampling::RowLimit,
+        stats::{auto_bins, histogram, Bin, MAX_BINS},
+        tooltip, AppError, AxisFormat, NotationMode,
+    },
+    widgets::{modal::Modal, wizard::HistogramConfigState},
+    Message, ToolTipContainerStyle,
+};
+
+use super::{
+    barchart::GraphBar,
+    parse_seed,
+    shared::{
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            value_points, AspectRatio, Axis, AxisKind, ChartColor, Graph, GridStyle, LegendPosition,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{self, Image},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, EditorButtonStyle, StyleInputs,
+    },
+    tabs::TabLabel,
+    SessionTab, Viewable,
+};
+
+/// The point on the x axis a bin is drawn at
+fn bin_label(bin: &Bin) -> Data {
+    // Written as f32 so the bin edges are not shown with widening noise
+    Data::Text(format!("{}–{}", bin.start as f32, bin.end as f32))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramTabData {
+    file: PathBuf,
+    title: String,
+    /// The numbers of the column, in file order
+    values: Vec<f64>,
+    x_label: Option<String>,
+    theme: Theme,
+    caption: Option<String>,
+    config: HistogramConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl HistogramTabData {
+    pub fn new(file: PathBuf, config: HistogramConfigState) -> Result<Self, AppError> {
+        let HistogramConfigState {
+            title,
+            column,
+            caption,
+            flexible,
+            delimiter,
+            header_labels,
+            row_limit,
+            filters,
+            ..
+        } = config.clone();
+
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
+
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut HashSet::new())?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let content =
+            std::fs::read_to_string(&source).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = csv::parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        let header = if has_header { records.next() } else { None };
+        let x_label = header
+            .as_ref()
+            .and_then(|header| header.get(column))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+
+        let values = records
+            .filter_map(|record| record.get(column)?.trim().parse::<f64>().ok())
+            .filter(|value| value.is_finite())
+            .collect::<Vec<f64>>();
+
+        if values.is_empty() {
+            return Err(AppError::Simple(format!(
+                "Column {column} has no numbers to count"
+            )));
+        }
+
+        Ok(Self {
+            file,
+            title,
+            values,
+            x_label,
+            caption,
+            config,
+            sample,
+            shape,
+            theme: Theme::default(),
+        })
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HistogramMessage {
+    OpenEditor,
+    ToggleConfig,
+    TitleChanged(String),
+    Clean(bool),
+    Crosshair(bool),
+    RotateLabels(bool),
+    CaptionChange(String),
+    XLabelChanged(String),
+    YLabelChanged(String),
+    /// Splits the values into this many bins
+    BinCount(u16),
+    /// Picks the bin count from the spread of the values
+    AutoBins,
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    YFormat(AxisFormat),
+    ChangeSeed(String),
+    AccentChanged(String),
+    ApplySeed,
+    RandomSeed,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
+}
+
+#[derive(Debug)]
+pub struct HistogramTab {
+    file: PathBuf,
+    title: String,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    values: Vec<f64>,
+    bin_count: usize,
+    bins: Vec<Bin>,
+    bars: Vec<GraphBar>,
+    color: Color,
+    caption: Option<String>,
+    config_shown: bool,
+    color_seed: f32,
+    cache: canvas::Cache,
+    theme: Theme,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
+    config: HistogramConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl HistogramTab {
+    fn tools(&self) -> Element<'_, HistogramMessage> {
+        let spacing = 10.0;
+
+        let header = {
+            let header = text("Model Config").size(17.0);
+
+            row!(horizontal_space(), header, horizontal_space())
+                .padding([2, 0])
+                .align_y(Alignment::Center)
+        };
+
+        let title =
+            text_input("Graph Title", self.title.as_str()).on_input(HistogramMessage::TitleChanged);
+
+        let x_label = text_input(
+            "X axis label",
+            self.x_label
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(HistogramMessage::XLabelChanged);
+
+        let y_label = text_input(
+            "Y axis label",
+            self.y_label
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(HistogramMessage::YLabelChanged);
+
+        let caption = text_input(
+            "Graph Caption",
+            self.caption
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(HistogramMessage::CaptionChange);
+
+        let bins = {
+            let label = text("Bins");
+
+            let slider = slider(
+                1..=MAX_BINS as u16,
+                self.bin_count as u16,
+                HistogramMessage::BinCount,
+            )
+            .width(120.0);
+
+            let auto = button(text("Auto").size(13.0))
+                .padding([4, 8])
+                .style(button::secondary)
+                .on_press(HistogramMessage::AutoBins);
+
+            let tip = tooltip(
+                "How many equal ranges the values are split into. Auto picks a count from the spread of the values",
+            );
+
+            row!(label, slider, text(self.bin_count.to_string()), auto, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let clean = {
+            let check = {
+                let check = checkbox("", self.view_state.clean).on_toggle(HistogramMessage::Clean);
+                let label = text("Clean graph");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip("Only points on the axes have their outline drawn");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let crosshair = {
+            let check = {
+                let check =
+                    checkbox("", self.view_state.crosshair).on_toggle(HistogramMessage::Crosshair);
+                let label = text("Crosshair");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Draws guide lines from the cursor to the axes, labelled with their values",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let rotate_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.rotate_labels)
+                    .on_toggle(HistogramMessage::RotateLabels);
+                let label = text("Rotate X labels");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip =
+                tooltip("Slants the X axis labels. Labels that would overlap are always slanted");
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let seed = {
+            let value = self.color_seed;
+            let value = format!("{value:.4}");
+
+            let label = text("Coloring Seed");
+
+            let tip = tooltip("Sets the seed used to generate graph colors");
+
+            let btn = button(
+                icons::icon(icons::REDO)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .padding([4, 8])
+            .on_press(HistogramMessage::ApplySeed);
+
+            let rand = button(icons::icon(icons::SHUFFLE).align_y(alignment::Vertical::Center))
+                .padding([4, 8])
+                .style(button::secondary)
+                .on_press(HistogramMessage::RandomSeed);
+
+            let input = text_input("", &value)
+                .on_input(HistogramMessage::ChangeSeed)
+                .padding([2, 5])
+                .width(67.0);
+
+            row!(label, input, btn, rand, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(HistogramMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let y_format = axis_format_row(
+            "Y Axis Numbers",
+            self.view_state.y_format,
+            HistogramMessage::YFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the axes are written",
+            HistogramMessage::Notation,
+            HistogramMessage::Thresholds,
+        );
+
+        let editor = {
+            let font = Font::with_name(icons::NAME);
+
+            let btn = button(
+                text(icons::EDITOR)
+                    .font(font)
+                    .width(16.0)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .on_press(HistogramMessage::OpenEditor)
+            .style(|theme, status| {
+                <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+            })
+            .padding([4, 4]);
+
+            let tooltip = container(text("Open in Editor").size(12.0))
+                .max_width(200.0)
+                .padding([6, 8])
+                .style(|theme| {
+                    <ToolTipContainerStyle as container::Catalog>::style(
+                        &ToolTipContainerStyle,
+                        theme,
+                    )
+                })
+                .height(Length::Shrink);
+
+            let menu = Tooltip::new(btn, tooltip, iced::widget::tooltip::Position::Bottom)
+                .gap(2.0)
+                .snap_within_viewport(true);
+
+            let text = text("Open in Editor");
+
+            row!(text, menu).spacing(spacing).align_y(Alignment::Center)
+        };
+
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            HistogramMessage::ToggleStyle,
+            HistogramMessage::StyleColor,
+            HistogramMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, HistogramMessage::Aspect);
+        let reconfigure = reconfigure_row(HistogramMessage::Reconfigure);
+        let export = export_metadata_row(HistogramMessage::ExportMetadata);
+        let image = export_image_row(HistogramMessage::ExportImage);
+        let pdf = export_pdf_row(HistogramMessage::ExportPdf);
+
+        column!(
+            header,
+            title,
+            x_label,
+            y_label,
+            caption,
+            bins,
+            clean,
+            crosshair,
+            rotate_labels,
+            seed,
+            accent,
+            notation,
+            y_format,
+            style,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor,
+        )
+        .spacing(25.0)
+        .into()
+    }
+
+    /// Splits the values into `self.bin_count` bins and rebuilds their bars
+    fn rebin(&mut self) {
+        self.bins = histogram(&self.values, self.bin_count);
+        self.bars = self
+            .bins
+            .iter()
+            .map(|bin| {
+                let point = GraphPoint {
+                    x: bin_label(bin),
+                    y: Data::Integer(bin.count as i32),
+                };
+
+                // Bins are drawn touching, as they cover one continuous range
+                GraphBar::new(point, None, self.color).width(1.0)
+            })
+            .collect();
+
+        self.cache.clear();
+    }
+
+    fn create_axis(&self) -> Result<(Axis, Axis), AppError> {
+        if self.bins.is_empty() {
+            return Err(AppError::Simple(String::from(
+                "There is no data to place on the X axis",
+            )));
+        }
+
+        let categories = self.bins.iter().map(bin_label).collect::<Vec<Data>>();
+        let most = self.bins.iter().map(|bin| bin.count).max().unwrap_or(0);
+
+        let x_axis = Axis::new(AxisKind::BaseHorizontal(categories), 1.0, 1.0)
+            .clean(self.view_state.clean)
+            .label(self.x_label.clone())
+            .rotate_labels(self.view_state.rotate_labels);
+        let y_axis = Axis::new(
+            AxisKind::BaseVertical(value_points(0.0, most as f64)),
+            1.0,
+            1.0,
+        )
+        .clean(self.view_state.clean)
+        .label(self.y_label.clone())
+        .format(self.view_state.y_format);
+
+        Ok((x_axis, y_axis))
+    }
+
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(
+        &'a self,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphBar, HistogramMessage>, AppError> {
+        let (x_axis, y_axis) = self.create_axis()?;
+
+        Ok(
+            Graph::new(x_axis, y_axis, &self.bars, &self.theme, cache, false)
+                .title(Some(self.title.as_str()).filter(|title| !title.is_empty()))
+                .caption(self.caption.as_ref())
+                .legend(LegendPosition::None)
+                .notation(self.view_state.notation)
+                .crosshair(self.view_state.crosshair)
+                .style(self.view_state.style),
+        )
+    }
+
+    fn graph(&self) -> Element<'_, HistogramMessage> {
+        let content: Element<'_, HistogramMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
+
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    fn csv(&self) -> Option<String> {
+        let header = csv::record(["bin start", "bin end", "count"]);
+
+        let rows = self.bins.iter().map(|bin| {
+            csv::record([
+                bin.start.to_string(),
+                bin.end.to_string(),
+                bin.count.to_string(),
+            ])
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn recolor(&mut self, colors: ColorEngine) {
+        if let Some(color) = colors.count(1).next() {
+            self.color = color;
+        }
+
+        self.bars
+            .iter_mut()
+            .for_each(|bar| bar.set_color(self.color));
+        self.cache.clear()
+    }
+}
+
+impl Viewable for HistogramTab {
+    type Event = HistogramMessage;
+    type Data = HistogramTabData;
+
+    fn new(data: Self::Data) -> Self {
+        let HistogramTabData {
+            file,
+            title,
+            values,
+            x_label,
+            theme,
+            caption,
+            config,
+            sample,
+            shape,
+        } = data;
+
+        let mut colors = ColorEngine::new(&theme).count(1);
+        let seed = colors.seed();
+        let color = colors.next().unwrap_or(theme.palette().primary);
+
+        let bin_count = config.bins.unwrap_or_else(|| auto_bins(&values));
+
+        let mut tab = Self {
+            file,
+            title,
+            x_label,
+            y_label: Some(String::from("Count")),
+            values,
+            bin_count,
+            bins: Vec::new(),
+            bars: Vec::new(),
+            color,
+            caption,
+            theme,
+            color_seed: seed,
+            config_shown: false,
+            cache: canvas::Cache::default(),
+            view_state: ChartViewState::default(),
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+            config,
+            sample,
+            shape,
+        };
+        tab.rebin();
+
+        tab
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn label(&self) -> TabLabel {
+        let file_name = self
+            .file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("New File");
+
+        let font = Font::with_name(icons::NAME);
+
+        TabLabel::new(icons::BARCHART, format!("{} - {}", self.title, file_name)).icon_font(font)
+    }
+
+    fn content(&self) -> Option<String> {
+        None
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.file.clone())
+    }
+
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::Histogram(
+            self.file.clone(),
+            self.config.clone(),
+        ))
+    }
+
+    fn can_save(&self) -> bool {
+        false
+    }
+
+    fn modal_msg(&self) -> String {
+        "Seeing this means a logic error occurred".into()
+    }
+
+    fn refresh(&mut self, data: Self::Data) {
+        let new = <Self as Viewable>::new(data);
+        let view_state = self.view_state.clone();
+        let style_shown = self.style_shown;
+
+        *self = new;
+        self.style_shown = style_shown;
+        self.restore_view_state(view_state);
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let shape = &self.shape;
+        let mapped = [("Values", shape.column_name(self.config.column))];
+
+        Some(mapped_summary(shape, &mapped))
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = HistogramConfigState {
+            row_limit: RowLimit::default(),
+            bins: Some(self.bin_count),
+            ..self.config.clone()
+        };
+        let data = HistogramTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.cache.clear();
+    }
+
+    fn theme_changed(&mut self, theme: &Theme) {
+        if &self.theme == theme {
+            return;
+        }
+        self.theme = theme.clone();
+
+        let colors = ColorEngine::new(&self.theme).accent(self.view_state.accent);
+        self.color_seed = colors.seed();
+
+        self.recolor(colors);
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        self.csv()
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv()
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        let cache = canvas::Cache::new();
+
+        Some(
+            self.chart(&cache)
+                .and_then(|chart| offscreen::render(&chart, size, &self.theme)),
+        )
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let categories = self.bins.iter().map(bin_label).collect::<Vec<Data>>();
+        let counts = self
+            .bins
+            .iter()
+            .map(|bin| Data::Integer(bin.count as i32))
+            .collect::<Vec<Data>>();
+
+        let metadata = ChartMetadata {
+            kind: "histogram",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(self.x_label.as_ref(), categories.iter()),
+            y_axis: AxisMetadata::new(self.y_label.as_ref(), counts.iter()),
+            series: vec![SeriesMetadata::new(
+                self.x_label.as_ref(),
+                self.color,
+                counts.iter(),
+            )],
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = state;
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent);
+            self.recolor(colors);
+        }
+
+        self.cache.clear();
+    }
+
+    fn has_config(&self) -> bool {
+        true
+    }
+
+    fn config<'a, Message, F>(&'a self, map: F) -> Option<Element<'a, Message, Theme, Renderer>>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        Some(self.tools().map(map))
+    }
+
+    fn update(&mut self, message: Self::Event) -> Option<Message> {
+        match message {
+            HistogramMessage::OpenEditor => Some(Message::OpenEditor(Some(self.file.clone()))),
+            HistogramMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::Reconfigure => Some(Message::Reconfigure),
+            HistogramMessage::ExportMetadata => {
+                Some(Message::ChooseMetadataPath(self.file.clone()))
+            }
+            HistogramMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            HistogramMessage::ExportImage => Some(Message::ChooseImagePath(self.file.clone())),
+            HistogramMessage::ToggleConfig => {
+                self.config_shown = !self.config_shown;
+                None
+            }
+            HistogramMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            HistogramMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.cache.clear();
+                }
+                None
+            }
+            HistogramMessage::TitleChanged(title) => {
+                self.title = title;
+                None
+            }
+            HistogramMessage::BinCount(count) => {
+                self.bin_count = usize::from(count).max(1);
+                self.rebin();
+                None
+            }
+            HistogramMessage::AutoBins => {
+                self.bin_count = auto_bins(&self.values);
+                self.rebin();
+                None
+            }
+            HistogramMessage::Clean(clean) => {
+                self.view_state.clean = clean;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::Crosshair(crosshair) => {
+                self.view_state.crosshair = crosshair;
+                None
+            }
+            HistogramMessage::RotateLabels(rotate) => {
+                self.view_state.rotate_labels = rotate;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::CaptionChange(caption) => {
+                self.caption = if caption.is_empty() {
+                    None
+                } else {
+                    Some(caption)
+                };
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::XLabelChanged(label) => {
+                self.x_label = if label.is_empty() { None } else { Some(label) };
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::YLabelChanged(label) => {
+                self.y_label = if label.is_empty() { None } else { Some(label) };
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::YFormat(format) => {
+                self.view_state.y_format = format;
+                self.cache.clear();
+                None
+            }
+            HistogramMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            HistogramMessage::ChangeSeed(seed) => {
+                if let Some(seed) = parse_seed(seed, self.color_seed == 0.0) {
+                    self.color_seed = seed;
+                }
+
+                None
+            }
+            HistogramMessage::ApplySeed => {
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            HistogramMessage::RandomSeed => {
+                use rand::{thread_rng, Rng};
+                let seed: f32 = thread_rng().gen();
+                self.color_seed = seed;
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+        }
+    }
+
+    fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, Theme, Renderer>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        let title = {
+            let text = text(format!("{} - Histogram", self.title));
+            row!(horizontal_space(), text, horizontal_space())
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+        }
+        .height(Length::Shrink);
+
+        let content_area = container(self.graph())
+            .max_width(1450)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|theme| {
+                <ContentAreaContainer as container::Catalog>::style(&ContentAreaContainer, theme)
+            });
+
+        let content = column!(title, content_area)
+            .align_x(Alignment::Center)
+            .spacing(20)
+            .height(Length::Fill)
+            .width(Length::Fill);
+
+        let content: Element<Self::Event, Theme, Renderer> = if self.config_shown {
+            Modal::new(content, self.tools())
+                .on_blur(HistogramMessage::ToggleConfig)
+                .into()
+        } else {
+            content.into()
+        };
+
+        let content: Element<Self::Event, Theme, Renderer> = container(content)
+            .padding(Padding {
+                top: 10.,
+                right: 30.,
+                bottom: 30.,
+                left: 15.,
+            })
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+
+        content.map(map)
+    }
+}
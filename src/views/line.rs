@@ -1,6 +1,7 @@
 use std::{
+    collections::{HashMap, HashSet},
     fmt::{self, Debug},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 use tracing::warn;
 
@@ -9,9 +10,10 @@ use iced::{
     widget::{
         button,
         canvas::{self, Canvas, Frame, Path, Stroke},
-        checkbox, column, container, horizontal_space, row, text, text_input, Tooltip,
+        checkbox, column, container, horizontal_space, mouse_area, pick_list, row, stack, text,
+        text_input, Column, Row, Space, Tooltip,
     },
-    Alignment, Color, Element, Font, Length, Padding, Point, Renderer, Size, Theme,
+    Alignment, Color, Element, Font, Length, Padding, Point, Rectangle, Renderer, Size, Theme,
 };
 
 use modav_core::{
@@ -19,11 +21,22 @@ use modav_core::{
         line::{self, Line},
         Point as GraphPoint, Scale,
     },
-    repr::sheet::builders::SheetBuilder,
+    repr::sheet::{
+        builders::SheetBuilder,
+        utils::{HeaderLabelStrategy, LineLabelStrategy},
+    },
 };
 
 use crate::{
-    utils::{coloring::ColorEngine, icons, tooltip, AppError},
+    utils::{
+        coloring::ColorEngine,
+        csv,
+        formatting::{format_number, numeric_value, Thresholds},
+        icons,
+        sampling::RowLimit,
+        stats::series_stats,
+        tooltip, AppError, AxisFormat, NotationMode,
+    },
     widgets::{
         toolbar::{ToolBarOrientation, ToolbarMenu, ToolbarOption},
         wizard::LineConfigState,
@@ -34,10 +47,22 @@ use crate::{
 use super::{
     parse_seed,
     shared::{
-        graph::{create_axis, Axis, DrawnOutput, Graph, Graphable, LegendPosition},
-        ContentAreaContainer,
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            create_axis, hex_color, legend_row, log_points, muted, value_points, AspectRatio, Axis,
+            AxisKind, ChartColor, DrawnOutput, Graph, Graphable, GridStyle, HoverPoint,
+            LegendEntry, LegendPosition, LegendSort, LEGEND_ROW_HEIGHT,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{Image, Offscreen},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, StyleInputs,
     },
-    TabLabel, Viewable,
+    SessionTab, TabLabel, Viewable,
 };
 
 use super::shared::EditorButtonStyle;
@@ -48,10 +73,22 @@ pub enum GraphType {
     Point,
     #[default]
     LinePoint,
+    Area,
+    /// An area graph with the points marked
+    AreaPoint,
+    /// Unconnected points, optionally sized by a column of the data
+    Scatter,
 }
 
 impl GraphType {
-    const ALL: [Self; 3] = [Self::LinePoint, Self::Line, Self::Point];
+    pub const ALL: [Self; 6] = [
+        Self::LinePoint,
+        Self::Line,
+        Self::Point,
+        Self::Area,
+        Self::AreaPoint,
+        Self::Scatter,
+    ];
 }
 
 impl AsRef<str> for GraphType {
@@ -60,6 +97,9 @@ impl AsRef<str> for GraphType {
             Self::Line => "Line Graph",
             Self::Point => "Points Graph",
             Self::LinePoint => "Line Graph with Points",
+            Self::Area => "Area Graph",
+            Self::AreaPoint => "Area Graph with Points",
+            Self::Scatter => "Scatter Plot",
         }
     }
 }
@@ -76,6 +116,11 @@ impl ToolbarOption for GraphType {
             Self::Line => '\u{E800}',
             Self::Point => '\u{E801}',
             Self::LinePoint => '\u{E802}',
+            // No glyphs for these in the line type font; they fall back to the
+            // system font
+            Self::Area => '\u{25E2}',
+            Self::AreaPoint => '\u{25E3}',
+            Self::Scatter => '\u{2234}',
         }
     }
 }
@@ -85,20 +130,96 @@ pub struct GraphLine {
     points: Vec<GraphPoint>,
     label: Option<String>,
     color: Color,
+    /// Hidden lines are not drawn and are greyed out in the legend
+    hidden: bool,
+    /// Radius of the points in a scatter plot, if the line has size data
+    radius: Option<f32>,
+    /// Whether the line is plotted against the secondary Y axis
+    secondary: bool,
 }
 
+/// Radius of scatter plot points without size data
+const SCATTER_RADIUS: f32 = 5.0;
+/// Smallest and largest radius a scatter plot's size column maps to
+const SCATTER_RADII: (f32, f32) = (3.0, 12.0);
+
 impl GraphLine {
     pub fn new(points: Vec<GraphPoint>, label: Option<String>, color: Color) -> Self {
         Self {
             points,
             color,
             label,
+            hidden: false,
+            radius: None,
+            secondary: false,
         }
     }
 
+    pub fn radius(mut self, radius: Option<f32>) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn secondary(mut self, secondary: bool) -> Self {
+        self.secondary = secondary;
+        self
+    }
+
+    /// The line's label as listed in the legend, marked if the line uses the
+    /// secondary axis
+    fn legend_label(&self) -> Option<String> {
+        let label = self.label.as_ref()?;
+
+        Some(if self.secondary {
+            format!("{label} (right)")
+        } else {
+            label.clone()
+        })
+    }
+
     pub fn set_color(&mut self, color: Color) {
         self.color = color;
     }
+
+    pub fn toggle_hidden(&mut self) {
+        self.hidden = !self.hidden;
+    }
+
+    /// Fills the region between the line and the x axis with a translucent
+    /// version of the line's color. On split axes the x axis sits at zero, so
+    /// negative values are filled up to it.
+    fn draw_area(&self, frame: &mut Frame, x_output: &DrawnOutput, y_output: &DrawnOutput) {
+        let points = self
+            .points
+            .iter()
+            .filter_map(|point| {
+                let x = x_output.get_closest(&point.x, true)?;
+                let y = y_output.get_closest(&point.y, false)?;
+                Some(Point { x, y })
+            })
+            .collect::<Vec<Point>>();
+
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return;
+        };
+
+        let baseline = x_output.axis_pos;
+
+        let path = Path::new(|bdr| {
+            bdr.move_to(Point::new(first.x, baseline));
+            points.iter().for_each(|point| bdr.line_to(*point));
+            bdr.line_to(Point::new(last.x, baseline));
+            bdr.close();
+        });
+
+        frame.fill(
+            &path,
+            Color {
+                a: 0.35,
+                ..self.color
+            },
+        );
+    }
 }
 
 impl Graphable for GraphLine {
@@ -112,6 +233,17 @@ impl Graphable for GraphLine {
         self.label().is_some()
     }
 
+    fn uses_secondary_axis(&self, _data: &Self::Data<'_>) -> bool {
+        self.secondary
+    }
+
+    fn legend_value(&self, _data: &Self::Data<'_>) -> Option<f64> {
+        self.points
+            .iter()
+            .filter_map(|point| numeric_value(&point.y))
+            .reduce(f64::max)
+    }
+
     fn draw_legend(
         &self,
         frame: &mut Frame,
@@ -120,25 +252,26 @@ impl Graphable for GraphLine {
         idx: usize,
         _data: &Self::Data<'_>,
     ) {
-        if idx > 4 {
-            return;
-        }
-
-        let y_padding = bounds.height / (5.0);
         let spacing = 5.0;
         let text_size = 12.0;
         let color_size = Size::new(12.0, 12.0);
 
         let x = bounds.x;
-        let y = bounds.position().y + (idx as f32 * y_padding);
+        let y = bounds.position().y + (idx as f32 * LEGEND_ROW_HEIGHT);
         let position = Point::new(x, y);
 
-        frame.fill_rectangle(position, color_size, self.color);
+        let (swatch, color) = if self.hidden {
+            (muted(self.color), muted(color))
+        } else {
+            (self.color, color)
+        };
+
+        frame.fill_rectangle(position, color_size, swatch);
 
         let position = Point::new(x + spacing + color_size.width, y + 0.5 * color_size.height);
 
         let label = canvas::Text {
-            content: self.label.clone().unwrap_or_default(),
+            content: self.legend_label().unwrap_or_default(),
             position,
             color,
             size: text_size.into(),
@@ -149,6 +282,44 @@ impl Graphable for GraphLine {
         frame.fill_text(label);
     }
 
+    fn legend_entry_at(
+        &self,
+        bounds: iced::Rectangle,
+        idx: usize,
+        point: Point,
+        _data: &Self::Data<'_>,
+    ) -> Option<usize> {
+        legend_row(bounds, idx).contains(point).then_some(0)
+    }
+
+    fn hover_points(
+        &self,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        _data: &Self::Data<'_>,
+    ) -> Vec<HoverPoint> {
+        if self.hidden {
+            return Vec::new();
+        }
+
+        // Every graph type places the points in the same spot, so the line
+        // vertices can be hovered even when no markers are drawn
+        self.points
+            .iter()
+            .filter_map(|point| {
+                let x = x_output.get_closest(&point.x, true)?;
+                let y = y_output.get_closest(&point.y, false)?;
+
+                Some(HoverPoint {
+                    position: Point::new(x, y),
+                    label: self.label.clone(),
+                    x: point.x.clone(),
+                    y: point.y.clone(),
+                })
+            })
+            .collect()
+    }
+
     fn draw(
         &self,
         frame: &mut Frame,
@@ -156,6 +327,14 @@ impl Graphable for GraphLine {
         y_output: &DrawnOutput,
         data: &Self::Data<'_>,
     ) {
+        if self.hidden {
+            return;
+        }
+
+        if let GraphType::Area | GraphType::AreaPoint = data {
+            self.draw_area(frame, x_output, y_output);
+        }
+
         self.points.iter().fold(None, |prev, point| {
             let x = match x_output.get_closest(&point.x, true) {
                 Some(x) => x,
@@ -182,7 +361,7 @@ impl Graphable for GraphLine {
                     frame.fill(&path, self.color);
                 }
 
-                GraphType::Line => {
+                GraphType::Line | GraphType::Area => {
                     if let Some(prev) = prev {
                         let path = Path::new(|bdr| {
                             bdr.move_to(prev);
@@ -195,7 +374,24 @@ impl Graphable for GraphLine {
                     };
                 }
 
-                GraphType::LinePoint => {
+                GraphType::Scatter => {
+                    let radius = self.radius.unwrap_or(SCATTER_RADIUS);
+                    let path = Path::circle(point, radius);
+
+                    frame.fill(
+                        &path,
+                        Color {
+                            a: 0.6,
+                            ..self.color
+                        },
+                    );
+                    frame.stroke(
+                        &path,
+                        Stroke::default().with_width(1.0).with_color(self.color),
+                    );
+                }
+
+                GraphType::LinePoint | GraphType::AreaPoint => {
                     let path = Path::circle(point.clone(), 3.5);
 
                     frame.fill(&path, self.color);
@@ -225,6 +421,107 @@ pub struct LineTabData {
     theme: Theme,
     line: line::LineGraph,
     caption: Option<String>,
+    config: LineConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+    /// Facet names with the indices of the lines in each facet
+    facets: Vec<(String, Vec<usize>)>,
+    /// Scatter plot point radius of each line, if sized by a column
+    radii: Vec<Option<f32>>,
+}
+
+/// Most facets a graph can be split into before the grid is unreadable
+const MAX_FACETS: usize = 12;
+
+/// Reads the value in `column` of each row of `file` that becomes a line,
+/// in line order. Missing values are empty.
+fn line_values(
+    file: &Path,
+    column: usize,
+    has_header: bool,
+    row_exclude: &HashSet<usize>,
+) -> Result<Vec<String>, AppError> {
+    let content = std::fs::read_to_string(file).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+    let values = csv::parse(&content)
+        .into_iter()
+        .skip(has_header as usize)
+        .filter(|record| record.iter().any(|field| !field.trim().is_empty()))
+        .enumerate()
+        .filter(|(row, _)| !row_exclude.contains(row))
+        .map(|(_, record)| {
+            record
+                .get(column)
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    Ok(values)
+}
+
+/// Groups the lines read from `file` by the value in `column` of their row.
+/// Returns each value with the indices of its lines, in the order the values
+/// first appear.
+fn facet_lines(
+    file: &Path,
+    column: usize,
+    has_header: bool,
+    row_exclude: &HashSet<usize>,
+) -> Result<Vec<(String, Vec<usize>)>, AppError> {
+    let mut facets: Vec<(String, Vec<usize>)> = Vec::new();
+
+    for (line, value) in line_values(file, column, has_header, row_exclude)?
+        .into_iter()
+        .enumerate()
+    {
+        match facets.iter_mut().find(|(name, _)| *name == value) {
+            Some((_, lines)) => lines.push(line),
+            None => facets.push((value, vec![line])),
+        }
+    }
+
+    if facets.len() > MAX_FACETS {
+        return Err(AppError::Simple(format!(
+            "Cannot facet by column {column}: it has {} distinct values but at most {MAX_FACETS} are supported",
+            facets.len()
+        )));
+    }
+
+    Ok(facets)
+}
+
+/// Maps each line's size value linearly onto [`SCATTER_RADII`]. Lines
+/// without a numeric size get no radius.
+fn scatter_radii(sizes: &[String]) -> Vec<Option<f32>> {
+    let sizes = sizes
+        .iter()
+        .map(|size| size.parse::<f64>().ok().filter(|size| size.is_finite()))
+        .collect::<Vec<_>>();
+
+    let (min, max) = sizes
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), size| {
+            (min.min(*size), max.max(*size))
+        });
+
+    let (smallest, largest) = SCATTER_RADII;
+
+    sizes
+        .into_iter()
+        .map(|size| {
+            let size = size?;
+            let scale = if max > min {
+                ((size - min) / (max - min)) as f32
+            } else {
+                0.5
+            };
+
+            Some(smallest + scale * (largest - smallest))
+        })
+        .collect()
 }
 
 impl LineTabData {
@@ -234,17 +531,46 @@ impl LineTabData {
             x_label,
             y_label,
             label_strat,
-            row_exclude,
-            col_exclude,
+            mut row_exclude,
+            mut col_exclude,
             trim,
             flexible,
+            delimiter,
             header_types,
             header_labels,
             caption,
+            row_limit,
+            facet,
+            size_column,
+            filters,
             ..
-        } = config;
+        } = config.clone();
+
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
+
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut row_exclude)?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let facets = match facet {
+            Some(column) => {
+                col_exclude.insert(column);
+                facet_lines(&source, column, has_header, &row_exclude)?
+            }
+            None => Vec::new(),
+        };
 
-        let sht = SheetBuilder::new(file.clone().into())
+        let radii = match size_column {
+            Some(column) => {
+                col_exclude.insert(column);
+                scatter_radii(&line_values(&source, column, has_header, &row_exclude)?)
+            }
+            None => Vec::new(),
+        };
+
+        let sht = SheetBuilder::new(source.to_path_buf().into())
             .trim(trim)
             .flexible(flexible)
             .labels(header_labels)
@@ -267,6 +593,11 @@ impl LineTabData {
             title,
             line,
             caption,
+            config,
+            sample,
+            shape,
+            facets,
+            radii,
             theme: Theme::default(),
         })
     }
@@ -275,6 +606,22 @@ impl LineTabData {
         self.theme = theme;
         self
     }
+
+    /// Number of points left out for not being positive on a logarithmic
+    /// axis
+    pub fn log_skipped(&self) -> usize {
+        let skipped =
+            |log: bool, value| log && numeric_value(value).is_some_and(|value| value <= 0.0);
+
+        self.line
+            .lines
+            .iter()
+            .flat_map(|line| line.points.iter())
+            .filter(|point| {
+                skipped(self.config.log_x, &point.x) || skipped(self.config.log_y, &point.y)
+            })
+            .count()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -282,6 +629,22 @@ pub enum ModelMessage {
     OpenEditor,
     ToggleConfig,
     Legend(LegendPosition),
+    LegendSort(LegendSort),
+    /// Hides or shows the clicked legend entry's line
+    ToggleSeries(LegendEntry),
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    XFormat(AxisFormat),
+    YFormat(AxisFormat),
+    ShowStats(SeriesOption),
+    CloseStats,
+    /// Opens the color picker for the right-clicked legend entry's line
+    PickColor(LegendEntry),
+    ColorInput(String),
+    SeriesColor(Color),
+    ResetColor,
+    ClosePicker,
     GraphType(GraphType),
     TitleChanged(String),
     XLabelChanged(String),
@@ -290,9 +653,21 @@ pub enum ModelMessage {
     SequentialX(bool),
     SequentialY(bool),
     Clean(bool),
+    Crosshair(bool),
+    RotateLabels(bool),
     ChangeSeed(String),
+    AccentChanged(String),
     ApplySeed,
     RandomSeed,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
 }
 
 #[derive(Debug)]
@@ -306,57 +681,371 @@ pub struct LineGraphTab {
     lines: Vec<GraphLine>,
     theme: Theme,
     caption: Option<String>,
-    sequential_x: bool,
-    sequential_y: bool,
-    clean: bool,
     color_seed: f32,
     config_shown: bool,
-    legend: LegendPosition,
-    graph_type: GraphType,
+    stats_series: Option<usize>,
+    color_picker: Option<ColorPicker>,
+    /// Colors picked for lines, keyed by line label. Kept across refreshes
+    /// of the same file.
+    color_overrides: HashMap<String, Color>,
+    cache: canvas::Cache,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
+    config: LineConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+    facets: Vec<Facet>,
+}
+
+/// Colors offered by the color picker
+const SWATCHES: [Color; 10] = [
+    Color::from_rgb8(0x1f, 0x77, 0xb4),
+    Color::from_rgb8(0xff, 0x7f, 0x0e),
+    Color::from_rgb8(0x2c, 0xa0, 0x2c),
+    Color::from_rgb8(0xd6, 0x27, 0x28),
+    Color::from_rgb8(0x94, 0x67, 0xbd),
+    Color::from_rgb8(0x8c, 0x56, 0x4b),
+    Color::from_rgb8(0xe3, 0x77, 0xc2),
+    Color::from_rgb8(0x7f, 0x7f, 0x7f),
+    Color::from_rgb8(0xbc, 0xbd, 0x22),
+    Color::from_rgb8(0x17, 0xbe, 0xcf),
+];
+
+/// The line whose color is being picked
+#[derive(Debug)]
+struct ColorPicker {
+    series: usize,
+    input: String,
+}
+
+/// Returns the smallest and largest of `values`, if there are any
+fn value_range(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |range, value| match range {
+        Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+        None => Some((value, value)),
+    })
+}
+
+/// Whether the line labelled `label` is listed in `config` as plotted
+/// against the secondary Y axis
+fn uses_secondary(config: &LineConfigState, label: Option<&String>) -> bool {
+    label.is_some_and(|label| {
+        config
+            .secondary
+            .iter()
+            .any(|secondary| secondary.trim() == label.trim())
+    })
+}
+
+/// Sets the color of each labelled line with an override
+fn apply_overrides(lines: &mut [GraphLine], overrides: &HashMap<String, Color>) {
+    lines.iter_mut().for_each(|line| {
+        if let Some(color) = line.label.as_ref().and_then(|label| overrides.get(label)) {
+            line.set_color(*color);
+        }
+    })
+}
+
+/// One of the small charts a faceted graph is split into
+#[derive(Debug)]
+struct Facet {
+    name: String,
+    /// Indices of the graph's lines shown in this facet
+    indices: Vec<usize>,
+    lines: Vec<GraphLine>,
     cache: canvas::Cache,
 }
 
+impl Facet {
+    fn new(lines: &[GraphLine], (name, indices): (String, Vec<usize>)) -> Self {
+        Self {
+            lines: Self::select(lines, &indices),
+            name,
+            indices,
+            cache: canvas::Cache::default(),
+        }
+    }
+
+    fn select(lines: &[GraphLine], indices: &[usize]) -> Vec<GraphLine> {
+        indices
+            .iter()
+            .filter_map(|index| lines.get(*index))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A line of the graph as shown in the statistics picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesOption {
+    index: usize,
+    label: String,
+}
+
+impl fmt::Display for SeriesOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
 impl LineGraphTab {
-    fn create_axis(&self) -> (Axis, Axis) {
+    /// Creates the X and Y axes, and the secondary Y axis if any line uses
+    /// one. The lines on the secondary axis are left out of the Y axis.
+    fn create_axis(&self) -> Result<(Axis, Axis, Option<Axis>), AppError> {
+        let points = || self.lines.iter().flat_map(|line| line.points.iter());
+        let y_values = |secondary: bool| {
+            self.lines
+                .iter()
+                .filter(move |line| line.secondary == secondary)
+                .flat_map(|line| line.points.iter())
+                .filter_map(|point| numeric_value(&point.y))
+        };
+
+        let secondary_label =
+            Some(self.config.secondary_label.as_str()).filter(|label| !label.is_empty());
+        let secondary_axis = if self.config.log_y {
+            log_points(y_values(true))
+        } else {
+            value_range(y_values(true)).map(|(min, max)| value_points(min, max))
+        }
+        .map(|points| {
+            Axis::new(AxisKind::BaseVertical(points), 1.0, 1.0)
+                .clean(self.view_state.clean)
+                .log(self.config.log_y)
+                .label(secondary_label)
+                .format(self.view_state.y_format)
+        });
+
+        let log_x = self
+            .config
+            .log_x
+            .then(|| log_points(points().filter_map(|point| numeric_value(&point.x))))
+            .flatten();
+        // With a secondary axis, the Y axis only spans the remaining lines
+        let y_points = match (self.config.log_y, secondary_axis.is_some()) {
+            (true, _) => log_points(y_values(false)),
+            (false, true) => value_range(y_values(false)).map(|(min, max)| value_points(min, max)),
+            (false, false) => None,
+        };
+        let log_y = self.config.log_y && y_points.is_some();
+
         let (x_axis, y_axis) = create_axis(
             &self.x_scale,
             &self.y_scale,
-            self.sequential_x,
-            self.sequential_y,
-            self.clean,
-        );
+            self.view_state.sequential_x,
+            self.view_state.sequential_y,
+            self.view_state.clean,
+            log_x,
+            y_points,
+        )?;
+
+        Ok((
+            x_axis
+                .label(self.x_label.as_ref())
+                .format(self.view_state.x_format)
+                .rotate_labels(self.view_state.rotate_labels),
+            y_axis
+                .log(log_y)
+                .label(self.y_label.as_ref())
+                .format(self.view_state.y_format),
+            secondary_axis,
+        ))
+    }
 
-        return (
-            x_axis.label(self.x_label.as_ref()),
-            y_axis.label(self.y_label.as_ref()),
-        );
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(
+        &'a self,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphLine, ModelMessage>, AppError> {
+        let (x_axis, y_axis, secondary_axis) = self.create_axis()?;
+
+        Ok(Graph::new(
+            x_axis,
+            y_axis,
+            &self.lines,
+            &self.theme,
+            cache,
+            self.view_state.graph_type,
+        )
+        .title(Some(self.title.as_str()).filter(|title| !title.is_empty()))
+        .caption(self.caption.as_ref())
+        .labels_len(
+            self.lines
+                .iter()
+                .filter(|line| line.label.is_some())
+                .count(),
+        )
+        .legend(self.view_state.legend)
+        .legend_sort(self.view_state.legend_sort)
+        .on_legend_toggle(ModelMessage::ToggleSeries)
+        .on_legend_menu(ModelMessage::PickColor)
+        .secondary_axis(secondary_axis)
+        .notation(self.view_state.notation)
+        .crosshair(self.view_state.crosshair)
+        .style(self.view_state.style))
     }
 
-    fn graph(&self) -> Element<'_, ModelMessage> {
-        let (x_axis, y_axis) = self.create_axis();
-
-        let content = Canvas::new(
-            Graph::new(
-                x_axis,
-                y_axis,
-                &self.lines,
-                &self.theme,
-                &self.cache,
-                self.graph_type,
-            )
-            .caption(self.caption.as_ref())
-            .labels_len(
-                self.lines
-                    .iter()
-                    .filter(|line| line.label.is_some())
-                    .count(),
-            )
-            .legend(self.legend),
+    /// The chart of `facet`'s lines, on the graph's scales and without a
+    /// legend, drawn into `cache`
+    fn facet_chart<'a>(
+        &'a self,
+        facet: &'a Facet,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphLine, ModelMessage>, AppError> {
+        let (x_axis, y_axis, secondary_axis) = self.create_axis()?;
+
+        Ok(Graph::new(
+            x_axis,
+            y_axis,
+            &facet.lines,
+            &self.theme,
+            cache,
+            self.view_state.graph_type,
         )
-        .width(Length::FillPortion(24))
-        .height(Length::Fill);
+        .legend(LegendPosition::None)
+        .secondary_axis(secondary_axis)
+        .notation(self.view_state.notation)
+        .crosshair(self.view_state.crosshair)
+        .style(self.view_state.style))
+    }
+
+    /// Draws the chart into an image of `size` pixels. Facets are laid out
+    /// in a grid as in the tab, each titled with its name, and without the
+    /// shared legend.
+    fn render_image(&self, size: Size<u32>) -> Result<Image, AppError> {
+        let mut offscreen = Offscreen::new(size, &self.theme)?;
+        let bounds = offscreen.bounds();
+
+        if self.facets.is_empty() {
+            let cache = canvas::Cache::new();
+            offscreen.draw(&self.chart(&cache)?, bounds);
+
+            return offscreen.finish();
+        }
 
-        content.into()
+        let columns = (self.facets.len() as f32).sqrt().ceil() as usize;
+        let rows = self.facets.len().div_ceil(columns);
+        let cell = Size::new(bounds.width / columns as f32, bounds.height / rows as f32);
+
+        for (idx, facet) in self.facets.iter().enumerate() {
+            let cache = canvas::Cache::new();
+            let chart = self
+                .facet_chart(facet, &cache)?
+                .title(Some(facet.name.as_str()));
+
+            let origin = Point::new(
+                (idx % columns) as f32 * cell.width,
+                (idx / columns) as f32 * cell.height,
+            );
+            offscreen.draw(&chart, Rectangle::new(origin, cell));
+        }
+
+        offscreen.finish()
+    }
+
+    fn graph(&self) -> Element<'_, ModelMessage> {
+        if !self.facets.is_empty() {
+            return self.facet_grid();
+        }
+
+        let content: Element<'_, ModelMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
+
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    /// Lays the facets out in a grid of charts sharing the graph's scales,
+    /// with a single legend below
+    fn facet_grid(&self) -> Element<'_, ModelMessage> {
+        let columns = (self.facets.len() as f32).sqrt().ceil() as usize;
+
+        let charts = self.facets.chunks(columns).map(|facets| {
+            let charts = facets.iter().map(|facet| {
+                let chart: Element<'_, ModelMessage> = match self.facet_chart(facet, &facet.cache) {
+                    Ok(chart) => Canvas::new(chart)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .into(),
+                    Err(err) => empty_chart(&err),
+                };
+
+                column!(text(facet.name.as_str()).size(14.0), chart)
+                    .spacing(4.0)
+                    .align_x(Alignment::Center)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into()
+            });
+
+            Row::with_children(charts)
+                .spacing(10.0)
+                .height(Length::Fill)
+                .into()
+        });
+
+        let legend = (self.view_state.legend != LegendPosition::None).then(|| {
+            let labelled = self
+                .lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.label.is_some())
+                .collect::<Vec<_>>();
+
+            let order = self
+                .view_state
+                .legend_sort
+                .order(labelled.iter().map(|(_, line)| {
+                    (
+                        line.label.as_deref(),
+                        line.legend_value(&self.view_state.graph_type),
+                    )
+                }));
+
+            let entries = order.into_iter().filter_map(|position| {
+                let (series, line) = labelled[position];
+                let label = line.legend_label()?;
+                let hidden = line.hidden;
+                let color = if hidden {
+                    muted(line.color)
+                } else {
+                    line.color
+                };
+
+                let swatch = container(Space::new(12, 12))
+                    .style(move |_| container::Style::default().background(color));
+
+                let label = text(label)
+                    .size(13.0)
+                    .style(move |theme: &Theme| text::Style {
+                        color: hidden.then(|| muted(theme.palette().text)),
+                    });
+
+                let entry = row!(swatch, label).spacing(5.0).align_y(Alignment::Center);
+
+                Some(
+                    mouse_area(entry)
+                        .on_press(ModelMessage::ToggleSeries(LegendEntry { series, entry: 0 }))
+                        .interaction(iced::mouse::Interaction::Pointer)
+                        .into(),
+                )
+            });
+
+            Row::with_children(entries).spacing(15.0).wrap()
+        });
+
+        let grid = column!(Column::with_children(charts)
+            .spacing(10.0)
+            .height(Length::Fill))
+        .push_maybe(legend)
+        .spacing(10.0);
+
+        chart_container(grid, self.view_state.aspect).into()
     }
 
     fn tools(&self) -> Element<'_, ModelMessage> {
@@ -402,7 +1091,8 @@ impl LineGraphTab {
 
         let ranged_x = {
             let check = {
-                let check = checkbox("", self.sequential_x).on_toggle(ModelMessage::SequentialX);
+                let check =
+                    checkbox("", self.view_state.sequential_x).on_toggle(ModelMessage::SequentialX);
                 let label = text("Ranged X axis");
 
                 row!(label, check).spacing(8.0).align_y(Alignment::Center)
@@ -415,7 +1105,8 @@ impl LineGraphTab {
 
         let ranged_y = {
             let check = {
-                let check = checkbox("", self.sequential_y).on_toggle(ModelMessage::SequentialY);
+                let check =
+                    checkbox("", self.view_state.sequential_y).on_toggle(ModelMessage::SequentialY);
                 let label = text("Ranged Y axis");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -428,7 +1119,7 @@ impl LineGraphTab {
 
         let clean = {
             let check = {
-                let check = checkbox("", self.clean).on_toggle(ModelMessage::Clean);
+                let check = checkbox("", self.view_state.clean).on_toggle(ModelMessage::Clean);
                 let label = text("Clean graph");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -439,6 +1130,37 @@ impl LineGraphTab {
             row!(check, tip).spacing(spacing)
         };
 
+        let crosshair = {
+            let check = {
+                let check =
+                    checkbox("", self.view_state.crosshair).on_toggle(ModelMessage::Crosshair);
+                let label = text("Crosshair");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Draws guide lines from the cursor to the axes, labelled with their values",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let rotate_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.rotate_labels)
+                    .on_toggle(ModelMessage::RotateLabels);
+                let label = text("Rotate X labels");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip =
+                tooltip("Slants the X axis labels. Labels that would overlap are always slanted");
+
+            row!(check, tip).spacing(spacing)
+        };
+
         let seed = {
             let value = self.color_seed;
             let value = format!("{value:.4}");
@@ -476,7 +1198,7 @@ impl LineGraphTab {
 
             let menu = ToolbarMenu::new(
                 GraphType::ALL,
-                self.graph_type,
+                self.view_state.graph_type,
                 ModelMessage::GraphType,
                 icons,
             )
@@ -510,7 +1232,7 @@ impl LineGraphTab {
 
             let menu = ToolbarMenu::new(
                 LegendPosition::ALL,
-                self.legend,
+                self.view_state.legend,
                 ModelMessage::Legend,
                 icons,
             )
@@ -544,6 +1266,86 @@ impl LineGraphTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let legend_sort = {
+            let label = text("Legend Order");
+
+            let pick_list = pick_list(
+                LegendSort::ALL,
+                Some(self.view_state.legend_sort),
+                ModelMessage::LegendSort,
+            )
+            .padding([2, 5]);
+
+            let tip = tooltip("Reorders the legend entries without changing how lines are drawn");
+
+            row!(label, pick_list, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(ModelMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let x_format = axis_format_row(
+            "X Axis Numbers",
+            self.view_state.x_format,
+            ModelMessage::XFormat,
+        );
+
+        let y_format = axis_format_row(
+            "Y Axis Numbers",
+            self.view_state.y_format,
+            ModelMessage::YFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the axes are written",
+            ModelMessage::Notation,
+            ModelMessage::Thresholds,
+        );
+
+        let stats = {
+            let label = text("Series Statistics");
+
+            let options = self.series_options();
+            let selected = self
+                .stats_series
+                .and_then(|index| options.iter().find(|option| option.index == index))
+                .cloned();
+
+            let pick_list = pick_list(options, selected, ModelMessage::ShowStats)
+                .placeholder("Select a line")
+                .padding([2, 5]);
+
+            let tip = tooltip("Shows summary statistics of a line's plotted values");
+
+            row!(label, pick_list, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
         let editor = {
             let font = Font::with_name(icons::NAME);
 
@@ -580,26 +1382,292 @@ impl LineGraphTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            ModelMessage::ToggleStyle,
+            ModelMessage::StyleColor,
+            ModelMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, ModelMessage::Aspect);
+        let reconfigure = reconfigure_row(ModelMessage::Reconfigure);
+        let export = export_metadata_row(ModelMessage::ExportMetadata);
+        let image = export_image_row(ModelMessage::ExportImage);
+        let pdf = export_pdf_row(ModelMessage::ExportPdf);
+
         column!(
-            header, title, x_label, y_label, caption, ranged_x, ranged_y, clean, kind, seed,
-            legend, editor
+            header,
+            title,
+            x_label,
+            y_label,
+            caption,
+            ranged_x,
+            ranged_y,
+            clean,
+            crosshair,
+            rotate_labels,
+            kind,
+            seed,
+            accent,
+            notation,
+            x_format,
+            y_format,
+            legend,
+            legend_sort,
+            style,
+            stats,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor
         )
         .spacing(25.0)
         .into()
     }
 
+    fn series_options(&self) -> Vec<SeriesOption> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| SeriesOption {
+                index,
+                label: line
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| format!("Line {}", index + 1)),
+            })
+            .collect()
+    }
+
+    fn stats_popover(&self) -> Option<Element<'_, ModelMessage>> {
+        let index = self.stats_series?;
+        let line = self.lines.get(index)?;
+
+        let values = line
+            .points
+            .iter()
+            .filter_map(|point| numeric_value(&point.y))
+            .collect::<Vec<_>>();
+        let stats = series_stats(&values);
+
+        let header = {
+            let label = line
+                .label
+                .clone()
+                .unwrap_or_else(|| format!("Line {}", index + 1));
+
+            let close = button(icons::icon(icons::CLOSE).size(14.0))
+                .padding(0)
+                .style(button::text)
+                .on_press(ModelMessage::CloseStats);
+
+            row!(text(label).size(15.0), horizontal_space(), close)
+                .spacing(10)
+                .align_y(Alignment::Center)
+        };
+
+        let body = if stats.count == 0 {
+            column!(text("No numeric values").size(13.0))
+        } else {
+            let format = |value: f64| {
+                format_number(
+                    value,
+                    self.view_state.notation,
+                    self.view_state.thresholds(),
+                )
+            };
+
+            [
+                ("Count", stats.count.to_string()),
+                ("Sum", format(stats.sum)),
+                ("Mean", format(stats.mean)),
+                ("Median", format(stats.median)),
+                ("Min", format(stats.min)),
+                ("Max", format(stats.max)),
+                ("Std. Dev", format(stats.std_dev)),
+            ]
+            .into_iter()
+            .fold(column!().spacing(4), |col, (name, value)| {
+                col.push(row!(
+                    text(name).size(13.0),
+                    horizontal_space(),
+                    text(value).size(13.0)
+                ))
+            })
+        };
+
+        let popover = column!(header, body).spacing(8);
+
+        Some(self.beside_legend(popover))
+    }
+
+    fn color_picker(&self) -> Option<Element<'_, ModelMessage>> {
+        let picker = self.color_picker.as_ref()?;
+        let line = self.lines.get(picker.series)?;
+        let label = line.label.clone()?;
+        let overridden = self.color_overrides.contains_key(&label);
+
+        let header = {
+            let close = button(icons::icon(icons::CLOSE).size(14.0))
+                .padding(0)
+                .style(button::text)
+                .on_press(ModelMessage::ClosePicker);
+
+            row!(text(label).size(15.0), horizontal_space(), close)
+                .spacing(10)
+                .align_y(Alignment::Center)
+        };
+
+        let swatches = SWATCHES.chunks(5).map(|colors| {
+            let swatches = colors.iter().map(|color| {
+                let color = *color;
+                let swatch = container(Space::new(Length::Fill, Length::Fill))
+                    .width(24)
+                    .height(24)
+                    .style(move |_| container::Style::default().background(color));
+
+                button(swatch)
+                    .padding(0)
+                    .style(button::text)
+                    .on_press(ModelMessage::SeriesColor(color))
+                    .into()
+            });
+
+            Row::with_children(swatches).spacing(8).into()
+        });
+
+        let input = {
+            let input = text_input("#rrggbb", &picker.input)
+                .size(13.0)
+                .on_input(ModelMessage::ColorInput);
+
+            let reset = button(text("Reset").size(13.0))
+                .style(button::secondary)
+                .on_press_maybe(overridden.then_some(ModelMessage::ResetColor));
+
+            row!(input, reset).spacing(8).align_y(Alignment::Center)
+        };
+
+        let picker = column!(header, Column::with_children(swatches).spacing(8), input).spacing(8);
+
+        Some(self.beside_legend(picker))
+    }
+
+    /// Wraps `popover` in a card placed beside the legend, without covering it
+    fn beside_legend<'a>(
+        &self,
+        popover: impl Into<Element<'a, ModelMessage>>,
+    ) -> Element<'a, ModelMessage> {
+        let popover = container(popover)
+            .width(200)
+            .padding([8, 10])
+            .style(|theme| {
+                <ToolTipContainerStyle as container::Catalog>::style(&ToolTipContainerStyle, theme)
+            });
+
+        let (horizontal, vertical) = self.view_state.legend.alignment();
+        let legend_height =
+            25.0 + 20.0 * self.lines.iter().filter(|l| l.label.is_some()).count() as f32;
+        let gap = 30.0;
+
+        let padding = match vertical {
+            alignment::Vertical::Top => Padding::new(gap).top(legend_height + gap),
+            alignment::Vertical::Bottom => Padding::new(gap).bottom(legend_height + gap),
+            alignment::Vertical::Center => Padding::new(gap),
+        };
+
+        let vertical = match vertical {
+            alignment::Vertical::Center => alignment::Vertical::Top,
+            vertical => vertical,
+        };
+
+        let popover = container(popover)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(padding)
+            .align_x(horizontal)
+            .align_y(vertical);
+
+        popover.into()
+    }
+
+    /// Returns the plotted data as CSV, leaving out hidden series if
+    /// `visible_only`
+    fn csv(&self, visible_only: bool) -> Option<String> {
+        let header = csv::record([
+            "series",
+            self.x_label.as_deref().unwrap_or("x"),
+            self.y_label.as_deref().unwrap_or("y"),
+        ]);
+
+        let lines = self
+            .lines
+            .iter()
+            .filter(|line| !(visible_only && line.hidden));
+
+        let rows = lines.flat_map(|line| {
+            let label = line.label.clone().unwrap_or_default();
+            line.points.iter().map(move |point| {
+                csv::record([label.clone(), point.x.to_string(), point.y.to_string()])
+            })
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
     fn redraw(&mut self) {
-        self.cache.clear()
+        self.cache.clear();
+        self.facets.iter().for_each(|facet| facet.cache.clear());
     }
 
     fn recolor(&mut self, colors: ColorEngine) {
+        let colors = colors.count(self.lines.len() as u32);
+
         self.lines
             .iter_mut()
             .zip(colors)
             .for_each(|(line, color)| line.set_color(color));
+        apply_overrides(&mut self.lines, &self.color_overrides);
 
+        self.sync_facets();
         self.redraw()
     }
+
+    /// Gives the picked line `color`, remembering it by the line's label
+    fn set_override(&mut self, color: Color) {
+        let Some(line) = self
+            .color_picker
+            .as_ref()
+            .and_then(|picker| self.lines.get_mut(picker.series))
+        else {
+            return;
+        };
+        let Some(label) = line.label.clone() else {
+            return;
+        };
+
+        line.set_color(color);
+        self.color_overrides.insert(label, color);
+        self.sync_facets();
+        self.redraw();
+    }
+
+    /// Copies changes to the graph's lines into the facets showing them
+    fn sync_facets(&mut self) {
+        let lines = &self.lines;
+        self.facets
+            .iter_mut()
+            .for_each(|facet| facet.lines = Facet::select(lines, &facet.indices));
+    }
 }
 
 impl Viewable for LineGraphTab {
@@ -613,6 +1681,11 @@ impl Viewable for LineGraphTab {
             line,
             theme,
             caption,
+            config,
+            sample,
+            shape,
+            facets,
+            radii,
         } = data;
 
         let line::LineGraph {
@@ -629,18 +1702,27 @@ impl Viewable for LineGraphTab {
             title
         };
 
-        let colors = ColorEngine::new(&theme);
+        let colors = ColorEngine::new(&theme).count(lines.len() as u32);
         let seed = colors.seed();
 
-        let lines = lines
+        let lines: Vec<GraphLine> = lines
             .into_iter()
             .zip(colors)
-            .map(|(line, color)| {
+            .enumerate()
+            .map(|(index, (line, color))| {
                 let Line { points, label } = line;
+                let secondary = uses_secondary(&config, label.as_ref());
                 GraphLine::new(points, label, color)
+                    .radius(radii.get(index).copied().flatten())
+                    .secondary(secondary)
             })
             .collect();
 
+        let facets = facets
+            .into_iter()
+            .map(|facet| Facet::new(&lines, facet))
+            .collect();
+
         Self {
             file,
             title,
@@ -651,14 +1733,19 @@ impl Viewable for LineGraphTab {
             x_label: Some(x_label),
             y_label: Some(y_label),
             caption,
-            sequential_x: false,
-            sequential_y: false,
-            clean: false,
             color_seed: seed,
             config_shown: false,
             cache: canvas::Cache::default(),
-            legend: LegendPosition::default(),
-            graph_type: GraphType::default(),
+            stats_series: None,
+            color_picker: None,
+            color_overrides: HashMap::new(),
+            view_state: ChartViewState::default(),
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+            config,
+            sample,
+            shape,
+            facets,
         }
     }
 
@@ -690,6 +1777,13 @@ impl Viewable for LineGraphTab {
         Some(self.file.clone())
     }
 
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::LineGraph(
+            self.file.clone(),
+            self.config.clone(),
+        ))
+    }
+
     fn can_save(&self) -> bool {
         false
     }
@@ -705,6 +1799,11 @@ impl Viewable for LineGraphTab {
             line,
             theme,
             caption,
+            config,
+            sample,
+            shape,
+            facets,
+            radii,
         } = data;
 
         let line::LineGraph {
@@ -715,26 +1814,87 @@ impl Viewable for LineGraphTab {
             lines,
         } = line;
 
-        let colors = ColorEngine::new(&self.theme);
+        let colors = ColorEngine::new(&self.theme)
+            .accent(self.view_state.accent)
+            .count(lines.len() as u32);
 
-        let lines = lines
+        let mut lines: Vec<GraphLine> = lines
             .into_iter()
             .zip(colors)
-            .map(|(line, color)| {
+            .enumerate()
+            .map(|(index, (line, color))| {
                 let Line { points, label } = line;
+                let secondary = uses_secondary(&config, label.as_ref());
                 GraphLine::new(points, label, color)
+                    .radius(radii.get(index).copied().flatten())
+                    .secondary(secondary)
             })
             .collect();
 
+        if file != self.file {
+            self.color_overrides.clear();
+        }
+        apply_overrides(&mut lines, &self.color_overrides);
+
+        self.facets = facets
+            .into_iter()
+            .map(|facet| Facet::new(&lines, facet))
+            .collect();
         self.title = title;
         self.file = file;
         self.lines = lines;
+        self.stats_series = self.stats_series.filter(|index| *index < self.lines.len());
+        self.color_picker = None;
         self.theme = theme;
         self.x_scale = x_scale;
         self.y_scale = y_scale;
         self.x_label = Some(x_label);
         self.y_label = Some(y_label);
         self.caption = caption;
+        self.config = config;
+        self.sample = sample;
+        self.shape = shape;
+        self.redraw();
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let shape = &self.shape;
+        let mut mapped = Vec::new();
+
+        if let LineLabelStrategy::FromCell(column) = self.config.label_strat {
+            mapped.push(("Labels", shape.column_name(column)));
+        }
+        if let Some(column) = self.config.facet {
+            mapped.push(("Facets", shape.column_name(column)));
+        }
+        if let Some(column) = self.config.size_column {
+            mapped.push(("Size", shape.column_name(column)));
+        }
+
+        Some(mapped_summary(shape, &mapped))
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = LineConfigState {
+            row_limit: RowLimit::default(),
+            ..self.config.clone()
+        };
+        let data = LineTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.redraw();
     }
 
     fn theme_changed(&mut self, theme: &Theme) {
@@ -744,12 +1904,68 @@ impl Viewable for LineGraphTab {
 
         self.theme = theme.clone();
 
-        let colors = ColorEngine::new(&self.theme);
+        let colors = ColorEngine::new(&self.theme).accent(self.view_state.accent);
         self.color_seed = colors.seed();
 
         self.recolor(colors);
     }
 
+    fn to_csv(&self) -> Option<String> {
+        self.csv(false)
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv(true)
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        Some(self.render_image(size))
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let points = || self.lines.iter().flat_map(|line| line.points.iter());
+
+        let metadata = ChartMetadata {
+            kind: "line",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(self.x_label.as_ref(), points().map(|point| &point.x)),
+            y_axis: AxisMetadata::new(self.y_label.as_ref(), points().map(|point| &point.y)),
+            series: self
+                .lines
+                .iter()
+                .map(|line| {
+                    SeriesMetadata::new(
+                        line.label.as_ref(),
+                        line.color,
+                        line.points.iter().map(|point| &point.y),
+                    )
+                })
+                .collect(),
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = state;
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent);
+            self.recolor(colors);
+        }
+
+        self.redraw();
+    }
+
     fn has_config(&self) -> bool {
         true
     }
@@ -765,38 +1981,171 @@ impl Viewable for LineGraphTab {
     fn update(&mut self, message: Self::Event) -> Option<Message> {
         match message {
             ModelMessage::OpenEditor => Some(Message::OpenEditor(Some(self.file.clone()))),
+            ModelMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.redraw();
+                None
+            }
+            ModelMessage::Reconfigure => Some(Message::Reconfigure),
+            ModelMessage::ExportMetadata => Some(Message::ChooseMetadataPath(self.file.clone())),
+            ModelMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            ModelMessage::ExportImage => Some(Message::ChooseImagePath(self.file.clone())),
             ModelMessage::ToggleConfig => {
                 self.config_shown = !self.config_shown;
                 None
             }
+            ModelMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.redraw();
+                None
+            }
+            ModelMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            ModelMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.redraw();
+                }
+                None
+            }
+            ModelMessage::ShowStats(series) => {
+                self.color_picker = None;
+                self.stats_series = Some(series.index);
+                None
+            }
+            ModelMessage::CloseStats => {
+                self.stats_series = None;
+                None
+            }
+            ModelMessage::PickColor(LegendEntry { series, .. }) => {
+                if let Some(line) = self.lines.get(series) {
+                    self.stats_series = None;
+                    self.color_picker = Some(ColorPicker {
+                        series,
+                        input: hex_color(line.color),
+                    });
+                }
+                None
+            }
+            ModelMessage::ColorInput(input) => {
+                let color = Color::parse(input.trim());
+                let Some(picker) = self.color_picker.as_mut() else {
+                    return None;
+                };
+                picker.input = input;
+
+                if let Some(color) = color {
+                    self.set_override(color);
+                }
+                None
+            }
+            ModelMessage::SeriesColor(color) => {
+                if let Some(picker) = self.color_picker.as_mut() {
+                    picker.input = hex_color(color);
+                }
+                self.set_override(color);
+                None
+            }
+            ModelMessage::ResetColor => {
+                let label = self
+                    .color_picker
+                    .as_ref()
+                    .and_then(|picker| self.lines.get(picker.series))
+                    .and_then(|line| line.label.clone());
+
+                if let Some(label) = label {
+                    self.color_overrides.remove(&label);
+
+                    let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                        .accent(self.view_state.accent);
+                    self.recolor(colors);
+                }
+
+                if let Some(picker) = self.color_picker.as_mut() {
+                    if let Some(line) = self.lines.get(picker.series) {
+                        picker.input = hex_color(line.color);
+                    }
+                }
+                None
+            }
+            ModelMessage::ClosePicker => {
+                self.color_picker = None;
+                None
+            }
+            ModelMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.redraw();
+                None
+            }
+            ModelMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.redraw();
+                None
+            }
+            ModelMessage::XFormat(format) => {
+                self.view_state.x_format = format;
+                self.redraw();
+                None
+            }
+            ModelMessage::YFormat(format) => {
+                self.view_state.y_format = format;
+                self.redraw();
+                None
+            }
             ModelMessage::Legend(legend) => {
-                self.legend = legend;
-                self.cache.clear();
+                self.view_state.legend = legend;
+                self.redraw();
+                None
+            }
+            ModelMessage::ToggleSeries(LegendEntry { series, .. }) => {
+                if let Some(line) = self.lines.get_mut(series) {
+                    line.toggle_hidden();
+                    self.sync_facets();
+                    self.redraw();
+                }
+                None
+            }
+            ModelMessage::LegendSort(sort) => {
+                self.view_state.legend_sort = sort;
+                self.redraw();
                 None
             }
             ModelMessage::GraphType(kind) => {
-                self.graph_type = kind;
-                self.cache.clear();
+                self.view_state.graph_type = kind;
+                self.redraw();
                 None
             }
             ModelMessage::TitleChanged(title) => {
                 self.title = title;
-                self.cache.clear();
+                self.redraw();
                 None
             }
             ModelMessage::Clean(clean) => {
-                self.clean = clean;
-                self.cache.clear();
+                self.view_state.clean = clean;
+                self.redraw();
+                None
+            }
+            ModelMessage::Crosshair(crosshair) => {
+                self.view_state.crosshair = crosshair;
+                None
+            }
+            ModelMessage::RotateLabels(rotate) => {
+                self.view_state.rotate_labels = rotate;
+                self.redraw();
                 None
             }
             ModelMessage::SequentialX(seq) => {
-                self.sequential_x = seq;
-                self.cache.clear();
+                self.view_state.sequential_x = seq;
+                self.redraw();
                 None
             }
             ModelMessage::SequentialY(seq) => {
-                self.sequential_y = seq;
-                self.cache.clear();
+                self.view_state.sequential_y = seq;
+                self.redraw();
                 None
             }
             ModelMessage::CaptionChange(caption) => {
@@ -805,21 +2154,38 @@ impl Viewable for LineGraphTab {
                 } else {
                     Some(caption)
                 };
-                self.cache.clear();
+                self.redraw();
                 None
             }
             ModelMessage::XLabelChanged(label) => {
                 self.x_label = if label.is_empty() { None } else { Some(label) };
-                self.cache.clear();
+                self.redraw();
                 None
             }
             ModelMessage::YLabelChanged(label) => {
                 self.y_label = if label.is_empty() { None } else { Some(label) };
-                self.cache.clear();
+                self.redraw();
                 None
             }
             ModelMessage::ApplySeed => {
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            ModelMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
                 self.recolor(colors);
                 None
             }
@@ -835,7 +2201,8 @@ impl Viewable for LineGraphTab {
                 let seed: f32 = thread_rng().gen();
                 self.color_seed = seed;
 
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
                 self.recolor(colors);
                 None
             }
@@ -855,7 +2222,12 @@ impl Viewable for LineGraphTab {
         }
         .height(Length::Shrink);
 
-        let content_area = container(self.graph())
+        let graph = match self.color_picker().or_else(|| self.stats_popover()) {
+            Some(popover) => stack!(self.graph(), popover).into(),
+            None => self.graph(),
+        };
+
+        let content_area = container(graph)
             .max_width(1450)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -883,3 +2255,68 @@ impl Viewable for LineGraphTab {
         content.map(map)
     }
 }
+
+#[test]
+fn test_facet_lines() {
+    let file = tempfile::Builder::new()
+        .suffix(".csv")
+        .tempfile()
+        .unwrap()
+        .into_temp_path();
+    std::fs::write(
+        &file,
+        "name,region,q1,q2\na,north,1,2\nb,south,3,4\n\nc,north,5,6\n",
+    )
+    .unwrap();
+
+    let facets = facet_lines(&file, 1, true, &HashSet::new()).unwrap();
+    assert_eq!(
+        facets,
+        vec![("north".into(), vec![0, 2]), ("south".into(), vec![1])]
+    );
+
+    let facets = facet_lines(&file, 1, true, &HashSet::from([0])).unwrap();
+    assert_eq!(
+        facets,
+        vec![("south".into(), vec![0]), ("north".into(), vec![1])]
+    );
+}
+
+#[test]
+fn test_scatter_radii() {
+    let sizes = ["1", "", "3", "x", "2"].map(String::from);
+    assert_eq!(
+        scatter_radii(&sizes),
+        vec![Some(3.0), None, Some(12.0), None, Some(7.5)]
+    );
+
+    assert_eq!(scatter_radii(&["4".to_string()]), vec![Some(7.5)]);
+}
+
+#[test]
+fn test_apply_overrides() {
+    let mut lines = vec![
+        GraphLine::new(vec![], Some("a".into()), Color::BLACK),
+        GraphLine::new(vec![], None, Color::BLACK),
+        GraphLine::new(vec![], Some("b".into()), Color::BLACK),
+    ];
+    let overrides = HashMap::from([("b".to_string(), Color::WHITE)]);
+
+    apply_overrides(&mut lines, &overrides);
+
+    let colors = lines.iter().map(|line| line.color).collect::<Vec<_>>();
+    assert_eq!(colors, vec![Color::BLACK, Color::BLACK, Color::WHITE]);
+}
+
+#[test]
+fn test_uses_secondary() {
+    let config = LineConfigState {
+        secondary: vec!["rain".into(), " wind ".into()],
+        ..Default::default()
+    };
+
+    assert!(uses_secondary(&config, Some(&"rain".to_string())));
+    assert!(uses_secondary(&config, Some(&"wind".to_string())));
+    assert!(!uses_secondary(&config, Some(&"heat".to_string())));
+    assert!(!uses_secondary(&config, None));
+}
@@ -0,0 +1,1225 @@
+use std::{collections::HashSet, f32::consts::PI, fmt::Debug, path::PathBuf};
+
+use iced::{
+    alignment::{self, Horizontal, Vertical},
+    font, mouse,
+    widget::{
+        button,
+        canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text},
+        checkbox, column, container, horizontal_space, row, text, text_input, Tooltip,
+    },
+    Alignment, Color, Element, Font, Length, Padding, Point, Rectangle, Renderer, Size, Theme,
+    Vector,
+};
+
+use modav_core::repr::sheet::utils::{Data, HeaderLabelStrategy};
+
+use crate::{
+    utils::{
+        coloring::ColorEngine, csv, format_data, formatting::Thresholds, icons, parse_ints,
+        sampling::RowLimit, tooltip, AppError, AxisFormat, NotationMode, Selection,
+    },
+    widgets::{modal::Modal, wizard::RadarConfigState},
+    Message, ToolTipContainerStyle,
+};
+
+use super::{
+    parse_seed,
+    shared::{
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            draw_legend_overflow, legend_row, legend_rows, AspectRatio, ChartColor, ChartStyle,
+            GridStyle, LegendPosition, LEGEND_ROW_HEIGHT,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{self, Image},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, EditorButtonStyle, StyleInputs,
+    },
+    tabs::TabLabel,
+    SessionTab, Viewable,
+};
+
+/// Fewest metrics that form a polygon
+const MIN_METRICS: usize = 3;
+/// Most rows a chart can compare before the polygons are unreadable
+const MAX_RECORDS: usize = 20;
+/// Number of rings drawn between the center and the tip of the axes
+const RINGS: usize = 5;
+
+/// A row of the source, drawn as a closed polygon over the axes
+#[derive(Debug, Clone, PartialEq)]
+struct RadarRecord {
+    label: String,
+    /// The value of each metric, if the cell held a number
+    values: Vec<Option<f64>>,
+}
+
+/// Returns the range of values each of `metrics` axes spans, from its center
+/// to its tip. Axes share the range of every value unless `per_axis`. Ranges
+/// start at zero unless a value is negative.
+fn axis_ranges(records: &[RadarRecord], metrics: usize, per_axis: bool) -> Vec<(f64, f64)> {
+    let range = |values: &mut dyn Iterator<Item = f64>| {
+        let (low, high) = values.fold((0.0_f64, f64::NEG_INFINITY), |(low, high), value| {
+            (low.min(value), high.max(value))
+        });
+
+        if high > low {
+            (low, high)
+        } else {
+            (low, low + 1.0)
+        }
+    };
+
+    let metric_values = |metric: usize| {
+        records
+            .iter()
+            .filter_map(move |record| record.values.get(metric).copied().flatten())
+    };
+
+    if per_axis {
+        (0..metrics)
+            .map(|metric| range(&mut metric_values(metric)))
+            .collect()
+    } else {
+        let shared = range(&mut (0..metrics).flat_map(metric_values));
+        vec![shared; metrics]
+    }
+}
+
+/// Returns where `value` lies in `range`, from 0 at the center to 1 at the
+/// tip of its axis
+fn fraction(value: f64, (low, high): (f64, f64)) -> f32 {
+    ((value - low) / (high - low)).clamp(0.0, 1.0) as f32
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarTabData {
+    file: PathBuf,
+    title: String,
+    /// Names of the metrics, one per axis
+    metrics: Vec<String>,
+    records: Vec<RadarRecord>,
+    theme: Theme,
+    caption: Option<String>,
+    config: RadarConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl RadarTabData {
+    pub fn new(file: PathBuf, config: RadarConfigState) -> Result<Self, AppError> {
+        let RadarConfigState {
+            title,
+            label_col,
+            metric_cols_str,
+            caption,
+            trim,
+            flexible,
+            delimiter,
+            header_labels,
+            row_limit,
+            filters,
+            ..
+        } = config.clone();
+
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
+
+        // Without chosen metrics, every numeric column but the labels is one
+        let metric_cols = if metric_cols_str.trim().is_empty() {
+            csv::column_types(&source, csv::Delimiter::Comma, has_header)?
+                .into_iter()
+                .enumerate()
+                .filter(|(column, (_, kind))| *column != label_col && kind.is_numeric())
+                .map(|(column, _)| column)
+                .collect::<Vec<usize>>()
+        } else {
+            Selection::to_vec(
+                parse_ints(&metric_cols_str),
+                shape.columns.len().saturating_sub(1),
+            )
+            .into_iter()
+            .filter(|column| *column < shape.columns.len())
+            .collect::<Vec<usize>>()
+        };
+
+        if metric_cols.len() < MIN_METRICS {
+            return Err(AppError::Simple(format!(
+                "A radar chart needs at least {MIN_METRICS} metric columns"
+            )));
+        }
+
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut HashSet::new())?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let content =
+            std::fs::read_to_string(&source).map_err(|err| AppError::FileLoading(err.kind()))?;
+
+        let mut records = csv::parse(&content)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+        if has_header {
+            records.next();
+        }
+
+        let records = records
+            .enumerate()
+            .map(|(row, record)| {
+                let label = record.get(label_col).cloned().unwrap_or_default();
+                let label = if trim {
+                    label.trim().to_string()
+                } else {
+                    label
+                };
+                let label = if label.trim().is_empty() {
+                    format!("Row {}", row + 1)
+                } else {
+                    label
+                };
+
+                let values = metric_cols
+                    .iter()
+                    .map(|column| {
+                        record
+                            .get(*column)
+                            .and_then(|cell| cell.trim().parse::<f64>().ok())
+                            .filter(|value| value.is_finite())
+                    })
+                    .collect();
+
+                RadarRecord { label, values }
+            })
+            .filter(|record| record.values.iter().any(Option::is_some))
+            .collect::<Vec<RadarRecord>>();
+
+        if records.is_empty() {
+            return Err(AppError::Simple(String::from(
+                "The metric columns have no numbers to plot",
+            )));
+        }
+
+        if records.len() > MAX_RECORDS {
+            return Err(AppError::Simple(format!(
+                "A radar chart can compare at most {MAX_RECORDS} rows, but {} were found. Filter or limit the rows first",
+                records.len()
+            )));
+        }
+
+        let metrics = metric_cols
+            .iter()
+            .map(|column| shape.column_name(*column))
+            .collect();
+
+        Ok(Self {
+            file,
+            title,
+            metrics,
+            records,
+            caption,
+            config,
+            sample,
+            shape,
+            theme: Theme::default(),
+        })
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RadarMessage {
+    OpenEditor,
+    ToggleConfig,
+    TitleChanged(String),
+    CaptionChange(String),
+    /// Scales each axis to its own range
+    PerAxis(bool),
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    ValueFormat(AxisFormat),
+    ChangeSeed(String),
+    AccentChanged(String),
+    ApplySeed,
+    RandomSeed,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
+}
+
+#[derive(Debug)]
+pub struct RadarTab {
+    file: PathBuf,
+    title: String,
+    metrics: Vec<String>,
+    records: Vec<RadarRecord>,
+    /// Color of each record's polygon
+    colors: Vec<Color>,
+    caption: Option<String>,
+    config_shown: bool,
+    color_seed: f32,
+    cache: canvas::Cache,
+    theme: Theme,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
+    config: RadarConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+}
+
+impl RadarTab {
+    fn tools(&self) -> Element<'_, RadarMessage> {
+        let spacing = 10.0;
+
+        let header = {
+            let header = text("Model Config").size(17.0);
+
+            row!(horizontal_space(), header, horizontal_space())
+                .padding([2, 0])
+                .align_y(Alignment::Center)
+        };
+
+        let title =
+            text_input("Graph Title", self.title.as_str()).on_input(RadarMessage::TitleChanged);
+
+        let caption = text_input(
+            "Graph Caption",
+            self.caption
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or_default(),
+        )
+        .on_input(RadarMessage::CaptionChange);
+
+        let per_axis = {
+            let check = {
+                let check = checkbox("", self.config.per_axis).on_toggle(RadarMessage::PerAxis);
+                let label = text("Scale axes separately");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Stretches every axis over its own range of values instead of the range of all of them",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let seed = {
+            let value = self.color_seed;
+            let value = format!("{value:.4}");
+
+            let label = text("Coloring Seed");
+
+            let tip = tooltip("Sets the seed used to generate graph colors");
+
+            let btn = button(
+                icons::icon(icons::REDO)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .padding([4, 8])
+            .on_press(RadarMessage::ApplySeed);
+
+            let rand = button(icons::icon(icons::SHUFFLE).align_y(alignment::Vertical::Center))
+                .padding([4, 8])
+                .style(button::secondary)
+                .on_press(RadarMessage::RandomSeed);
+
+            let input = text_input("", &value)
+                .on_input(RadarMessage::ChangeSeed)
+                .padding([2, 5])
+                .width(67.0);
+
+            row!(label, input, btn, rand, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(RadarMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let value_format = axis_format_row(
+            "Ring Numbers",
+            self.view_state.y_format,
+            RadarMessage::ValueFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the rings are written",
+            RadarMessage::Notation,
+            RadarMessage::Thresholds,
+        );
+
+        let editor = {
+            let font = Font::with_name(icons::NAME);
+
+            let btn = button(
+                text(icons::EDITOR)
+                    .font(font)
+                    .width(16.0)
+                    .align_y(alignment::Vertical::Center)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .on_press(RadarMessage::OpenEditor)
+            .style(|theme, status| {
+                <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+            })
+            .padding([4, 4]);
+
+            let tooltip = container(text("Open in Editor").size(12.0))
+                .max_width(200.0)
+                .padding([6, 8])
+                .style(|theme| {
+                    <ToolTipContainerStyle as container::Catalog>::style(
+                        &ToolTipContainerStyle,
+                        theme,
+                    )
+                })
+                .height(Length::Shrink);
+
+            let menu = Tooltip::new(btn, tooltip, iced::widget::tooltip::Position::Bottom)
+                .gap(2.0)
+                .snap_within_viewport(true);
+
+            let text = text("Open in Editor");
+
+            row!(text, menu).spacing(spacing).align_y(Alignment::Center)
+        };
+
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            RadarMessage::ToggleStyle,
+            RadarMessage::StyleColor,
+            RadarMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, RadarMessage::Aspect);
+        let reconfigure = reconfigure_row(RadarMessage::Reconfigure);
+        let export = export_metadata_row(RadarMessage::ExportMetadata);
+        let image = export_image_row(RadarMessage::ExportImage);
+        let pdf = export_pdf_row(RadarMessage::ExportPdf);
+
+        column!(
+            header,
+            title,
+            caption,
+            per_axis,
+            seed,
+            accent,
+            notation,
+            value_format,
+            style,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor,
+        )
+        .spacing(25.0)
+        .into()
+    }
+
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(&'a self, cache: &'a canvas::Cache) -> Result<RadarChart<'a>, AppError> {
+        if self.records.is_empty() {
+            return Err(AppError::Simple(String::from("There is no data to plot")));
+        }
+
+        Ok(RadarChart {
+            metrics: &self.metrics,
+            records: &self.records,
+            colors: &self.colors,
+            ranges: axis_ranges(&self.records, self.metrics.len(), self.config.per_axis),
+            per_axis: self.config.per_axis,
+            theme: &self.theme,
+            cache,
+            title: Some(self.title.as_str()).filter(|title| !title.is_empty()),
+            caption: self.caption.as_ref(),
+            legend: self.view_state.legend,
+            format: AxisFormat {
+                notation: self.view_state.notation,
+                ..self.view_state.y_format
+            },
+            style: self.view_state.style,
+        })
+    }
+
+    fn graph(&self) -> Element<'_, RadarMessage> {
+        let content: Element<'_, RadarMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
+
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    fn csv(&self) -> Option<String> {
+        let header =
+            csv::record(std::iter::once(String::from("label")).chain(self.metrics.iter().cloned()));
+
+        let rows = self.records.iter().map(|record| {
+            csv::record(
+                std::iter::once(record.label.clone()).chain(
+                    record
+                        .values
+                        .iter()
+                        .map(|value| value.map(|value| value.to_string()).unwrap_or_default()),
+                ),
+            )
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn recolor(&mut self, colors: ColorEngine) {
+        self.colors = colors.count(self.records.len() as u32).collect();
+        self.cache.clear()
+    }
+}
+
+impl Viewable for RadarTab {
+    type Event = RadarMessage;
+    type Data = RadarTabData;
+
+    fn new(data: Self::Data) -> Self {
+        let RadarTabData {
+            file,
+            title,
+            metrics,
+            records,
+            theme,
+            caption,
+            config,
+            sample,
+            shape,
+        } = data;
+
+        let colors = ColorEngine::new(&theme).count(records.len() as u32);
+        let seed = colors.seed();
+        let colors = colors.collect();
+
+        Self {
+            file,
+            title,
+            metrics,
+            records,
+            colors,
+            caption,
+            theme,
+            color_seed: seed,
+            config_shown: false,
+            cache: canvas::Cache::default(),
+            view_state: ChartViewState::default(),
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+            config,
+            sample,
+            shape,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn label(&self) -> TabLabel {
+        let file_name = self
+            .file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("New File");
+
+        let font = Font::with_name(icons::NAME);
+
+        TabLabel::new(icons::CHART, format!("{} - {}", self.title, file_name)).icon_font(font)
+    }
+
+    fn content(&self) -> Option<String> {
+        None
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.file.clone())
+    }
+
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::Radar(self.file.clone(), self.config.clone()))
+    }
+
+    fn can_save(&self) -> bool {
+        false
+    }
+
+    fn modal_msg(&self) -> String {
+        "Seeing this means a logic error occurred".into()
+    }
+
+    fn refresh(&mut self, data: Self::Data) {
+        let new = <Self as Viewable>::new(data);
+        let view_state = self.view_state.clone();
+        let style_shown = self.style_shown;
+
+        *self = new;
+        self.style_shown = style_shown;
+        self.restore_view_state(view_state);
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let mapped = [
+            ("Labels", self.shape.column_name(self.config.label_col)),
+            ("Metrics", self.metrics.join(", ")),
+        ];
+
+        Some(mapped_summary(&self.shape, &mapped))
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = RadarConfigState {
+            row_limit: RowLimit::default(),
+            ..self.config.clone()
+        };
+        let data = RadarTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.cache.clear();
+    }
+
+    fn theme_changed(&mut self, theme: &Theme) {
+        if &self.theme == theme {
+            return;
+        }
+        self.theme = theme.clone();
+
+        let colors = ColorEngine::new(&self.theme).accent(self.view_state.accent);
+        self.color_seed = colors.seed();
+
+        self.recolor(colors);
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        self.csv()
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv()
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        let cache = canvas::Cache::new();
+
+        Some(
+            self.chart(&cache)
+                .and_then(|chart| offscreen::render::<RadarMessage, _>(&chart, size, &self.theme)),
+        )
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let metrics = self
+            .metrics
+            .iter()
+            .cloned()
+            .map(Data::Text)
+            .collect::<Vec<Data>>();
+        let values = self
+            .records
+            .iter()
+            .flat_map(|record| record.values.iter().flatten())
+            .map(|value| Data::Float(*value as f32))
+            .collect::<Vec<Data>>();
+
+        let metadata = ChartMetadata {
+            kind: "radar",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(None, metrics.iter()),
+            y_axis: AxisMetadata::new(None, values.iter()),
+            series: self
+                .records
+                .iter()
+                .zip(&self.colors)
+                .map(|(record, color)| {
+                    SeriesMetadata::with_values(
+                        Some(&record.label),
+                        *color,
+                        record.values.iter().flatten().copied().collect(),
+                    )
+                })
+                .collect(),
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = state;
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent);
+            self.recolor(colors);
+        }
+
+        self.cache.clear();
+    }
+
+    fn has_config(&self) -> bool {
+        true
+    }
+
+    fn config<'a, Message, F>(&'a self, map: F) -> Option<Element<'a, Message, Theme, Renderer>>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        Some(self.tools().map(map))
+    }
+
+    fn update(&mut self, message: Self::Event) -> Option<Message> {
+        match message {
+            RadarMessage::OpenEditor => Some(Message::OpenEditor(Some(self.file.clone()))),
+            RadarMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::Reconfigure => Some(Message::Reconfigure),
+            RadarMessage::ExportMetadata => Some(Message::ChooseMetadataPath(self.file.clone())),
+            RadarMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            RadarMessage::ExportImage => Some(Message::ChooseImagePath(self.file.clone())),
+            RadarMessage::ToggleConfig => {
+                self.config_shown = !self.config_shown;
+                None
+            }
+            RadarMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            RadarMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.cache.clear();
+                }
+                None
+            }
+            RadarMessage::TitleChanged(title) => {
+                self.title = title;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::CaptionChange(caption) => {
+                self.caption = if caption.is_empty() {
+                    None
+                } else {
+                    Some(caption)
+                };
+                self.cache.clear();
+                None
+            }
+            RadarMessage::PerAxis(per_axis) => {
+                self.config.per_axis = per_axis;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.cache.clear();
+                None
+            }
+            RadarMessage::ValueFormat(format) => {
+                self.view_state.y_format = format;
+                self.cache.clear();
+                None
+            }
+            RadarMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            RadarMessage::ChangeSeed(seed) => {
+                if let Some(seed) = parse_seed(seed, self.color_seed == 0.0) {
+                    self.color_seed = seed;
+                }
+
+                None
+            }
+            RadarMessage::ApplySeed => {
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+            RadarMessage::RandomSeed => {
+                use rand::{thread_rng, Rng};
+                let seed: f32 = thread_rng().gen();
+                self.color_seed = seed;
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent);
+                self.recolor(colors);
+                None
+            }
+        }
+    }
+
+    fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, Theme, Renderer>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        let title = {
+            let text = text(format!("{} - Radar Chart", self.title));
+            row!(horizontal_space(), text, horizontal_space())
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+        }
+        .height(Length::Shrink);
+
+        let content_area = container(self.graph())
+            .max_width(1450)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|theme| {
+                <ContentAreaContainer as container::Catalog>::style(&ContentAreaContainer, theme)
+            });
+
+        let content = column!(title, content_area)
+            .align_x(Alignment::Center)
+            .spacing(20)
+            .height(Length::Fill)
+            .width(Length::Fill);
+
+        let content: Element<Self::Event, Theme, Renderer> = if self.config_shown {
+            Modal::new(content, self.tools())
+                .on_blur(RadarMessage::ToggleConfig)
+                .into()
+        } else {
+            content.into()
+        };
+
+        let content: Element<Self::Event, Theme, Renderer> = container(content)
+            .padding(Padding {
+                top: 10.,
+                right: 30.,
+                bottom: 30.,
+                left: 15.,
+            })
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+
+        content.map(map)
+    }
+}
+
+/// Draws records as polygons over one radial axis per metric
+struct RadarChart<'a> {
+    metrics: &'a [String],
+    records: &'a [RadarRecord],
+    colors: &'a [Color],
+    /// Range of values each axis spans, from its center to its tip
+    ranges: Vec<(f64, f64)>,
+    per_axis: bool,
+    theme: &'a Theme,
+    cache: &'a canvas::Cache,
+    title: Option<&'a str>,
+    caption: Option<&'a String>,
+    legend: LegendPosition,
+    /// Format of the numbers labelling the rings
+    format: AxisFormat,
+    style: ChartStyle,
+}
+
+impl<'a> RadarChart<'a> {
+    /// Room left above the plot for the title
+    const TITLE_SPACE: f32 = 36.0;
+    /// Room left around the plot for the metric names
+    const LABEL_SPACE: f32 = 48.0;
+
+    /// Returns where `fraction` of the way along `axis` lies, for a plot of
+    /// `radius` around `center`. The first axis points up and the rest
+    /// follow clockwise.
+    fn vertex(&self, center: Point, radius: f32, axis: usize, fraction: f32) -> Point {
+        let angle = -0.5 * PI + 2.0 * PI * axis as f32 / self.metrics.len() as f32;
+
+        center + Vector::new(angle.cos(), angle.sin()) * (radius * fraction)
+    }
+
+    /// Returns the polygon through `fractions` of each axis
+    fn polygon(&self, center: Point, radius: f32, fractions: impl Iterator<Item = f32>) -> Path {
+        Path::new(|builder| {
+            for (axis, fraction) in fractions.enumerate() {
+                let vertex = self.vertex(center, radius, axis, fraction);
+
+                if axis == 0 {
+                    builder.move_to(vertex);
+                } else {
+                    builder.line_to(vertex);
+                }
+            }
+            builder.close();
+        })
+    }
+
+    fn draw_plot(&self, frame: &mut Frame, center: Point, radius: f32) {
+        let axis_color = self.style.color(ChartColor::Axis, self.theme);
+        let tick_color = self.style.color(ChartColor::TickText, self.theme);
+        let label_color = self.style.color(ChartColor::AxisLabel, self.theme);
+
+        let grid = Stroke {
+            line_dash: self.style.grid.line_dash(),
+            ..Stroke::default().with_width(1.0).with_color(Color {
+                a: 0.35,
+                ..axis_color
+            })
+        };
+
+        for ring in 1..=RINGS {
+            let fraction = ring as f32 / RINGS as f32;
+            let ring_path = self.polygon(
+                center,
+                radius,
+                std::iter::repeat(fraction).take(self.metrics.len()),
+            );
+            frame.stroke(&ring_path, grid);
+
+            // Rings only mark common values when every axis shares a range
+            if !self.per_axis {
+                let (low, high) = self.ranges.first().copied().unwrap_or((0.0, 1.0));
+                let value = low + (high - low) * f64::from(fraction);
+
+                frame.fill_text(Text {
+                    content: format_data(&Data::Float(value as f32), &self.format),
+                    position: self.vertex(center, radius, 0, fraction) + Vector::new(4.0, 0.0),
+                    vertical_alignment: Vertical::Center,
+                    size: 12.0.into(),
+                    color: tick_color,
+                    ..Default::default()
+                });
+            }
+        }
+
+        for (axis, metric) in self.metrics.iter().enumerate() {
+            let tip = self.vertex(center, radius, axis, 1.0);
+            frame.stroke(
+                &Path::line(center, tip),
+                Stroke::default().with_width(1.0).with_color(axis_color),
+            );
+
+            let direction = tip - center;
+            let (cos, sin) = (direction.x / radius, direction.y / radius);
+
+            let horizontal_alignment = if cos > 0.3 {
+                Horizontal::Left
+            } else if cos < -0.3 {
+                Horizontal::Right
+            } else {
+                Horizontal::Center
+            };
+            let vertical_alignment = if sin > 0.3 {
+                Vertical::Top
+            } else if sin < -0.3 {
+                Vertical::Bottom
+            } else {
+                Vertical::Center
+            };
+
+            // Axes with their own range show it beside their name
+            let content = match self.ranges.get(axis) {
+                Some((low, high)) if self.per_axis => format!(
+                    "{metric} ({}–{})",
+                    format_data(&Data::Float(*low as f32), &self.format),
+                    format_data(&Data::Float(*high as f32), &self.format)
+                ),
+                _ => metric.clone(),
+            };
+
+            frame.fill_text(Text {
+                content,
+                position: self.vertex(center, radius + 8.0, axis, 1.0),
+                horizontal_alignment,
+                vertical_alignment,
+                size: 14.0.into(),
+                color: label_color,
+                ..Default::default()
+            });
+        }
+
+        for (record, color) in self.records.iter().zip(self.colors) {
+            // Missing values are drawn at the center so the polygon stays
+            // closed
+            let fractions = record
+                .values
+                .iter()
+                .zip(&self.ranges)
+                .map(|(value, range)| value.map_or(0.0, |value| fraction(value, *range)))
+                .collect::<Vec<f32>>();
+
+            let shape = self.polygon(center, radius, fractions.iter().copied());
+            frame.fill(&shape, Color { a: 0.15, ..*color });
+            frame.stroke(&shape, Stroke::default().with_width(2.0).with_color(*color));
+
+            for (axis, fraction) in fractions.iter().enumerate() {
+                if record.values[axis].is_some() {
+                    let point = self.vertex(center, radius, axis, *fraction);
+                    frame.fill(&Path::circle(point, 3.0), *color);
+                }
+            }
+        }
+    }
+
+    fn draw_annotations(&self, frame: &mut Frame) {
+        let size = frame.size();
+
+        if let Some(title) = self.title {
+            frame.fill_text(Text {
+                content: title.to_string(),
+                position: Point::new(0.5 * size.width, 0.5 * Self::TITLE_SPACE),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                size: 20.0.into(),
+                color: self.style.color(ChartColor::Title, self.theme),
+                font: font::Font {
+                    weight: font::Weight::Bold,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+
+        if let Some(caption) = self.caption {
+            frame.fill_text(Text {
+                content: caption.clone(),
+                position: Point::new(0.8 * size.width, size.height - 14.0),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                size: 14.0.into(),
+                color: self.style.color(ChartColor::Caption, self.theme),
+                font: font::Font {
+                    style: font::Style::Italic,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+    }
+
+    fn draw_legend(&self, frame: &mut Frame, bounds: Rectangle) {
+        if self.legend == LegendPosition::None {
+            return;
+        }
+
+        let palette = self.theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let padding = 5.0;
+        let header_size = 16.0;
+        let (listed, more) = legend_rows(self.records.len());
+        let rows = listed + usize::from(more > 0);
+
+        let size = Size::new(
+            f32::min(bounds.width * 0.15, 175.0),
+            25.0 + LEGEND_ROW_HEIGHT * rows as f32,
+        );
+        let position = self.legend.position(bounds, size);
+
+        let legend = Path::rectangle(position, size);
+        frame.fill(&legend, palette.background.weak.color);
+        frame.stroke(&legend, Stroke::default().with_width(1.5));
+
+        frame.fill_text(Text {
+            content: "Legend".into(),
+            position: position + Vector::new(padding, 2.5),
+            size: header_size.into(),
+            color: text_color,
+            ..Default::default()
+        });
+
+        let entries = Rectangle::new(
+            position + Vector::new(padding, 2.5 + 1.5 * header_size),
+            Size::new(size.width - 2.0 * padding, LEGEND_ROW_HEIGHT * rows as f32),
+        );
+
+        for (idx, (record, color)) in self
+            .records
+            .iter()
+            .zip(self.colors)
+            .take(listed)
+            .enumerate()
+        {
+            let y = legend_row(entries, idx).y;
+            let swatch = Size::new(12.0, 12.0);
+
+            frame.fill_rectangle(Point::new(entries.x, y), swatch, *color);
+            frame.fill_text(Text {
+                content: record.label.clone(),
+                position: Point::new(entries.x + padding + swatch.width, y + 0.5 * swatch.height),
+                size: 12.0.into(),
+                color: text_color,
+                vertical_alignment: Vertical::Center,
+                ..Default::default()
+            });
+        }
+
+        draw_legend_overflow(frame, entries, listed, more, text_color);
+    }
+}
+
+impl<'a, Message> canvas::Program<Message> for RadarChart<'a> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let content = self.cache.draw(renderer, bounds.size(), |frame| {
+            let size = frame.size();
+            let top = if self.title.is_some() {
+                Self::TITLE_SPACE
+            } else {
+                0.0
+            };
+            let bottom = if self.caption.is_some() { 28.0 } else { 0.0 };
+
+            let height = size.height - top - bottom;
+            let center = Point::new(0.5 * size.width, top + 0.5 * height);
+            let radius = 0.5 * f32::min(size.width, height) - Self::LABEL_SPACE;
+
+            if radius > 0.0 {
+                self.draw_plot(frame, center, radius);
+            }
+
+            self.draw_annotations(frame);
+            self.draw_legend(frame, Rectangle::with_size(size));
+        });
+
+        vec![content]
+    }
+}
+
+#[test]
+fn test_axis_ranges() {
+    let records = vec![
+        RadarRecord {
+            label: "a".into(),
+            values: vec![Some(2.0), Some(10.0), None],
+        },
+        RadarRecord {
+            label: "b".into(),
+            values: vec![Some(4.0), Some(-5.0), Some(3.0)],
+        },
+    ];
+
+    assert_eq!(axis_ranges(&records, 3, false), vec![(-5.0, 10.0); 3]);
+    assert_eq!(
+        axis_ranges(&records, 3, true),
+        vec![(0.0, 4.0), (-5.0, 10.0), (0.0, 3.0)]
+    );
+    assert_eq!(axis_ranges(&[], 2, true), vec![(0.0, 1.0); 2]);
+
+    assert_eq!(fraction(2.0, (0.0, 4.0)), 0.5);
+    assert_eq!(fraction(-5.0, (-5.0, 10.0)), 0.0);
+    assert_eq!(fraction(12.0, (0.0, 4.0)), 1.0);
+}
@@ -1,16 +1,46 @@
 use iced::{
     alignment,
-    widget::{button, text, Button},
-    Font,
+    widget::{
+        button, checkbox, column, container, pick_list, row, text, text_input, Button, Column,
+        Container, Row,
+    },
+    Alignment, Element, Font, Length,
 };
 
-use crate::utils::icons;
+use crate::utils::{
+    csv::Shape, formatting::Thresholds, icons, tooltip, AppError, AxisFormat, NotationMode,
+};
+
+use crate::widgets::aspect::AspectFrame;
+
+use graph::{hex_color, AspectRatio, ChartColor, ChartStyle, GridStyle};
 
 pub mod graph;
+pub mod metadata;
+pub mod offscreen;
+pub mod state;
 pub mod styles;
 
 pub use styles::*;
 
+/// Describes the size of a chart's source followed by the columns it maps,
+/// e.g. `1,240 rows × 6 cols · X: region, Y: sales`
+pub fn mapped_summary(shape: &Shape, mapped: &[(&str, String)]) -> String {
+    let summary = shape.summary();
+
+    if mapped.is_empty() {
+        return summary;
+    }
+
+    let mapped = mapped
+        .iter()
+        .map(|(role, columns)| format!("{role}: {columns}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{summary} · {mapped}")
+}
+
 pub fn tools_button<'a, Message>() -> Button<'a, Message> {
     button(
         text(icons::TOOLS)
@@ -22,3 +52,342 @@ pub fn tools_button<'a, Message>() -> Button<'a, Message> {
     .padding([4, 8])
     .style(|theme, status| <ToolsButton as button::Catalog>::style(&ToolsButton, theme, status))
 }
+
+/// Toolbar row for exporting the chart description as JSON
+pub fn export_metadata_row<'a, Message>(on_press: Message) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let btn = button(
+        text(icons::FILE)
+            .font(Font::with_name(icons::NAME))
+            .width(16.0)
+            .align_y(alignment::Vertical::Center)
+            .align_x(alignment::Horizontal::Center),
+    )
+    .on_press(on_press)
+    .style(|theme, status| {
+        <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+    })
+    .padding([4, 4]);
+
+    let tip = tooltip(
+        "Saves the chart's type, axes, series colors and statistics as JSON, without the image",
+    );
+
+    row!(text("Export Metadata"), btn, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Toolbar row for reopening the wizard with the chart's configuration
+pub fn reconfigure_row<'a, Message>(on_press: Message) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let btn = button(
+        text(icons::CONFIG)
+            .font(Font::with_name(icons::NAME))
+            .width(16.0)
+            .align_y(alignment::Vertical::Center)
+            .align_x(alignment::Horizontal::Center),
+    )
+    .on_press(on_press)
+    .style(|theme, status| {
+        <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+    })
+    .padding([4, 4]);
+
+    let tip = tooltip("Changes the columns and settings the chart was made with");
+
+    row!(text("Reconfigure"), btn, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Wraps the chart drawn by a tab, centered at `aspect`
+pub fn chart_container<'a, Message>(
+    chart: impl Into<Element<'a, Message>>,
+    aspect: AspectRatio,
+) -> Container<'a, Message>
+where
+    Message: 'a,
+{
+    let chart = container(chart).width(Length::Fill).height(Length::Fill);
+
+    container(AspectFrame::new(chart, aspect.ratio()))
+        .width(Length::FillPortion(24))
+        .height(Length::Fill)
+}
+
+/// Toolbar row picking the width to height ratio the chart is drawn at
+pub fn aspect_row<'a, Message>(
+    aspect: AspectRatio,
+    on_select: impl Fn(AspectRatio) -> Message + 'a,
+) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let pick_list = pick_list(AspectRatio::ALL, Some(aspect), on_select).padding([2, 5]);
+
+    let tip = tooltip("Keeps the chart at a fixed shape, centered in the tab, instead of stretching it to fill the tab");
+
+    row!(text("Aspect Ratio"), pick_list, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Shown in place of a chart whose axes could not be made from its data
+pub fn empty_chart<'a, Message>(error: &AppError) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    let content = column!(
+        text("Nothing to chart").size(18.0),
+        text(error.message()).size(13.0)
+    )
+    .spacing(6.0)
+    .align_x(Alignment::Center);
+
+    container(content).center(Length::Fill).into()
+}
+
+/// Toolbar row for exporting the chart as a PNG image
+pub fn export_image_row<'a, Message>(on_press: Message) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let btn = button(
+        text(icons::CHART)
+            .font(Font::with_name(icons::NAME))
+            .width(16.0)
+            .align_y(alignment::Vertical::Center)
+            .align_x(alignment::Horizontal::Center),
+    )
+    .on_press(on_press)
+    .style(|theme, status| {
+        <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+    })
+    .padding([4, 4]);
+
+    let tip = tooltip("Saves the chart as a PNG image. The image size is set in the settings");
+
+    row!(text("Export Image"), btn, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Toolbar row for exporting the chart on a single page PDF
+pub fn export_pdf_row<'a, Message>(on_press: Message) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let btn = button(
+        text(icons::FILE)
+            .font(Font::with_name(icons::NAME))
+            .width(16.0)
+            .align_y(alignment::Vertical::Center)
+            .align_x(alignment::Horizontal::Center),
+    )
+    .on_press(on_press)
+    .style(|theme, status| {
+        <EditorButtonStyle as button::Catalog>::style(&EditorButtonStyle, theme, status)
+    })
+    .padding([4, 4]);
+
+    let tip = tooltip(
+        "Saves the chart on a single PDF page. The page size and DPI are set in the settings",
+    );
+
+    row!(text("Export PDF"), btn, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Toolbar row setting the decimal places and digit grouping of an axis'
+/// numbers
+pub fn axis_format_row<'a, Message>(
+    label: &'a str,
+    format: AxisFormat,
+    on_change: impl Fn(AxisFormat) -> Message + Clone + 'a,
+) -> Row<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let value = format
+        .decimals
+        .map(|decimals| decimals.to_string())
+        .unwrap_or_default();
+
+    let decimals = text_input("Auto", &value)
+        .on_input({
+            let on_change = on_change.clone();
+            move |input| {
+                let input = input.trim();
+                let decimals = if input.is_empty() {
+                    None
+                } else {
+                    input
+                        .parse()
+                        .ok()
+                        .map(|decimals: usize| decimals.min(AxisFormat::MAX_DECIMALS))
+                        .or(format.decimals)
+                };
+
+                on_change(AxisFormat { decimals, ..format })
+            }
+        })
+        .padding([2, 5])
+        .width(50.0);
+
+    let thousands = checkbox("1,000", format.thousands).on_toggle(move |thousands| {
+        on_change(AxisFormat {
+            thousands,
+            ..format
+        })
+    });
+
+    let tip = tooltip(
+        "Decimal places of the axis' numbers, and whether their digits are grouped in thousands",
+    );
+
+    row!(text(label), decimals, thousands, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center)
+}
+
+/// Toolbar rows for picking how numbers are written and, in
+/// [`NotationMode::Auto`], the magnitudes at which plain numbers give way to
+/// scientific and SI notation. `tip` describes where the numbers are.
+pub fn notation_row<'a, Message>(
+    notation: NotationMode,
+    thresholds: Thresholds,
+    tip: &'a str,
+    on_notation: impl Fn(NotationMode) -> Message + 'a,
+    on_thresholds: impl Fn(Thresholds) -> Message + Clone + 'a,
+) -> Column<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let pick = pick_list(NotationMode::ALL, Some(notation), on_notation).padding([2, 5]);
+
+    let notation_row = row!(text("Number Format"), pick, tooltip(tip))
+        .spacing(10.0)
+        .align_y(Alignment::Center);
+
+    if notation != NotationMode::Auto {
+        return column!(notation_row);
+    }
+
+    let small = pick_list(Thresholds::SMALL, Some(thresholds.small), {
+        let on_thresholds = on_thresholds.clone();
+        move |small| {
+            on_thresholds(Thresholds {
+                small,
+                ..thresholds
+            })
+        }
+    })
+    .padding([2, 5]);
+
+    let large = pick_list(Thresholds::LARGE, Some(thresholds.large), move |large| {
+        on_thresholds(Thresholds {
+            large,
+            ..thresholds
+        })
+    })
+    .padding([2, 5]);
+
+    let tip = tooltip("Numbers smaller than the first are written in scientific notation, and numbers from the second up with SI prefixes");
+
+    let thresholds_row = row!(text("Plain From"), small, text("To"), large, tip)
+        .spacing(10.0)
+        .align_y(Alignment::Center);
+
+    column!(notation_row, thresholds_row).spacing(10.0)
+}
+
+/// The text of the style override inputs. Kept apart from [`ChartStyle`] so
+/// that partially typed colors are not discarded.
+#[derive(Debug, Clone, Default)]
+pub struct StyleInputs([String; 5]);
+
+impl StyleInputs {
+    pub fn new(style: &ChartStyle) -> Self {
+        Self(ChartColor::ALL.map(|part| style.get(part).map(hex_color).unwrap_or_default()))
+    }
+
+    /// Stores `input` and updates `style` when it is a valid color or empty.
+    /// Returns whether `style` changed.
+    pub fn update(&mut self, style: &mut ChartStyle, part: ChartColor, input: String) -> bool {
+        let parsed = ChartStyle::parse(&input);
+        self.0[part as usize] = input;
+
+        match parsed {
+            Some(color) if color != style.get(part) => {
+                style.set(part, color);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Collapsible toolbar section for overriding chart colors and gridlines
+pub fn style_panel<'a, Message>(
+    inputs: &'a StyleInputs,
+    style: &ChartStyle,
+    shown: bool,
+    on_toggle: Message,
+    on_input: impl Fn(ChartColor, String) -> Message + Clone + 'a,
+    on_grid: impl Fn(GridStyle) -> Message + 'a,
+) -> Column<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let header = {
+        let btn = button(text(if shown { "Hide" } else { "Show" }).size(13.0))
+            .on_press(on_toggle)
+            .padding([2, 8]);
+
+        let tip = tooltip(
+            "Override the colors of the axes, labels, caption and title, and the gridline style",
+        );
+
+        row!(text("Advanced Style"), btn, tip)
+            .spacing(10.0)
+            .align_y(Alignment::Center)
+    };
+
+    let mut panel = column!(header).spacing(10.0);
+
+    if shown {
+        let grid = pick_list(GridStyle::ALL, Some(style.grid), on_grid)
+            .text_size(14.0)
+            .padding([2, 5]);
+
+        panel = panel.push(
+            row!(text("Gridlines").size(14.0), grid)
+                .spacing(10.0)
+                .align_y(Alignment::Center),
+        );
+
+        for part in ChartColor::ALL {
+            let input = text_input("Theme", &inputs.0[part as usize])
+                .on_input({
+                    let on_input = on_input.clone();
+                    move |input| on_input(part, input)
+                })
+                .padding([2, 5])
+                .width(90.0);
+
+            panel = panel.push(
+                row!(text(part.to_string()).size(14.0), input)
+                    .spacing(10.0)
+                    .align_y(Alignment::Center),
+            );
+        }
+    }
+
+    panel
+}
@@ -7,14 +7,17 @@ use std::{
 
 use iced::{
     alignment::{Horizontal, Vertical},
-    font,
-    widget::canvas::{self, Frame, Geometry, Path, Stroke, Text},
+    font, mouse,
+    widget::canvas::{self, event, Frame, Geometry, LineDash, Path, Stroke, Text},
     Color, Pixels, Point, Rectangle, Renderer, Size, Theme, Vector,
 };
 
 use modav_core::models::{AxisPoints, Scale};
 
-use crate::widgets::toolbar::ToolbarOption;
+use crate::{
+    utils::{format_data, formatting::numeric_value, AppError, AxisFormat, NotationMode},
+    widgets::toolbar::ToolbarOption,
+};
 
 #[derive(Debug, Clone, Default, Copy, PartialEq)]
 #[allow(dead_code)]
@@ -62,8 +65,23 @@ impl LegendPosition {
         }
     }
 
+    /// Returns the alignment of the legend within the graph bounds
+    pub fn alignment(&self) -> (Horizontal, Vertical) {
+        match self {
+            Self::TopLeft => (Horizontal::Left, Vertical::Top),
+            Self::TopCenter => (Horizontal::Center, Vertical::Top),
+            Self::TopRight | Self::None => (Horizontal::Right, Vertical::Top),
+            Self::CenterLeft => (Horizontal::Left, Vertical::Center),
+            Self::Center => (Horizontal::Center, Vertical::Center),
+            Self::CenterRight => (Horizontal::Right, Vertical::Center),
+            Self::BottomLeft => (Horizontal::Left, Vertical::Bottom),
+            Self::BottomCenter => (Horizontal::Center, Vertical::Bottom),
+            Self::BottomRight => (Horizontal::Right, Vertical::Bottom),
+        }
+    }
+
     /// Returns the top left point of the legend given its size and a bound.
-    fn position(&self, bounds: Rectangle, size: Size) -> Point {
+    pub fn position(&self, bounds: Rectangle, size: Size) -> Point {
         // Bottom poistions also need some review
         match self {
             LegendPosition::TopLeft => {
@@ -187,6 +205,361 @@ impl ToolbarOption for LegendPosition {
     }
 }
 
+/// The parts of a chart whose color can be overridden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartColor {
+    Axis,
+    TickText,
+    AxisLabel,
+    Caption,
+    Title,
+}
+
+impl ChartColor {
+    pub const ALL: [Self; 5] = [
+        Self::Axis,
+        Self::TickText,
+        Self::AxisLabel,
+        Self::Caption,
+        Self::Title,
+    ];
+
+    /// Key used when persisting the override
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Axis => "axis_color",
+            Self::TickText => "tick_text_color",
+            Self::AxisLabel => "axis_label_color",
+            Self::Caption => "caption_color",
+            Self::Title => "title_color",
+        }
+    }
+
+    /// The theme derived color and the minimum contrast it must have against
+    /// the background. Lines and large text only need 3:1, body text 4.5:1.
+    fn theme_default(&self, theme: &Theme) -> (Color, f32) {
+        let palette = theme.extended_palette();
+
+        match self {
+            Self::Axis => (palette.background.strong.color, 3.0),
+            Self::TickText => (theme.palette().text, 4.5),
+            Self::AxisLabel | Self::Caption => (palette.secondary.strong.text, 4.5),
+            Self::Title => (palette.primary.strong.color, 3.0),
+        }
+    }
+}
+
+impl fmt::Display for ChartColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Axis => "Axis Lines",
+            Self::TickText => "Tick Text",
+            Self::AxisLabel => "Axis Labels",
+            Self::Caption => "Caption",
+            Self::Title => "Title",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// How gridlines are stroked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl GridStyle {
+    pub const ALL: [Self; 3] = [Self::Solid, Self::Dashed, Self::Dotted];
+
+    pub fn line_dash(&self) -> LineDash<'static> {
+        let segments: &'static [f32] = match self {
+            Self::Solid => &[],
+            Self::Dashed => &[6.0, 4.0],
+            Self::Dotted => &[1.5, 3.0],
+        };
+
+        LineDash {
+            segments,
+            offset: 0,
+        }
+    }
+}
+
+impl fmt::Display for GridStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Solid => "Solid",
+            Self::Dashed => "Dashed",
+            Self::Dotted => "Dotted",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The width to height ratio a chart is drawn at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AspectRatio {
+    /// Stretches to fill the tab
+    #[default]
+    Fill,
+    Square,
+    Standard,
+    Wide,
+}
+
+impl AspectRatio {
+    pub const ALL: [Self; 4] = [Self::Fill, Self::Square, Self::Standard, Self::Wide];
+
+    /// Returns the width to height ratio, if fixed
+    pub fn ratio(&self) -> Option<f32> {
+        match self {
+            Self::Fill => None,
+            Self::Square => Some(1.0),
+            Self::Standard => Some(4.0 / 3.0),
+            Self::Wide => Some(16.0 / 9.0),
+        }
+    }
+}
+
+impl fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Fill => "Fill",
+            Self::Square => "1:1",
+            Self::Standard => "4:3",
+            Self::Wide => "16:9",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// A legend entry that was clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegendEntry {
+    /// Index of the graphable that drew the entry
+    pub series: usize,
+    /// Index of the entry among those drawn by the graphable. Always 0 for
+    /// graphables with a single entry.
+    pub entry: usize,
+}
+
+/// A plotted point whose values are shown when the cursor is over it
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoverPoint {
+    /// Where the point is drawn
+    pub position: Point,
+    /// The series or segment the point belongs to
+    pub label: Option<String>,
+    pub x: Data,
+    pub y: Data,
+}
+
+/// Height of a legend row
+pub const LEGEND_ROW_HEIGHT: f32 = 20.0;
+/// Most entries a legend lists. The rest are counted in a "+N more" row.
+pub const LEGEND_MAX_ROWS: usize = 8;
+
+/// Returns how many of `entries` legend entries are listed, and how many
+/// are left over for the "+N more" row
+pub fn legend_rows(entries: usize) -> (usize, usize) {
+    if entries <= LEGEND_MAX_ROWS {
+        (entries, 0)
+    } else {
+        let listed = LEGEND_MAX_ROWS - 1;
+        (listed, entries - listed)
+    }
+}
+
+/// Returns the row of a legend entry drawn at `idx` within `bounds`
+pub fn legend_row(bounds: Rectangle, idx: usize) -> Rectangle {
+    Rectangle::new(
+        Point::new(bounds.x, bounds.y + idx as f32 * LEGEND_ROW_HEIGHT),
+        Size::new(bounds.width, LEGEND_ROW_HEIGHT),
+    )
+}
+
+/// Draws the "+N more" row counting the `more` entries a legend left out,
+/// below the `listed` entries in `bounds`
+pub fn draw_legend_overflow(
+    frame: &mut Frame,
+    bounds: Rectangle,
+    listed: usize,
+    more: usize,
+    color: Color,
+) {
+    if more == 0 {
+        return;
+    }
+
+    let row = legend_row(bounds, listed);
+
+    frame.fill_text(Text {
+        content: format!("+{more} more"),
+        position: Point::new(row.x, row.y + 6.0),
+        color: Color { a: 0.7, ..color },
+        size: 12.0.into(),
+        vertical_alignment: Vertical::Center,
+        font: font::Font {
+            style: font::Style::Italic,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+}
+
+/// Greys out a legend color, for series that are hidden
+pub fn muted(color: Color) -> Color {
+    Color {
+        a: color.a * 0.35,
+        ..color
+    }
+}
+
+/// The order legend entries are listed in. Only the legend is reordered,
+/// series are still drawn and stacked in their original order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LegendSort {
+    #[default]
+    DrawOrder,
+    Alphabetical,
+    ValueDescending,
+}
+
+impl LegendSort {
+    pub const ALL: [Self; 3] = [Self::DrawOrder, Self::Alphabetical, Self::ValueDescending];
+
+    /// Returns the positions of `entries`, given as label and value pairs in
+    /// draw order, in the order they should be listed. Entries without a
+    /// label or value are listed last.
+    pub fn order<'b>(
+        &self,
+        entries: impl IntoIterator<Item = (Option<&'b str>, Option<f64>)>,
+    ) -> Vec<usize> {
+        let mut entries = entries.into_iter().enumerate().collect::<Vec<_>>();
+
+        match self {
+            Self::DrawOrder => {}
+            Self::Alphabetical => entries.sort_by(|(_, one), (_, two)| match (one.0, two.0) {
+                (Some(one), Some(two)) => one.to_lowercase().cmp(&two.to_lowercase()),
+                (one, two) => two.is_some().cmp(&one.is_some()),
+            }),
+            Self::ValueDescending => entries.sort_by(|(_, one), (_, two)| match (one.1, two.1) {
+                (Some(one), Some(two)) => two.total_cmp(&one),
+                (one, two) => two.is_some().cmp(&one.is_some()),
+            }),
+        }
+
+        entries.into_iter().map(|(idx, _)| idx).collect()
+    }
+}
+
+impl fmt::Display for LegendSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::DrawOrder => "By draw order",
+            Self::Alphabetical => "Alphabetical",
+            Self::ValueDescending => "By value descending",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Colors used for the non-data parts of a chart. Parts without an override
+/// use a theme derived color that is readable against the background.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChartStyle {
+    pub axis: Option<Color>,
+    pub tick_text: Option<Color>,
+    pub axis_label: Option<Color>,
+    pub caption: Option<Color>,
+    pub title: Option<Color>,
+    pub grid: GridStyle,
+}
+
+impl ChartStyle {
+    pub fn get(&self, part: ChartColor) -> Option<Color> {
+        match part {
+            ChartColor::Axis => self.axis,
+            ChartColor::TickText => self.tick_text,
+            ChartColor::AxisLabel => self.axis_label,
+            ChartColor::Caption => self.caption,
+            ChartColor::Title => self.title,
+        }
+    }
+
+    pub fn set(&mut self, part: ChartColor, color: Option<Color>) {
+        let slot = match part {
+            ChartColor::Axis => &mut self.axis,
+            ChartColor::TickText => &mut self.tick_text,
+            ChartColor::AxisLabel => &mut self.axis_label,
+            ChartColor::Caption => &mut self.caption,
+            ChartColor::Title => &mut self.title,
+        };
+
+        *slot = color;
+    }
+
+    /// Returns the override for `part` or its theme default
+    pub fn color(&self, part: ChartColor, theme: &Theme) -> Color {
+        self.get(part).unwrap_or_else(|| {
+            let background = theme.palette().background;
+            let (color, min_contrast) = part.theme_default(theme);
+
+            if contrast_ratio(color, background) >= min_contrast {
+                color
+            } else {
+                theme.palette().text
+            }
+        })
+    }
+
+    /// Parses a `#rrggbb` color, with an empty input clearing the override.
+    /// Returns `None` for invalid input.
+    pub fn parse(input: &str) -> Option<Option<Color>> {
+        let input = input.trim();
+
+        if input.is_empty() {
+            Some(None)
+        } else {
+            Color::parse(input).map(Some)
+        }
+    }
+}
+
+fn is_zero_value(data: &Data) -> bool {
+    match data {
+        Data::Integer(0) | Data::Number(0) => true,
+        Data::Float(value) => *value == 0.0,
+        _ => false,
+    }
+}
+
+/// Formats a color as `#rrggbb`
+pub fn hex_color(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    let [r, g, b, _] = color.into_linear();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio between two colors, from 1 to 21
+fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+
+    (light + 0.05) / (dark + 0.05)
+}
+
 pub trait Graphable {
     type Data<'a>: Debug
     where
@@ -208,6 +581,40 @@ pub trait Graphable {
         true
     }
 
+    /// The value used when sorting the legend by value
+    fn legend_value(&self, _data: &Self::Data<'_>) -> Option<f64> {
+        None
+    }
+
+    /// Whether `Self` is plotted against the graph's secondary Y axis, if it
+    /// has one
+    fn uses_secondary_axis(&self, _data: &Self::Data<'_>) -> bool {
+        false
+    }
+
+    /// Returns which of the legend entries drawn by `Self` is at `point`.
+    /// `bounds` and `idx` are the same as in [`Graphable::draw_legend`].
+    fn legend_entry_at(
+        &self,
+        _bounds: Rectangle,
+        _idx: usize,
+        _point: Point,
+        _data: &Self::Data<'_>,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Returns the points drawn by `Self` that show their values on hover.
+    /// `x_output` and `y_output` are the same as in [`Graphable::draw`].
+    fn hover_points(
+        &self,
+        _x_output: &DrawnOutput,
+        _y_output: &DrawnOutput,
+        _data: &Self::Data<'_>,
+    ) -> Vec<HoverPoint> {
+        Vec::new()
+    }
+
     fn draw(
         &self,
         frame: &mut Frame,
@@ -223,13 +630,17 @@ struct AxisData {
     axis_color: Color,
     label_color: Color,
     text_color: Color,
+    caption_color: Color,
+    title_color: Color,
     outlines_color: Color,
+    grid: GridStyle,
 
     height: f32,
     width: f32,
 
     label_size: Pixels,
     caption_size: Pixels,
+    title_size: Pixels,
     point_size: Pixels,
     x_point_padding: f32,
 
@@ -254,14 +665,31 @@ struct AxisData {
     x_right: f32,
 
     bottom_text_y: f32,
+    top_text_y: f32,
+
+    format: AxisFormat,
+    /// Rotates the labels of horizontal axes even if they would fit
+    rotate_labels: bool,
 }
 
 impl AxisData {
-    fn new(frame: &Frame, theme: &Theme, x_pos: f32, y_pos: f32) -> Self {
-        let background = theme.extended_palette().background;
-        let axis_color = background.base.color;
-        let label_color = theme.extended_palette().secondary.strong.text;
-        let text_color = theme.palette().text;
+    /// Room left above the plot area for a chart title
+    const TITLE_SPACE: f32 = 36.0;
+
+    fn new(
+        frame: &Frame,
+        theme: &Theme,
+        style: &ChartStyle,
+        x_pos: f32,
+        y_pos: f32,
+        titled: bool,
+        secondary: bool,
+    ) -> Self {
+        let axis_color = style.color(ChartColor::Axis, theme);
+        let label_color = style.color(ChartColor::AxisLabel, theme);
+        let text_color = style.color(ChartColor::TickText, theme);
+        let caption_color = style.color(ChartColor::Caption, theme);
+        let title_color = style.color(ChartColor::Title, theme);
         let outlines_color = theme.extended_palette().background.weak.color;
 
         let height = frame.height();
@@ -269,18 +697,30 @@ impl AxisData {
 
         let label_size = 16.0.into();
         let caption_size = 14.0.into();
+        let title_size = 20.0.into();
         let point_size = 14.0.into();
         let x_point_padding = 5.0;
 
         let x_padding_left = 0.05 * width;
-        let x_padding_right = x_padding_left;
+        // A secondary axis needs room on the right for its points and label
+        let x_padding_right = if secondary {
+            2.0 * x_padding_left
+        } else {
+            x_padding_left
+        };
         let true_x_length = width - x_padding_left - x_padding_right;
         let x_offset_right = 0.015 * true_x_length;
         let x_offset_left = 0.045 * true_x_length;
         let x_offset_length = true_x_length - x_offset_left - x_offset_right;
 
-        let y_padding_top = 0.025 * height;
-        let y_padding_bottom = 2.5 * y_padding_top;
+        let base_padding = 0.025 * height;
+        let y_padding_top = if titled {
+            base_padding + Self::TITLE_SPACE
+        } else {
+            base_padding
+        };
+        let y_padding_bottom = 2.5 * base_padding;
+        let top_text_y = base_padding + 0.5 * Self::TITLE_SPACE;
         let true_y_length = height - y_padding_top - y_padding_bottom;
 
         let y_offset_top = 0.025 * true_y_length;
@@ -298,11 +738,15 @@ impl AxisData {
             axis_color,
             label_color,
             text_color,
+            caption_color,
+            title_color,
             outlines_color,
+            grid: style.grid,
             height,
             width,
             label_size,
             caption_size,
+            title_size,
             point_size,
             x_point_padding,
             x_padding_left,
@@ -322,6 +766,9 @@ impl AxisData {
             x_left,
             x_right,
             bottom_text_y: y,
+            top_text_y,
+            format: AxisFormat::default(),
+            rotate_labels: false,
         }
     }
 }
@@ -338,75 +785,204 @@ pub struct DrawnOutput {
     pub spacing: f32,
     /// The smallest difference between any two numeric points
     pub step: f32,
+    /// Whether the points are powers of ten, spaced logarithmically
+    pub log: bool,
+    /// Values of the numeric points and their positions, sorted by value
+    numeric: Vec<(f64, f32)>,
 }
 
 impl DrawnOutput {
+    /// Sorts the numeric points once, so [`DrawnOutput::get_closest`] can
+    /// search them
+    pub fn new(record: HashMap<Data, f32>, axis_pos: f32, spacing: f32, step: f32) -> Self {
+        let mut numeric = record
+            .iter()
+            .filter_map(|(point, position)| Some((numeric_value(point)?, *position)))
+            .collect::<Vec<_>>();
+        numeric.sort_by(|(one, _), (two, _)| one.total_cmp(two));
+
+        Self {
+            record,
+            axis_pos,
+            spacing,
+            step,
+            log: false,
+            numeric,
+        }
+    }
+
+    /// Marks the points as powers of ten, spaced logarithmically
+    pub fn log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
     /// Returns the position of data if present, else the closest approximate point
-    /// of where data would be
+    /// of where data would be. Numeric data between two points is placed
+    /// between them and data beyond the points continues from the nearest
+    /// two.
     pub fn get_closest(&self, data: &Data, is_x: bool) -> Option<f32> {
-        let data = match self.record.get(data) {
-            Some(point) => *point,
-            None => {
-                let closest = self
-                    .record
-                    .keys()
-                    .into_iter()
-                    .fold(None, |acc, curr| match acc {
-                        Some(prev) => {
-                            if curr < data && curr > prev {
-                                Some(curr)
-                            } else if curr < data && prev > data {
-                                Some(curr)
-                            } else {
-                                Some(prev)
-                            }
-                        }
-                        None => Some(curr),
-                    });
+        if self.log {
+            return self.get_closest_log(data);
+        }
 
-                let closest = closest?;
+        if let Some(point) = self.record.get(data) {
+            return Some(*point);
+        }
 
-                let point = self.record.get(closest).unwrap();
+        let value = numeric_value(data)?;
 
-                match (data, closest) {
-                    (Data::Integer(a), Data::Integer(b)) => {
-                        let diff = a - b;
-                        let ratio = diff as f32 / self.step;
-                        if is_x {
-                            point + (ratio * self.spacing)
-                        } else {
-                            point - (ratio * self.spacing)
-                        }
-                    }
-                    (Data::Number(a), Data::Number(b)) => {
-                        let diff = a - b;
-                        let ratio = diff as f32 / self.step;
-                        if is_x {
-                            point + (ratio * self.spacing)
-                        } else {
-                            point - (ratio * self.spacing)
-                        }
-                    }
-                    (Data::Float(a), Data::Float(b)) => {
-                        let diff = a - b;
-                        let ratio = diff / self.step;
-                        if is_x {
-                            point + (ratio * self.spacing)
-                        } else {
-                            point - (ratio * self.spacing)
-                        }
-                    }
-                    _ => {
-                        return None;
-                    }
+        let (lower, upper) = match self.numeric.as_slice() {
+            [] => return None,
+            [(point, position)] => {
+                // Without a neighbour, the spacing between points gives the slope
+                let ratio = (value - point) as f32 / self.step;
+                let offset = ratio * self.spacing;
+
+                return Some(if is_x {
+                    position + offset
+                } else {
+                    position - offset
+                });
+            }
+            points => {
+                let idx = points
+                    .partition_point(|(point, _)| *point < value)
+                    .clamp(1, points.len() - 1);
+
+                (points[idx - 1], points[idx])
+            }
+        };
+
+        let ((low, low_position), (high, high_position)) = (lower, upper);
+        if high == low {
+            return Some(low_position);
+        }
+
+        let ratio = ((value - low) / (high - low)) as f32;
+
+        Some(low_position + ratio * (high_position - low_position))
+    }
+
+    /// Places data between the powers of ten around it. Non-positive data
+    /// has no place on a logarithmic axis.
+    fn get_closest_log(&self, data: &Data) -> Option<f32> {
+        let raw = numeric_value(data).filter(|value| *value > 0.0)?;
+        let value = raw.log10();
+
+        // Only the points on either side of the value can be the closest
+        let idx = self.numeric.partition_point(|(point, _)| *point < raw);
+        let (exponent, point) = self.numeric[idx.saturating_sub(1)..]
+            .iter()
+            .take(2)
+            .map(|(point, position)| (point.log10(), *position))
+            .min_by(|(one, _), (two, _)| (value - one).abs().total_cmp(&(value - two).abs()))?;
+
+        // Neighbouring points are a power of ten apart. The spacing is
+        // negative on horizontal axes, so larger values are placed right of
+        // their point and above it on vertical ones.
+        Some(point - (value - exponent) as f32 * self.spacing)
+    }
+
+    /// Returns the data at `position` along the axis, the reverse of
+    /// [`DrawnOutput::get_closest`]. Numeric axes give the value between the
+    /// points around `position`, to three significant figures. Other axes
+    /// give the nearest point.
+    pub fn value_at(&self, position: f32) -> Option<Data> {
+        let scale = |value: f64| if self.log { value.log10() } else { value };
+
+        let mut points = self
+            .record
+            .iter()
+            .filter_map(|(point, at)| Some((scale(numeric_value(point)?), *at)))
+            .filter(|(value, _)| value.is_finite())
+            .collect::<Vec<_>>();
+        points.sort_by(|(_, one), (_, two)| one.total_cmp(two));
+
+        let value = match points.as_slice() {
+            [] => {
+                return self
+                    .record
+                    .iter()
+                    .min_by(|(_, one), (_, two)| {
+                        (*one - position).abs().total_cmp(&(*two - position).abs())
+                    })
+                    .map(|(point, _)| point.clone());
+            }
+            [(value, _)] => *value,
+            points => {
+                let idx = points
+                    .partition_point(|(_, at)| *at < position)
+                    .clamp(1, points.len() - 1);
+                let ((low, low_at), (high, high_at)) = (points[idx - 1], points[idx]);
+
+                if high_at == low_at {
+                    low
+                } else {
+                    low + (high - low) * ((position - low_at) / (high_at - low_at)) as f64
                 }
             }
         };
 
-        Some(data)
+        let value = if self.log { 10f64.powf(value) } else { value };
+        let precision = match value {
+            0.0 => 1.0,
+            value => 10f64.powf(value.abs().log10().floor() - 2.0),
+        };
+
+        Some(Data::Float(
+            ((value / precision).round() * precision) as f32,
+        ))
     }
 }
 
+/// Returns evenly stepped points covering `min` to `max`, for a value axis
+pub fn value_points(min: f64, max: f64) -> Vec<Data> {
+    const TICKS: f64 = 8.0;
+
+    let range = match max - min {
+        range if range > 0.0 => range,
+        _ => f64::max(min.abs(), 1.0),
+    };
+
+    let rough = range / TICKS;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).floor() as i64;
+    let last = f64::max((max / step).ceil(), first as f64 + 1.0) as i64;
+
+    (first..=last)
+        .map(|idx| Data::Float((idx as f64 * step) as f32))
+        .collect()
+}
+
+/// Returns the powers of ten spanning the positive `values`, for use as the
+/// points of a logarithmic axis. Returns `None` if there are no positive
+/// values.
+pub fn log_points(values: impl IntoIterator<Item = f64>) -> Option<Vec<Data>> {
+    let (min, max) = values
+        .into_iter()
+        .filter(|value| *value > 0.0 && value.is_finite())
+        .fold(None, |acc, value| match acc {
+            None => Some((value, value)),
+            Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+        })?;
+
+    let first = min.log10().floor() as i32;
+    let last = i32::max(max.log10().ceil() as i32, first + 1);
+
+    let points = (first..=last)
+        .map(|exponent| Data::Float(10f32.powi(exponent)))
+        .collect();
+
+    Some(points)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AxisKind {
     BaseHorizontal(Vec<Data>),
@@ -418,8 +994,38 @@ pub enum AxisKind {
 #[allow(dead_code)]
 impl AxisKind {
     const AXIS_THICKNESS: f32 = 2.0;
+    /// Angle, in degrees, rotated labels are drawn at
+    const LABEL_ROTATION: f32 = 45.0;
     const OUTLINES_THICKNESS: f32 = 0.5;
     const POINT_THICKNESS: f32 = Self::OUTLINES_THICKNESS * 2.0;
+    const ZERO_THICKNESS: f32 = Self::POINT_THICKNESS * 1.5;
+
+    /// Stroke of the gridline through a labelled point. The zero line is
+    /// always solid and heavier so the sign boundary stands out.
+    fn tick_stroke(axis_data: &AxisData, is_zero: bool) -> Stroke<'static> {
+        if is_zero {
+            Stroke::default()
+                .with_width(Self::ZERO_THICKNESS)
+                .with_color(axis_data.axis_color)
+        } else {
+            Stroke {
+                line_dash: axis_data.grid.line_dash(),
+                ..Stroke::default()
+                    .with_width(Self::POINT_THICKNESS)
+                    .with_color(axis_data.outlines_color)
+            }
+        }
+    }
+
+    /// Stroke of the minor gridlines between labelled points
+    fn grid_stroke(axis_data: &AxisData) -> Stroke<'static> {
+        Stroke {
+            line_dash: axis_data.grid.line_dash(),
+            ..Stroke::default()
+                .with_width(Self::OUTLINES_THICKNESS)
+                .with_color(axis_data.outlines_color)
+        }
+    }
 
     fn is_split(&self) -> bool {
         match self {
@@ -464,6 +1070,63 @@ impl AxisKind {
         }
     }
 
+    /// Whether the labels of the horizontal axis `points`, `spacing` apart,
+    /// are drawn rotated. They are if asked for, or if the widest label would
+    /// run into its neighbours.
+    fn rotate_labels<'a>(
+        points: impl Iterator<Item = &'a Data>,
+        spacing: f32,
+        axis_data: &AxisData,
+    ) -> bool {
+        // Text is not measured on a frame, so the width is estimated
+        let widest = points
+            .map(|point| format_data(point, &axis_data.format).chars().count())
+            .max()
+            .unwrap_or(0) as f32
+            * 0.6
+            * axis_data.point_size.0;
+
+        axis_data.rotate_labels || widest > spacing
+    }
+
+    /// Draws the label of a point on a horizontal axis below `position`.
+    /// Rotated labels end at `position` and slant down to the left so long
+    /// labels don't overlap.
+    fn draw_horizontal_label(
+        frame: &mut Frame,
+        content: String,
+        position: Point,
+        axis_data: &AxisData,
+        rotate: bool,
+    ) {
+        let text = Text {
+            content,
+            color: axis_data.text_color,
+            size: axis_data.point_size,
+            ..Default::default()
+        };
+
+        if !rotate {
+            frame.fill_text(Text {
+                position,
+                horizontal_alignment: Horizontal::Center,
+                ..text
+            });
+            return;
+        }
+
+        frame.with_save(|frame| {
+            frame.translate(Vector::new(position.x, position.y));
+            frame.rotate(-Self::LABEL_ROTATION * f32::consts::PI / 180.0);
+            frame.fill_text(Text {
+                position: Point::ORIGIN,
+                horizontal_alignment: Horizontal::Right,
+                vertical_alignment: Vertical::Center,
+                ..text
+            });
+        });
+    }
+
     fn draw_base_horizontal(
         frame: &mut Frame,
         points: &[Data],
@@ -474,10 +1137,7 @@ impl AxisKind {
         let points_len = points.len();
 
         let axis_color = axis_data.axis_color;
-        let text_color = axis_data.text_color;
-        let outlines_color = axis_data.outlines_color;
 
-        let point_size = axis_data.point_size.into();
         let x_point_padding = axis_data.x_point_padding;
 
         let x_padding_left = axis_data.x_padding_left;
@@ -524,6 +1184,7 @@ impl AxisKind {
         let outlines_width = (dx * 0.9) / (outlines_number as f32);
         let mut outlines_count = 1.0;
         let mut point_count = 0;
+        let rotate = Self::rotate_labels(points.iter(), 0.9 * dx, &axis_data);
 
         let mut points = points.iter();
 
@@ -535,7 +1196,10 @@ impl AxisKind {
                 prev_x = x;
                 point_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), x);
 
                     if prev_prev == Data::None {
@@ -554,35 +1218,21 @@ impl AxisKind {
                         }
                     };
 
-                    let text_position = Point::new(x, y + x_point_padding);
-                    let text = Text {
-                        content: point.clone().to_string(),
-                        position: text_position,
-                        horizontal_alignment: Horizontal::Center,
-                        color: text_color,
-                        size: point_size,
-                        ..Default::default()
-                    };
-
-                    frame.fill_text(text);
+                    Self::draw_horizontal_label(
+                        frame,
+                        format_data(point, &axis_data.format),
+                        Point::new(x, y + x_point_padding),
+                        &axis_data,
+                        rotate,
+                    );
                 }
 
                 let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                frame.stroke(
-                    &outline,
-                    Stroke::default()
-                        .with_width(Self::POINT_THICKNESS)
-                        .with_color(outlines_color),
-                );
+                frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
             } else {
                 if !clean {
                     let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -596,12 +1246,7 @@ impl AxisKind {
             _ => 0.0,
         };
 
-        DrawnOutput {
-            record,
-            step,
-            axis_pos: axis_pos - Self::AXIS_THICKNESS,
-            spacing: x_dist,
-        }
+        DrawnOutput::new(record, axis_pos - Self::AXIS_THICKNESS, x_dist, step)
     }
 
     fn draw_base_vertical(
@@ -615,7 +1260,6 @@ impl AxisKind {
 
         let axis_color = axis_data.axis_color;
         let text_color = axis_data.text_color;
-        let outlines_color = axis_data.outlines_color;
 
         let point_size = axis_data.point_size;
 
@@ -677,7 +1321,10 @@ impl AxisKind {
                 prev_y = y;
                 point_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), y);
 
                     if prev_prev == Data::None {
@@ -698,7 +1345,7 @@ impl AxisKind {
 
                     let text_position = Point::new(x, y);
                     let text = Text {
-                        content: point.clone().to_string(),
+                        content: format_data(point, &axis_data.format),
                         position: text_position,
                         horizontal_alignment: Horizontal::Center,
                         vertical_alignment: Vertical::Center,
@@ -714,24 +1361,14 @@ impl AxisKind {
                     [offset_end, y].into(),
                 );
 
-                frame.stroke(
-                    &outline,
-                    Stroke::default()
-                        .with_width(Self::POINT_THICKNESS)
-                        .with_color(outlines_color),
-                );
+                frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
             } else {
                 if !clean {
                     let outline = Path::line(
                         [x + (0.5 * x_offset_left), y].into(),
                         [offset_end, y].into(),
                     );
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -745,38 +1382,95 @@ impl AxisKind {
             _ => 0.0,
         };
 
-        DrawnOutput {
-            record,
-            axis_pos: axis_pos + Self::AXIS_THICKNESS,
-            spacing: y_dist,
-            step,
-        }
+        DrawnOutput::new(record, axis_pos + Self::AXIS_THICKNESS, y_dist, step)
     }
 
-    fn draw_split_vertical(
+    /// Draws a vertical axis along the right edge of the plot, with its
+    /// points to its right. No grid lines are drawn so as not to clash with
+    /// those of the primary axis.
+    fn draw_secondary_vertical(
         frame: &mut Frame,
-        pos_points: &[Data],
-        neg_points: &[Data],
+        points: &[Data],
         axis_data: AxisData,
-        clean: bool,
     ) -> DrawnOutput {
         let mut record = HashMap::new();
-        let pos_points_len = pos_points.len();
-        let neg_points_len = neg_points.len();
 
-        let axis_color = axis_data.axis_color;
-        let text_color = axis_data.text_color;
-        let outlines_color = axis_data.outlines_color;
+        let x = axis_data.width - axis_data.x_padding_right;
+        let y = axis_data.y_padding_top;
 
-        let point_size = axis_data.point_size;
+        let axis_start = Point::new(x, y);
+        let axis_end = Point::new(
+            x,
+            y + axis_data.true_y_length - (0.75 * axis_data.y_offset_bottom),
+        );
 
-        let x_padding_left = axis_data.x_padding_left;
-        let x_offset_right = axis_data.x_offset_right;
-        let x_offset_left = axis_data.x_offset_left;
-        let x_offset_length = axis_data.x_offset_length;
+        frame.stroke(
+            &Path::line(axis_start, axis_end),
+            Stroke::default()
+                .with_color(axis_data.axis_color)
+                .with_width(Self::AXIS_THICKNESS),
+        );
 
-        let y_padding_top = axis_data.y_padding_top;
-        let true_y_length = axis_data.true_y_length;
+        let top = axis_data.y_padding_top + axis_data.y_offset_top;
+        let spacing = 0.9 * axis_data.y_offset_length / (points.len().max(1) as f32);
+        let tick_length = 0.1 * axis_data.x_padding_right;
+
+        for (idx, point) in points.iter().rev().enumerate() {
+            let y = top + spacing * (idx + 1) as f32;
+            record.insert(point.clone(), y);
+
+            frame.stroke(
+                &Path::line([x, y].into(), [x + tick_length, y].into()),
+                Stroke::default()
+                    .with_color(axis_data.axis_color)
+                    .with_width(Self::AXIS_THICKNESS),
+            );
+
+            frame.fill_text(Text {
+                content: format_data(point, &axis_data.format),
+                position: Point::new(x + 2.0 * tick_length, y),
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                color: axis_data.text_color,
+                size: axis_data.point_size,
+                ..Default::default()
+            });
+        }
+
+        let step = match points {
+            [.., prev, last] => match (numeric_value(prev), numeric_value(last)) {
+                (Some(prev), Some(last)) => (last - prev).abs() as f32,
+                _ => 0.0,
+            },
+            _ => 0.0,
+        };
+
+        DrawnOutput::new(record, x, spacing, step)
+    }
+
+    fn draw_split_vertical(
+        frame: &mut Frame,
+        pos_points: &[Data],
+        neg_points: &[Data],
+        axis_data: AxisData,
+        clean: bool,
+    ) -> DrawnOutput {
+        let mut record = HashMap::new();
+        let pos_points_len = pos_points.len();
+        let neg_points_len = neg_points.len();
+
+        let axis_color = axis_data.axis_color;
+        let text_color = axis_data.text_color;
+
+        let point_size = axis_data.point_size;
+
+        let x_padding_left = axis_data.x_padding_left;
+        let x_offset_right = axis_data.x_offset_right;
+        let x_offset_left = axis_data.x_offset_left;
+        let x_offset_length = axis_data.x_offset_length;
+
+        let y_padding_top = axis_data.y_padding_top;
+        let true_y_length = axis_data.true_y_length;
 
         let y_offset_top = axis_data.y_offset_top;
         let y_offset_length = axis_data.y_offset_length;
@@ -836,7 +1530,10 @@ impl AxisKind {
                 prev_y = y;
                 point_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), y);
 
                     if prev_prev == Data::None {
@@ -857,7 +1554,7 @@ impl AxisKind {
 
                     let text_position = Point::new(x, y);
                     let text = Text {
-                        content: point.clone().to_string(),
+                        content: format_data(point, &axis_data.format),
                         position: text_position,
                         horizontal_alignment: Horizontal::Center,
                         vertical_alignment: Vertical::Center,
@@ -873,24 +1570,14 @@ impl AxisKind {
                     [offset_end, y].into(),
                 );
 
-                frame.stroke(
-                    &outline,
-                    Stroke::default()
-                        .with_width(Self::POINT_THICKNESS)
-                        .with_color(outlines_color),
-                );
+                frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
             } else {
                 if !clean {
                     let outline = Path::line(
                         [x + (0.5 * x_offset_left), y].into(),
                         [offset_end, y].into(),
                     );
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -915,7 +1602,10 @@ impl AxisKind {
 
                 point_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), y);
 
                     if set_prev {
@@ -938,7 +1628,7 @@ impl AxisKind {
 
                     let text_position = Point::new(x, y);
                     let text = Text {
-                        content: point.clone().to_string(),
+                        content: format_data(point, &axis_data.format),
                         position: text_position,
                         horizontal_alignment: Horizontal::Center,
                         vertical_alignment: Vertical::Center,
@@ -954,24 +1644,14 @@ impl AxisKind {
                     [offset_end, y].into(),
                 );
 
-                frame.stroke(
-                    &outline,
-                    Stroke::default()
-                        .with_width(Self::POINT_THICKNESS)
-                        .with_color(outlines_color),
-                );
+                frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
             } else {
                 if !clean {
                     let outline = Path::line(
                         [x + (0.5 * x_offset_left), y].into(),
                         [offset_end, y].into(),
                     );
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -985,12 +1665,7 @@ impl AxisKind {
             _ => 0.0,
         };
 
-        DrawnOutput {
-            record,
-            axis_pos: axis_pos + Self::AXIS_THICKNESS,
-            spacing: y_dist,
-            step,
-        }
+        DrawnOutput::new(record, axis_pos + Self::AXIS_THICKNESS, y_dist, step)
     }
 
     fn draw_split_horizontal(
@@ -1005,10 +1680,7 @@ impl AxisKind {
         let neg_points_len = neg_points.len();
 
         let axis_color = axis_data.axis_color;
-        let text_color = axis_data.text_color;
-        let outlines_color = axis_data.outlines_color;
 
-        let point_size = axis_data.point_size;
         let x_point_padding = axis_data.x_point_padding;
 
         let x_padding_left = axis_data.x_padding_left;
@@ -1055,6 +1727,8 @@ impl AxisKind {
             _ => 10,
         };
         let outlines_width = (dx * 0.85) / (outlines_number as f32);
+        let rotate =
+            Self::rotate_labels(pos_points.iter().chain(neg_points), 0.85 * dx, &axis_data);
         let has_zero = match pos_points.get(0).unwrap_or(&Data::None) {
             Data::Integer(0) | Data::Number(0) | Data::Float(0.0) => true,
             _ => false,
@@ -1073,7 +1747,10 @@ impl AxisKind {
                 prev_x = x;
                 points_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), x);
 
                     if prev_prev == Data::None {
@@ -1092,35 +1769,21 @@ impl AxisKind {
                         }
                     };
 
-                    let text_position = Point::new(x, y + x_point_padding);
-                    let text = Text {
-                        content: point.clone().to_string(),
-                        position: text_position,
-                        horizontal_alignment: Horizontal::Center,
-                        color: text_color,
-                        size: point_size,
-                        ..Default::default()
-                    };
-
-                    frame.fill_text(text);
+                    Self::draw_horizontal_label(
+                        frame,
+                        format_data(point, &axis_data.format),
+                        Point::new(x, y + x_point_padding),
+                        &axis_data,
+                        rotate,
+                    );
                 }
 
                 let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                frame.stroke(
-                    &outline,
-                    Stroke::default()
-                        .with_width(Self::POINT_THICKNESS)
-                        .with_color(outlines_color),
-                );
+                frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
             } else {
                 if !clean {
                     let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -1145,7 +1808,10 @@ impl AxisKind {
 
                 points_count += 1;
 
-                if let Some(point) = points.next() {
+                let point = points.next();
+                let is_zero = point.is_some_and(is_zero_value);
+
+                if let Some(point) = point {
                     record.insert(point.clone(), x);
 
                     if set_prev {
@@ -1166,35 +1832,21 @@ impl AxisKind {
                         };
                     }
 
-                    let text_position = Point::new(x, y + x_point_padding);
-                    let text = Text {
-                        content: point.clone().to_string(),
-                        position: text_position,
-                        horizontal_alignment: Horizontal::Center,
-                        color: text_color,
-                        size: point_size,
-                        ..Default::default()
-                    };
-
-                    frame.fill_text(text);
+                    Self::draw_horizontal_label(
+                        frame,
+                        format_data(point, &axis_data.format),
+                        Point::new(x, y + x_point_padding),
+                        &axis_data,
+                        rotate,
+                    );
 
                     let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_width(Self::POINT_THICKNESS)
-                            .with_color(outlines_color),
-                    );
+                    frame.stroke(&outline, Self::tick_stroke(&axis_data, is_zero));
                 }
             } else {
                 if !clean {
                     let outline = Path::line([x, y].into(), [x, y_offset_top].into());
-                    frame.stroke(
-                        &outline,
-                        Stroke::default()
-                            .with_color(outlines_color)
-                            .with_width(Self::OUTLINES_THICKNESS),
-                    );
+                    frame.stroke(&outline, Self::grid_stroke(&axis_data));
                 }
             }
 
@@ -1208,12 +1860,7 @@ impl AxisKind {
             _ => 0.0,
         };
 
-        DrawnOutput {
-            record,
-            step,
-            axis_pos: axis_pos - Self::AXIS_THICKNESS,
-            spacing: x_dist,
-        }
+        DrawnOutput::new(record, axis_pos - Self::AXIS_THICKNESS, x_dist, step)
     }
 
     fn draw(&self, frame: &mut Frame, axis_data: AxisData, clean: bool) -> DrawnOutput {
@@ -1239,6 +1886,13 @@ pub struct Axis {
     kind: AxisKind,
     axis_pos: f32,
     alt_axis_pos: f32,
+    /// Whether the points are powers of ten from [`log_points`]
+    log: bool,
+    /// How numeric points are written. The notation is set for the whole
+    /// graph by [`Graph::notation`].
+    format: AxisFormat,
+    /// Whether the labels of a horizontal axis are always drawn rotated
+    rotate_labels: bool,
 }
 
 impl Axis {
@@ -1249,6 +1903,9 @@ impl Axis {
             alt_axis_pos,
             label: None,
             clean: false,
+            log: false,
+            format: AxisFormat::default(),
+            rotate_labels: false,
         }
     }
 
@@ -1262,17 +1919,100 @@ impl Axis {
         self
     }
 
+    pub fn log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Sets the decimal places and digit grouping of numeric points
+    pub fn format(mut self, format: AxisFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Always rotates the labels of a horizontal axis. They are otherwise
+    /// only rotated if they would overlap.
+    pub fn rotate_labels(mut self, rotate: bool) -> Self {
+        self.rotate_labels = rotate;
+        self
+    }
+
+    /// Returns the format of the points, written in `notation`
+    fn format_with(&self, notation: NotationMode) -> AxisFormat {
+        AxisFormat {
+            notation,
+            ..self.format
+        }
+    }
+
+    /// Returns `axis_data` with its format and label rotation replaced by
+    /// this axis'
+    fn axis_data(&self, axis_data: AxisData) -> AxisData {
+        AxisData {
+            format: self.format_with(axis_data.format.notation),
+            rotate_labels: self.rotate_labels,
+            ..axis_data
+        }
+    }
+
     fn draw(&self, frame: &mut Frame, axis_data: AxisData) -> DrawnOutput {
-        self.kind.draw(frame, axis_data, self.clean)
+        self.kind
+            .draw(frame, self.axis_data(axis_data), self.clean)
+            .log(self.log)
+    }
+
+    /// Draws `self` as a secondary Y axis on the right of the plot. Only the
+    /// points of an [`AxisKind::BaseVertical`] are placed on it.
+    fn draw_secondary(&self, frame: &mut Frame, axis_data: AxisData) -> DrawnOutput {
+        let points = match &self.kind {
+            AxisKind::BaseVertical(points) => points.as_slice(),
+            _ => &[],
+        };
+
+        AxisKind::draw_secondary_vertical(frame, points, self.axis_data(axis_data)).log(self.log)
+    }
+}
+
+const LEGEND_X_PADDING: f32 = 5.0;
+const LEGEND_Y_PADDING: f32 = 2.5;
+const LEGEND_HEADER_SIZE: f32 = 16.0;
+/// How close, in pixels, the cursor has to be to a point to show its values
+const HOVER_RADIUS: f32 = 12.0;
+
+/// The state of a [`Graph`] canvas
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphState {
+    /// The cursor position relative to the canvas, if it is over it
+    cursor: Option<Point>,
+    /// Where the legend was dragged to, as fractions of the canvas size, and
+    /// the anchor it was dragged away from. The legend goes back to its
+    /// anchor once another one is picked.
+    legend: Option<(LegendPosition, Point)>,
+    /// The cursor's offset from the legend's top left corner while the
+    /// legend is dragged
+    dragging: Option<Vector>,
+}
+
+impl GraphState {
+    /// Returns the top left corner the legend was dragged to within
+    /// `bounds`, if it was dragged away from `anchor`
+    fn legend_origin(&self, anchor: LegendPosition, bounds: Rectangle) -> Option<Point> {
+        self.legend
+            .filter(|(dragged_from, _)| *dragged_from == anchor)
+            .map(|(_, origin)| Point::new(origin.x * bounds.width, origin.y * bounds.height))
     }
 }
 
-pub struct Graph<'a, G>
+pub struct Graph<'a, G, Message>
 where
     G: Graphable,
 {
     x_axis: Axis,
     y_axis: Axis,
+    /// Y axis drawn on the right for graphables that use it
+    secondary_axis: Option<Axis>,
+    /// Whether guide lines are drawn from the cursor to the axes
+    crosshair: bool,
     cache: &'a canvas::Cache,
     graphables: &'a [G],
     data: <G as Graphable>::Data<'a>,
@@ -1280,10 +2020,16 @@ where
     labels_len: usize,
     theme: &'a Theme,
     caption: Option<&'a String>,
+    title: Option<&'a str>,
+    notation: NotationMode,
+    style: ChartStyle,
+    legend_sort: LegendSort,
+    on_legend_toggle: Option<Box<dyn Fn(LegendEntry) -> Message + 'a>>,
+    on_legend_menu: Option<Box<dyn Fn(LegendEntry) -> Message + 'a>>,
 }
 
 #[allow(dead_code)]
-impl<'a, G> Graph<'a, G>
+impl<'a, G, Message> Graph<'a, G, Message>
 where
     G: Graphable,
 {
@@ -1298,6 +2044,8 @@ where
         Self {
             x_axis,
             y_axis,
+            secondary_axis: None,
+            crosshair: false,
             cache,
             graphables,
             theme,
@@ -1305,6 +2053,12 @@ where
             legend_position: LegendPosition::default(),
             labels_len: 0,
             caption: None,
+            title: None,
+            notation: NotationMode::default(),
+            legend_sort: LegendSort::default(),
+            style: ChartStyle::default(),
+            on_legend_toggle: None,
+            on_legend_menu: None,
         }
     }
 
@@ -1323,152 +2077,537 @@ where
         self
     }
 
+    /// Sets the title drawn centered above the plot area
+    pub fn title(mut self, title: Option<&'a str>) -> Self {
+        self.title = title;
+        self
+    }
+
     pub fn labels_len(mut self, len: usize) -> Self {
         self.labels_len = len;
         self
     }
 
-    fn draw_legend(&self, renderer: &Renderer, bounds: Rectangle, theme: &Theme) -> Geometry {
-        let mut frame = Frame::new(renderer, bounds.size());
+    /// Sets how numeric points on the axes are displayed
+    pub fn notation(mut self, notation: NotationMode) -> Self {
+        self.notation = notation;
+        self
+    }
 
-        if self.legend_position == LegendPosition::None {
-            return frame.into_geometry();
-        }
+    /// Sets the Y axis drawn on the right of the plot for the graphables
+    /// that use it
+    pub fn secondary_axis(mut self, axis: Option<Axis>) -> Self {
+        self.secondary_axis = axis;
+        self
+    }
 
-        if self.graphables.is_empty() {
-            return frame.into_geometry();
-        }
+    /// Sets whether guide lines are drawn from the cursor to the axes,
+    /// labelled with the values there
+    pub fn crosshair(mut self, crosshair: bool) -> Self {
+        self.crosshair = crosshair;
+        self
+    }
 
-        if self.labels_len == 0 {
-            return frame.into_geometry();
+    /// Sets the colors of the axes and surrounding text
+    pub fn style(mut self, style: ChartStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the order legend entries are listed in
+    pub fn legend_sort(mut self, sort: LegendSort) -> Self {
+        self.legend_sort = sort;
+        self
+    }
+
+    /// Makes legend entries clickable, producing the message returned by
+    /// `on_toggle` for the clicked entry
+    pub fn on_legend_toggle(mut self, on_toggle: impl Fn(LegendEntry) -> Message + 'a) -> Self {
+        self.on_legend_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// Makes legend entries right-clickable, producing the message returned
+    /// by `on_menu` for the clicked entry
+    pub fn on_legend_menu(mut self, on_menu: impl Fn(LegendEntry) -> Message + 'a) -> Self {
+        self.on_legend_menu = Some(Box::new(on_menu));
+        self
+    }
+
+    /// Returns the indices of the graphables shown in the legend, in the
+    /// order they are listed. The legend row `i` belongs to the graphable
+    /// at `legend_order()[i]`.
+    pub fn legend_order(&self) -> Vec<usize> {
+        let shown = self
+            .graphables
+            .iter()
+            .enumerate()
+            .filter(|(_, graphable)| graphable.draw_legend_filter(&self.data))
+            .collect::<Vec<_>>();
+
+        self.legend_sort
+            .order(shown.iter().map(|(_, graphable)| {
+                (
+                    graphable.label().map(String::as_str),
+                    graphable.legend_value(&self.data),
+                )
+            }))
+            .into_iter()
+            .map(|position| shown[position].0)
+            .collect()
+    }
+
+    /// Returns the legend box and the area its entries are drawn in,
+    /// relative to `bounds`, if the legend is shown. A legend dragged
+    /// elsewhere in `state` is kept there, within `bounds`.
+    fn legend_layout(
+        &self,
+        bounds: Rectangle,
+        state: &GraphState,
+    ) -> Option<(Rectangle, Rectangle)> {
+        if self.legend_position == LegendPosition::None
+            || self.graphables.is_empty()
+            || self.labels_len == 0
+        {
+            return None;
         }
 
         let size = {
-            let width = f32::min(frame.size().width * 0.15, 175.0);
-            let height = 25.0 + 20.0 * (self.labels_len as f32);
+            let (listed, more) = legend_rows(self.labels_len);
+            let rows = listed + usize::from(more > 0);
+
+            let width = f32::min(bounds.width * 0.15, 175.0);
+            let height = 25.0 + LEGEND_ROW_HEIGHT * (rows as f32);
             Size::new(width, height)
         };
 
-        let position = self.legend_position.position(bounds, size);
+        let position = match state.legend_origin(self.legend_position, bounds) {
+            Some(origin) => Point::new(
+                origin.x.clamp(0.0, (bounds.width - size.width).max(0.0)),
+                origin.y.clamp(0.0, (bounds.height - size.height).max(0.0)),
+            ),
+            None => self.legend_position.position(bounds, size),
+        };
+
+        let entries = Rectangle::new(
+            Point::new(
+                position.x + LEGEND_X_PADDING,
+                position.y + LEGEND_Y_PADDING + (LEGEND_HEADER_SIZE * 1.5),
+            ),
+            Size::new(
+                size.width - 2.0 * LEGEND_X_PADDING,
+                size.height - LEGEND_Y_PADDING - (LEGEND_HEADER_SIZE * 1.5),
+            ),
+        );
+
+        Some((Rectangle::new(position, size), entries))
+    }
+
+    /// Returns the legend entry at `point`, relative to `bounds`
+    fn legend_entry_at(
+        &self,
+        bounds: Rectangle,
+        state: &GraphState,
+        point: Point,
+    ) -> Option<LegendEntry> {
+        let (legend, entries) = self.legend_layout(bounds, state)?;
+
+        if !legend.contains(point) {
+            return None;
+        }
+
+        self.legend_order()
+            .into_iter()
+            .take(legend_rows(self.labels_len).0)
+            .enumerate()
+            .find_map(|(row, series)| {
+                self.graphables[series]
+                    .legend_entry_at(entries, row, point, &self.data)
+                    .map(|entry| LegendEntry { series, entry })
+            })
+    }
+
+    /// Returns the cursor's offset from the legend's top left corner, if
+    /// the cursor is over a part of the legend that is not a clickable
+    /// entry
+    fn legend_grab(
+        &self,
+        bounds: Rectangle,
+        state: &GraphState,
+        cursor: mouse::Cursor,
+    ) -> Option<Vector> {
+        let point = cursor.position_in(bounds)?;
+        let (legend, _) = self.legend_layout(bounds, state)?;
+
+        let clickable = self.on_legend_toggle.is_some() || self.on_legend_menu.is_some();
+        if !legend.contains(point)
+            || (clickable && self.legend_entry_at(bounds, state, point).is_some())
+        {
+            return None;
+        }
+
+        Some(point - legend.position())
+    }
+
+    fn draw_legend(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        state: &GraphState,
+        theme: &Theme,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let Some((legend, bounds)) = self.legend_layout(bounds, state) else {
+            return frame.into_geometry();
+        };
+
         let background = theme.extended_palette().background.weak.color;
         let text_color = theme.extended_palette().background.base.text;
 
         frame.stroke(
-            &Path::rectangle(position, size),
+            &Path::rectangle(legend.position(), legend.size()),
             Stroke::default().with_width(1.5),
         );
 
-        frame.fill(&Path::rectangle(position, size), background);
-
-        let x_padding = 5.0;
-        let y_padding = 2.5;
-
-        let position = Point::new(position.x + x_padding, position.y + y_padding);
-
-        let header_size = 16.0;
+        frame.fill(
+            &Path::rectangle(legend.position(), legend.size()),
+            background,
+        );
 
         let header = Text {
             content: "Legend".into(),
-            position,
-            size: header_size.into(),
+            position: Point::new(legend.x + LEGEND_X_PADDING, legend.y + LEGEND_Y_PADDING),
+            size: LEGEND_HEADER_SIZE.into(),
             color: text_color,
             ..Default::default()
         };
 
         frame.fill_text(header);
 
-        let new_y = position.y + (header_size * 1.5);
-        let position = Point::new(position.x, new_y);
+        let (listed, more) = legend_rows(self.labels_len);
 
-        let width = size.width - 2.0 * x_padding;
-        let height = size.height - y_padding - (header_size * 1.5);
+        for (row, idx) in self.legend_order().into_iter().take(listed).enumerate() {
+            self.graphables[idx].draw_legend(&mut frame, bounds, text_color, row, &self.data);
+        }
 
-        let bounds = Rectangle::new(position, Size::new(width, height));
+        draw_legend_overflow(&mut frame, bounds, listed, more, text_color);
 
-        for (i, graphable) in self
-            .graphables
-            .iter()
-            .filter(|graphable| graphable.draw_legend_filter(&self.data))
-            .enumerate()
-        {
-            graphable.draw_legend(&mut frame, bounds, text_color, i, &self.data);
+        frame.into_geometry()
+    }
+
+    fn axis_data(&self, frame: &Frame) -> AxisData {
+        AxisData {
+            format: self.notation.into(),
+            ..AxisData::new(
+                frame,
+                self.theme,
+                &self.style,
+                self.x_axis.axis_pos,
+                self.y_axis.axis_pos,
+                self.title.is_some(),
+                self.secondary_axis.is_some(),
+            )
+        }
+    }
+
+    /// Draws faint lines from `cursor` to the axes, labelled with the values
+    /// of the axes where they meet them
+    fn draw_crosshair(&self, renderer: &Renderer, bounds: Rectangle, cursor: Point) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let data = self.axis_data(&frame);
+
+        let plot = Rectangle::new(
+            Point::new(data.x_padding_left + data.x_offset_left, data.y_padding_top),
+            Size::new(data.x_offset_length, data.true_y_length),
+        );
+
+        if !plot.contains(cursor) {
+            return frame.into_geometry();
+        }
+
+        // As with hovering, the axes are laid out again on a scratch frame
+        let mut scratch = Frame::new(renderer, bounds.size());
+        let outputs = [
+            (&self.x_axis, self.x_axis.draw(&mut scratch, data)),
+            (&self.y_axis, self.y_axis.draw(&mut scratch, data)),
+        ];
+
+        let stroke = Stroke {
+            line_dash: LineDash {
+                segments: &[4.0, 4.0],
+                offset: 0,
+            },
+            ..Stroke::default().with_width(1.0).with_color(Color {
+                a: 0.5,
+                ..data.text_color
+            })
+        };
+
+        for (axis, output) in outputs {
+            let vertical = matches!(
+                axis.kind,
+                AxisKind::BaseVertical(_) | AxisKind::SplitVertical(..)
+            );
+
+            let (end, value, anchor, alignment) = if vertical {
+                (
+                    Point::new(output.axis_pos, cursor.y),
+                    output.value_at(cursor.y),
+                    Point::new(output.axis_pos - 4.0, cursor.y),
+                    Horizontal::Right,
+                )
+            } else {
+                (
+                    Point::new(cursor.x, output.axis_pos),
+                    output.value_at(cursor.x),
+                    Point::new(cursor.x, output.axis_pos + 12.0),
+                    Horizontal::Center,
+                )
+            };
+
+            frame.stroke(&Path::line(cursor, end), stroke);
+
+            if let Some(value) = value {
+                self.draw_crosshair_label(
+                    &mut frame,
+                    format_data(&value, &axis.format_with(self.notation)),
+                    anchor,
+                    alignment,
+                );
+            }
         }
 
         frame.into_geometry()
     }
-}
 
-impl<'a, G, Message> canvas::Program<Message> for Graph<'a, G>
-where
-    G: Graphable,
-{
-    type State = ();
+    /// Draws `content` in a small box vertically centered on `anchor`,
+    /// aligned to it by `alignment`
+    fn draw_crosshair_label(
+        &self,
+        frame: &mut Frame,
+        content: String,
+        anchor: Point,
+        alignment: Horizontal,
+    ) {
+        let palette = self.theme.extended_palette();
+        let text_size = 12.0;
+        let padding = 3.0;
+
+        // Text is not measured on a frame, so the width is estimated
+        let size = Size::new(
+            content.chars().count() as f32 * 0.6 * text_size + 2.0 * padding,
+            text_size + 2.0 * padding,
+        );
 
-    fn draw(
+        let x = match alignment {
+            Horizontal::Left => anchor.x,
+            Horizontal::Center => anchor.x - 0.5 * size.width,
+            Horizontal::Right => anchor.x - size.width,
+        };
+        let position = Point::new(x, anchor.y - 0.5 * size.height);
+
+        frame.fill(
+            &Path::rectangle(position, size),
+            palette.background.weak.color,
+        );
+        frame.fill_text(Text {
+            content,
+            position: Point::new(position.x + padding, anchor.y),
+            vertical_alignment: Vertical::Center,
+            size: text_size.into(),
+            color: palette.background.base.text,
+            ..Default::default()
+        });
+    }
+
+    /// Returns the output of the Y axis `graphable` is plotted against
+    fn y_output<'b>(
+        &self,
+        graphable: &G,
+        primary: &'b DrawnOutput,
+        secondary: Option<&'b DrawnOutput>,
+    ) -> &'b DrawnOutput {
+        match secondary {
+            Some(secondary) if graphable.uses_secondary_axis(&self.data) => secondary,
+            _ => primary,
+        }
+    }
+
+    /// Returns the plotted point closest to `cursor`, if it is within
+    /// [`HOVER_RADIUS`]. The axes are laid out again on a scratch frame since
+    /// their records are only kept while the cached plot is drawn.
+    fn hovered_point(
         &self,
-        _state: &Self::State,
         renderer: &Renderer,
-        _theme: &Theme,
         bounds: Rectangle,
-        _cursor: iced::advanced::mouse::Cursor,
-    ) -> Vec<Geometry> {
-        let content = self.cache.draw(renderer, bounds.size(), |frame| {
-            let data = AxisData::new(
-                frame,
-                self.theme,
-                self.x_axis.axis_pos,
-                self.y_axis.axis_pos,
-            );
+        cursor: Point,
+    ) -> Option<HoverPoint> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let data = self.axis_data(&frame);
+
+        let x_output = self.x_axis.draw(&mut frame, data);
+        let y_output = self.y_axis.draw(&mut frame, data);
+        let secondary_output = self
+            .secondary_axis
+            .as_ref()
+            .map(|axis| axis.draw_secondary(&mut frame, data));
+
+        self.graphables
+            .iter()
+            .flat_map(|graphable| {
+                let y_output = self.y_output(graphable, &y_output, secondary_output.as_ref());
+                graphable.hover_points(&x_output, y_output, &self.data)
+            })
+            .map(|point| (point.position.distance(cursor), point))
+            .filter(|(distance, _)| *distance <= HOVER_RADIUS)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, point)| point)
+    }
+
+    /// Draws the values of the point under `cursor` in a small box beside it
+    fn draw_hover(&self, renderer: &Renderer, bounds: Rectangle, cursor: Point) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let Some(point) = self.hovered_point(renderer, bounds, cursor) else {
+            return frame.into_geometry();
+        };
+
+        let palette = self.theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let text_size = 12.0;
+        let line_height = 16.0;
+        let padding = 6.0;
+
+        let lines = point
+            .label
+            .iter()
+            .cloned()
+            .chain([
+                format!(
+                    "x: {}",
+                    format_data(&point.x, &self.x_axis.format_with(self.notation))
+                ),
+                format!(
+                    "y: {}",
+                    format_data(&point.y, &self.y_axis.format_with(self.notation))
+                ),
+            ])
+            .collect::<Vec<String>>();
+
+        // Text is not measured on a frame, so the width is estimated from the
+        // longest line
+        let longest = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let size = Size::new(
+            longest as f32 * 0.6 * text_size + 2.0 * padding,
+            lines.len() as f32 * line_height + 2.0 * padding,
+        );
+
+        let mut position = point.position + Vector::new(HOVER_RADIUS, -HOVER_RADIUS - size.height);
+        if position.x + size.width > bounds.width {
+            position.x = point.position.x - HOVER_RADIUS - size.width;
+        }
+        if position.y < 0.0 {
+            position.y = point.position.y + HOVER_RADIUS;
+        }
 
-            let x_output = self.x_axis.draw(frame, data);
-            let y_output = self.y_axis.draw(frame, data);
+        frame.stroke(
+            &Path::circle(point.position, 6.0),
+            Stroke::default().with_width(2.0).with_color(text_color),
+        );
 
-            self.graphables.iter().for_each(|graphable| {
-                graphable.draw(frame, &x_output, &y_output, &self.data);
+        let background = Path::rectangle(position, size);
+        frame.fill(&background, palette.background.weak.color);
+        frame.stroke(
+            &background,
+            Stroke::default().with_width(1.0).with_color(text_color),
+        );
+
+        for (idx, line) in lines.into_iter().enumerate() {
+            frame.fill_text(Text {
+                content: line,
+                position: Point::new(
+                    position.x + padding,
+                    position.y + padding + idx as f32 * line_height,
+                ),
+                size: text_size.into(),
+                color: text_color,
+                ..Default::default()
             });
+        }
 
-            if let Some(label) = self.x_axis.label.clone() {
-                let x = (data.x_offset_length / 2.0) + data.x_offset_left + data.x_padding_left;
-                let y = data.bottom_text_y;
-                let label_position = Point::new(x, y);
-                let label_size = data.label_size;
+        frame.into_geometry()
+    }
 
-                let text = Text {
-                    content: label.clone(),
-                    position: label_position,
-                    horizontal_alignment: Horizontal::Center,
-                    vertical_alignment: Vertical::Center,
-                    size: label_size,
-                    color: data.label_color,
+    /// Draws the title, axis labels and caption relative to the frame bounds
+    fn draw_annotations(&self, frame: &mut Frame, data: &AxisData) {
+        if let Some(title) = self.title {
+            let x = (data.x_offset_length / 2.0) + data.x_offset_left + data.x_padding_left;
+            let title_position = Point::new(x, data.top_text_y);
+
+            let text = Text {
+                content: title.to_string(),
+                position: title_position,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                size: data.title_size,
+                color: data.title_color,
+                font: font::Font {
+                    weight: font::Weight::Bold,
                     ..Default::default()
-                };
-
-                frame.fill_text(text);
-            }
+                },
+                ..Default::default()
+            };
+            frame.fill_text(text);
+        }
 
-            if let Some(caption) = self.caption {
-                let x = (data.x_offset_length * 0.80) + data.x_padding_left + data.x_offset_left;
-                let y = data.bottom_text_y;
-                let caption_position = Point::new(x, y);
-                let caption_size = data.caption_size;
+        if let Some(label) = self.x_axis.label.clone() {
+            let x = (data.x_offset_length / 2.0) + data.x_offset_left + data.x_padding_left;
+            let y = data.bottom_text_y;
+            let label_position = Point::new(x, y);
+            let label_size = data.label_size;
+
+            let text = Text {
+                content: label.clone(),
+                position: label_position,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                size: label_size,
+                color: data.label_color,
+                ..Default::default()
+            };
+
+            frame.fill_text(text);
+        }
 
-                let text = Text {
-                    content: caption.clone(),
-                    position: caption_position,
-                    horizontal_alignment: Horizontal::Center,
-                    vertical_alignment: Vertical::Center,
-                    size: caption_size,
-                    color: data.label_color,
-                    font: font::Font {
-                        style: font::Style::Italic,
-                        ..Default::default()
-                    },
+        if let Some(caption) = self.caption {
+            let x = (data.x_offset_length * 0.80) + data.x_padding_left + data.x_offset_left;
+            let y = data.bottom_text_y;
+            let caption_position = Point::new(x, y);
+            let caption_size = data.caption_size;
+
+            let text = Text {
+                content: caption.clone(),
+                position: caption_position,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                size: caption_size,
+                color: data.caption_color,
+                font: font::Font {
+                    style: font::Style::Italic,
                     ..Default::default()
-                };
-                frame.fill_text(text);
-            }
+                },
+                ..Default::default()
+            };
+            frame.fill_text(text);
+        }
 
-            if let Some(label) = self.y_axis.label.clone() {
+        if let Some(label) = self.y_axis.label.clone() {
+            frame.with_save(|frame| {
                 let x_padding = 0.5 * data.x_padding_left;
                 let y_padding = data.y_padding_top + (0.5 * data.true_y_length);
 
@@ -1487,78 +2626,484 @@ where
                     ..Default::default()
                 };
                 frame.fill_text(text);
+            });
+        }
+
+        if let Some(label) = self
+            .secondary_axis
+            .as_ref()
+            .and_then(|axis| axis.label.clone())
+        {
+            frame.with_save(|frame| {
+                let x = data.width - 0.2 * data.x_padding_right;
+                let y = data.y_padding_top + (0.5 * data.true_y_length);
+
+                frame.translate(Vector::new(x, y));
+                frame.rotate(90.0 * f32::consts::PI / 180.0);
+                let text = Text {
+                    content: label,
+                    position: Point::ORIGIN,
+                    horizontal_alignment: Horizontal::Center,
+                    vertical_alignment: Vertical::Center,
+                    color: data.label_color,
+                    size: data.label_size,
+                    ..Default::default()
+                };
+                frame.fill_text(text);
+            });
+        }
+    }
+}
+
+impl<'a, G, Message> canvas::Program<Message> for Graph<'a, G, Message>
+where
+    G: Graphable,
+{
+    type State = GraphState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<Message>) {
+        match event {
+            canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                state.cursor = cursor.position_in(bounds);
+            }
+            canvas::Event::Mouse(mouse::Event::CursorLeft) => {
+                state.cursor = None;
+            }
+            _ => {}
+        }
+
+        if let Some(grab) = state.dragging {
+            match event {
+                canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    if let Some(point) = cursor.position_in(bounds) {
+                        let origin = point - grab;
+                        let origin = Point::new(
+                            origin.x / bounds.width.max(1.0),
+                            origin.y / bounds.height.max(1.0),
+                        );
+                        state.legend = Some((self.legend_position, origin));
+                    }
+
+                    return (event::Status::Captured, None);
+                }
+                canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    state.dragging = None;
+                    return (event::Status::Captured, None);
+                }
+                _ => {}
+            }
+        }
+
+        let handler = match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                self.on_legend_toggle.as_ref()
             }
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                self.on_legend_menu.as_ref()
+            }
+            _ => None,
+        };
+
+        if let Some(handler) = handler {
+            if let Some(entry) = cursor
+                .position_in(bounds)
+                .and_then(|point| self.legend_entry_at(bounds, state, point))
+            {
+                return (event::Status::Captured, Some(handler(entry)));
+            }
+        }
+
+        // Pressing anywhere else on the legend starts dragging it
+        if let canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(legend) = self.legend_grab(bounds, state, cursor) {
+                state.dragging = Some(legend);
+                return (event::Status::Captured, None);
+            }
+        }
+
+        (event::Status::Ignored, None)
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.dragging.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+
+        let over_entry = (self.on_legend_toggle.is_some() || self.on_legend_menu.is_some())
+            && cursor
+                .position_in(bounds)
+                .and_then(|point| self.legend_entry_at(bounds, state, point))
+                .is_some();
+
+        if over_entry {
+            mouse::Interaction::Pointer
+        } else if self.legend_grab(bounds, state, cursor).is_some() {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: iced::advanced::mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let content = self.cache.draw(renderer, bounds.size(), |frame| {
+            let data = self.axis_data(frame);
+
+            // The plot is drawn in its own transform scope so that the
+            // annotations below are always placed relative to the visible
+            // bounds, whatever the plot itself is transformed by.
+            frame.with_save(|frame| {
+                let x_output = self.x_axis.draw(frame, data);
+                let y_output = self.y_axis.draw(frame, data);
+                let secondary_output = self
+                    .secondary_axis
+                    .as_ref()
+                    .map(|axis| axis.draw_secondary(frame, data));
+
+                self.graphables.iter().for_each(|graphable| {
+                    let y_output = self.y_output(graphable, &y_output, secondary_output.as_ref());
+                    graphable.draw(frame, &x_output, y_output, &self.data);
+                });
+            });
+
+            self.draw_annotations(frame, &data);
         });
 
-        vec![content, self.draw_legend(renderer, bounds, self.theme)]
+        let mut geometries = vec![
+            content,
+            self.draw_legend(renderer, bounds, state, self.theme),
+        ];
+
+        if let Some(cursor) = state.cursor {
+            if self.crosshair {
+                geometries.push(self.draw_crosshair(renderer, bounds, cursor));
+            }
+
+            geometries.push(self.draw_hover(renderer, bounds, cursor));
+        }
+
+        geometries
     }
 }
 
+/// Creates the axes of a chart. An axis given points from [`log_points`]
+/// uses those instead of its scale's points, spaced logarithmically. Scales
+/// without any points, as left by degenerate data, are an error.
 pub fn create_axis(
     x_scale: &Scale,
     y_scale: &Scale,
     sequential_x: bool,
     sequential_y: bool,
     clean: bool,
-) -> (Axis, Axis) {
-    let (x_kind, y_fraction) = match x_scale.axis_points(sequential_x) {
-        AxisPoints::Categorical(points) => {
-            let kind = AxisKind::BaseHorizontal(points);
-            (kind, 1.0)
-        }
+    log_x: Option<Vec<Data>>,
+    log_y: Option<Vec<Data>>,
+) -> Result<(Axis, Axis), AppError> {
+    let (is_log_x, is_log_y) = (log_x.is_some(), log_y.is_some());
+
+    let x_points = match log_x {
+        Some(points) => AxisPoints::Numeric {
+            positives: points,
+            negatives: Vec::new(),
+        },
+        None => x_scale.axis_points(sequential_x),
+    };
+
+    if is_empty(&x_points) {
+        return Err(AppError::Simple(String::from(
+            "There is no data to place on the X axis",
+        )));
+    }
+
+    let (x_kind, y_fraction) = horizontal_kind(x_points);
+
+    let y_points = match log_y {
+        Some(points) => AxisPoints::Numeric {
+            positives: points,
+            negatives: Vec::new(),
+        },
+        None => y_scale.axis_points(sequential_y),
+    };
+
+    if is_empty(&y_points) {
+        return Err(AppError::Simple(String::from(
+            "There is no data to place on the Y axis",
+        )));
+    }
+
+    let (y_kind, x_fraction) = vertical_kind(y_points);
+
+    let x_axis = Axis::new(x_kind, x_fraction, y_fraction)
+        .clean(clean)
+        .log(is_log_x);
+    let y_axis = Axis::new(y_kind, y_fraction, x_fraction)
+        .clean(clean)
+        .log(is_log_y);
+
+    Ok((x_axis, y_axis))
+}
+
+/// Creates the axes of a chart whose values are shares of a whole, running
+/// from 0 to 100 percent. The categories of `scale` lie along the Y axis if
+/// `horizontal`, and along the X axis otherwise.
+pub fn create_percent_axis(
+    scale: &Scale,
+    sequential: bool,
+    clean: bool,
+    horizontal: bool,
+) -> Result<(Axis, Axis), AppError> {
+    let points = scale.axis_points(sequential);
+
+    if is_empty(&points) {
+        return Err(AppError::Simple(String::from(
+            "There are no categories to place on the axis",
+        )));
+    }
+
+    let percents = value_points(0.0, 100.0);
+
+    let (x_axis, y_axis) = if horizontal {
+        let (y_kind, x_fraction) = vertical_kind(points);
+
+        (
+            Axis::new(AxisKind::BaseHorizontal(percents), x_fraction, 1.0),
+            Axis::new(y_kind, 1.0, x_fraction),
+        )
+    } else {
+        let (x_kind, y_fraction) = horizontal_kind(points);
+
+        (
+            Axis::new(x_kind, 1.0, y_fraction),
+            Axis::new(AxisKind::BaseVertical(percents), y_fraction, 1.0),
+        )
+    };
+
+    Ok((x_axis.clean(clean), y_axis.clean(clean)))
+}
+
+/// Returns the kind of a horizontal axis over `points` and the fraction of
+/// the chart's height above it
+fn horizontal_kind(points: AxisPoints) -> (AxisKind, f32) {
+    match points {
+        AxisPoints::Categorical(points) => (AxisKind::BaseHorizontal(points), 1.0),
         AxisPoints::Numeric {
             positives,
             negatives,
         } => {
             if positives.is_empty() {
-                let kind = AxisKind::BaseHorizontal(negatives);
-                (kind, 0.0)
+                (AxisKind::BaseHorizontal(negatives), 0.0)
             } else if negatives.is_empty() {
-                let kind = AxisKind::BaseHorizontal(positives);
-                (kind, 1.0)
-            } else if positives.is_empty() && negatives.is_empty() {
-                // Scale is never empty.
-                panic!("StackedBarChart: Empty Scale")
+                (AxisKind::BaseHorizontal(positives), 1.0)
             } else {
                 let fraction = positives.len() as f32 / (positives.len() + negatives.len()) as f32;
-                let kind = AxisKind::SplitHorizontal(positives, negatives);
-                (kind, fraction)
+                (AxisKind::SplitHorizontal(positives, negatives), fraction)
             }
         }
-    };
-
-    let y_points = y_scale.axis_points(sequential_y);
+    }
+}
 
-    let (y_kind, x_fraction) = match y_points {
-        AxisPoints::Categorical(points) => {
-            let kind = AxisKind::BaseVertical(points);
-            (kind, 1.0)
-        }
+/// Returns the kind of a vertical axis over `points` and the fraction of
+/// the chart's width right of it
+fn vertical_kind(points: AxisPoints) -> (AxisKind, f32) {
+    match points {
+        AxisPoints::Categorical(points) => (AxisKind::BaseVertical(points), 1.0),
         AxisPoints::Numeric {
             positives,
             negatives,
         } => {
             if positives.is_empty() {
-                let kind = AxisKind::BaseVertical(negatives);
-                (kind, 0.0)
+                (AxisKind::BaseVertical(negatives), 0.0)
             } else if negatives.is_empty() {
-                let kind = AxisKind::BaseVertical(positives);
-                (kind, 1.0)
-            } else if positives.is_empty() && negatives.is_empty() {
-                // Scale is never empty.
-                panic!("StackedBarChart: Empty Scale")
+                (AxisKind::BaseVertical(positives), 1.0)
             } else {
                 let fraction = positives.len() as f32 / (positives.len() + negatives.len()) as f32;
+                (AxisKind::SplitVertical(positives, negatives), fraction)
+            }
+        }
+    }
+}
+
+fn is_empty(points: &AxisPoints) -> bool {
+    match points {
+        AxisPoints::Categorical(points) => points.is_empty(),
+        AxisPoints::Numeric {
+            positives,
+            negatives,
+        } => positives.is_empty() && negatives.is_empty(),
+    }
+}
 
-                let kind = AxisKind::SplitVertical(positives, negatives);
+#[test]
+fn test_chart_style_colors() {
+    let mut style = ChartStyle::default();
+    style.set(ChartColor::Caption, ChartStyle::parse("#ff0000").unwrap());
 
-                (kind, fraction)
+    for theme in Theme::ALL {
+        let background = theme.palette().background;
+
+        for part in ChartColor::ALL {
+            let color = style.color(part, theme);
+
+            if part == ChartColor::Caption {
+                assert_eq!(color, Color::from_rgb8(255, 0, 0));
+            } else {
+                assert!(contrast_ratio(color, background) >= 3.0, "{theme} {part}");
             }
         }
+    }
+
+    assert_eq!(ChartStyle::parse(" "), Some(None));
+    assert_eq!(ChartStyle::parse("#12"), None);
+}
+
+#[test]
+fn test_legend_sort_order() {
+    let entries = [
+        (Some("beta"), Some(2.0)),
+        (None, Some(9.0)),
+        (Some("Alpha"), None),
+        (Some("gamma"), Some(5.0)),
+    ];
+
+    assert_eq!(LegendSort::DrawOrder.order(entries), vec![0, 1, 2, 3]);
+    assert_eq!(LegendSort::Alphabetical.order(entries), vec![2, 0, 3, 1]);
+    assert_eq!(LegendSort::ValueDescending.order(entries), vec![1, 3, 0, 2]);
+}
+
+#[test]
+fn test_legend_rows() {
+    assert_eq!(legend_rows(0), (0, 0));
+    assert_eq!(legend_rows(LEGEND_MAX_ROWS), (LEGEND_MAX_ROWS, 0));
+    assert_eq!(legend_rows(LEGEND_MAX_ROWS + 1), (LEGEND_MAX_ROWS - 1, 2));
+    assert_eq!(
+        legend_rows(20),
+        (LEGEND_MAX_ROWS - 1, 20 - (LEGEND_MAX_ROWS - 1))
+    );
+}
+
+#[test]
+fn test_log_points() {
+    let decades = |points: Vec<Data>| {
+        points
+            .iter()
+            .filter_map(numeric_value)
+            .map(|value| value.log10().round() as i32)
+            .collect::<Vec<_>>()
     };
 
-    let x_axis = Axis::new(x_kind, x_fraction, y_fraction).clean(clean);
-    let y_axis = Axis::new(y_kind, y_fraction, x_fraction).clean(clean);
+    assert_eq!(log_points([-5.0, 0.0]), None);
+    assert_eq!(
+        log_points([0.5, 20.0, -3.0, 999.0]).map(decades),
+        Some(vec![-1, 0, 1, 2, 3])
+    );
+    assert_eq!(log_points([100.0]).map(decades), Some(vec![2, 3]));
+}
+
+#[test]
+fn test_get_closest() {
+    let output =
+        |record: &[(Data, f32)]| DrawnOutput::new(record.iter().cloned().collect(), 0.0, 10.0, 1.0);
+
+    let horizontal = output(&[
+        (Data::Integer(0), 100.0),
+        (Data::Integer(10), 200.0),
+        (Data::Integer(20), 300.0),
+    ]);
+    assert_eq!(
+        horizontal.get_closest(&Data::Integer(10), true),
+        Some(200.0)
+    );
+    assert_eq!(horizontal.get_closest(&Data::Float(5.0), true), Some(150.0));
+    assert_eq!(horizontal.get_closest(&Data::Number(15), true), Some(250.0));
+    assert_eq!(horizontal.get_closest(&Data::Integer(-10), true), Some(0.0));
+    assert_eq!(
+        horizontal.get_closest(&Data::Float(30.0), true),
+        Some(400.0)
+    );
+    assert_eq!(horizontal.get_closest(&Data::None, true), None);
+
+    let vertical = output(&[
+        (Data::Float(20.0), 100.0),
+        (Data::Float(0.0), 300.0),
+        (Data::Float(10.0), 200.0),
+    ]);
+    assert_eq!(vertical.get_closest(&Data::Integer(15), false), Some(150.0));
+    assert_eq!(
+        vertical.get_closest(&Data::Integer(-10), false),
+        Some(400.0)
+    );
+    assert_eq!(vertical.get_closest(&Data::Number(40), false), Some(-100.0));
+
+    let single = output(&[(Data::Integer(5), 50.0)]);
+    assert_eq!(single.get_closest(&Data::Integer(7), true), Some(70.0));
+    assert_eq!(single.get_closest(&Data::Integer(7), false), Some(30.0));
+}
+
+#[test]
+fn test_value_at() {
+    let output = |record: &[(Data, f32)], log: bool| {
+        DrawnOutput::new(record.iter().cloned().collect(), 0.0, 10.0, 1.0).log(log)
+    };
+
+    let vertical = output(
+        &[
+            (Data::Integer(20), 100.0),
+            (Data::Integer(0), 300.0),
+            (Data::Integer(10), 200.0),
+        ],
+        false,
+    );
+    assert_eq!(vertical.value_at(250.0), Some(Data::Float(5.0)));
+    assert_eq!(vertical.value_at(50.0), Some(Data::Float(25.0)));
+
+    let log = output(
+        &[(Data::Float(1.0), 0.0), (Data::Float(100.0), 100.0)],
+        true,
+    );
+    assert_eq!(log.value_at(50.0), Some(Data::Float(10.0)));
+
+    let categories = output(
+        &[
+            (Data::Text("a".into()), 100.0),
+            (Data::Text("b".into()), 200.0),
+        ],
+        false,
+    );
+    assert_eq!(categories.value_at(180.0), Some(Data::Text("b".into())));
+    assert_eq!(output(&[], false).value_at(0.0), None);
+}
 
-    return (x_axis, y_axis);
+#[test]
+fn test_value_points() {
+    let points = value_points(0.0, 100.0);
+    assert_eq!(points.first(), Some(&Data::Float(0.0)));
+    assert_eq!(points.last(), Some(&Data::Float(100.0)));
+    assert_eq!(points.len(), 6);
+
+    let points = value_points(-3.0, 9.0);
+    assert_eq!(points.first(), Some(&Data::Float(-4.0)));
+    assert_eq!(points.last(), Some(&Data::Float(10.0)));
+
+    // A single value still gets an axis around it
+    let points = value_points(5.0, 5.0);
+    assert!(points.len() > 1);
+    assert!(points
+        .iter()
+        .any(|point| matches!(point, Data::Float(value) if *value <= 5.0)));
 }
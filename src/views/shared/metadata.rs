@@ -0,0 +1,176 @@
+//! Machine readable description of a chart, for external tooling.
+//!
+//! The exported document is a single JSON object with the following fields.
+//! Fields are only ever added between versions, never renamed or removed.
+//!
+//! - `version`: schema version, currently `1`
+//! - `type`: one of `"line"`, `"bar"`, `"stacked_bar"`, `"box_plot"`,
+//!   `"histogram"` or `"radar"`, as named in saved sessions
+//! - `title`: the chart title
+//! - `source`: path of the file the chart was created from
+//! - `caption`: the chart caption or `null`
+//! - `x_axis`, `y_axis`: objects with a `label` (or `null`) and the `min` and
+//!   `max` numeric values plotted on the axis (`null` for non-numeric axes)
+//! - `series`: array of objects with a `name` (or `null`), a `color` as
+//!   `"#rrggbb"` and the `statistics` of its numeric values (or `null`)
+//!
+//! `statistics` objects hold `count`, `sum`, `mean`, `median`, `min`, `max`
+//! and `std_dev`.
+use std::path::Path;
+
+use iced::Color;
+use modav_core::repr::sheet::utils::Data;
+
+use crate::utils::{
+    formatting::numeric_value,
+    json::{json, Value},
+    stats::{series_stats, Stats},
+};
+
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisMetadata {
+    label: Option<String>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl AxisMetadata {
+    pub fn new<'a>(label: Option<&String>, values: impl Iterator<Item = &'a Data>) -> Self {
+        let (min, max) = values.filter_map(numeric_value).fold(
+            (None, None),
+            |(min, max): (Option<f64>, Option<f64>), value| {
+                (
+                    Some(min.map_or(value, |min| min.min(value))),
+                    Some(max.map_or(value, |max| max.max(value))),
+                )
+            },
+        );
+
+        Self {
+            label: label.cloned(),
+            min,
+            max,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "label": self.label,
+            "min": self.min,
+            "max": self.max,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesMetadata {
+    name: Option<String>,
+    color: Color,
+    values: Vec<f64>,
+}
+
+impl SeriesMetadata {
+    pub fn new<'a>(
+        name: Option<&String>,
+        color: Color,
+        values: impl Iterator<Item = &'a Data>,
+    ) -> Self {
+        Self {
+            name: name.cloned(),
+            color,
+            values: values.filter_map(numeric_value).collect(),
+        }
+    }
+
+    pub fn with_values(name: Option<&String>, color: Color, values: Vec<f64>) -> Self {
+        Self {
+            name: name.cloned(),
+            color,
+            values,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let [r, g, b, _] = self.color.into_rgba8();
+
+        let statistics = (!self.values.is_empty()).then(|| series_stats(&self.values));
+
+        json!({
+            "name": self.name,
+            "color": format!("#{r:02x}{g:02x}{b:02x}"),
+            "statistics": statistics.map(stats_json),
+        })
+    }
+}
+
+fn stats_json(stats: Stats) -> Value {
+    json!({
+        "count": stats.count,
+        "sum": stats.sum,
+        "mean": stats.mean,
+        "median": stats.median,
+        "min": stats.min,
+        "max": stats.max,
+        "std_dev": stats.std_dev,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartMetadata<'a> {
+    /// The chart's `type`, listed above
+    pub kind: &'a str,
+    pub title: &'a str,
+    pub source: &'a Path,
+    pub caption: Option<&'a String>,
+    pub x_axis: AxisMetadata,
+    pub y_axis: AxisMetadata,
+    pub series: Vec<SeriesMetadata>,
+}
+
+impl<'a> ChartMetadata<'a> {
+    pub fn to_json(&self) -> String {
+        json!({
+            "version": VERSION,
+            "type": self.kind,
+            "title": self.title,
+            "source": self.source.display().to_string(),
+            "caption": self.caption,
+            "x_axis": self.x_axis.to_json(),
+            "y_axis": self.y_axis.to_json(),
+            "series": self.series.iter().map(SeriesMetadata::to_json).collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+}
+
+#[test]
+fn test_chart_metadata() {
+    let values = [Data::Integer(2), Data::Float(4.5), Data::None];
+
+    let metadata = ChartMetadata {
+        kind: "line",
+        title: "Sales",
+        source: Path::new("sales.csv"),
+        caption: None,
+        x_axis: AxisMetadata::new(None, [Data::None].iter()),
+        y_axis: AxisMetadata::new(Some(&String::from("Units")), values.iter()),
+        series: vec![SeriesMetadata::new(
+            None,
+            Color::from_rgb8(255, 0, 16),
+            values.iter(),
+        )],
+    };
+
+    assert_eq!(
+        metadata.to_json(),
+        concat!(
+            r#"{"version":1,"type":"line","title":"Sales","source":"sales.csv","caption":null,"#,
+            r#""x_axis":{"label":null,"min":null,"max":null},"#,
+            r#""y_axis":{"label":"Units","min":2.0,"max":4.5},"#,
+            r##""series":[{"name":null,"color":"#ff0010","statistics":{"count":2,"sum":6.5,"##,
+            r#""mean":3.25,"median":3.25,"min":2.0,"max":4.5,"std_dev":1.25}}]}"#
+        )
+    );
+}
@@ -0,0 +1,147 @@
+//! Drawing charts into images without a window.
+//!
+//! Charts are drawn by the same [`canvas::Program`]s shown in their tabs,
+//! only with the software renderer and into a pixel buffer of a chosen size.
+use std::borrow::Cow;
+
+use iced::{
+    advanced::{
+        graphics::{geometry::Renderer as _, text::font_system, Viewport},
+        Renderer as _,
+    },
+    mouse,
+    widget::canvas,
+    Font, Pixels, Rectangle, Size, Theme, Vector,
+};
+
+use crate::utils::AppError;
+
+/// Largest width or height, in pixels, a chart can be drawn at
+pub const MAX_SIDE: u32 = 8192;
+
+/// An RGBA8 image of a chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// An image being drawn
+pub struct Offscreen {
+    renderer: iced::Renderer,
+    size: Size<u32>,
+    theme: Theme,
+}
+
+impl Offscreen {
+    pub fn new(size: Size<u32>, theme: &Theme) -> Result<Self, AppError> {
+        if !(1..=MAX_SIDE).contains(&size.width) || !(1..=MAX_SIDE).contains(&size.height) {
+            return Err(AppError::Simple(format!(
+                "Charts can be drawn from 1 to {MAX_SIDE} pixels wide and high, not {}×{}",
+                size.width, size.height
+            )));
+        }
+
+        let renderer = iced_tiny_skia::Renderer::new(Font::DEFAULT, Pixels(16.0));
+
+        Ok(Self {
+            renderer: iced::Renderer::Secondary(renderer),
+            size,
+            theme: theme.clone(),
+        })
+    }
+
+    /// The size of the image
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::with_size(Size::new(self.size.width as f32, self.size.height as f32))
+    }
+
+    /// Draws `program` into `bounds` of the image
+    pub fn draw<Message, P>(&mut self, program: &P, bounds: Rectangle)
+    where
+        P: canvas::Program<Message>,
+    {
+        let geometry = program.draw(
+            &P::State::default(),
+            &self.renderer,
+            &self.theme,
+            Rectangle::with_size(bounds.size()),
+            mouse::Cursor::Unavailable,
+        );
+
+        self.renderer
+            .with_translation(Vector::new(bounds.x, bounds.y), |renderer| {
+                for geometry in geometry {
+                    renderer.draw_geometry(geometry);
+                }
+            });
+    }
+
+    /// Rasterizes what was drawn over the theme's background
+    pub fn finish(self) -> Result<Image, AppError> {
+        let Size { width, height } = self.size;
+        let failed = || AppError::Simple(format!("Could not draw a {width}×{height} image"));
+
+        let iced::Renderer::Secondary(mut renderer) = self.renderer else {
+            return Err(failed());
+        };
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or_else(failed)?;
+        let mut mask = tiny_skia::Mask::new(width, height).ok_or_else(failed)?;
+
+        renderer.draw::<&str>(
+            &mut pixmap.as_mut(),
+            &mut mask,
+            &Viewport::with_physical_size(self.size, 1.0),
+            &[self.bounds()],
+            self.theme.palette().background,
+            &[],
+        );
+
+        // The software renderer writes blue in the red channel and the other
+        // way around, as its window surfaces expect
+        let rgba = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.blue(), pixel.green(), pixel.red(), pixel.alpha()]
+            })
+            .collect();
+
+        Ok(Image {
+            width,
+            height,
+            rgba,
+        })
+    }
+}
+
+/// Draws `program` alone into an image of `size` pixels
+pub fn render<Message, P>(program: &P, size: Size<u32>, theme: &Theme) -> Result<Image, AppError>
+where
+    P: canvas::Program<Message>,
+{
+    let mut offscreen = Offscreen::new(size, theme)?;
+    let bounds = offscreen.bounds();
+
+    offscreen.draw(program, bounds);
+    offscreen.finish()
+}
+
+/// Makes the app's icon fonts available to charts drawn before, or without,
+/// the app starting. The app loads them itself otherwise.
+pub fn load_fonts() {
+    let Ok(mut fonts) = font_system().write() else {
+        return;
+    };
+
+    for font in [
+        include_bytes!("../../../fonts/util-icons.ttf").as_slice(),
+        include_bytes!("../../../fonts/legend-icons.ttf").as_slice(),
+        include_bytes!("../../../fonts/line-type-icons.ttf").as_slice(),
+    ] {
+        fonts.load_font(Cow::Borrowed(font));
+    }
+}
@@ -0,0 +1,256 @@
+use crate::utils::{formatting::Thresholds, AxisFormat, NotationMode};
+
+use super::super::line::GraphType;
+use super::graph::{
+    hex_color, AspectRatio, ChartColor, ChartStyle, GridStyle, LegendPosition, LegendSort,
+};
+
+/// The view options of a chart tab which are set from its toolbar and
+/// should survive refreshes and restores.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChartViewState {
+    pub legend: LegendPosition,
+    pub legend_sort: LegendSort,
+    /// Only used by line graphs
+    pub graph_type: GraphType,
+    pub notation: NotationMode,
+    /// Decimal places and digit grouping of the X axis. Its notation is
+    /// `notation`.
+    pub x_format: AxisFormat,
+    /// Decimal places and digit grouping of the Y axis. Its notation is
+    /// `notation`.
+    pub y_format: AxisFormat,
+    /// Hue, in the range [0, 1], that colors are generated around
+    pub accent: Option<f32>,
+    pub sequential_x: bool,
+    pub sequential_y: bool,
+    pub clean: bool,
+    /// Draws guide lines from the cursor to the axes
+    pub crosshair: bool,
+    /// Always rotates the X axis labels
+    pub rotate_labels: bool,
+    /// Only used by bar charts
+    pub horizontal: bool,
+    /// Only used by bar charts
+    pub order: bool,
+    /// Writes the value of each segment inside it. Only used by stacked bar
+    /// charts
+    pub value_labels: bool,
+    /// Keeps the chart at a fixed width to height ratio instead of filling
+    /// the tab
+    pub aspect: AspectRatio,
+    pub style: ChartStyle,
+}
+
+impl ChartViewState {
+    /// Where [`NotationMode::Auto`] switches away from plain numbers. Both
+    /// axes share them.
+    pub fn thresholds(&self) -> Thresholds {
+        self.y_format.thresholds
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: Thresholds) {
+        self.x_format.thresholds = thresholds;
+        self.y_format.thresholds = thresholds;
+    }
+
+    /// Serializes self as `key=value` lines
+    pub fn serialize(&self) -> String {
+        let accent = self
+            .accent
+            .map(|accent| accent.to_string())
+            .unwrap_or_else(|| "none".into());
+
+        let decimals = |format: &AxisFormat| {
+            format
+                .decimals
+                .map(|decimals| decimals.to_string())
+                .unwrap_or_else(|| "none".into())
+        };
+
+        let style = ChartColor::ALL.into_iter().map(|part| {
+            let color = self
+                .style
+                .get(part)
+                .map(hex_color)
+                .unwrap_or_else(|| "none".into());
+
+            (part.key(), color)
+        });
+
+        [
+            ("legend", self.legend.to_string()),
+            ("legend_sort", self.legend_sort.to_string()),
+            ("graph_type", self.graph_type.to_string()),
+            ("notation", self.notation.to_string()),
+            ("small_threshold", self.thresholds().small.to_string()),
+            ("large_threshold", self.thresholds().large.to_string()),
+            ("x_decimals", decimals(&self.x_format)),
+            ("x_thousands", self.x_format.thousands.to_string()),
+            ("y_decimals", decimals(&self.y_format)),
+            ("y_thousands", self.y_format.thousands.to_string()),
+            ("accent", accent),
+            ("sequential_x", self.sequential_x.to_string()),
+            ("sequential_y", self.sequential_y.to_string()),
+            ("clean", self.clean.to_string()),
+            ("crosshair", self.crosshair.to_string()),
+            ("rotate_labels", self.rotate_labels.to_string()),
+            ("horizontal", self.horizontal.to_string()),
+            ("order", self.order.to_string()),
+            ("value_labels", self.value_labels.to_string()),
+            ("aspect", self.aspect.to_string()),
+            ("grid", self.style.grid.to_string()),
+        ]
+        .into_iter()
+        .chain(style)
+        .map(|(key, value)| format!("{key}={value}\n"))
+        .collect()
+    }
+
+    /// Parses `key=value` lines produced by [`ChartViewState::serialize`].
+    /// Unknown keys and invalid values are ignored, leaving the default in
+    /// place.
+    pub fn deserialize(input: &str) -> Self {
+        let mut state = Self::default();
+
+        for (key, value) in input
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+        {
+            match key {
+                "legend" => {
+                    if let Some(legend) = LegendPosition::ALL
+                        .into_iter()
+                        .find(|legend| legend.to_string() == value)
+                    {
+                        state.legend = legend;
+                    }
+                }
+                "legend_sort" => {
+                    if let Some(sort) = LegendSort::ALL
+                        .into_iter()
+                        .find(|sort| sort.to_string() == value)
+                    {
+                        state.legend_sort = sort;
+                    }
+                }
+                "graph_type" => {
+                    if let Some(kind) = GraphType::ALL
+                        .into_iter()
+                        .find(|kind| kind.to_string() == value)
+                    {
+                        state.graph_type = kind;
+                    }
+                }
+                "notation" => {
+                    if let Some(notation) = NotationMode::ALL
+                        .into_iter()
+                        .find(|notation| notation.to_string() == value)
+                    {
+                        state.notation = notation;
+                    }
+                }
+                "small_threshold" => {
+                    if let Ok(small) = value.parse() {
+                        state.set_thresholds(Thresholds {
+                            small,
+                            ..state.thresholds()
+                        });
+                    }
+                }
+                "large_threshold" => {
+                    if let Ok(large) = value.parse() {
+                        state.set_thresholds(Thresholds {
+                            large,
+                            ..state.thresholds()
+                        });
+                    }
+                }
+                "x_decimals" => state.x_format.decimals = value.parse().ok(),
+                "x_thousands" => state.x_format.thousands = value.parse().unwrap_or_default(),
+                "y_decimals" => state.y_format.decimals = value.parse().ok(),
+                "y_thousands" => state.y_format.thousands = value.parse().unwrap_or_default(),
+                "accent" => state.accent = value.parse().ok(),
+                "sequential_x" => state.sequential_x = value.parse().unwrap_or_default(),
+                "sequential_y" => state.sequential_y = value.parse().unwrap_or_default(),
+                "clean" => state.clean = value.parse().unwrap_or_default(),
+                "crosshair" => state.crosshair = value.parse().unwrap_or_default(),
+                "rotate_labels" => state.rotate_labels = value.parse().unwrap_or_default(),
+                "horizontal" => state.horizontal = value.parse().unwrap_or_default(),
+                "order" => state.order = value.parse().unwrap_or_default(),
+                "value_labels" => state.value_labels = value.parse().unwrap_or_default(),
+                "aspect" => {
+                    if let Some(aspect) = AspectRatio::ALL
+                        .into_iter()
+                        .find(|aspect| aspect.to_string() == value)
+                    {
+                        state.aspect = aspect;
+                    }
+                }
+                "grid" => {
+                    if let Some(grid) = GridStyle::ALL
+                        .into_iter()
+                        .find(|grid| grid.to_string() == value)
+                    {
+                        state.style.grid = grid;
+                    }
+                }
+                key => {
+                    if let Some(part) = ChartColor::ALL.into_iter().find(|part| part.key() == key) {
+                        state.style.set(part, iced::Color::parse(value));
+                    }
+                }
+            }
+        }
+
+        state
+    }
+}
+
+#[test]
+fn test_view_state_round_trip() {
+    let mut state = ChartViewState {
+        legend: LegendPosition::BottomCenter,
+        legend_sort: LegendSort::ValueDescending,
+        graph_type: GraphType::Point,
+        notation: NotationMode::SI,
+        x_format: AxisFormat {
+            decimals: Some(2),
+            ..Default::default()
+        },
+        y_format: AxisFormat {
+            thousands: true,
+            ..Default::default()
+        },
+        accent: Some(0.25),
+        sequential_x: true,
+        sequential_y: false,
+        clean: true,
+        crosshair: true,
+        rotate_labels: true,
+        horizontal: true,
+        order: false,
+        value_labels: true,
+        aspect: AspectRatio::Wide,
+        style: ChartStyle {
+            axis: Some(iced::Color::from_rgb8(0x12, 0x34, 0x56)),
+            caption: Some(iced::Color::WHITE),
+            grid: GridStyle::Dotted,
+            ..Default::default()
+        },
+    };
+    state.set_thresholds(Thresholds {
+        small: 1e-4,
+        large: 1e3,
+    });
+
+    assert_eq!(ChartViewState::deserialize(&state.serialize()), state);
+
+    let defaults = ChartViewState::default();
+    assert_eq!(ChartViewState::deserialize(&defaults.serialize()), defaults);
+    assert_eq!(
+        ChartViewState::deserialize("legend=Elsewhere\nunknown=1"),
+        defaults
+    );
+}
@@ -1,12 +1,16 @@
-use std::{collections::HashMap, fmt::Debug, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    path::PathBuf,
+};
 
 use iced::{
     alignment,
     widget::{
-        button, canvas, checkbox, column, container, horizontal_space, row, text, text_input,
-        Canvas, Tooltip,
+        button, canvas, checkbox, column, container, horizontal_space, pick_list, row, text,
+        text_input, Canvas, Tooltip,
     },
-    Alignment, Color, Element, Font, Length, Padding, Point, Renderer, Size, Theme,
+    Alignment, Color, Element, Font, Length, Padding, Point, Rectangle, Renderer, Size, Theme,
 };
 
 use modav_core::{
@@ -14,12 +18,22 @@ use modav_core::{
         stacked_bar::{StackedBar, StackedBarChart},
         Scale,
     },
-    repr::sheet::{builders::SheetBuilder, utils::Data},
+    repr::sheet::{
+        builders::SheetBuilder,
+        utils::{Data, HeaderLabelStrategy},
+    },
 };
 use tracing::warn;
 
 use crate::{
-    utils::{coloring::ColorEngine, icons, parse_ints, tooltip, AppError, Selection},
+    utils::{
+        coloring::ColorEngine,
+        csv, format_data,
+        formatting::{numeric_value, Thresholds},
+        icons, parse_ints,
+        sampling::RowLimit,
+        tooltip, AppError, AxisFormat, NotationMode, Selection,
+    },
     widgets::{
         toolbar::{ToolBarOrientation, ToolbarMenu},
         wizard::StackedBarChartConfigState,
@@ -30,11 +44,23 @@ use crate::{
 use super::{
     parse_seed,
     shared::{
-        graph::{create_axis, Axis, DrawnOutput, Graph, Graphable, LegendPosition},
-        ContentAreaContainer, EditorButtonStyle,
+        aspect_row, axis_format_row, chart_container, empty_chart, export_image_row,
+        export_metadata_row, export_pdf_row,
+        graph::{
+            create_axis, create_percent_axis, legend_row, legend_rows, muted, AspectRatio, Axis,
+            ChartColor, DrawnOutput, Graph, Graphable, GridStyle, HoverPoint, LegendEntry,
+            LegendPosition, LegendSort, LEGEND_ROW_HEIGHT,
+        },
+        mapped_summary,
+        metadata::{AxisMetadata, ChartMetadata, SeriesMetadata},
+        notation_row,
+        offscreen::{self, Image},
+        reconfigure_row,
+        state::ChartViewState,
+        style_panel, ContentAreaContainer, EditorButtonStyle, StyleInputs,
     },
     tabs::TabLabel,
-    Viewable,
+    SessionTab, Viewable,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -55,10 +81,115 @@ impl GraphBar {
     fn y(&self) -> &Data {
         &self.bar.point.y
     }
+
+    /// Returns the label, fraction and bounds of each visible segment, in
+    /// stacking order
+    fn segments<'a>(
+        &'a self,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        is_horizontal: bool,
+        hidden: &HashSet<String>,
+    ) -> Vec<(&'a String, f64, Rectangle)> {
+        let (x_output, y_output) = if is_horizontal {
+            (y_output, x_output)
+        } else {
+            (x_output, y_output)
+        };
+
+        let x = match x_output.get_closest(self.x(), true) {
+            Some(x) => x,
+            None => {
+                warn!("Stacked BartChart x point, {} not found", self.x());
+                return Vec::new();
+            }
+        };
+
+        let y = match y_output.get_closest(self.y(), false) {
+            Some(y) => y,
+            None => {
+                warn!("Stacked BarChart y point, {} not found", self.y());
+                return Vec::new();
+            }
+        };
+
+        let DrawnOutput {
+            axis_pos: x_axis,
+            spacing: x_spacing,
+            ..
+        } = x_output;
+
+        // Hidden segments are left out, so the bar only stacks up to the
+        // total of the visible ones
+        let mut fractions = self
+            .bar
+            .fractions
+            .iter()
+            .filter(|(label, _)| !hidden.contains(*label))
+            .collect::<Vec<(&String, &f64)>>();
+        fractions.sort_by(|x, y| {
+            let x = *x.1;
+            let y = y.1;
+
+            x.total_cmp(y)
+        });
+
+        let mut segments = Vec::with_capacity(fractions.len());
+
+        if is_horizontal {
+            let height = x_spacing / 2.0;
+
+            let mut base = *x_axis;
+            let y = y - x_axis;
+
+            for (label, fraction) in fractions.into_iter().rev() {
+                let width = (*fraction * y as f64) as f32;
+
+                let top_left = Point::new(base, x - (0.5 * height));
+                base += width;
+
+                segments.push((
+                    label,
+                    *fraction,
+                    Rectangle::new(top_left, Size::new(width, height)),
+                ));
+            }
+        } else {
+            let width = x_spacing / 2.0;
+            let mut base = *x_axis;
+            let y = x_axis - y;
+
+            for (label, fraction) in fractions.into_iter().rev() {
+                let height = (*fraction * y as f64) as f32;
+                base -= height;
+
+                let top_left = Point::new(x - (width * 0.5), base);
+
+                segments.push((
+                    label,
+                    *fraction,
+                    Rectangle::new(top_left, Size::new(width, height)),
+                ));
+            }
+        }
+
+        segments
+    }
 }
 
 impl Graphable for GraphBar {
-    type Data<'a> = (usize, bool, &'a HashMap<String, Color>);
+    /// The id of the bar drawing the legend, whether bars are horizontal,
+    /// the label colors, the labels in legend order, the hidden labels and
+    /// the color and format of segment values and whether they are
+    /// percentages, if they are labelled
+    type Data<'a> = (
+        usize,
+        bool,
+        &'a HashMap<String, Color>,
+        &'a [String],
+        &'a HashSet<String>,
+        Option<(Color, AxisFormat, bool)>,
+    );
 
     fn label(&self) -> Option<&String> {
         None
@@ -77,27 +208,35 @@ impl Graphable for GraphBar {
         data: &Self::Data<'_>,
     ) {
         let colors = data.2;
+        let labels = data.3;
+        let hidden = data.4;
 
         if self.id != data.0 {
             return;
         }
 
-        if colors.len() == 0 {
+        if labels.is_empty() {
             return;
         }
 
-        let y_padding = bounds.height / (colors.len() as f32);
         let spacing = 5.0;
         let text_size = 12.0;
         let color_size = Size::new(12.0, 12.0);
 
         let mut count = 0.0;
 
-        for (label, label_color) in colors.iter() {
-            let y = bounds.position().y + (count * y_padding);
+        for label in labels.iter().take(legend_rows(labels.len()).0) {
+            let y = bounds.position().y + (count * LEGEND_ROW_HEIGHT);
             let position = Point::new(bounds.x, y);
+            let label_color = colors.get(label).copied().unwrap_or(color);
 
-            frame.fill_rectangle(position, color_size, *label_color);
+            let (label_color, color) = if hidden.contains(label) {
+                (muted(label_color), muted(color))
+            } else {
+                (label_color, color)
+            };
+
+            frame.fill_rectangle(position, color_size, label_color);
 
             let position = Point::new(
                 position.x + spacing + color_size.width,
@@ -119,6 +258,52 @@ impl Graphable for GraphBar {
         }
     }
 
+    fn legend_entry_at(
+        &self,
+        bounds: iced::Rectangle,
+        _idx: usize,
+        point: Point,
+        data: &Self::Data<'_>,
+    ) -> Option<usize> {
+        if self.id != data.0 {
+            return None;
+        }
+
+        let (rows, _) = legend_rows(data.3.len());
+        (0..rows).find(|row| legend_row(bounds, *row).contains(point))
+    }
+
+    fn hover_points(
+        &self,
+        x_output: &DrawnOutput,
+        y_output: &DrawnOutput,
+        data: &Self::Data<'_>,
+    ) -> Vec<HoverPoint> {
+        let is_horizontal = data.1;
+        let total = numeric_value(self.y());
+
+        // Each segment shows its own value at its outer edge
+        self.segments(x_output, y_output, is_horizontal, data.4)
+            .into_iter()
+            .map(|(label, fraction, segment)| {
+                let position = if is_horizontal {
+                    Point::new(segment.x + segment.width, segment.center_y())
+                } else {
+                    Point::new(segment.center_x(), segment.y)
+                };
+
+                HoverPoint {
+                    position,
+                    label: Some(label.clone()),
+                    x: self.x().clone(),
+                    y: total
+                        .map(|total| Data::Float((fraction * total) as f32))
+                        .unwrap_or(Data::None),
+                }
+            })
+            .collect()
+    }
+
     fn draw(
         &self,
         frame: &mut canvas::Frame,
@@ -126,79 +311,44 @@ impl Graphable for GraphBar {
         y_output: &DrawnOutput,
         data: &Self::Data<'_>,
     ) {
-        let is_horizontal = data.1;
         let colors = data.2;
+        let segments = self.segments(x_output, y_output, data.1, data.4);
 
-        let mut x_output = x_output;
-        let mut y_output = y_output;
+        for (label, _, segment) in segments.iter() {
+            let color = colors.get(*label).copied().unwrap_or(Color::BLACK);
 
-        if is_horizontal {
-            let temp = x_output;
-            x_output = y_output;
-            y_output = temp;
+            frame.fill_rectangle(segment.position(), segment.size(), color);
         }
 
-        let x = match x_output.get_closest(self.x(), true) {
-            Some(x) => x,
-            None => {
-                warn!("Stacked BartChart x point, {} not found", self.x());
-                return;
-            }
+        let Some((text_color, format, percent)) = data.5 else {
+            return;
         };
-
-        let y = match y_output.get_closest(self.y(), false) {
-            Some(y) => y,
-            None => {
-                warn!("Stacked BarChart y point, {} not found", self.y());
-                return;
-            }
+        let Some(total) = numeric_value(self.y()) else {
+            return;
         };
 
-        let DrawnOutput {
-            axis_pos: x_axis,
-            spacing: x_spacing,
-            ..
-        } = x_output;
-
-        let mut fractions = self.bar.fractions.iter().collect::<Vec<(&String, &f64)>>();
-        fractions.sort_by(|x, y| {
-            let x = *x.1;
-            let y = y.1;
+        let text_size = 11.0;
+        let padding = 2.0;
 
-            x.total_cmp(y)
-        });
+        for (_, fraction, segment) in segments {
+            let value = format_data(&Data::Float((fraction * total) as f32), &format);
+            let value = if percent { format!("{value}%") } else { value };
 
-        if is_horizontal {
-            let height = x_spacing / 2.0;
-
-            let mut base = *x_axis;
-            let y = y - x_axis;
-
-            for (label, fraction) in fractions.iter().rev() {
-                let width = (*fraction * y as f64) as f32;
-
-                let size = Size::new(width, height);
-                let top_left = Point::new(base, x - (0.5 * height));
-                let color = colors.get(*label).copied().unwrap_or(Color::BLACK);
-                base += width;
-
-                frame.fill_rectangle(top_left, size, color);
+            // Values are left out of segments they would spill over
+            let width = value.chars().count() as f32 * 0.6 * text_size + 2.0 * padding;
+            if width > segment.width || text_size + 2.0 * padding > segment.height {
+                continue;
             }
-        } else {
-            let width = x_spacing / 2.0;
-            let mut base = *x_axis;
-            let y = x_axis - y;
-
-            for (label, fraction) in fractions.iter().rev() {
-                let height = (*fraction * y as f64) as f32;
-                base -= height;
 
-                let size = Size::new(width, height);
-                let top_left = Point::new(x - (width * 0.5), base);
-                let color = colors.get(*label).copied().unwrap_or(Color::BLACK);
-
-                frame.fill_rectangle(top_left, size, color);
-            }
+            frame.fill_text(canvas::Text {
+                content: value,
+                position: segment.center(),
+                color: text_color,
+                size: text_size.into(),
+                horizontal_alignment: alignment::Horizontal::Center,
+                vertical_alignment: alignment::Vertical::Center,
+                ..Default::default()
+            });
         }
     }
 }
@@ -209,16 +359,36 @@ pub enum StackedBarChartMessage {
     SequentialX(bool),
     SequentialY(bool),
     Clean(bool),
+    Crosshair(bool),
+    RotateLabels(bool),
     Horizontal(bool),
+    ValueLabels(bool),
     CaptionChange(String),
     XLabelChanged(String),
     YLabelChanged(String),
     TitleChanged(String),
     Legend(LegendPosition),
+    LegendSort(LegendSort),
+    /// Hides or shows the segments of the clicked legend entry's label
+    ToggleSeries(LegendEntry),
+    Notation(NotationMode),
+    /// Sets where automatic notation switches away from plain numbers
+    Thresholds(Thresholds),
+    XFormat(AxisFormat),
+    YFormat(AxisFormat),
     ChangeSeed(String),
+    AccentChanged(String),
     ApplySeed,
     RandomSeed,
-    Debug,
+    Aspect(AspectRatio),
+    /// Reopens the wizard with the chart's configuration
+    Reconfigure,
+    ExportMetadata,
+    ExportImage,
+    ExportPdf,
+    ToggleStyle,
+    StyleColor(ChartColor, String),
+    GridStyle(GridStyle),
     None,
 }
 
@@ -231,6 +401,10 @@ pub struct StackedBarChartTabData {
     is_horizontal: bool,
     theme: Theme,
     chart: StackedBarChart,
+    config: StackedBarChartConfigState,
+    /// Rows shown and total rows, if only a sample of rows was loaded
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
 }
 
 impl StackedBarChartTabData {
@@ -241,16 +415,28 @@ impl StackedBarChartTabData {
             x_col,
             is_horizontal,
             order,
+            percent,
             axis_label,
             header_types,
             header_labels,
             flexible,
+            delimiter,
             trim,
             caption,
+            row_limit,
+            filters,
             ..
-        } = config;
+        } = config.clone();
+
+        let has_header = matches!(header_labels, HeaderLabelStrategy::ReadLabels);
+        let source = csv::normalize(&file, delimiter, flexible)?;
+        let source = csv::filter(&source, has_header, &filters)?;
+        let shape = csv::Shape::read(&source, has_header)?;
 
-        let sht = SheetBuilder::new(file.clone().into())
+        let (source, sample) = csv::sample(&source, has_header, row_limit, &mut HashSet::new())?;
+        let sample = sample.map(|sample| (sample.shown, sample.total));
+
+        let sht = SheetBuilder::new(source.to_path_buf().into())
             .trim(trim)
             .flexible(flexible)
             .labels(header_labels)
@@ -262,10 +448,18 @@ impl StackedBarChartTabData {
         let end = if sht.width() == 0 { 0 } else { sht.width() - 1 };
         let acc_cols = Selection::to_vec(acc_cols, end);
 
-        let stacked = sht
+        let mut stacked = sht
             .create_stacked_bar_chart(x_col, acc_cols, axis_label)
             .map_err(AppError::CSVError)?;
 
+        // Segments keep their fractions, so stretching each bar to 100 is
+        // enough to show their shares
+        if percent {
+            for bar in stacked.bars.iter_mut() {
+                bar.point.y = Data::Float(100.0);
+            }
+        }
+
         Ok(Self {
             file,
             title,
@@ -274,6 +468,9 @@ impl StackedBarChartTabData {
             is_horizontal,
             caption,
             theme: Theme::default(),
+            config,
+            sample,
+            shape,
         })
     }
 
@@ -291,67 +488,206 @@ pub struct StackedBarChartTab {
     x_label: Option<String>,
     y_axis: Scale,
     y_label: Option<String>,
-    is_horizontal: bool,
     config_shown: bool,
-    order: bool,
-    sequential_x: bool,
-    sequential_y: bool,
     bars: Vec<GraphBar>,
-    clean: bool,
     cache: canvas::Cache,
     labels_len: usize,
     theme: Theme,
     colors: HashMap<String, Color>,
+    /// Labels in the order they are listed in the legend
+    legend: Vec<String>,
+    /// Labels whose segments are not drawn
+    hidden: HashSet<String>,
     color_seed: f32,
     caption: Option<String>,
-    legend: LegendPosition,
+    config: StackedBarChartConfigState,
+    sample: Option<(usize, usize)>,
+    shape: csv::Shape,
+    view_state: ChartViewState,
+    style_shown: bool,
+    style_inputs: StyleInputs,
 }
 
 impl StackedBarChartTab {
-    fn create_axis(&self) -> (Axis, Axis) {
-        let (x_scale, y_scale) = if self.is_horizontal {
-            (&self.y_axis, &self.x_axis)
+    fn create_axis(&self) -> Result<(Axis, Axis), AppError> {
+        let horizontal = self.view_state.horizontal;
+
+        let (x_axis, y_axis) = if self.config.percent {
+            let sequential = if horizontal {
+                self.view_state.sequential_y
+            } else {
+                self.view_state.sequential_x
+            };
+
+            create_percent_axis(&self.x_axis, sequential, self.view_state.clean, horizontal)?
         } else {
-            (&self.x_axis, &self.y_axis)
+            let (x_scale, y_scale) = if horizontal {
+                (&self.y_axis, &self.x_axis)
+            } else {
+                (&self.x_axis, &self.y_axis)
+            };
+
+            create_axis(
+                x_scale,
+                y_scale,
+                self.view_state.sequential_x,
+                self.view_state.sequential_y,
+                self.view_state.clean,
+                None,
+                None,
+            )?
         };
 
-        let (x_axis, y_axis) = create_axis(
-            x_scale,
-            y_scale,
-            self.sequential_x,
-            self.sequential_y,
-            self.clean,
-        );
+        let value_label = if self.config.percent {
+            Some(match &self.y_label {
+                Some(label) => format!("{label} (%)"),
+                None => String::from("%"),
+            })
+        } else {
+            self.y_label.clone()
+        };
 
-        let (x_label, y_label) = if self.is_horizontal {
-            (self.y_label.clone(), self.x_label.clone())
+        let (x_label, y_label) = if horizontal {
+            (value_label, self.x_label.clone())
         } else {
-            (self.x_label.clone(), self.y_label.clone())
+            (self.x_label.clone(), value_label)
         };
 
-        return (x_axis.label(x_label), y_axis.label(y_label));
+        Ok((
+            x_axis
+                .label(x_label)
+                .format(self.view_state.x_format)
+                .rotate_labels(self.view_state.rotate_labels),
+            y_axis.label(y_label).format(self.view_state.y_format),
+        ))
+    }
+
+    /// The color and format of segment values and whether they are
+    /// percentages, if they are labelled
+    fn value_labels(&self) -> Option<(Color, AxisFormat, bool)> {
+        self.view_state.value_labels.then(|| {
+            let format = AxisFormat {
+                notation: self.view_state.notation,
+                ..self.view_state.y_format
+            };
+
+            (self.theme.palette().text, format, self.config.percent)
+        })
+    }
+
+    /// The chart shown in the tab, drawn into `cache`
+    fn chart<'a>(
+        &'a self,
+        cache: &'a canvas::Cache,
+    ) -> Result<Graph<'a, GraphBar, StackedBarChartMessage>, AppError> {
+        let (x_axis, y_axis) = self.create_axis()?;
+
+        Ok(Graph::new(
+            x_axis,
+            y_axis,
+            &self.bars,
+            &self.theme,
+            cache,
+            (
+                0,
+                self.view_state.horizontal,
+                &self.colors,
+                self.legend.as_slice(),
+                &self.hidden,
+                self.value_labels(),
+            ),
+        )
+        .title(Some(self.title.as_str()).filter(|title| !title.is_empty()))
+        .caption(self.caption.as_ref())
+        .labels_len(self.labels_len)
+        .legend(self.view_state.legend)
+        .on_legend_toggle(StackedBarChartMessage::ToggleSeries)
+        .notation(self.view_state.notation)
+        .crosshair(self.view_state.crosshair)
+        .style(self.view_state.style))
     }
 
     fn graph(&self) -> Element<'_, StackedBarChartMessage> {
-        let (x_axis, y_axis) = self.create_axis();
+        let content: Element<'_, StackedBarChartMessage> = match self.chart(&self.cache) {
+            Ok(chart) => Canvas::new(chart)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            Err(err) => empty_chart(&err),
+        };
+
+        chart_container(content, self.view_state.aspect).into()
+    }
+
+    /// The chart alone, without a legend or interaction, for the wizard's
+    /// live preview
+    pub fn preview<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        let (x_axis, y_axis) = match self.create_axis() {
+            Ok(axes) => axes,
+            Err(err) => return empty_chart(&err),
+        };
 
-        let content = Canvas::new(
+        Canvas::new(
             Graph::new(
                 x_axis,
                 y_axis,
                 &self.bars,
                 &self.theme,
                 &self.cache,
-                (0, self.is_horizontal, &self.colors),
+                (
+                    0,
+                    self.view_state.horizontal,
+                    &self.colors,
+                    self.legend.as_slice(),
+                    &self.hidden,
+                    self.value_labels(),
+                ),
             )
-            .caption(self.caption.as_ref())
             .labels_len(self.labels_len)
-            .legend(self.legend),
+            .legend(LegendPosition::None)
+            .notation(self.view_state.notation)
+            .style(self.view_state.style),
         )
-        .width(Length::FillPortion(24))
-        .height(Length::Fill);
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
 
-        content.into()
+    /// Returns the plotted data as CSV, leaving out hidden series if
+    /// `visible_only`
+    fn csv(&self, visible_only: bool) -> Option<String> {
+        let mut labels = self
+            .colors
+            .keys()
+            .filter(|label| !(visible_only && self.hidden.contains(*label)))
+            .collect::<Vec<&String>>();
+        labels.sort();
+
+        let header = csv::record(
+            std::iter::once(self.x_label.as_deref().unwrap_or("x"))
+                .chain(labels.iter().map(|label| label.as_str())),
+        );
+
+        let rows = self.bars.iter().map(|bar| {
+            let total = numeric_value(bar.y());
+            let values = labels.iter().map(|label| {
+                bar.bar
+                    .fractions
+                    .get(*label)
+                    .zip(total)
+                    .map(|(fraction, total)| (fraction * total).to_string())
+                    .unwrap_or_default()
+            });
+
+            csv::record(std::iter::once(bar.x().to_string()).chain(values))
+        });
+
+        Some(
+            std::iter::once(header)
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
     }
 
     fn redraw(&mut self) {
@@ -401,8 +737,8 @@ impl StackedBarChartTab {
 
         let ranged_x = {
             let check = {
-                let check =
-                    checkbox("", self.sequential_x).on_toggle(StackedBarChartMessage::SequentialX);
+                let check = checkbox("", self.view_state.sequential_x)
+                    .on_toggle(StackedBarChartMessage::SequentialX);
                 let label = text("Ranged X axis");
 
                 row!(label, check).spacing(8.0).align_y(Alignment::Center)
@@ -415,8 +751,8 @@ impl StackedBarChartTab {
 
         let ranged_y = {
             let check = {
-                let check =
-                    checkbox("", self.sequential_y).on_toggle(StackedBarChartMessage::SequentialY);
+                let check = checkbox("", self.view_state.sequential_y)
+                    .on_toggle(StackedBarChartMessage::SequentialY);
                 let label = text("Ranged Y axis");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -429,7 +765,8 @@ impl StackedBarChartTab {
 
         let clean = {
             let check = {
-                let check = checkbox("", self.clean).on_toggle(StackedBarChartMessage::Clean);
+                let check =
+                    checkbox("", self.view_state.clean).on_toggle(StackedBarChartMessage::Clean);
                 let label = text("Clean graph");
 
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
@@ -440,6 +777,37 @@ impl StackedBarChartTab {
             row!(check, tip).spacing(spacing)
         };
 
+        let crosshair = {
+            let check = {
+                let check = checkbox("", self.view_state.crosshair)
+                    .on_toggle(StackedBarChartMessage::Crosshair);
+                let label = text("Crosshair");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip(
+                "Draws guide lines from the cursor to the axes, labelled with their values",
+            );
+
+            row!(check, tip).spacing(spacing)
+        };
+
+        let rotate_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.rotate_labels)
+                    .on_toggle(StackedBarChartMessage::RotateLabels);
+                let label = text("Rotate X labels");
+
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip =
+                tooltip("Slants the X axis labels. Labels that would overlap are always slanted");
+
+            row!(check, tip).spacing(spacing)
+        };
+
         let seed = {
             let value = self.color_seed;
             let value = format!("{value:.4}");
@@ -474,8 +842,8 @@ impl StackedBarChartTab {
 
         let horizontal = {
             let check = {
-                let check =
-                    checkbox("", self.is_horizontal).on_toggle(StackedBarChartMessage::Horizontal);
+                let check = checkbox("", self.view_state.horizontal)
+                    .on_toggle(StackedBarChartMessage::Horizontal);
                 let label = text("Horizontal bars");
                 row!(label, check).align_y(Alignment::Center).spacing(8.0)
             };
@@ -485,12 +853,25 @@ impl StackedBarChartTab {
             row!(check, tip).spacing(spacing)
         };
 
+        let value_labels = {
+            let check = {
+                let check = checkbox("", self.view_state.value_labels)
+                    .on_toggle(StackedBarChartMessage::ValueLabels);
+                let label = text("Segment values");
+                row!(label, check).align_y(Alignment::Center).spacing(8.0)
+            };
+
+            let tip = tooltip("Writes the value of each segment inside it, if it fits");
+
+            row!(check, tip).spacing(spacing)
+        };
+
         let legend = {
             let icons = Font::with_name("legend-icons");
 
             let menu = ToolbarMenu::new(
                 LegendPosition::ALL,
-                self.legend,
+                self.view_state.legend,
                 StackedBarChartMessage::Legend,
                 icons,
             )
@@ -524,6 +905,66 @@ impl StackedBarChartTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let legend_sort = {
+            let label = text("Legend Order");
+
+            let pick_list = pick_list(
+                LegendSort::ALL,
+                Some(self.view_state.legend_sort),
+                StackedBarChartMessage::LegendSort,
+            )
+            .padding([2, 5]);
+
+            let tip = tooltip("Reorders the legend entries without changing how bars are stacked");
+
+            row!(label, pick_list, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let accent = {
+            let value = self
+                .view_state
+                .accent
+                .map(|hue| format!("{:.0}", hue * 360.0))
+                .unwrap_or_default();
+
+            let label = text("Accent Hue");
+
+            let input = text_input("Theme", &value)
+                .on_input(StackedBarChartMessage::AccentChanged)
+                .padding([2, 5])
+                .width(67.0);
+
+            let tip = tooltip(
+                "Hue, in degrees, that graph colors are generated around. Leave empty to follow the theme",
+            );
+
+            row!(label, input, tip)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+        };
+
+        let x_format = axis_format_row(
+            "X Axis Numbers",
+            self.view_state.x_format,
+            StackedBarChartMessage::XFormat,
+        );
+
+        let y_format = axis_format_row(
+            "Y Axis Numbers",
+            self.view_state.y_format,
+            StackedBarChartMessage::YFormat,
+        );
+
+        let notation = notation_row(
+            self.view_state.notation,
+            self.view_state.thresholds(),
+            "How very large or very small numbers on the axes are written",
+            StackedBarChartMessage::Notation,
+            StackedBarChartMessage::Thresholds,
+        );
+
         let editor = {
             let font = Font::with_name(icons::NAME);
 
@@ -560,15 +1001,86 @@ impl StackedBarChartTab {
             row!(text, menu).spacing(spacing).align_y(Alignment::Center)
         };
 
+        let style = style_panel(
+            &self.style_inputs,
+            &self.view_state.style,
+            self.style_shown,
+            StackedBarChartMessage::ToggleStyle,
+            StackedBarChartMessage::StyleColor,
+            StackedBarChartMessage::GridStyle,
+        );
+
+        let aspect = aspect_row(self.view_state.aspect, StackedBarChartMessage::Aspect);
+        let reconfigure = reconfigure_row(StackedBarChartMessage::Reconfigure);
+        let export = export_metadata_row(StackedBarChartMessage::ExportMetadata);
+        let image = export_image_row(StackedBarChartMessage::ExportImage);
+        let pdf = export_pdf_row(StackedBarChartMessage::ExportPdf);
+
         column!(
-            header, title, x_label, y_label, caption, ranged_x, ranged_y, clean, horizontal, seed,
-            legend, editor,
+            header,
+            title,
+            x_label,
+            y_label,
+            caption,
+            ranged_x,
+            ranged_y,
+            clean,
+            crosshair,
+            rotate_labels,
+            horizontal,
+            value_labels,
+            seed,
+            accent,
+            notation,
+            x_format,
+            y_format,
+            legend,
+            legend_sort,
+            style,
+            aspect,
+            reconfigure,
+            export,
+            image,
+            pdf,
+            editor,
         )
         .spacing(25.0)
         .into()
     }
 
+    /// Orders the legend labels by the legend sort. Segments are stacked by
+    /// their share of each bar, so labels have no overall draw order and
+    /// are listed by name instead.
+    fn sort_legend(&mut self) {
+        let mut labels = self.colors.keys().cloned().collect::<Vec<String>>();
+        labels.sort();
+
+        let order = self
+            .view_state
+            .legend_sort
+            .order(labels.iter().map(|label| {
+                let total = self
+                    .bars
+                    .iter()
+                    .filter_map(|bar| {
+                        let total = numeric_value(bar.y())?;
+                        let fraction = bar.bar.fractions.get(label)?;
+                        Some(fraction * total)
+                    })
+                    .sum::<f64>();
+
+                (Some(label.as_str()), Some(total))
+            }));
+
+        self.legend = order
+            .into_iter()
+            .map(|position| labels[position].clone())
+            .collect();
+    }
+
     fn recolor(&mut self, colors: ColorEngine) {
+        let colors = colors.count(self.colors.len() as u32);
+
         self.colors
             .iter_mut()
             .zip(colors)
@@ -593,6 +1105,9 @@ impl Viewable for StackedBarChartTab {
             order,
             caption,
             is_horizontal,
+            config,
+            sample,
+            shape,
         } = data;
 
         let StackedBarChart {
@@ -611,7 +1126,9 @@ impl Viewable for StackedBarChartTab {
             bars.sort_by(|one, two| one.point.y.cmp(&two.point.y));
         }
 
-        let engine = ColorEngine::new(&theme).gradual(order);
+        let engine = ColorEngine::new(&theme)
+            .gradual(order)
+            .count(labels_len as u32);
         let seed = engine.seed();
 
         let colors = labels
@@ -625,7 +1142,7 @@ impl Viewable for StackedBarChartTab {
             .map(|(id, bar)| GraphBar::new(id, bar))
             .collect::<Vec<GraphBar>>();
 
-        Self {
+        let mut tab = Self {
             title,
             file,
             labels_len,
@@ -633,20 +1150,29 @@ impl Viewable for StackedBarChartTab {
             x_label: x_axis,
             y_axis: y_scale,
             y_label: y_axis,
-            is_horizontal,
             config_shown: false,
             bars,
-            order,
             theme,
             colors,
-            sequential_x: false,
-            sequential_y: false,
+            legend: Vec::default(),
+            hidden: HashSet::default(),
             caption,
-            clean: false,
+            config,
+            sample,
+            shape,
             color_seed: seed,
             cache: canvas::Cache::default(),
-            legend: LegendPosition::default(),
-        }
+            view_state: ChartViewState {
+                horizontal: is_horizontal,
+                order,
+                ..Default::default()
+            },
+            style_shown: false,
+            style_inputs: StyleInputs::default(),
+        };
+
+        tab.sort_legend();
+        tab
     }
 
     fn is_dirty(&self) -> bool {
@@ -677,6 +1203,33 @@ impl Viewable for StackedBarChartTab {
         Some(self.file.clone())
     }
 
+    fn session(&self) -> Option<SessionTab> {
+        Some(SessionTab::StackedBarChart(
+            self.file.clone(),
+            self.config.clone(),
+        ))
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        self.sample
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        let shape = &self.shape;
+        let end = shape.columns.len().saturating_sub(1);
+        let stacked = Selection::to_vec(parse_ints(&self.config.acc_cols_str), end)
+            .into_iter()
+            .map(|column| shape.column_name(column))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        let mapped = [
+            ("X", shape.column_name(self.config.x_col)),
+            ("Stacked", stacked),
+        ];
+
+        Some(mapped_summary(shape, &mapped))
+    }
+
     fn can_save(&self) -> bool {
         false
     }
@@ -687,8 +1240,32 @@ impl Viewable for StackedBarChartTab {
 
     fn refresh(&mut self, data: Self::Data) {
         let new = <Self as Viewable>::new(data);
+        let view_state = self.view_state.clone();
+        let style_shown = self.style_shown;
 
         *self = new;
+        self.style_shown = style_shown;
+        self.restore_view_state(view_state);
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        if self.sample.is_none() {
+            return Ok(());
+        }
+
+        let config = StackedBarChartConfigState {
+            row_limit: RowLimit::default(),
+            ..self.config.clone()
+        };
+        let data =
+            StackedBarChartTabData::new(self.file.clone(), config)?.theme(self.theme.clone());
+        self.refresh(data);
+
+        Ok(())
+    }
+
+    fn scale_factor_changed(&mut self, _scale_factor: f32) {
+        self.cache.clear();
     }
 
     fn theme_changed(&mut self, theme: &Theme) {
@@ -698,12 +1275,88 @@ impl Viewable for StackedBarChartTab {
 
         self.theme = theme.clone();
 
-        let colors = ColorEngine::new(&self.theme).gradual(self.order);
+        let colors = ColorEngine::new(&self.theme)
+            .accent(self.view_state.accent)
+            .gradual(self.view_state.order);
         self.color_seed = colors.seed();
 
         self.recolor(colors);
     }
 
+    fn to_csv(&self) -> Option<String> {
+        self.csv(false)
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        self.csv(true)
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        let cache = canvas::Cache::new();
+
+        Some(
+            self.chart(&cache)
+                .and_then(|chart| offscreen::render(&chart, size, &self.theme)),
+        )
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        let mut labels = self.colors.iter().collect::<Vec<(&String, &Color)>>();
+        labels.sort_by(|one, two| one.0.cmp(two.0));
+
+        let series = labels
+            .into_iter()
+            .map(|(label, color)| {
+                let values = self
+                    .bars
+                    .iter()
+                    .filter_map(|bar| {
+                        let total = numeric_value(bar.y())?;
+                        let fraction = bar.bar.fractions.get(label)?;
+                        Some(fraction * total)
+                    })
+                    .collect();
+
+                SeriesMetadata::with_values(Some(label), *color, values)
+            })
+            .collect();
+
+        let metadata = ChartMetadata {
+            kind: "stacked_bar",
+            title: &self.title,
+            source: &self.file,
+            caption: self.caption.as_ref(),
+            x_axis: AxisMetadata::new(self.x_label.as_ref(), self.bars.iter().map(GraphBar::x)),
+            y_axis: AxisMetadata::new(self.y_label.as_ref(), self.bars.iter().map(GraphBar::y)),
+            series,
+        };
+
+        Some(metadata.to_json())
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        Some(self.view_state.clone())
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        let recolor = state.accent != self.view_state.accent;
+        self.style_inputs = StyleInputs::new(&state.style);
+        self.view_state = ChartViewState {
+            order: self.view_state.order,
+            ..state
+        };
+
+        if recolor {
+            let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                .accent(self.view_state.accent)
+                .gradual(self.view_state.order);
+            self.recolor(colors);
+        }
+
+        self.sort_legend();
+        self.cache.clear();
+    }
+
     fn has_config(&self) -> bool {
         true
     }
@@ -719,8 +1372,39 @@ impl Viewable for StackedBarChartTab {
     fn update(&mut self, message: Self::Event) -> Option<Message> {
         match message {
             StackedBarChartMessage::None => None,
-            StackedBarChartMessage::Debug => {
-                dbg!("Debugging!");
+            StackedBarChartMessage::GridStyle(grid) => {
+                self.view_state.style.grid = grid;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::ToggleStyle => {
+                self.style_shown = !self.style_shown;
+                None
+            }
+            StackedBarChartMessage::StyleColor(part, input) => {
+                if self
+                    .style_inputs
+                    .update(&mut self.view_state.style, part, input)
+                {
+                    self.cache.clear();
+                }
+                None
+            }
+            StackedBarChartMessage::AccentChanged(input) => {
+                let input = input.trim();
+
+                if input.is_empty() {
+                    self.view_state.accent = None;
+                } else if let Ok(degrees) = input.parse::<f32>() {
+                    self.view_state.accent = Some((degrees / 360.0).rem_euclid(1.0));
+                } else {
+                    return None;
+                }
+
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
+                self.recolor(colors);
                 None
             }
             StackedBarChartMessage::ChangeSeed(seed) => {
@@ -731,7 +1415,9 @@ impl Viewable for StackedBarChartTab {
                 None
             }
             StackedBarChartMessage::ApplySeed => {
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
                 self.recolor(colors);
                 None
             }
@@ -740,31 +1426,60 @@ impl Viewable for StackedBarChartTab {
                 let seed: f32 = thread_rng().gen();
                 self.color_seed = seed;
 
-                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed);
+                let colors = ColorEngine::new_with_seed(&self.theme, self.color_seed)
+                    .accent(self.view_state.accent)
+                    .gradual(self.view_state.order);
                 self.recolor(colors);
                 None
             }
+            StackedBarChartMessage::Aspect(aspect) => {
+                self.view_state.aspect = aspect;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::Reconfigure => Some(Message::Reconfigure),
+            StackedBarChartMessage::ExportMetadata => {
+                Some(Message::ChooseMetadataPath(self.file.clone()))
+            }
+            StackedBarChartMessage::ExportPdf => Some(Message::ChoosePdfPath(self.file.clone())),
+            StackedBarChartMessage::ExportImage => {
+                Some(Message::ChooseImagePath(self.file.clone()))
+            }
             StackedBarChartMessage::OpenEditor => {
                 self.config_shown = false;
                 Some(Message::OpenEditor(Some(self.file.clone())))
             }
             StackedBarChartMessage::SequentialX(seq) => {
-                self.sequential_x = seq;
+                self.view_state.sequential_x = seq;
                 self.cache.clear();
                 None
             }
             StackedBarChartMessage::SequentialY(seq) => {
-                self.sequential_y = seq;
+                self.view_state.sequential_y = seq;
                 self.cache.clear();
                 None
             }
             StackedBarChartMessage::Clean(clean) => {
-                self.clean = clean;
+                self.view_state.clean = clean;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::Crosshair(crosshair) => {
+                self.view_state.crosshair = crosshair;
+                None
+            }
+            StackedBarChartMessage::RotateLabels(rotate) => {
+                self.view_state.rotate_labels = rotate;
                 self.cache.clear();
                 None
             }
             StackedBarChartMessage::Horizontal(is_horizontal) => {
-                self.is_horizontal = is_horizontal;
+                self.view_state.horizontal = is_horizontal;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::ValueLabels(value_labels) => {
+                self.view_state.value_labels = value_labels;
                 self.cache.clear();
                 None
             }
@@ -791,8 +1506,42 @@ impl Viewable for StackedBarChartTab {
                 self.title = title;
                 None
             }
+            StackedBarChartMessage::Notation(notation) => {
+                self.view_state.notation = notation;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::Thresholds(thresholds) => {
+                self.view_state.set_thresholds(thresholds);
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::XFormat(format) => {
+                self.view_state.x_format = format;
+                self.cache.clear();
+                None
+            }
+            StackedBarChartMessage::YFormat(format) => {
+                self.view_state.y_format = format;
+                self.cache.clear();
+                None
+            }
             StackedBarChartMessage::Legend(legend) => {
-                self.legend = legend;
+                self.view_state.legend = legend;
+                None
+            }
+            StackedBarChartMessage::ToggleSeries(LegendEntry { entry, .. }) => {
+                if let Some(label) = self.legend.get(entry) {
+                    if !self.hidden.remove(label) {
+                        self.hidden.insert(label.clone());
+                    }
+                    self.cache.clear();
+                }
+                None
+            }
+            StackedBarChartMessage::LegendSort(sort) => {
+                self.view_state.legend_sort = sort;
+                self.sort_legend();
                 None
             }
         }
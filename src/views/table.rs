@@ -0,0 +1,552 @@
+use iced::{
+    alignment,
+    widget::{
+        button, column, container, horizontal_space, row, scrollable, text, text_input, tooltip,
+        Column, Row, Space, Tooltip,
+    },
+    Alignment, Element, Font, Length, Renderer, Theme,
+};
+use std::{fmt::Debug, path::PathBuf};
+
+use super::{ClosePolicy, SessionTab, TabLabel, Viewable};
+use crate::{
+    utils::{self, csv, icons},
+    Message, ToolTipContainerStyle,
+};
+
+/// Rows past this are not shown, to keep large files responsive
+const MAX_SHOWN_ROWS: usize = 500;
+const CELL_WIDTH: f32 = 130.0;
+/// Width of the buttons removing a row or column
+const REMOVE_WIDTH: f32 = 22.0;
+
+/// Header, rows and column kinds of a parsed table
+type Parsed = (Vec<String>, Vec<Vec<String>>, Vec<ColumnKind>);
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableTabData {
+    path: Option<PathBuf>,
+    data: String,
+    /// Where the data came from, for a table without a path
+    name: Option<String>,
+    /// Set by [`TableTabData::parse`] so the tab does not parse `data` again
+    parsed: Option<Parsed>,
+}
+
+impl TableTabData {
+    pub fn new(path: Option<PathBuf>, data: String) -> Self {
+        Self {
+            path,
+            data,
+            name: None,
+            parsed: None,
+        }
+    }
+
+    /// Names where the data came from, such as `clipboard`, for a table
+    /// without a path. Named tables start out unsaved so closing them
+    /// prompts first
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub fn data(mut self, data: String) -> Self {
+        self.data = data;
+        self.parsed = None;
+        self
+    }
+
+    /// Parses the data ahead of making the tab. This is slow for large
+    /// files, so it can be done away from the UI thread
+    pub fn parse(mut self) -> Self {
+        self.parsed = Some(TableTab::parse(&self.data));
+        self
+    }
+}
+
+/// What values a column accepts, inferred from its contents when loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numeric,
+    Text,
+}
+
+impl ColumnKind {
+    fn infer<'a>(mut values: impl Iterator<Item = &'a str>) -> Self {
+        let mut any = false;
+
+        let numeric = values.all(|value| {
+            let value = value.trim();
+            any |= !value.is_empty();
+            value.is_empty() || value.parse::<f64>().is_ok()
+        });
+
+        if numeric && any {
+            Self::Numeric
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Returns why `value` cannot be stored in a column of this kind
+    fn validate(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+
+        match self {
+            Self::Numeric if !value.is_empty() && value.parse::<f64>().is_err() => {
+                Err(format!("\"{value}\" is not a number"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An edit that was not applied because it does not suit its column
+#[derive(Debug, Clone, PartialEq)]
+struct RejectedEdit {
+    row: usize,
+    column: usize,
+    input: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum TableMessage {
+    EditCell(usize, usize, String),
+    EditHeader(usize, String),
+    AddRow,
+    RemoveRow(usize),
+    AddColumn,
+    RemoveColumn(usize),
+}
+
+#[derive(Debug)]
+pub struct TableTab {
+    file_path: Option<PathBuf>,
+    /// Where the data came from, until it is saved to a file
+    name: Option<String>,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    kinds: Vec<ColumnKind>,
+    /// Header as of the last load or save
+    saved_header: Vec<String>,
+    /// Rows as of the last load or save
+    saved: Vec<Vec<String>>,
+    rejected: Option<RejectedEdit>,
+}
+
+impl TableTab {
+    /// Splits `data` into a header and rows padded to the same width
+    fn parse(data: &str) -> Parsed {
+        let mut records = csv::parse(data).into_iter();
+        let mut header = records.next().unwrap_or_default();
+        let mut rows = records.collect::<Vec<_>>();
+
+        let width = rows.iter().map(Vec::len).fold(header.len(), usize::max);
+
+        header.resize(width, String::default());
+        rows.iter_mut()
+            .for_each(|row| row.resize(width, String::default()));
+
+        let kinds = (0..width)
+            .map(|col| ColumnKind::infer(rows.iter().map(|row| row[col].as_str())))
+            .collect();
+
+        (header, rows, kinds)
+    }
+
+    fn edit_cell(&mut self, row: usize, column: usize, input: String) {
+        let Some(kind) = self.kinds.get(column) else {
+            return;
+        };
+
+        if row >= self.rows.len() {
+            return;
+        }
+
+        match kind.validate(&input) {
+            Ok(()) => {
+                self.rows[row][column] = input;
+                self.rejected = None;
+            }
+            Err(reason) => {
+                self.rejected = Some(RejectedEdit {
+                    row,
+                    column,
+                    input,
+                    reason,
+                })
+            }
+        }
+    }
+
+    fn add_row(&mut self) {
+        self.rows.push(vec![String::default(); self.header.len()]);
+    }
+
+    fn remove_row(&mut self, row: usize) {
+        if row < self.rows.len() {
+            self.rows.remove(row);
+            self.rejected = None;
+        }
+    }
+
+    fn add_column(&mut self) {
+        self.header
+            .push(format!("Column {}", self.header.len() + 1));
+        self.kinds.push(ColumnKind::Text);
+        self.rows
+            .iter_mut()
+            .for_each(|row| row.push(String::default()));
+    }
+
+    fn remove_column(&mut self, column: usize) {
+        if column >= self.header.len() {
+            return;
+        }
+
+        self.header.remove(column);
+        self.kinds.remove(column);
+        self.rows.iter_mut().for_each(|row| {
+            row.remove(column);
+        });
+        self.rejected = None;
+    }
+
+    fn remove_button<'a>(
+        on_press: TableMessage,
+        description: &'static str,
+    ) -> Element<'a, TableMessage> {
+        let btn = button(
+            text(icons::CLOSE)
+                .font(Font::with_name(icons::NAME))
+                .size(11.0)
+                .align_x(alignment::Horizontal::Center)
+                .align_y(alignment::Vertical::Center),
+        )
+        .on_press(on_press)
+        .style(button::text)
+        .padding([2, 4])
+        .width(REMOVE_WIDTH);
+
+        let tip = container(text(description).size(12.0))
+            .padding([6, 8])
+            .style(|theme| {
+                <ToolTipContainerStyle as container::Catalog>::style(&ToolTipContainerStyle, theme)
+            });
+
+        Tooltip::new(btn, tip, tooltip::Position::Bottom)
+            .gap(2.0)
+            .snap_within_viewport(true)
+            .into()
+    }
+
+    fn cell(&self, row: usize, column: usize) -> Element<'_, TableMessage> {
+        let rejected = self
+            .rejected
+            .as_ref()
+            .filter(|edit| edit.row == row && edit.column == column);
+
+        let value = rejected
+            .map(|edit| edit.input.as_str())
+            .unwrap_or(self.rows[row][column].as_str());
+        let is_rejected = rejected.is_some();
+
+        text_input("", value)
+            .on_input(move |input| TableMessage::EditCell(row, column, input))
+            .size(14.0)
+            .padding([2, 5])
+            .width(CELL_WIDTH)
+            .style(move |theme: &Theme, status| {
+                let mut style = text_input::default(theme, status);
+
+                if is_rejected {
+                    style.border.color = theme.extended_palette().danger.base.color;
+                    style.border.width = 1.5;
+                }
+
+                style
+            })
+            .into()
+    }
+
+    fn grid(&self) -> Element<'_, TableMessage> {
+        let header = self.header.iter().enumerate().map(|(column, label)| {
+            let input = text_input("Name", label.as_str())
+                .on_input(move |input| TableMessage::EditHeader(column, input))
+                .size(14.0)
+                .padding([2, 5])
+                .width(CELL_WIDTH - REMOVE_WIDTH)
+                .font(Font {
+                    weight: iced::font::Weight::Semibold,
+                    ..Default::default()
+                });
+
+            row!(
+                input,
+                Self::remove_button(TableMessage::RemoveColumn(column), "Remove column")
+            )
+            .width(CELL_WIDTH)
+            .align_y(Alignment::Center)
+            .into()
+        });
+
+        let header: Row<'_, TableMessage> = row!(Space::with_width(REMOVE_WIDTH))
+            .extend(header)
+            .spacing(2.0);
+
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .take(MAX_SHOWN_ROWS)
+            .map(|(row, cells)| {
+                row!(Self::remove_button(
+                    TableMessage::RemoveRow(row),
+                    "Remove row"
+                ))
+                .extend((0..cells.len()).map(|column| self.cell(row, column)))
+                .spacing(2.0)
+                .align_y(Alignment::Center)
+                .into()
+            });
+
+        let body = column!(header, Column::with_children(rows).spacing(2.0)).spacing(6.0);
+
+        scrollable(body)
+            .direction(scrollable::Direction::Both {
+                vertical: scrollable::Scrollbar::default(),
+                horizontal: scrollable::Scrollbar::default(),
+            })
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+impl Viewable for TableTab {
+    type Data = TableTabData;
+    type Event = TableMessage;
+
+    fn new(data: Self::Data) -> Self {
+        let TableTabData {
+            path,
+            data,
+            name,
+            parsed,
+        } = data;
+        let (header, rows, kinds) = parsed.unwrap_or_else(|| Self::parse(&data));
+
+        Self {
+            file_path: path,
+            name,
+            saved_header: header.clone(),
+            header,
+            saved: rows.clone(),
+            rows,
+            kinds,
+            rejected: None,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        (self.file_path.is_none() && self.name.is_some())
+            || self.rows != self.saved
+            || self.header != self.saved_header
+    }
+
+    fn label(&self) -> TabLabel {
+        let font = Font::with_name(icons::NAME);
+
+        TabLabel::new(icons::CONFIG, self.title()).icon_font(font)
+    }
+
+    fn title(&self) -> String {
+        self.file_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .or(self.name.as_deref())
+            .map(|name| format!("{name} - Table"))
+            .unwrap_or_else(|| "Untitled - Table".into())
+    }
+
+    fn update(&mut self, message: Self::Event) -> Option<Message> {
+        match message {
+            TableMessage::EditCell(row, column, input) => self.edit_cell(row, column, input),
+            TableMessage::EditHeader(column, input) => {
+                if let Some(label) = self.header.get_mut(column) {
+                    *label = input;
+                }
+            }
+            TableMessage::AddRow => self.add_row(),
+            TableMessage::RemoveRow(row) => self.remove_row(row),
+            TableMessage::AddColumn => self.add_column(),
+            TableMessage::RemoveColumn(column) => self.remove_column(column),
+        }
+
+        None
+    }
+
+    fn content(&self) -> Option<String> {
+        let content = std::iter::once(&self.header)
+            .chain(self.rows.iter())
+            .map(|record| csv::record(record) + "\n")
+            .collect();
+
+        Some(content)
+    }
+
+    fn view<'a, Message, F>(&'a self, map: F) -> Element<'a, Message, Theme, Renderer>
+    where
+        F: 'a + Fn(Self::Event) -> Message,
+        Message: 'a + Clone + Debug,
+    {
+        let status = {
+            let shown = self.rows.len().min(MAX_SHOWN_ROWS);
+            let count = text(format!("{shown} of {} rows shown", self.rows.len())).size(13.0);
+
+            let rejected = self
+                .rejected
+                .as_ref()
+                .map(|edit| text(edit.reason.as_str()).size(13.0).style(text::danger));
+
+            let add_row = button(text("Add Row").size(13.0))
+                .on_press(TableMessage::AddRow)
+                .padding([2, 8]);
+            let add_column = button(text("Add Column").size(13.0))
+                .on_press(TableMessage::AddColumn)
+                .padding([2, 8]);
+            let tip =
+                utils::tooltip("New rows are added at the bottom and new columns on the right");
+
+            row!(count)
+                .push_maybe(rejected)
+                .push(horizontal_space())
+                .push(add_row)
+                .push(add_column)
+                .push(tip)
+                .spacing(20.0)
+                .align_y(Alignment::Center)
+        };
+
+        let content: Element<'_, TableMessage> = column!(status, self.grid())
+            .spacing(10.0)
+            .padding([5, 10])
+            .into();
+
+        container(content.map(map))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn modal_msg(&self) -> String {
+        format!("Do you want to save changes to {}?", self.title())
+    }
+
+    fn refresh(&mut self, data: Self::Data) {
+        let (header, rows, kinds) = data.parsed.unwrap_or_else(|| Self::parse(&data.data));
+
+        self.file_path = data.path;
+        self.saved_header = header.clone();
+        self.header = header;
+        self.saved = rows.clone();
+        self.rows = rows;
+        self.kinds = kinds;
+        self.rejected = None;
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.file_path.clone()
+    }
+
+    fn session(&self) -> Option<SessionTab> {
+        self.file_path.clone().map(SessionTab::Table)
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        Some(csv::size_summary(self.rows.len(), self.header.len()))
+    }
+
+    fn can_save(&self) -> bool {
+        true
+    }
+
+    fn close_policy(&self) -> ClosePolicy {
+        ClosePolicy::PromptIfDirty
+    }
+}
+
+#[test]
+fn test_table_edits() {
+    let data = TableTabData::new(None, "name,count\napple,3\npear,\n".into());
+    let mut table = TableTab::new(data);
+
+    assert_eq!(table.kinds, vec![ColumnKind::Text, ColumnKind::Numeric]);
+
+    table.update(TableMessage::EditCell(0, 1, "three".into()));
+    assert!(table.rejected.is_some());
+    assert!(!table.is_dirty());
+
+    table.update(TableMessage::EditCell(1, 1, "4.5".into()));
+    table.update(TableMessage::EditCell(0, 0, "big, red apple".into()));
+    assert!(table.rejected.is_none());
+    assert!(table.is_dirty());
+
+    assert_eq!(
+        table.content().unwrap(),
+        "name,count\n\"big, red apple\",3\npear,4.5\n"
+    );
+}
+
+#[test]
+fn test_table_structure_edits() {
+    let data = TableTabData::new(None, "name,count\napple,3\n".into());
+    let mut table = TableTab::new(data);
+
+    table.update(TableMessage::AddColumn);
+    table.update(TableMessage::AddRow);
+    table.update(TableMessage::EditCell(1, 2, "ripe".into()));
+    assert!(table.rejected.is_none());
+    assert_eq!(
+        table.content().unwrap(),
+        "name,count,Column 3\napple,3,\n,,ripe\n"
+    );
+
+    table.update(TableMessage::RemoveColumn(1));
+    table.update(TableMessage::RemoveRow(0));
+    table.update(TableMessage::EditHeader(1, "state".into()));
+    assert_eq!(table.content().unwrap(), "name,state\n,ripe\n");
+    assert!(table.is_dirty());
+
+    // Renaming a column back and forth leaves the table unchanged
+    let data = TableTabData::new(None, "name\napple\n".into());
+    let mut table = TableTab::new(data);
+    table.update(TableMessage::EditHeader(0, "fruit".into()));
+    assert!(table.is_dirty());
+    table.update(TableMessage::EditHeader(0, "name".into()));
+    assert!(!table.is_dirty());
+}
+
+#[test]
+fn test_table_parsed_ahead() {
+    let csv = "name,count\napple,3\npear,\n";
+    let parsed = TableTab::new(TableTabData::new(None, csv.into()).parse());
+    let table = TableTab::new(TableTabData::new(None, csv.into()));
+
+    assert_eq!(parsed.header, table.header);
+    assert_eq!(parsed.rows, table.rows);
+    assert_eq!(parsed.kinds, table.kinds);
+
+    let data = TableTabData::new(None, csv.into())
+        .parse()
+        .data("a\n1\n".into());
+    assert!(data.parsed.is_none());
+}
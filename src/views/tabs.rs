@@ -3,26 +3,32 @@
 
 use iced::{
     alignment::{self, Horizontal, Vertical},
+    mouse::Interaction,
     widget::{
-        button, column, container, container::bordered_box, row, scrollable, text, vertical_space,
-        Space,
+        button, column, container, container::bordered_box, mouse_area, responsive, row,
+        scrollable, stack, text, text_input, vertical_rule, vertical_space, Space,
     },
-    Alignment, Element, Length, Padding, Renderer, Theme,
+    Alignment, Element, Length, Padding, Point, Renderer, Size, Theme,
 };
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::{
     barchart::{BarChartMessage, BarChartTab, BarChartTabData},
+    boxplot::{BoxPlotMessage, BoxPlotTab, BoxPlotTabData},
     editor::{EditorMessage, EditorTab, EditorTabData},
+    histogram::{HistogramMessage, HistogramTab, HistogramTabData},
     line::{LineGraphTab, LineTabData, ModelMessage},
-    shared::tools_button,
+    radar::{RadarMessage, RadarTab, RadarTabData},
+    shared::{offscreen::Image, state::ChartViewState, tools_button},
     stacked_barchart::{StackedBarChartMessage, StackedBarChartTab, StackedBarChartTabData},
+    table::{TableMessage, TableTab, TableTabData},
 };
-use super::{View, ViewType, Viewable};
+use super::{ClosePolicy, Session, SessionTab, View, ViewType, Viewable};
 
 use crate::context;
-use crate::widgets::style::DialogContainer;
+use crate::utils::AppError;
+use crate::widgets::style::{access_key, DialogContainer};
 use crate::Context;
 use crate::Message;
 
@@ -39,6 +45,10 @@ pub enum Tab {
     LineGraph(LineGraphTab),
     BarChart(BarChartTab),
     StackedBarChart(StackedBarChartTab),
+    BoxPlot(BoxPlotTab),
+    Histogram(HistogramTab),
+    Radar(RadarTab),
+    Table(TableTab),
 }
 
 impl Tab {
@@ -52,6 +62,14 @@ impl Tab {
             (Tab::BarChart(_), _) => None,
             (Tab::StackedBarChart(tab), TabMessage::StackedBarChart(tsg)) => tab.update(tsg),
             (Tab::StackedBarChart(_), _) => None,
+            (Tab::BoxPlot(tab), TabMessage::BoxPlot(tsg)) => tab.update(tsg),
+            (Tab::BoxPlot(_), _) => None,
+            (Tab::Histogram(tab), TabMessage::Histogram(tsg)) => tab.update(tsg),
+            (Tab::Histogram(_), _) => None,
+            (Tab::Radar(tab), TabMessage::Radar(tsg)) => tab.update(tsg),
+            (Tab::Radar(_), _) => None,
+            (Tab::Table(tab), TabMessage::Table(tsg)) => tab.update(tsg),
+            (Tab::Table(_), _) => None,
         }
     }
 
@@ -61,16 +79,44 @@ impl Tab {
             Tab::LineGraph(tab) => tab.is_dirty(),
             Tab::BarChart(tab) => tab.is_dirty(),
             Tab::StackedBarChart(tab) => tab.is_dirty(),
+            Tab::BoxPlot(tab) => tab.is_dirty(),
+            Tab::Histogram(tab) => tab.is_dirty(),
+            Tab::Radar(tab) => tab.is_dirty(),
+            Tab::Table(tab) => tab.is_dirty(),
         }
     }
 
-    fn label(&self) -> TabLabel {
+    fn close_policy(&self) -> ClosePolicy {
         match self {
+            Tab::Editor(tab) => tab.close_policy(),
+            Tab::LineGraph(tab) => tab.close_policy(),
+            Tab::BarChart(tab) => tab.close_policy(),
+            Tab::StackedBarChart(tab) => tab.close_policy(),
+            Tab::BoxPlot(tab) => tab.close_policy(),
+            Tab::Histogram(tab) => tab.close_policy(),
+            Tab::Radar(tab) => tab.close_policy(),
+            Tab::Table(tab) => tab.close_policy(),
+        }
+    }
+
+    /// Returns true if closing self should first ask the user to save
+    fn needs_close_prompt(&self) -> bool {
+        self.close_policy() == ClosePolicy::PromptIfDirty && self.is_dirty()
+    }
+
+    fn label(&self) -> TabLabel {
+        let label = match self {
             Tab::Editor(tab) => tab.label(),
             Tab::LineGraph(tab) => tab.label(),
             Tab::BarChart(tab) => tab.label(),
             Tab::StackedBarChart(tab) => tab.label(),
-        }
+            Tab::BoxPlot(tab) => tab.label(),
+            Tab::Histogram(tab) => tab.label(),
+            Tab::Radar(tab) => tab.label(),
+            Tab::Table(tab) => tab.label(),
+        };
+
+        label.dirty(self.is_dirty())
     }
 
     fn content(&self) -> Option<String> {
@@ -79,6 +125,10 @@ impl Tab {
             Tab::LineGraph(tab) => tab.content(),
             Tab::BarChart(tab) => tab.content(),
             Tab::StackedBarChart(tab) => tab.content(),
+            Tab::BoxPlot(tab) => tab.content(),
+            Tab::Histogram(tab) => tab.content(),
+            Tab::Radar(tab) => tab.content(),
+            Tab::Table(tab) => tab.content(),
         }
     }
 
@@ -96,6 +146,18 @@ impl Tab {
             Tab::StackedBarChart(tab) => {
                 tab.view(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::StackedBarChart(msg)))
             }
+            Tab::BoxPlot(tab) => {
+                tab.view(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::BoxPlot(msg)))
+            }
+            Tab::Histogram(tab) => {
+                tab.view(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Histogram(msg)))
+            }
+            Tab::Radar(tab) => {
+                tab.view(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Radar(msg)))
+            }
+            Tab::Table(tab) => {
+                tab.view(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Table(msg)))
+            }
         }
     }
 
@@ -112,6 +174,18 @@ impl Tab {
             }
             Tab::StackedBarChart(tab) => tab
                 .config(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::StackedBarChart(msg))),
+            Tab::BoxPlot(tab) => {
+                tab.config(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::BoxPlot(msg)))
+            }
+            Tab::Histogram(tab) => {
+                tab.config(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Histogram(msg)))
+            }
+            Tab::Radar(tab) => {
+                tab.config(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Radar(msg)))
+            }
+            Tab::Table(tab) => {
+                tab.config(move |msg| TabBarMessage::UpdateTab(idx, TabMessage::Table(msg)))
+            }
         }
     }
 
@@ -121,6 +195,10 @@ impl Tab {
             Self::LineGraph(tab) => tab.has_config(),
             Self::BarChart(tab) => tab.has_config(),
             Self::StackedBarChart(tab) => tab.has_config(),
+            Self::BoxPlot(tab) => tab.has_config(),
+            Self::Histogram(tab) => tab.has_config(),
+            Self::Radar(tab) => tab.has_config(),
+            Self::Table(tab) => tab.has_config(),
         }
     }
 
@@ -136,6 +214,157 @@ impl Tab {
             (Tab::BarChart(_), _) => {}
             (Tab::StackedBarChart(tab), Refresh::StackedBarChart(data)) => tab.refresh(data),
             (Tab::StackedBarChart(_), _) => {}
+            (Tab::BoxPlot(tab), Refresh::BoxPlot(data)) => tab.refresh(data),
+            (Tab::BoxPlot(_), _) => {}
+            (Tab::Histogram(tab), Refresh::Histogram(data)) => tab.refresh(data),
+            (Tab::Histogram(_), _) => {}
+            (Tab::Radar(tab), Refresh::Radar(data)) => tab.refresh(data),
+            (Tab::Radar(_), _) => {}
+            (Tab::Table(tab), Refresh::Table(data)) => tab.refresh(data),
+            (Tab::Table(_), _) => {}
+        }
+    }
+
+    fn view_state(&self) -> Option<ChartViewState> {
+        match self {
+            Self::Editor(tab) => tab.view_state(),
+            Self::BarChart(tab) => tab.view_state(),
+            Self::LineGraph(tab) => tab.view_state(),
+            Self::StackedBarChart(tab) => tab.view_state(),
+            Self::BoxPlot(tab) => tab.view_state(),
+            Self::Histogram(tab) => tab.view_state(),
+            Self::Radar(tab) => tab.view_state(),
+            Self::Table(tab) => tab.view_state(),
+        }
+    }
+
+    fn restore_view_state(&mut self, state: ChartViewState) {
+        match self {
+            Self::Editor(tab) => tab.restore_view_state(state),
+            Self::BarChart(tab) => tab.restore_view_state(state),
+            Self::LineGraph(tab) => tab.restore_view_state(state),
+            Self::StackedBarChart(tab) => tab.restore_view_state(state),
+            Self::BoxPlot(tab) => tab.restore_view_state(state),
+            Self::Histogram(tab) => tab.restore_view_state(state),
+            Self::Radar(tab) => tab.restore_view_state(state),
+            Self::Table(tab) => tab.restore_view_state(state),
+        }
+    }
+
+    fn session(&self) -> Option<SessionTab> {
+        match self {
+            Self::Editor(tab) => tab.session(),
+            Self::BarChart(tab) => tab.session(),
+            Self::LineGraph(tab) => tab.session(),
+            Self::StackedBarChart(tab) => tab.session(),
+            Self::BoxPlot(tab) => tab.session(),
+            Self::Histogram(tab) => tab.session(),
+            Self::Radar(tab) => tab.session(),
+            Self::Table(tab) => tab.session(),
+        }
+    }
+
+    fn status_summary(&self) -> Option<String> {
+        match self {
+            Self::Editor(tab) => tab.status_summary(),
+            Self::BarChart(tab) => tab.status_summary(),
+            Self::LineGraph(tab) => tab.status_summary(),
+            Self::StackedBarChart(tab) => tab.status_summary(),
+            Self::BoxPlot(tab) => tab.status_summary(),
+            Self::Histogram(tab) => tab.status_summary(),
+            Self::Radar(tab) => tab.status_summary(),
+            Self::Table(tab) => tab.status_summary(),
+        }
+    }
+
+    fn row_sample(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Editor(tab) => tab.row_sample(),
+            Self::BarChart(tab) => tab.row_sample(),
+            Self::LineGraph(tab) => tab.row_sample(),
+            Self::StackedBarChart(tab) => tab.row_sample(),
+            Self::BoxPlot(tab) => tab.row_sample(),
+            Self::Histogram(tab) => tab.row_sample(),
+            Self::Radar(tab) => tab.row_sample(),
+            Self::Table(tab) => tab.row_sample(),
+        }
+    }
+
+    fn load_all_rows(&mut self) -> Result<(), AppError> {
+        match self {
+            Self::Editor(tab) => tab.load_all_rows(),
+            Self::BarChart(tab) => tab.load_all_rows(),
+            Self::LineGraph(tab) => tab.load_all_rows(),
+            Self::StackedBarChart(tab) => tab.load_all_rows(),
+            Self::BoxPlot(tab) => tab.load_all_rows(),
+            Self::Histogram(tab) => tab.load_all_rows(),
+            Self::Radar(tab) => tab.load_all_rows(),
+            Self::Table(tab) => tab.load_all_rows(),
+        }
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        match self {
+            Self::Editor(tab) => tab.to_csv(),
+            Self::BarChart(tab) => tab.to_csv(),
+            Self::LineGraph(tab) => tab.to_csv(),
+            Self::StackedBarChart(tab) => tab.to_csv(),
+            Self::BoxPlot(tab) => tab.to_csv(),
+            Self::Histogram(tab) => tab.to_csv(),
+            Self::Radar(tab) => tab.to_csv(),
+            Self::Table(tab) => tab.to_csv(),
+        }
+    }
+
+    fn to_visible_csv(&self) -> Option<String> {
+        match self {
+            Self::Editor(tab) => tab.to_visible_csv(),
+            Self::BarChart(tab) => tab.to_visible_csv(),
+            Self::LineGraph(tab) => tab.to_visible_csv(),
+            Self::StackedBarChart(tab) => tab.to_visible_csv(),
+            Self::BoxPlot(tab) => tab.to_visible_csv(),
+            Self::Histogram(tab) => tab.to_visible_csv(),
+            Self::Radar(tab) => tab.to_visible_csv(),
+            Self::Table(tab) => tab.to_visible_csv(),
+        }
+    }
+
+    fn render(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        match self {
+            Self::Editor(tab) => tab.render(size),
+            Self::BarChart(tab) => tab.render(size),
+            Self::LineGraph(tab) => tab.render(size),
+            Self::StackedBarChart(tab) => tab.render(size),
+            Self::BoxPlot(tab) => tab.render(size),
+            Self::Histogram(tab) => tab.render(size),
+            Self::Radar(tab) => tab.render(size),
+            Self::Table(tab) => tab.render(size),
+        }
+    }
+
+    fn to_metadata_json(&self) -> Option<String> {
+        match self {
+            Self::Editor(tab) => tab.to_metadata_json(),
+            Self::BarChart(tab) => tab.to_metadata_json(),
+            Self::LineGraph(tab) => tab.to_metadata_json(),
+            Self::StackedBarChart(tab) => tab.to_metadata_json(),
+            Self::BoxPlot(tab) => tab.to_metadata_json(),
+            Self::Histogram(tab) => tab.to_metadata_json(),
+            Self::Radar(tab) => tab.to_metadata_json(),
+            Self::Table(tab) => tab.to_metadata_json(),
+        }
+    }
+
+    fn scale_factor_changed(&mut self, scale_factor: f32) {
+        match self {
+            Self::Editor(tab) => tab.scale_factor_changed(scale_factor),
+            Self::BarChart(tab) => tab.scale_factor_changed(scale_factor),
+            Self::LineGraph(tab) => tab.scale_factor_changed(scale_factor),
+            Self::StackedBarChart(tab) => tab.scale_factor_changed(scale_factor),
+            Self::BoxPlot(tab) => tab.scale_factor_changed(scale_factor),
+            Self::Histogram(tab) => tab.scale_factor_changed(scale_factor),
+            Self::Radar(tab) => tab.scale_factor_changed(scale_factor),
+            Self::Table(tab) => tab.scale_factor_changed(scale_factor),
         }
     }
 
@@ -145,6 +374,10 @@ impl Tab {
             Self::BarChart(tab) => tab.theme_changed(theme),
             Self::LineGraph(tab) => tab.theme_changed(theme),
             Self::StackedBarChart(tab) => tab.theme_changed(theme),
+            Self::BoxPlot(tab) => tab.theme_changed(theme),
+            Self::Histogram(tab) => tab.theme_changed(theme),
+            Self::Radar(tab) => tab.theme_changed(theme),
+            Self::Table(tab) => tab.theme_changed(theme),
         }
     }
 
@@ -155,6 +388,10 @@ impl Tab {
             Tab::LineGraph(_) => ViewType::LineGraph,
             Tab::BarChart(_) => ViewType::BarChart,
             Tab::StackedBarChart(_) => ViewType::StackedBarChart,
+            Tab::BoxPlot(_) => ViewType::BoxPlot,
+            Tab::Histogram(_) => ViewType::Histogram,
+            Tab::Radar(_) => ViewType::Radar,
+            Tab::Table(_) => ViewType::Table,
         }
     }
 
@@ -164,6 +401,10 @@ impl Tab {
             Tab::LineGraph(tab) => tab.modal_msg(),
             Tab::BarChart(tab) => tab.modal_msg(),
             Tab::StackedBarChart(tab) => tab.modal_msg(),
+            Tab::BoxPlot(tab) => tab.modal_msg(),
+            Tab::Histogram(tab) => tab.modal_msg(),
+            Tab::Radar(tab) => tab.modal_msg(),
+            Tab::Table(tab) => tab.modal_msg(),
         }
     }
 
@@ -173,6 +414,10 @@ impl Tab {
             Tab::LineGraph(tab) => tab.path(),
             Tab::BarChart(tab) => tab.path(),
             Tab::StackedBarChart(tab) => tab.path(),
+            Tab::BoxPlot(tab) => tab.path(),
+            Tab::Histogram(tab) => tab.path(),
+            Tab::Radar(tab) => tab.path(),
+            Tab::Table(tab) => tab.path(),
         }
     }
 
@@ -182,6 +427,10 @@ impl Tab {
             Tab::LineGraph(tab) => tab.can_save(),
             Tab::BarChart(tab) => tab.can_save(),
             Tab::StackedBarChart(tab) => tab.can_save(),
+            Tab::BoxPlot(tab) => tab.can_save(),
+            Tab::Histogram(tab) => tab.can_save(),
+            Tab::Radar(tab) => tab.can_save(),
+            Tab::Table(tab) => tab.can_save(),
         }
     }
 }
@@ -192,6 +441,10 @@ pub enum TabMessage {
     LineGraph(ModelMessage),
     BarChart(BarChartMessage),
     StackedBarChart(StackedBarChartMessage),
+    BoxPlot(BoxPlotMessage),
+    Histogram(HistogramMessage),
+    Radar(RadarMessage),
+    Table(TableMessage),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -201,12 +454,53 @@ pub enum DirtyTabModalAction {
     Cancel,
 }
 
+/// Draws the chart of `view` into an image of `size` pixels, as exporting it
+/// from its tab would, without a window. Returns None if `view` is not a
+/// chart.
+pub fn render_view(view: View, size: Size<u32>) -> Option<Result<Image, AppError>> {
+    let tab = match view {
+        View::LineGraph(data) => Tab::LineGraph(LineGraphTab::new(data)),
+        View::BarChart(data) => Tab::BarChart(BarChartTab::new(data)),
+        View::StackedBarChart(data) => Tab::StackedBarChart(StackedBarChartTab::new(data)),
+        View::BoxPlot(data) => Tab::BoxPlot(BoxPlotTab::new(data)),
+        View::Histogram(data) => Tab::Histogram(HistogramTab::new(data)),
+        View::Radar(data) => Tab::Radar(RadarTab::new(data)),
+        View::Editor(_) | View::Table(_) => return None,
+    };
+
+    tab.render(size)
+}
+
 #[derive(Debug, Clone)]
 pub enum Refresh {
     Editor(EditorTabData),
     LineGraph(LineTabData),
     BarChart(BarChartTabData),
     StackedBarChart(StackedBarChartTabData),
+    BoxPlot(BoxPlotTabData),
+    Histogram(HistogramTabData),
+    Radar(RadarTabData),
+    Table(TableTabData),
+}
+
+impl Refresh {
+    /// Returns the refresh showing `view` in a tab of `kind`, if `view` is
+    /// of that kind
+    pub fn matching(kind: Option<ViewType>, view: View) -> Option<Self> {
+        match (kind?, view) {
+            (ViewType::Editor, View::Editor(data)) => Some(Self::Editor(data)),
+            (ViewType::LineGraph, View::LineGraph(data)) => Some(Self::LineGraph(data)),
+            (ViewType::BarChart, View::BarChart(data)) => Some(Self::BarChart(data)),
+            (ViewType::StackedBarChart, View::StackedBarChart(data)) => {
+                Some(Self::StackedBarChart(data))
+            }
+            (ViewType::BoxPlot, View::BoxPlot(data)) => Some(Self::BoxPlot(data)),
+            (ViewType::Histogram, View::Histogram(data)) => Some(Self::Histogram(data)),
+            (ViewType::Radar, View::Radar(data)) => Some(Self::Radar(data)),
+            (ViewType::Table, View::Table(data)) => Some(Self::Table(data)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -221,16 +515,68 @@ pub enum TabBarMessage {
     TabSelected(usize),
     AddTab(View),
     CloseTab(usize, bool),
+    /// Moves the tab at the first index to the second
+    ReorderTab(usize, usize),
     DirtyTabModal(DirtyTabModalAction),
     UpdateTab(usize, TabMessage),
     RefreshTab(usize, Refresh),
     NewTabModal,
     NewTabModalAction(NewTabModalAction),
     ToggleConfig,
+    /// Shows a second tab beside the active one, or goes back to one
+    ToggleSplit,
+    /// Selects the tab shown in the second pane
+    SplitTabSelected(usize),
+    SplitDragStarted,
+    /// The divider was dragged to the given fraction of the width
+    SplitDragged(f32),
+    SplitDragEnded,
+    /// Opens the context menu of the tab at the index, at the given point
+    /// of the bar. The flag is set for the bar of the second pane
+    TabMenu(usize, Point, bool),
+    CloseTabMenu,
+    CloseAll,
+    /// Closes every tab except the one at the index
+    CloseOthers(usize),
+    /// Pins the tab at the index to the front of the bar, or unpins it
+    TogglePin(usize),
     Exit,
     None,
 }
 
+/// The context menu opened by right clicking a tab
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TabMenu {
+    tab: usize,
+    position: Point,
+    second: bool,
+}
+
+/// The tabs still to be closed by a close all or close others action.
+/// Pinned tabs are never part of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BulkClose {
+    All,
+    /// Every tab except the one at the index
+    Others(usize),
+}
+
+/// The second pane shown beside the active tab when the view is split
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Split {
+    /// Index of the tab shown in the second pane
+    active: usize,
+    /// Fraction of the width taken by the first pane
+    ratio: f32,
+    dragging: bool,
+}
+
+impl Split {
+    const MIN_RATIO: f32 = 0.2;
+    const MAX_RATIO: f32 = 0.8;
+    const DIVIDER_WIDTH: f32 = 8.0;
+}
+
 pub struct TabsState<Theme>
 where
     Theme: StyleSheet,
@@ -245,9 +591,18 @@ where
     labels: Vec<TabLabel>,
     tabs: Vec<Tab>,
     active_tab: Option<usize>,
+    split: Option<Split>,
+    tab_menu: Option<TabMenu>,
+    closing: Option<BulkClose>,
     close_size: f32,
     modal_shown: bool,
     config_shown: bool,
+    access_keys_shown: bool,
+    flat_ui: bool,
+    /// Whether new editors use syntax highlighting
+    highlighting: bool,
+    /// Whether new editors wrap long lines
+    word_wrap: bool,
     new_tab_modal_shown: bool,
     exiting: bool,
     on_open: Option<Message>,
@@ -275,6 +630,63 @@ where
                 tab.theme_changed(&self.theme)
             }
         }
+        if let Some(split) = self.split {
+            if let Some(tab) = self.tabs.get_mut(split.active) {
+                tab.theme_changed(&self.theme)
+            }
+        }
+    }
+
+    /// Notifies all tabs of a change in the window's scale factor
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.tabs
+            .iter_mut()
+            .for_each(|tab| tab.scale_factor_changed(scale_factor));
+    }
+
+    /// Draws the tab dialogs without drop shadows if `flat`
+    pub fn flat_ui(&mut self, flat: bool) {
+        self.flat_ui = flat;
+    }
+
+    /// Turns syntax highlighting on or off for new and open editors
+    pub fn highlighting(&mut self, enabled: bool) {
+        self.highlighting = enabled;
+
+        for tab in self.tabs.iter_mut() {
+            if let Tab::Editor(editor) = tab {
+                editor.update(EditorMessage::Highlighting(enabled));
+            }
+        }
+    }
+
+    /// Turns word wrap on or off for new and open editors
+    pub fn word_wrap(&mut self, enabled: bool) {
+        self.word_wrap = enabled;
+
+        for tab in self.tabs.iter_mut() {
+            if let Tab::Editor(editor) = tab {
+                editor.update(EditorMessage::WordWrap(enabled));
+            }
+        }
+    }
+
+    /// Makes an editor following the highlighting and word wrap preferences
+    fn editor(&self, data: EditorTabData) -> Tab {
+        let data = if self.highlighting {
+            data
+        } else {
+            data.plain(true)
+        };
+
+        let mut editor = EditorTab::new(data);
+        editor.update(EditorMessage::WordWrap(self.word_wrap));
+        Tab::Editor(editor)
+    }
+
+    /// Shows or hides the access key badges of the tab actions
+    pub fn show_access_keys(&mut self, show: bool) {
+        self.access_keys_shown = show;
     }
 
     pub fn with_tabs(tabs: impl Iterator<Item = Tab>, theme: Theme) -> Self {
@@ -299,6 +711,9 @@ where
             tab_bar_padding: Padding::ZERO,
             tab_spacing: 0.0,
             active_tab: if len > 0 { Some(len - 1) } else { None },
+            split: None,
+            tab_menu: None,
+            closing: None,
             tabs: tabs_list,
             on_open: None,
             on_new_active_tab: None,
@@ -308,6 +723,10 @@ where
             close_size: 16.0,
             modal_shown: false,
             config_shown: false,
+            access_keys_shown: false,
+            flat_ui: false,
+            highlighting: true,
+            word_wrap: true,
             new_tab_modal_shown: false,
             exiting: false,
             style: <Theme as StyleSheet>::Style::default(),
@@ -390,19 +809,26 @@ where
     }
 
     fn push_tab(&mut self, tab: Tab) {
-        let new_active = self.active_tab.map(|idx| idx + 1).unwrap_or(0);
+        let new_active = self
+            .active_tab
+            .map(|idx| idx + 1)
+            .unwrap_or(0)
+            .max(self.pinned_count());
 
         self.labels.insert(new_active, tab.label());
         self.tabs.insert(new_active, tab);
 
+        if let Some(split) = self.split.as_mut() {
+            split.active = index_after_insert(split.active, new_active);
+        }
+
         self.tab_selected(new_active);
     }
 
     pub fn push_view(&mut self, view: View) {
         match view {
             View::Editor(data) => {
-                let editor = EditorTab::new(data);
-                let tab = Tab::Editor(editor);
+                let tab = self.editor(data);
                 self.push_tab(tab);
             }
             View::LineGraph(data) => {
@@ -420,6 +846,26 @@ where
                 let tab = Tab::StackedBarChart(chart);
                 self.push_tab(tab)
             }
+            View::BoxPlot(data) => {
+                let chart = BoxPlotTab::new(data);
+                let tab = Tab::BoxPlot(chart);
+                self.push_tab(tab)
+            }
+            View::Histogram(data) => {
+                let chart = HistogramTab::new(data);
+                let tab = Tab::Histogram(chart);
+                self.push_tab(tab)
+            }
+            View::Radar(data) => {
+                let chart = RadarTab::new(data);
+                let tab = Tab::Radar(chart);
+                self.push_tab(tab)
+            }
+            View::Table(data) => {
+                let table = TableTab::new(data);
+                let tab = Tab::Table(table);
+                self.push_tab(tab)
+            }
 
             View::None => {}
         }
@@ -431,71 +877,16 @@ where
     {
         match self.active_tab {
             Some(idx) => {
-                let mut bar = TabBar::new_with_tabs(
-                    self.labels.clone(),
-                    TabBarMessage::TabSelected,
-                    &self.style,
-                )
-                .height(self.tab_bar_height)
-                .width(self.width)
-                .spacing(self.tab_spacing)
-                .tab_padding(self.tab_padding)
-                .bar_padding(self.tab_bar_padding)
-                .tab_height(self.tab_height)
-                .close_size(self.close_size)
-                .on_close(|idx| TabBarMessage::CloseTab(idx, false));
-
-                bar = bar.on_expand(|| TabBarMessage::NewTabModal);
-
-                bar.set_active_tab(idx);
-
-                let tab = self
-                    .get_active_tab()
-                    .expect("Tab: Has active tab index but no active tab");
-
-                let view = tab.view(idx);
-
-                let config_btn: Element<'_, TabBarMessage> =
-                    if tab.has_config() && !self.config_shown {
-                        column!(
-                            vertical_space(),
-                            tools_button().on_press(TabBarMessage::ToggleConfig),
-                            vertical_space()
-                        )
-                        .padding(Padding {
-                            top: 0.,
-                            right: 5.,
-                            bottom: 0.,
-                            left: 0.,
-                        })
-                        .align_x(Alignment::Center)
-                        .into()
-                    } else {
-                        Space::with_width(0).into()
-                    };
-
-                let config: Option<Element<'_, TabBarMessage>> =
-                    (tab.has_config() && self.config_shown).then(|| {
-                        let Some(config) = tab.config(idx) else {
-                            return Space::with_width(0.0).into();
-                        };
-                        context!(container(scrollable(config))
-                            .padding([16, 12])
-                            .height(Length::Fill)
-                            .width(325.0)
-                            .style(bordered_box)
-                        ; TabBarMessage::ToggleConfig)
-                        .padding(0)
-                        .height(Length::Fill)
-                        .into()
-                    });
-
-                let content = row!(view, config_btn).push_maybe(config);
-
-                let content: Element<'a, TabBarMessage, Theme, Renderer> = column!(bar, content)
-                    .width(self.width)
-                    .height(self.height)
-                    .into();
+                let content: Element<'a, TabBarMessage, Theme, Renderer> = match self.split {
+                    Some(split) => container(responsive(move |size| self.split_view(split, size)))
+                        .width(self.width)
+                        .height(self.height)
+                        .into(),
+                    None => container(self.pane(idx, false))
+                        .width(self.width)
+                        .height(self.height)
+                        .into(),
+                };
 
                 if self.modal_shown {
                     let modal = self.modal_content();
@@ -519,6 +910,165 @@ where
         }
     }
 
+    /// A tab bar with the tab at `idx` selected above its content. Only the
+    /// first pane has the tab's config.
+    fn pane(&self, idx: usize, second: bool) -> Element<'_, TabBarMessage, Theme, Renderer> {
+        let on_select: fn(usize) -> TabBarMessage = if second {
+            TabBarMessage::SplitTabSelected
+        } else {
+            TabBarMessage::TabSelected
+        };
+
+        // Tabs can become dirty or be saved outside of an update, so
+        // dirtiness is read afresh each time the bar is built
+        let labels = self
+            .labels
+            .iter()
+            .zip(&self.tabs)
+            .map(|(label, tab)| label.clone().dirty(tab.is_dirty()))
+            .collect::<Vec<TabLabel>>();
+
+        let mut bar = TabBar::new_with_tabs(labels, on_select, &self.style)
+            .height(self.tab_bar_height)
+            .width(Length::Fill)
+            .spacing(self.tab_spacing)
+            .tab_padding(self.tab_padding)
+            .bar_padding(self.tab_bar_padding)
+            .tab_height(self.tab_height)
+            .close_size(self.close_size)
+            .on_close(|idx| TabBarMessage::CloseTab(idx, false))
+            .on_reorder(TabBarMessage::ReorderTab)
+            .on_menu(move |idx, position| TabBarMessage::TabMenu(idx, position, second));
+
+        bar = bar.on_expand(|| TabBarMessage::NewTabModal);
+
+        bar.set_active_tab(idx);
+
+        let Some(tab) = self.tabs.get(idx) else {
+            return column!(bar).into();
+        };
+
+        let view = tab.view(idx);
+
+        let config_btn: Element<'_, TabBarMessage> =
+            if !second && tab.has_config() && !self.config_shown {
+                let key = self.access_keys_shown.then(|| access_key('t'));
+
+                column!(
+                    vertical_space(),
+                    tools_button().on_press(TabBarMessage::ToggleConfig),
+                )
+                .push_maybe(key)
+                .push(vertical_space())
+                .spacing(4)
+                .padding(Padding {
+                    top: 0.,
+                    right: 5.,
+                    bottom: 0.,
+                    left: 0.,
+                })
+                .align_x(Alignment::Center)
+                .into()
+            } else {
+                Space::with_width(0).into()
+            };
+
+        let config: Option<Element<'_, TabBarMessage>> =
+            (!second && tab.has_config() && self.config_shown).then(|| {
+                let Some(config) = tab.config(idx) else {
+                    return Space::with_width(0.0).into();
+                };
+                context!(container(scrollable(config))
+                    .padding([16, 12])
+                    .height(Length::Fill)
+                    .width(325.0)
+                    .style(bordered_box)
+                ; TabBarMessage::ToggleConfig)
+                .padding(0)
+                .height(Length::Fill)
+                .into()
+            });
+
+        let content = row!(view, config_btn).push_maybe(config);
+
+        let pane = column!(bar, content)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        match self.tab_menu.filter(|menu| menu.second == second) {
+            Some(menu) => stack!(pane, self.tab_menu(menu)).into(),
+            None => pane.into(),
+        }
+    }
+
+    /// The context menu of a tab, placed where it was opened. Clicking
+    /// anywhere else dismisses it
+    fn tab_menu(&self, menu: TabMenu) -> Element<'_, TabBarMessage, Theme, Renderer> {
+        let item = |label: &'static str| {
+            button(text(label).size(14.0))
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(button::text)
+        };
+
+        let pinned = self
+            .labels
+            .get(menu.tab)
+            .map_or(false, |label| label.is_pinned());
+
+        let items = column!(
+            item(if pinned { "Unpin" } else { "Pin" }).on_press(TabBarMessage::TogglePin(menu.tab)),
+            item("Close").on_press(TabBarMessage::CloseTab(menu.tab, false)),
+            item("Close Others").on_press_maybe(
+                (self.tabs.len() > 1).then_some(TabBarMessage::CloseOthers(menu.tab))
+            ),
+            item("Close All").on_press(TabBarMessage::CloseAll),
+        );
+
+        let items = container(items).width(150).padding(4).style(bordered_box);
+
+        let dismiss = mouse_area(Space::new(Length::Fill, Length::Fill))
+            .on_press(TabBarMessage::CloseTabMenu)
+            .on_right_press(TabBarMessage::CloseTabMenu);
+
+        let items = container(items)
+            .padding(Padding::ZERO.left(menu.position.x).top(menu.position.y))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        stack!(dismiss, items).into()
+    }
+
+    /// The two panes of a split view side by side, `size` being the space
+    /// they share
+    fn split_view(&self, split: Split, size: Size) -> Element<'_, TabBarMessage, Theme, Renderer> {
+        let first = self.active_tab.unwrap_or_default();
+        let portion = (split.ratio * 100.0).round() as u16;
+
+        let divider = mouse_area(vertical_rule(Split::DIVIDER_WIDTH))
+            .on_press(TabBarMessage::SplitDragStarted)
+            .interaction(Interaction::ResizingHorizontally);
+
+        let panes = row!(
+            container(self.pane(first, false)).width(Length::FillPortion(portion)),
+            divider,
+            container(self.pane(split.active, true)).width(Length::FillPortion(100 - portion)),
+        )
+        .height(Length::Fill);
+
+        let panes = mouse_area(panes);
+
+        if split.dragging {
+            panes
+                .on_move(move |point| TabBarMessage::SplitDragged(point.x / size.width))
+                .on_release(TabBarMessage::SplitDragEnded)
+                .interaction(Interaction::ResizingHorizontally)
+                .into()
+        } else {
+            panes.into()
+        }
+    }
+
     pub fn update(&mut self, message: TabBarMessage) -> Option<Message> {
         match message {
             TabBarMessage::None => None,
@@ -527,16 +1077,30 @@ where
                 return self.on_new_active_tab.clone();
             }
             TabBarMessage::TabSelected(idx) => {
+                // Picking the tab shown in the second pane swaps the panes,
+                // so both never show the same tab
+                if let (Some(split), Some(active)) = (self.split.as_mut(), self.active_tab) {
+                    if split.active == idx {
+                        split.active = active;
+                    }
+                }
                 self.tab_selected(idx);
                 self.on_new_active_tab.clone()
             }
             TabBarMessage::CloseTab(idx, force) => {
-                if self.close_tab(idx, force) {
+                self.tab_menu = None;
+
+                // A tab saved before closing may be part of a bulk close
+                if self.close_tab(idx, force) | self.continue_closing() {
                     return self.on_new_active_tab.clone();
                 } else {
                     return None;
                 };
             }
+            TabBarMessage::ReorderTab(from, to) => {
+                self.reorder_tab(from, to);
+                None
+            }
             TabBarMessage::NewTabModal => {
                 self.new_tab_modal_shown = true;
                 None
@@ -553,9 +1117,7 @@ where
                 NewTabModalAction::New => {
                     self.new_tab_modal_shown = false;
 
-                    let data = EditorTabData::default();
-                    let tab = EditorTab::new(data);
-                    let new = Tab::Editor(tab);
+                    let new = self.editor(EditorTabData::default());
                     self.push_tab(new);
 
                     return self.on_new_active_tab.clone();
@@ -565,11 +1127,80 @@ where
                 self.config_shown = !self.config_shown;
                 None
             }
+            TabBarMessage::ToggleSplit => {
+                self.toggle_split();
+                None
+            }
+            TabBarMessage::SplitTabSelected(idx) => {
+                if let Some(split) = self.split.as_mut() {
+                    if self.active_tab == Some(idx) {
+                        self.active_tab = Some(split.active);
+                    }
+                    split.active = idx;
+                }
+                if let Some(tab) = self.tabs.get_mut(idx) {
+                    tab.theme_changed(&self.theme);
+                }
+                None
+            }
+            TabBarMessage::SplitDragStarted => {
+                if let Some(split) = self.split.as_mut() {
+                    split.dragging = true;
+                }
+                None
+            }
+            TabBarMessage::SplitDragged(ratio) => {
+                if let Some(split) = self.split.as_mut().filter(|split| split.dragging) {
+                    split.ratio = ratio.clamp(Split::MIN_RATIO, Split::MAX_RATIO);
+                }
+                None
+            }
+            TabBarMessage::SplitDragEnded => {
+                if let Some(split) = self.split.as_mut() {
+                    split.dragging = false;
+                }
+                None
+            }
+
+            TabBarMessage::TabMenu(tab, position, second) => {
+                self.tab_menu = Some(TabMenu {
+                    tab,
+                    position,
+                    second,
+                });
+                None
+            }
+            TabBarMessage::CloseTabMenu => {
+                self.tab_menu = None;
+                None
+            }
+            TabBarMessage::CloseAll => {
+                self.tab_menu = None;
+                self.closing = Some(BulkClose::All);
+
+                self.continue_closing()
+                    .then(|| self.on_new_active_tab.clone())
+                    .flatten()
+            }
+            TabBarMessage::CloseOthers(idx) => {
+                self.tab_menu = None;
+                self.closing = Some(BulkClose::Others(idx));
+
+                self.continue_closing()
+                    .then(|| self.on_new_active_tab.clone())
+                    .flatten()
+            }
+            TabBarMessage::TogglePin(idx) => {
+                self.tab_menu = None;
+                self.toggle_pin(idx);
+                None
+            }
 
             TabBarMessage::DirtyTabModal(action) => match action {
                 DirtyTabModalAction::Cancel => {
                     self.modal_shown = false;
                     self.exiting = false;
+                    self.closing = None;
                     return None;
                 }
                 DirtyTabModalAction::Save => {
@@ -608,14 +1239,20 @@ where
                     if self.exiting {
                         self.check_exit.clone()
                     } else {
-                        None
+                        self.continue_closing();
+                        self.on_new_active_tab.clone()
                     }
                 }
             },
 
             TabBarMessage::UpdateTab(idx, tsg) => {
                 let msg = self.tabs.get_mut(idx).and_then(|tab| tab.update(tsg));
-                self.labels = self.tabs.iter().map(|tab| tab.label()).collect();
+                self.labels = self
+                    .tabs
+                    .iter()
+                    .zip(&self.labels)
+                    .map(|(tab, label)| tab.label().pinned(label.is_pinned()))
+                    .collect();
                 msg
             }
 
@@ -641,6 +1278,104 @@ where
         }
     }
 
+    fn reorder_tab(&mut self, from: usize, to: usize) {
+        // Tabs stay on their side of the pinned ones
+        let pinned = self.pinned_count();
+        let to = if from < pinned {
+            to.min(pinned - 1)
+        } else {
+            to.max(pinned)
+        };
+
+        if from == to || from >= self.tabs.len() || to >= self.tabs.len() {
+            return;
+        }
+
+        let label = self.labels.remove(from);
+        self.labels.insert(to, label);
+
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+
+        self.active_tab = self.active_tab.map(|idx| moved_index(idx, from, to));
+
+        if let Some(split) = self.split.as_mut() {
+            split.active = moved_index(split.active, from, to);
+        }
+    }
+
+    /// Splits the view, showing the tab after the active one in the second
+    /// pane, or goes back to a single pane
+    fn toggle_split(&mut self) {
+        if self.split.take().is_some() {
+            return;
+        }
+
+        let Some(active_tab) = self.active_tab else {
+            return;
+        };
+
+        // Each pane shows its own tab
+        if self.tabs.len() < 2 {
+            return;
+        }
+
+        let active = (active_tab + 1) % self.tabs.len();
+
+        if let Some(tab) = self.tabs.get_mut(active) {
+            tab.theme_changed(&self.theme);
+        }
+
+        self.split = Some(Split {
+            active,
+            ratio: 0.5,
+            dragging: false,
+        });
+    }
+
+    /// Pins the tab at `idx`, moving it after the other pinned tabs, or
+    /// unpins it, moving it before the unpinned ones
+    fn toggle_pin(&mut self, idx: usize) {
+        let pinned = self.pinned_count();
+
+        let Some(label) = self.labels.get_mut(idx) else {
+            return;
+        };
+
+        let to = if label.is_pinned() {
+            pinned - 1
+        } else {
+            pinned
+        };
+
+        *label = label.clone().pinned(!label.is_pinned());
+
+        // Bypasses the pinned boundary `reorder_tab` keeps
+        let label = self.labels.remove(idx);
+        self.labels.insert(to, label);
+
+        let tab = self.tabs.remove(idx);
+        self.tabs.insert(to, tab);
+
+        self.active_tab = self.active_tab.map(|active| moved_index(active, idx, to));
+
+        if let Some(split) = self.split.as_mut() {
+            split.active = moved_index(split.active, idx, to);
+        }
+    }
+
+    /// The number of pinned tabs, all of which come before the others
+    fn pinned_count(&self) -> usize {
+        self.labels
+            .iter()
+            .take_while(|label| label.is_pinned())
+            .count()
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.split.is_some()
+    }
+
     fn tab_selected(&mut self, idx: usize) {
         if let Some(active_tab) = self.tabs.get_mut(idx) {
             active_tab.theme_changed(&self.theme);
@@ -689,9 +1424,14 @@ where
             .width(Length::Fill)
             .spacing(24);
 
+        let flat = self.flat_ui;
+
         container(col)
-            .style(|theme| {
-                <DialogContainer as container::Catalog>::style(&DialogContainer::default(), theme)
+            .style(move |theme| {
+                <DialogContainer as container::Catalog>::style(
+                    &DialogContainer::default().flat(flat),
+                    theme,
+                )
             })
             .center_y(175)
             .padding(16)
@@ -728,9 +1468,14 @@ where
             .width(Length::Fill)
             .spacing(24);
 
+        let flat = self.flat_ui;
+
         container(col)
-            .style(|theme| {
-                <DialogContainer as container::Catalog>::style(&DialogContainer::default(), theme)
+            .style(move |theme| {
+                <DialogContainer as container::Catalog>::style(
+                    &DialogContainer::default().flat(flat),
+                    theme,
+                )
             })
             .height(175)
             .padding(16)
@@ -750,38 +1495,229 @@ where
         self.get_active_tab().map(|tab| tab.path())?
     }
 
-    pub fn active_tab_can_save(&self) -> bool {
-        self.get_active_tab()
-            .map(|tab| tab.can_save())
-            .unwrap_or(false)
+    /// Where the active tab's data came from when it has no path
+    pub fn active_source(&self) -> Option<String> {
+        match self.get_active_tab()? {
+            Tab::Editor(tab) => tab.source().map(String::from),
+            _ => None,
+        }
     }
 
-    pub fn active_tab_idx(&self) -> Option<usize> {
-        self.active_tab
+    /// Returns the index, kind and options of every chart tab plotting `path`
+    pub fn linked_charts(&self, path: &Path) -> Vec<(usize, ViewType, SessionTab)> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| tab.view_state().is_some())
+            .filter(|(_, tab)| tab.path().as_deref() == Some(path))
+            .filter_map(|(idx, tab)| Some((idx, tab.kind(), tab.session()?)))
+            .collect()
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.tabs.is_empty()
+    /// Returns the index and kind of every tab of `path`, and whether it
+    /// has unsaved changes
+    pub fn tabs_with_path(&self, path: &Path) -> Vec<(usize, ViewType, bool)> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| tab.path().as_deref() == Some(path))
+            .map(|(idx, tab)| (idx, tab.kind(), tab.needs_close_prompt()))
+            .collect()
     }
 
-    /// Returns the index of the first dirty tab if any
-    pub fn has_dirty_tab(&self) -> Option<usize> {
+    /// Returns the index, path and contents of every editor tab with unsaved
+    /// changes to a file. Tabs that have never been saved are left out
+    pub fn unsaved_editors(&self) -> Vec<(usize, PathBuf, String)> {
         self.tabs
             .iter()
             .enumerate()
-            .find(|(_, tab)| tab.is_dirty())
-            .map(|(idx, _)| idx)
+            .filter(|(_, tab)| matches!(tab, Tab::Editor(_)))
+            .filter(|(_, tab)| tab.is_dirty() && tab.can_save())
+            .filter_map(|(idx, tab)| Some((idx, tab.path()?, tab.content()?)))
+            .collect()
     }
 
-    /// Returns true if a tab was successfully closed
-    fn close_tab(&mut self, idx: usize, force: bool) -> bool {
-        if force {
-            self.force_close_tab(idx);
-            return true;
-        }
+    /// Returns the open tabs that can be reopened in a later session, in
+    /// tab order
+    pub fn session(&self) -> Session {
+        Session::new(
+            self.tabs
+                .iter()
+                .filter_map(|tab| Some((tab.session()?, tab.view_state())))
+                .collect(),
+        )
+    }
+
+    /// Returns what is needed to reopen the tab at `idx`, if it can be
+    /// reopened
+    pub fn tab_session(&self, idx: usize) -> Option<SessionTab> {
+        self.tabs.get(idx).and_then(|tab| tab.session())
+    }
+
+    /// Returns the kind of the tab at `idx`
+    pub fn tab_kind(&self, idx: usize) -> Option<ViewType> {
+        self.tabs.get(idx).map(|tab| tab.kind())
+    }
+
+    /// Returns the path of every open file, without repeats
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        for path in self.tabs.iter().filter_map(|tab| tab.path()) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+
+        paths
+    }
+
+    pub fn active_tab_can_save(&self) -> bool {
+        self.get_active_tab()
+            .map(|tab| tab.can_save())
+            .unwrap_or(false)
+    }
+
+    pub fn active_view_state(&self) -> Option<ChartViewState> {
+        self.get_active_tab().and_then(|tab| tab.view_state())
+    }
+
+    /// Returns the rows shown and the total rows of the active tab, if it
+    /// only shows a sample of its file
+    pub fn active_row_sample(&self) -> Option<(usize, usize)> {
+        self.get_active_tab().and_then(|tab| tab.row_sample())
+    }
+
+    /// Returns the size of the active tab's data and the columns it maps,
+    /// if it shows a model
+    pub fn active_status_summary(&self) -> Option<String> {
+        self.get_active_tab().and_then(|tab| tab.status_summary())
+    }
+
+    /// Reloads the active tab with every row of its file
+    pub fn load_all_active_rows(&mut self) -> Result<(), AppError> {
+        match self.active_tab_mut() {
+            Some(tab) => tab.load_all_rows(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the data plotted by the active tab as CSV, if it is a chart
+    pub fn active_csv(&self) -> Option<String> {
+        self.get_active_tab().and_then(|tab| tab.to_csv())
+    }
+
+    /// Returns the data shown by the active tab as CSV, if it is a chart
+    pub fn active_visible_csv(&self) -> Option<String> {
+        self.get_active_tab().and_then(|tab| tab.to_visible_csv())
+    }
+
+    /// Returns the JSON description of the active tab, if it is a chart
+    pub fn active_metadata(&self) -> Option<String> {
+        self.get_active_tab().and_then(|tab| tab.to_metadata_json())
+    }
+
+    /// Draws the chart of the active tab into an image of `size` pixels,
+    /// if it is a chart
+    pub fn render_active(&self, size: Size<u32>) -> Option<Result<Image, AppError>> {
+        self.get_active_tab().and_then(|tab| tab.render(size))
+    }
+
+    pub fn restore_active_view_state(&mut self, state: ChartViewState) {
+        if let Some(tab) = self.active_tab_mut() {
+            tab.restore_view_state(state);
+        }
+    }
+
+    pub fn active_tab_has_config(&self) -> bool {
+        self.get_active_tab()
+            .map(|tab| tab.has_config())
+            .unwrap_or(false)
+    }
+
+    /// Opens the find bar of the active tab, with the replace field if
+    /// `replace`. Returns the id of the find field, or None if the active
+    /// tab is not an editor
+    pub fn open_find(&mut self, replace: bool) -> Option<text_input::Id> {
+        match self.active_tab_mut() {
+            Some(Tab::Editor(editor)) => {
+                editor.update(EditorMessage::OpenFind(replace));
+                Some(editor.find_id())
+            }
+            _ => None,
+        }
+    }
+
+    /// Selects `line` of the active tab. Returns the id of the scrollable to
+    /// scroll to it, or None if the active tab is not an editor
+    pub fn go_to_line(&mut self, line: usize) -> Option<scrollable::Id> {
+        match self.active_tab_mut() {
+            Some(Tab::Editor(editor)) => {
+                editor.update(EditorMessage::GoToLine(line));
+                Some(editor.scroll_id())
+            }
+            _ => None,
+        }
+    }
+
+    /// Undoes the last edit of the active tab, or redoes the last undone one
+    /// if `redo`. Returns false if the active tab is not an editor
+    pub fn undo(&mut self, redo: bool) -> bool {
+        match self.active_tab_mut() {
+            Some(Tab::Editor(editor)) => {
+                editor.update(if redo {
+                    EditorMessage::Redo
+                } else {
+                    EditorMessage::Undo
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Closes the find bar of the active tab. Returns false if the active tab
+    /// is not an editor
+    pub fn close_find(&mut self) -> bool {
+        match self.active_tab_mut() {
+            Some(Tab::Editor(editor)) => {
+                editor.update(EditorMessage::CloseFind);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn active_tab_idx(&self) -> Option<usize> {
+        self.active_tab
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns the index of the first dirty tab if any
+    pub fn has_dirty_tab(&self) -> Option<usize> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .find(|(_, tab)| tab.needs_close_prompt())
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns true if a tab was successfully closed
+    fn close_tab(&mut self, idx: usize, force: bool) -> bool {
+        if force {
+            self.force_close_tab(idx);
+            return true;
+        }
 
         if let Some(tab) = self.tabs.get(idx) {
-            if tab.is_dirty() {
+            if tab.needs_close_prompt() {
                 self.tab_selected(idx);
                 self.modal_shown = true;
                 return false;
@@ -794,31 +1730,94 @@ where
         return false;
     }
 
-    fn force_close_tab(&mut self, idx: usize) {
-        if let Some(active_tab) = self.active_tab {
-            // Deleteing active tab
-            if active_tab == idx {
-                if idx == 0 && self.labels.len() > 1 {
-                    self.tab_selected(0);
-                } else if idx == 0 {
-                    self.active_tab = None;
-                } else if idx == self.labels.len() - 1 {
-                    self.tab_selected(idx - 1);
-                } else {
-                    self.tab_selected(idx);
-                }
+    /// Closes the remaining tabs of a bulk close. Clean tabs are closed
+    /// straight away while the first dirty tab is selected and prompted for,
+    /// the bulk close resuming once it is saved or discarded. Returns true if
+    /// any tab was closed
+    fn continue_closing(&mut self) -> bool {
+        let mut closed = false;
+        let mut idx = 0;
+
+        while let Some(bulk) = self.closing {
+            let Some(tab) = self.tabs.get(idx) else {
+                break;
+            };
+
+            let pinned = self.labels[idx].is_pinned();
+
+            if bulk == BulkClose::Others(idx) || pinned || tab.needs_close_prompt() {
+                idx += 1;
+                continue;
             }
-            // Deleting other tab on left
-            else if idx < active_tab {
-                self.tab_selected(active_tab - 1);
+
+            self.force_close_tab(idx);
+            closed = true;
+        }
+
+        let Some(bulk) = self.closing else {
+            return closed;
+        };
+
+        let dirty =
+            (self.pinned_count()..self.tabs.len()).find(|idx| bulk != BulkClose::Others(*idx));
+
+        match dirty {
+            Some(dirty) => {
+                self.tab_selected(dirty);
+                self.modal_shown = true;
             }
-            // Deleting other tab on right
-            else {
-                self.tab_selected(active_tab);
+            None => {
+                if let BulkClose::Others(kept) = bulk {
+                    self.tab_selected(kept);
+                }
+                if self.tabs.len() < 2 {
+                    self.split = None;
+                }
+                self.closing = None;
             }
+        }
+
+        closed
+    }
+
+    fn force_close_tab(&mut self, idx: usize) {
+        self.closing = match self.closing {
+            // The kept tab being closed leaves nothing to keep
+            Some(BulkClose::Others(kept)) if kept == idx => None,
+            Some(BulkClose::Others(kept)) if idx < kept => Some(BulkClose::Others(kept - 1)),
+            closing => closing,
+        };
+
+        if let Some(active_tab) = self.active_tab {
+            match index_after_close(active_tab, idx, self.labels.len()) {
+                Some(active_tab) => self.tab_selected(active_tab),
+                None => self.active_tab = None,
+            }
+
+            // The second pane keeps its own tab unless that tab is closed
+            self.split = self
+                .split
+                .filter(|_| self.active_tab.is_some())
+                .and_then(|split| {
+                    let active = index_after_close(split.active, idx, self.labels.len())?;
+                    Some(Split { active, ..split })
+                });
 
             self.labels.remove(idx);
             self.tabs.remove(idx);
+
+            // The first pane may have moved onto the tab of the second
+            let len = self.tabs.len();
+            self.split = self.split.filter(|_| len > 1).map(|split| {
+                if Some(split.active) == self.active_tab {
+                    Split {
+                        active: (split.active + 1) % len,
+                        ..split
+                    }
+                } else {
+                    split
+                }
+            });
         }
     }
 
@@ -840,6 +1839,252 @@ where
     }
 }
 
+/// Returns the index of the tab selected in place of the tab at `active`
+/// after the tab at `closed` is closed, out of `len` tabs. The selection
+/// moves to a neighbour only if the selected tab itself is closed.
+fn index_after_close(active: usize, closed: usize, len: usize) -> Option<usize> {
+    if active == closed {
+        if closed == 0 && len > 1 {
+            Some(0)
+        } else if closed == 0 {
+            None
+        } else if closed == len - 1 {
+            Some(closed - 1)
+        } else {
+            Some(closed)
+        }
+    } else if closed < active {
+        Some(active - 1)
+    } else {
+        Some(active)
+    }
+}
+
+/// Returns where the tab at `idx` ends up after the tab at `from` is moved to `to`
+fn moved_index(idx: usize, from: usize, to: usize) -> usize {
+    if idx == from {
+        to
+    } else if from < idx && idx <= to {
+        idx - 1
+    } else if to <= idx && idx < from {
+        idx + 1
+    } else {
+        idx
+    }
+}
+
+/// Returns where the tab at `idx` ends up after a tab is inserted at `inserted`
+fn index_after_insert(idx: usize, inserted: usize) -> usize {
+    if idx >= inserted {
+        idx + 1
+    } else {
+        idx
+    }
+}
+
+#[test]
+fn test_moved_index() {
+    // Moving right shifts the tabs in between to the left
+    assert_eq!(moved_index(1, 1, 3), 3);
+    assert_eq!(moved_index(2, 1, 3), 1);
+    assert_eq!(moved_index(3, 1, 3), 2);
+    assert_eq!(moved_index(4, 1, 3), 4);
+
+    // Moving left shifts them to the right
+    assert_eq!(moved_index(3, 3, 0), 0);
+    assert_eq!(moved_index(0, 3, 0), 1);
+    assert_eq!(moved_index(2, 3, 0), 3);
+    assert_eq!(moved_index(4, 3, 0), 4);
+}
+
+#[test]
+fn test_index_after_insert() {
+    // Tabs at or after the new one shift to the right
+    assert_eq!(index_after_insert(2, 2), 3);
+    assert_eq!(index_after_insert(3, 2), 4);
+
+    // Tabs before it stay put
+    assert_eq!(index_after_insert(1, 2), 1);
+    assert_eq!(index_after_insert(0, 1), 0);
+}
+
+#[test]
+fn test_index_after_close() {
+    // Closing another tab keeps the same tab selected
+    assert_eq!(index_after_close(2, 0, 4), Some(1));
+    assert_eq!(index_after_close(1, 3, 4), Some(1));
+
+    // Closing the selected tab selects a neighbour
+    assert_eq!(index_after_close(1, 1, 4), Some(1));
+    assert_eq!(index_after_close(3, 3, 4), Some(2));
+    assert_eq!(index_after_close(0, 0, 2), Some(0));
+    assert_eq!(index_after_close(0, 0, 1), None);
+}
+
+#[test]
+fn test_close_prompting() {
+    use crate::widgets::wizard::{
+        BarChartConfigState, LineConfigState, StackedBarChartConfigState,
+    };
+    use iced::widget::text_editor::{Action, Edit};
+
+    let edit = || TabMessage::Editor(EditorMessage::Action(Action::Edit(Edit::Insert('b'))));
+
+    let mut editor = Tab::Editor(EditorTab::new(EditorTabData::new(None, "a".into())));
+    assert!(!editor.needs_close_prompt());
+    editor.update(edit());
+    assert!(editor.needs_close_prompt());
+
+    let data = EditorTabData::new(None, "piped".into()).name("stdin");
+    let stdin = Tab::Editor(EditorTab::new(data));
+    assert!(stdin.needs_close_prompt());
+
+    let data = EditorTabData::new(None, "log".into()).read_only(true);
+    let mut log = Tab::Editor(EditorTab::new(data));
+    log.update(edit());
+    assert_eq!(log.close_policy(), ClosePolicy::Never);
+    assert!(!log.needs_close_prompt());
+
+    let file = tempfile::Builder::new()
+        .suffix(".csv")
+        .tempfile()
+        .unwrap()
+        .into_temp_path();
+    std::fs::write(&file, "x,a,b\n1,2,3\n2,4,5\n").unwrap();
+
+    let line = LineTabData::new(file.to_path_buf(), LineConfigState::default()).unwrap();
+    let bar = BarChartConfigState {
+        y_col: 1,
+        ..Default::default()
+    };
+    let bar = BarChartTabData::new(file.to_path_buf(), bar).unwrap();
+    let stacked = StackedBarChartConfigState {
+        acc_cols_str: "1,2".into(),
+        ..Default::default()
+    };
+    let stacked = StackedBarChartTabData::new(file.to_path_buf(), stacked).unwrap();
+
+    let tabs = vec![
+        editor,
+        log,
+        Tab::LineGraph(LineGraphTab::new(line)),
+        Tab::BarChart(BarChartTab::new(bar)),
+        Tab::StackedBarChart(StackedBarChartTab::new(stacked)),
+    ];
+    let mut state = TabsState::with_tabs(tabs.into_iter(), Theme::default());
+
+    // Only the edited editor asks before closing
+    assert_eq!(state.has_dirty_tab(), Some(0));
+    for idx in (1..5).rev() {
+        assert!(state.close_tab(idx, false));
+    }
+    assert!(!state.close_tab(0, false));
+    assert!(state.modal_shown);
+}
+
+#[test]
+fn test_refresh_keeps_view_state() {
+    use super::{line::GraphType, shared::graph::LegendPosition};
+    use crate::widgets::wizard::{BarChartConfigState, LineConfigState};
+
+    let file = tempfile::Builder::new()
+        .suffix(".csv")
+        .tempfile()
+        .unwrap()
+        .into_temp_path();
+    std::fs::write(&file, "x,a,b\n1,2,3\n2,4,5\n").unwrap();
+
+    let state = ChartViewState {
+        legend: LegendPosition::BottomRight,
+        graph_type: GraphType::Point,
+        ..Default::default()
+    };
+
+    let line = LineTabData::new(file.to_path_buf(), LineConfigState::default()).unwrap();
+    let mut tab = Tab::LineGraph(LineGraphTab::new(line.clone()));
+    tab.restore_view_state(state.clone());
+    tab.refresh(Refresh::LineGraph(line));
+    assert_eq!(tab.view_state(), Some(state.clone()));
+
+    let bar = BarChartConfigState {
+        y_col: 1,
+        ..Default::default()
+    };
+    let bar = BarChartTabData::new(file.to_path_buf(), bar).unwrap();
+    let mut tab = Tab::BarChart(BarChartTab::new(bar.clone()));
+    tab.restore_view_state(state.clone());
+    tab.refresh(Refresh::BarChart(bar));
+    assert_eq!(
+        tab.view_state().map(|view_state| view_state.legend),
+        Some(LegendPosition::BottomRight)
+    );
+}
+
+#[test]
+fn test_bulk_close() {
+    use iced::widget::text_editor::{Action, Edit};
+
+    let edit = || TabMessage::Editor(EditorMessage::Action(Action::Edit(Edit::Insert('b'))));
+    let editor =
+        |content: &str| Tab::Editor(EditorTab::new(EditorTabData::new(None, content.into())));
+
+    let mut dirty = editor("a");
+    dirty.update(edit());
+    let tabs = vec![editor("b"), dirty, editor("c"), editor("d")];
+    let mut state = TabsState::with_tabs(tabs.into_iter(), Theme::default());
+
+    // Clean tabs close straight away, pausing at the dirty one
+    state.update(TabBarMessage::CloseOthers(3));
+    assert_eq!(state.len(), 2);
+    assert_eq!(state.active_tab_idx(), Some(0));
+    assert!(state.modal_shown);
+
+    // Discarding the dirty tab finishes the close, keeping the chosen tab
+    state.update(TabBarMessage::DirtyTabModal(DirtyTabModalAction::DontSave));
+    assert_eq!(state.len(), 1);
+    assert_eq!(state.active_tab_idx(), Some(0));
+    assert_eq!(state.closing, None);
+    assert_eq!(state.tabs[0].content().unwrap().trim(), "d");
+
+    state.update(TabBarMessage::CloseAll);
+    assert!(state.is_empty());
+    assert_eq!(state.active_tab_idx(), None);
+}
+
+#[test]
+fn test_pinning() {
+    let editor =
+        |content: &str| Tab::Editor(EditorTab::new(EditorTabData::new(None, content.into())));
+    let content = |state: &TabsState<Theme>| {
+        state
+            .tabs
+            .iter()
+            .map(|tab| tab.content().unwrap().trim().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let tabs = vec![editor("a"), editor("b"), editor("c"), editor("d")];
+    let mut state = TabsState::with_tabs(tabs.into_iter(), Theme::default());
+
+    // Pinned tabs move to the front, keeping the active tab selected
+    state.update(TabBarMessage::TogglePin(2));
+    state.update(TabBarMessage::TogglePin(3));
+    assert_eq!(content(&state), ["c", "d", "a", "b"]);
+    assert_eq!(state.active_tab_idx(), Some(1));
+
+    // Unpinned tabs cannot be dragged in among pinned ones
+    state.update(TabBarMessage::ReorderTab(3, 0));
+    assert_eq!(content(&state), ["c", "d", "b", "a"]);
+
+    state.update(TabBarMessage::TogglePin(0));
+    assert_eq!(content(&state), ["d", "c", "b", "a"]);
+
+    // Pinned tabs survive closing everything
+    state.update(TabBarMessage::CloseAll);
+    assert_eq!(content(&state), ["d"]);
+    assert_eq!(state.active_tab_idx(), Some(0));
+}
+
 pub mod bar {
     use iced::{
         advanced::{
@@ -855,7 +2100,7 @@ pub mod bar {
         touch,
         widget::{horizontal_space, text, Row, Text},
         Alignment, Background, Border, Color, Element, Font, Length, Padding, Pixels, Point,
-        Rectangle, Shadow, Size, Theme,
+        Rectangle, Shadow, Size, Theme, Vector,
     };
 
     use crate::utils::icons;
@@ -868,6 +2113,10 @@ pub mod bar {
         icon: char,
         icon_font: Option<Font>,
         icon_size: f32,
+        /// Dirty tabs show a dot in place of their close button
+        dirty: bool,
+        /// Pinned tabs only show their icon and cannot be closed from the bar
+        pinned: bool,
     }
 
     impl TabLabel {
@@ -879,6 +2128,8 @@ pub mod bar {
                 text: text.into(),
                 text_size: 16.0,
                 icon,
+                dirty: false,
+                pinned: false,
             }
         }
 
@@ -905,6 +2156,20 @@ pub mod bar {
             self.text_size = size;
             self
         }
+
+        pub fn dirty(mut self, dirty: bool) -> Self {
+            self.dirty = dirty;
+            self
+        }
+
+        pub fn pinned(mut self, pinned: bool) -> Self {
+            self.pinned = pinned;
+            self
+        }
+
+        pub fn is_pinned(&self) -> bool {
+            self.pinned
+        }
     }
 
     #[derive(Debug, Clone, Copy, PartialEq)]
@@ -988,8 +2253,35 @@ pub mod bar {
         min_tab_width: f32,
         max_tab_width: f32,
         tabs_spacing: f32,
+        drag: Option<TabDrag>,
+        /// How far the tabs are scrolled to the left, once they overflow the bar
+        scroll_offset: f32,
+        max_scroll_offset: f32,
+        /// The active tab and tab count last scrolled into view
+        revealed: Option<(usize, usize)>,
+    }
+
+    /// A tab that was pressed and may be dragged to a new position
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct TabDrag {
+        from: usize,
+        origin: Point,
+        /// Whether the cursor has moved far enough from `origin` to count
+        /// as a drag rather than a click
+        dragging: bool,
+    }
+
+    impl TabDrag {
+        /// How far, in pixels, the cursor has to move before a press becomes a drag
+        const THRESHOLD: f32 = 5.0;
     }
 
+    /// How far, in pixels, one line of mouse wheel scrolling moves the tabs
+    const SCROLL_LINE: f32 = 60.0;
+
+    /// The width left for the icon of a pinned tab, besides its padding
+    const PINNED_ICON_WIDTH: f32 = 24.0;
+
     impl TabBarState {
         fn new() -> Self {
             Self {
@@ -998,6 +2290,10 @@ pub mod bar {
                 max_tab_width: 250.0,
                 add_tabs_width: 32.0,
                 tabs_spacing: 2.0,
+                drag: None,
+                scroll_offset: 0.0,
+                max_scroll_offset: 0.0,
+                revealed: None,
             }
         }
     }
@@ -1017,6 +2313,8 @@ pub mod bar {
         on_close: Option<Box<dyn Fn(usize) -> Message>>,
         on_expand: Option<Box<dyn Fn() -> Message>>,
         on_select: Box<dyn Fn(usize) -> Message>,
+        on_reorder: Option<Box<dyn Fn(usize, usize) -> Message>>,
+        on_menu: Option<Box<dyn Fn(usize, Point) -> Message>>,
         close_size: f32,
         close_width: f32,
         close_height: f32,
@@ -1062,6 +2360,8 @@ pub mod bar {
                 width: Length::Fill,
                 height: Length::Shrink,
                 on_select: Box::new(on_select),
+                on_reorder: None,
+                on_menu: None,
                 close_size,
                 spacing: 0.0,
                 tab_padding: Padding::ZERO,
@@ -1115,11 +2415,146 @@ pub mod bar {
             self
         }
 
+        /// Makes tabs draggable. `on_reorder` receives the index of the
+        /// dragged tab and the index it should be moved to
+        pub fn on_reorder<F>(mut self, on_reorder: F) -> Self
+        where
+            F: 'static + Fn(usize, usize) -> Message,
+        {
+            self.on_reorder = Some(Box::new(on_reorder));
+            self
+        }
+
+        /// Sets the message produced when a tab is right clicked. `on_menu`
+        /// receives the index of the tab and the cursor position relative to
+        /// the bar
+        pub fn on_menu<F>(mut self, on_menu: F) -> Self
+        where
+            F: 'static + Fn(usize, Point) -> Message,
+        {
+            self.on_menu = Some(Box::new(on_menu));
+            self
+        }
+
         pub fn spacing(mut self, spacing: f32) -> Self {
             self.spacing = spacing;
             self
         }
 
+        /// Draws a thin marker in the gap a dragged tab would be dropped into
+        fn draw_drop_target<Renderer>(
+            &self,
+            renderer: &mut Renderer,
+            layout: advanced::Layout<'_>,
+            cursor: advanced::mouse::Cursor,
+            drag: TabDrag,
+            color: Color,
+        ) where
+            Renderer: renderer::Renderer,
+        {
+            let Some(point) = cursor
+                .position()
+                .filter(|point| layout.bounds().contains(*point))
+            else {
+                return;
+            };
+
+            let insertion = self.insertion_index(layout, point.x);
+
+            // Dropping a tab next to itself does not move it
+            if insertion == drag.from || insertion == drag.from + 1 {
+                return;
+            }
+
+            let Some(tab) = layout
+                .children()
+                .nth(insertion.saturating_sub(1))
+                .map(|layout| layout.bounds())
+            else {
+                return;
+            };
+
+            let width = 3.0;
+            let x = if insertion == 0 {
+                tab.x - width
+            } else {
+                tab.x + tab.width
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle::new(Point::new(x, tab.y), Size::new(width, tab.height)),
+                    border: Border::default().rounded(width / 2.0),
+                    shadow: Shadow::default(),
+                },
+                Color { a: 0.6, ..color },
+            );
+        }
+
+        /// Returns where the visible tabs end, relative to the start of a bar
+        /// `bar_width` wide. The expand button is pinned past this point.
+        fn tabs_end(&self, state: &TabBarState, bar_width: f32) -> f32 {
+            let add_tabs_width = if self.on_expand.is_some() {
+                state.add_tabs_width
+            } else {
+                0.0
+            };
+
+            bar_width - self.bar_padding.right - add_tabs_width
+        }
+
+        /// The part of the bar the tabs are visible in
+        fn tabs_viewport(&self, state: &TabBarState, bounds: Rectangle) -> Rectangle {
+            let x = bounds.x + self.bar_padding.left;
+            let width = self.tabs_end(state, bounds.width) - self.bar_padding.left;
+
+            Rectangle::new(
+                Point::new(x, bounds.y),
+                Size::new(width.max(0.0), bounds.height),
+            )
+        }
+
+        /// Returns the index of the tab under the cursor. The parts of tabs
+        /// scrolled out of view are ignored.
+        fn hovered_tab(
+            &self,
+            state: &TabBarState,
+            layout: advanced::Layout<'_>,
+            cursor: advanced::mouse::Cursor,
+        ) -> Option<usize> {
+            let point = cursor
+                .position()
+                .filter(|point| self.tabs_viewport(state, layout.bounds()).contains(*point))?;
+
+            layout
+                .children()
+                .take(self.labels.len())
+                .position(|layout| layout.bounds().contains(point))
+        }
+
+        /// Returns true if the cursor is over the expand button
+        fn is_over_add_tabs(
+            &self,
+            layout: advanced::Layout<'_>,
+            cursor: advanced::mouse::Cursor,
+        ) -> bool {
+            self.on_expand.is_some()
+                && layout
+                    .children()
+                    .last()
+                    .map_or(false, |layout| cursor.is_over(layout.bounds()))
+        }
+
+        /// Returns the index a tab dropped at `x` is inserted before. The
+        /// result ranges from 0 up to the number of tabs.
+        fn insertion_index(&self, layout: advanced::Layout<'_>, x: f32) -> usize {
+            layout
+                .children()
+                .take(self.labels.len())
+                .filter(|layout| layout.bounds().center_x() < x)
+                .count()
+        }
+
         pub fn tab_padding(mut self, padding: impl Into<Padding>) -> Self {
             self.tab_padding = padding.into();
             self
@@ -1179,17 +2614,25 @@ pub mod bar {
         fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
             let state = tree.state.downcast_mut::<TabBarState>();
 
+            let bar_width = limits
+                .clone()
+                .resolve(self.width, self.height, Size::UNIT)
+                .width;
+
+            let pinned_width = self.tab_padding.horizontal() + PINNED_ICON_WIDTH;
+
             // Dynamic tab widths
             {
-                let bar_width = limits
-                    .clone()
-                    .resolve(self.width, self.height, Size::UNIT)
-                    .width;
-                let tabs_space = bar_width - state.add_tabs_width - self.bar_padding.horizontal();
+                let pinned = self.labels.iter().filter(|label| label.pinned).count();
+                let tabs_space = bar_width
+                    - state.add_tabs_width
+                    - self.bar_padding.horizontal()
+                    - pinned_width * pinned as f32;
 
                 let total_spacing = state.tabs_spacing * ((self.labels.len() - 1) as f32);
 
-                let mut tabs_width = (tabs_space - total_spacing) / (self.labels.len() as f32);
+                let unpinned = (self.labels.len() - pinned).max(1);
+                let mut tabs_width = (tabs_space - total_spacing) / (unpinned as f32);
 
                 tabs_width = tabs_width.clamp(state.min_tab_width, state.max_tab_width);
 
@@ -1209,7 +2652,11 @@ pub mod bar {
                         Text::<Theme, Renderer>::new(label.text.clone())
                             .font(font)
                             .align_x(alignment::Horizontal::Center)
-                            .width(Length::Shrink)
+                            .width(if label.pinned {
+                                Length::Fixed(0.0)
+                            } else {
+                                Length::Shrink
+                            })
                     };
 
                     let icon = {
@@ -1220,23 +2667,35 @@ pub mod bar {
                             .align_x(alignment::Horizontal::Center)
                             .align_y(alignment::Vertical::Center)
                             .shaping(advanced::text::Shaping::Advanced)
-                            .width(Length::Shrink)
+                            .width(if label.pinned {
+                                Length::Fill
+                            } else {
+                                Length::Shrink
+                            })
+                    };
+
+                    // Pinned tabs keep the same layout children, with room
+                    // for only their icon
+                    let (tab_width, icon_text_width, spacing) = if label.pinned {
+                        (pinned_width, PINNED_ICON_WIDTH, 0.0)
+                    } else {
+                        (state.tab_width, icon_text_width, self.spacing)
                     };
 
                     let icon_text = Row::new()
-                        .spacing(self.spacing)
+                        .spacing(spacing)
                         .push(icon)
                         .push(text)
                         .width(icon_text_width);
 
                     let mut label_row = Row::new()
                         .push(icon_text)
-                        .width(state.tab_width)
+                        .width(tab_width)
                         .height(self.tab_height)
                         .padding(self.tab_padding)
                         .align_y(Alignment::Center);
 
-                    if self.on_close.is_some() {
+                    if self.on_close.is_some() && !label.pinned {
                         label_row = label_row.push(horizontal_space()).push(
                             Row::new()
                                 .width(Length::Fixed(self.close_width))
@@ -1250,7 +2709,6 @@ pub mod bar {
                 .spacing(state.tabs_spacing)
                 .padding(self.bar_padding)
                 .align_y(Alignment::Center)
-                .width(self.width)
                 .height(self.height);
 
             if self.on_expand.is_some() {
@@ -1273,14 +2731,50 @@ pub mod bar {
                 &mut tree.children[0]
             };
 
-            element
-                .as_widget()
-                .layout(tab_tree, renderer, &limits.loose())
+            // Tabs at their minimum width may not fit, so they are laid out
+            // unbounded and scrolled within the bar
+            let unbounded = Limits::new(Size::ZERO, Size::new(f32::INFINITY, limits.max().height));
+            let row = element.as_widget().layout(tab_tree, renderer, &unbounded);
+            let (tabs, add_tabs) = row.children().split_at(self.labels.len());
+
+            let tabs_end = self.tabs_end(state, bar_width);
+            let content_end = tabs
+                .last()
+                .map_or(0.0, |tab| tab.bounds().x + tab.bounds().width);
+
+            state.max_scroll_offset = (content_end - tabs_end).max(0.0);
+
+            // Only a change of tab scrolls on its own, leaving the wheel free
+            // to move away from the active tab
+            let revealed = (self.active_tab, self.labels.len());
+            if state.revealed != Some(revealed) {
+                if let Some(active) = tabs.get(self.active_tab).map(|tab| tab.bounds()) {
+                    let start = active.x - self.bar_padding.left;
+                    let end = active.x + active.width - tabs_end;
+
+                    state.scroll_offset = state.scroll_offset.min(start).max(end);
+                }
+                state.revealed = Some(revealed);
+            }
+
+            state.scroll_offset = state.scroll_offset.clamp(0.0, state.max_scroll_offset);
+
+            let offset = state.scroll_offset;
+            let children = tabs
+                .iter()
+                .map(|tab| tab.clone().translate(Vector::new(-offset, 0.0)))
+                .chain(add_tabs.iter().map(|add_tabs| {
+                    let x = (add_tabs.bounds().x - offset).min(tabs_end);
+                    add_tabs.clone().move_to(Point::new(x, add_tabs.bounds().y))
+                }))
+                .collect();
+
+            Node::with_children(Size::new(bar_width, row.size().height), children)
         }
 
         fn draw(
             &self,
-            _tree: &Tree,
+            tree: &Tree,
             renderer: &mut Renderer,
             theme: &Theme,
             _style: &renderer::Style,
@@ -1303,127 +2797,196 @@ pub mod bar {
                 )
             }
 
-            for ((idx, tab), layout) in self.labels.iter().enumerate().zip(children) {
-                let is_selected = idx == self.active_tab;
-                let icon_data = (
-                    tab.icon_font.unwrap_or(renderer.default_font()),
-                    tab.icon_size,
-                );
-                let text_data = (
-                    tab.text_font.unwrap_or(renderer.default_font()),
-                    tab.text_size,
-                );
+            let state = tree.state.downcast_ref::<TabBarState>();
+            let tabs_viewport = self.tabs_viewport(state, bounds);
 
-                let bounds = layout.bounds();
-                let is_mouse_over = cursor
-                    .position()
-                    .map_or(false, |point| bounds.contains(point));
+            // Tabs scrolled out of view are neither drawn nor hovered
+            let tabs_cursor = match cursor.position() {
+                Some(point) if !tabs_viewport.contains(point) => {
+                    advanced::mouse::Cursor::Unavailable
+                }
+                _ => cursor,
+            };
 
-                let mut children = layout.children();
-                let label_layout = children
-                    .next()
-                    .expect("TabBar: Layout should have a label layout");
+            renderer.with_layer(tabs_viewport, |renderer| {
+                for ((idx, tab), layout) in self.labels.iter().enumerate().zip(children) {
+                    let is_selected = idx == self.active_tab;
+                    let icon_data = (
+                        tab.icon_font.unwrap_or(renderer.default_font()),
+                        tab.icon_size,
+                    );
+                    let text_data = (
+                        tab.text_font.unwrap_or(renderer.default_font()),
+                        tab.text_size,
+                    );
 
-                let mut label_layout_children = label_layout.children();
+                    let bounds = layout.bounds();
+                    let is_mouse_over = tabs_cursor.is_over(bounds);
 
-                if bounds.intersects(viewport) {
-                    renderer.fill_quad(
-                        renderer::Quad {
-                            bounds,
-                            border: style.tab_border,
-                            shadow: style.tab_shadow,
-                        },
-                        if is_selected || is_mouse_over {
-                            style.tab_background_hovered
-                        } else {
-                            style.tab_background_active
-                        },
-                    )
-                };
+                    let mut children = layout.children();
+                    let label_layout = children
+                        .next()
+                        .expect("TabBar: Layout should have a label layout");
 
-                let icon_bounds = label_layout_children
-                    .next()
-                    .expect("TabBar: Layout should have an icon layout")
-                    .bounds();
-                let text_bounds = label_layout_children
-                    .next()
-                    .expect("TabBar: Layout should have an text layout")
-                    .bounds();
-
-                renderer.fill_text(
-                    advanced::Text {
-                        content: tab.icon.to_string(),
-                        bounds: Size::new(icon_bounds.width, icon_bounds.height),
-                        size: Pixels(icon_data.1),
-                        line_height: LineHeight::default(),
-                        font: icon_data.0,
-                        horizontal_alignment: alignment::Horizontal::Center,
-                        vertical_alignment: alignment::Vertical::Center,
-                        shaping: Shaping::Advanced,
-                        wrapping: Wrapping::None,
-                    },
-                    Point::new(icon_bounds.center_x(), icon_bounds.center_y()),
-                    style.tab_icon_color.unwrap_or(style.tab_text_color),
-                    icon_bounds,
-                );
-
-                renderer.fill_text(
-                    advanced::Text {
-                        content: tab.text.to_string(),
-                        bounds: Size::new(text_bounds.width, text_bounds.height),
-                        size: Pixels(text_data.1),
-                        line_height: LineHeight::default(),
-                        font: text_data.0,
-                        horizontal_alignment: alignment::Horizontal::Center,
-                        vertical_alignment: alignment::Vertical::Center,
-                        shaping: Shaping::Advanced,
-                        wrapping: Wrapping::None,
-                    },
-                    Point::new(text_bounds.center_x(), text_bounds.center_y()),
-                    style.tab_text_color,
-                    text_bounds,
-                );
+                    let mut label_layout_children = label_layout.children();
 
-                if is_selected || is_mouse_over {
-                    let _ = children.next();
-                    if let Some(close_layout) = children.next() {
-                        let close_bounds = close_layout.bounds();
-                        let is_mouse_over = cursor.is_over(close_bounds);
+                    if bounds.intersects(viewport) {
+                        renderer.fill_quad(
+                            renderer::Quad {
+                                bounds,
+                                border: style.tab_border,
+                                shadow: style.tab_shadow,
+                            },
+                            if is_selected || is_mouse_over {
+                                style.tab_background_hovered
+                            } else {
+                                style.tab_background_active
+                            },
+                        )
+                    };
+
+                    let icon_bounds = label_layout_children
+                        .next()
+                        .expect("TabBar: Layout should have an icon layout")
+                        .bounds();
+                    let text_bounds = label_layout_children
+                        .next()
+                        .expect("TabBar: Layout should have an text layout")
+                        .bounds();
 
-                        let font = Font::with_name(icons::NAME);
+                    renderer.fill_text(
+                        advanced::Text {
+                            content: tab.icon.to_string(),
+                            bounds: Size::new(icon_bounds.width, icon_bounds.height),
+                            size: Pixels(icon_data.1),
+                            line_height: LineHeight::default(),
+                            font: icon_data.0,
+                            horizontal_alignment: alignment::Horizontal::Center,
+                            vertical_alignment: alignment::Vertical::Center,
+                            shaping: Shaping::Advanced,
+                            wrapping: Wrapping::None,
+                        },
+                        Point::new(icon_bounds.center_x(), icon_bounds.center_y()),
+                        style.tab_icon_color.unwrap_or(style.tab_text_color),
+                        icon_bounds,
+                    );
 
+                    if tab.pinned {
+                        // The icon font has no pin, so pinned tabs are marked
+                        // with a bar beneath their icon
+                        let (width, height) = (12.0, 3.0);
+
+                        renderer.fill_quad(
+                            renderer::Quad {
+                                bounds: Rectangle::new(
+                                    Point::new(
+                                        icon_bounds.center_x() - width / 2.0,
+                                        bounds.y + bounds.height - height - 3.0,
+                                    ),
+                                    Size::new(width, height),
+                                ),
+                                border: Border::default().rounded(height / 2.0),
+                                shadow: Shadow::default(),
+                            },
+                            style.close_text_color.unwrap_or(style.tab_text_color),
+                        );
+                    } else {
                         renderer.fill_text(
                             advanced::Text {
-                                content: icons::CLOSE.to_string(),
-                                bounds: Size::new(close_bounds.width, close_bounds.height),
-                                size: Pixels(
-                                    self.close_size * if is_mouse_over { 1.05 } else { 1.0 },
-                                ),
+                                content: tab.text.to_string(),
+                                bounds: Size::new(text_bounds.width, text_bounds.height),
+                                size: Pixels(text_data.1),
                                 line_height: LineHeight::default(),
-                                font,
+                                font: text_data.0,
                                 horizontal_alignment: alignment::Horizontal::Center,
                                 vertical_alignment: alignment::Vertical::Center,
                                 shaping: Shaping::Advanced,
                                 wrapping: Wrapping::None,
                             },
-                            Point::new(close_bounds.center_x(), close_bounds.center_y()),
-                            style.close_text_color.unwrap_or(style.tab_text_color),
-                            close_bounds,
+                            Point::new(text_bounds.center_x(), text_bounds.center_y()),
+                            style.tab_text_color,
+                            text_bounds,
                         );
+                    }
+
+                    let _ = children.next();
+                    let close_layout = children.next();
+
+                    if let Some(close_layout) = close_layout.filter(|_| tab.dirty) {
+                        let close_bounds = close_layout.bounds();
+
+                        if !tabs_cursor.is_over(close_bounds) && close_bounds.intersects(viewport) {
+                            let radius = 4.0;
 
-                        if is_mouse_over && close_bounds.intersects(viewport) {
                             renderer.fill_quad(
                                 renderer::Quad {
-                                    bounds: close_bounds,
-                                    border: style.close_border,
+                                    bounds: Rectangle::new(
+                                        Point::new(
+                                            close_bounds.center_x() - radius,
+                                            close_bounds.center_y() - radius,
+                                        ),
+                                        Size::new(2.0 * radius, 2.0 * radius),
+                                    ),
+                                    border: Border::default().rounded(radius),
                                     shadow: Shadow::default(),
                                 },
-                                style.close_background,
-                            )
+                                style.close_text_color.unwrap_or(style.tab_text_color),
+                            );
+
+                            continue;
+                        }
+                    }
+
+                    if is_selected || is_mouse_over {
+                        if let Some(close_layout) = close_layout {
+                            let close_bounds = close_layout.bounds();
+                            let is_mouse_over = tabs_cursor.is_over(close_bounds);
+
+                            let font = Font::with_name(icons::NAME);
+
+                            renderer.fill_text(
+                                advanced::Text {
+                                    content: icons::CLOSE.to_string(),
+                                    bounds: Size::new(close_bounds.width, close_bounds.height),
+                                    size: Pixels(
+                                        self.close_size * if is_mouse_over { 1.05 } else { 1.0 },
+                                    ),
+                                    line_height: LineHeight::default(),
+                                    font,
+                                    horizontal_alignment: alignment::Horizontal::Center,
+                                    vertical_alignment: alignment::Vertical::Center,
+                                    shaping: Shaping::Advanced,
+                                    wrapping: Wrapping::None,
+                                },
+                                Point::new(close_bounds.center_x(), close_bounds.center_y()),
+                                style.close_text_color.unwrap_or(style.tab_text_color),
+                                close_bounds,
+                            );
+
+                            if is_mouse_over && close_bounds.intersects(viewport) {
+                                renderer.fill_quad(
+                                    renderer::Quad {
+                                        bounds: close_bounds,
+                                        border: style.close_border,
+                                        shadow: Shadow::default(),
+                                    },
+                                    style.close_background,
+                                )
+                            }
                         }
                     }
                 }
-            }
+
+                if let Some(drag) = state.drag.filter(|drag| drag.dragging) {
+                    self.draw_drop_target(
+                        renderer,
+                        layout,
+                        tabs_cursor,
+                        drag,
+                        style.tab_text_color,
+                    );
+                }
+            });
 
             if self.on_expand.is_some() {
                 let add_tabs_layout = layout
@@ -1478,37 +3041,30 @@ pub mod bar {
 
         fn mouse_interaction(
             &self,
-            _state: &Tree,
+            tree: &Tree,
             layout: advanced::Layout<'_>,
             cursor: advanced::mouse::Cursor,
             _viewport: &Rectangle,
             _renderer: &Renderer,
         ) -> mouse::Interaction {
-            let children = layout.children();
-            let mut mouse_interaction = mouse::Interaction::default();
-
-            for layout in children {
-                let is_mouse_over = cursor
-                    .position()
-                    .map_or(false, |point| layout.bounds().contains(point));
-
-                let new_interaction = if is_mouse_over {
-                    mouse::Interaction::Pointer
-                } else {
-                    mouse::Interaction::default()
-                };
+            let state = tree.state.downcast_ref::<TabBarState>();
 
-                if new_interaction > mouse_interaction {
-                    mouse_interaction = new_interaction;
-                }
+            if state.drag.map_or(false, |drag| drag.dragging) {
+                return mouse::Interaction::Grabbing;
             }
 
-            mouse_interaction
+            if self.is_over_add_tabs(layout, cursor)
+                || self.hovered_tab(state, layout, cursor).is_some()
+            {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            }
         }
 
         fn on_event(
             &mut self,
-            _state: &mut Tree,
+            tree: &mut Tree,
             event: Event,
             layout: advanced::Layout<'_>,
             cursor: mouse::Cursor,
@@ -1517,6 +3073,8 @@ pub mod bar {
             shell: &mut advanced::Shell<'_, Message>,
             _viewport: &Rectangle,
         ) -> Status {
+            let state = tree.state.downcast_mut::<TabBarState>();
+
             match event {
                 Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
                 | Event::Touch(touch::Event::FingerPressed { .. }) => {
@@ -1524,33 +3082,135 @@ pub mod bar {
                         .position()
                         .map_or(false, |point| layout.bounds().contains(point))
                     {
-                        let tabs_map: Vec<bool> = layout
-                            .children()
-                            .map(|layout| {
-                                cursor
-                                    .position()
-                                    .map_or(false, |point| layout.bounds().contains(point))
-                            })
-                            .collect();
+                        let on_expand = self
+                            .on_expand
+                            .as_ref()
+                            .filter(|_| self.is_over_add_tabs(layout, cursor));
+
+                        if let Some(on_expand) = on_expand {
+                            shell.publish((on_expand)())
+                        } else if let Some(selected) = self.hovered_tab(state, layout, cursor) {
+                            let on_close = self.on_close.as_ref().filter(|_on_close| {
+                                let tab_layout = layout.children().nth(selected).expect(
+                                    "TabBar: Layout should have a tab layout at selected index",
+                                );
+                                // Pinned tabs have no close layout
+                                tab_layout.children().nth(2).map_or(false, |cross_layout| {
+                                    cursor.is_over(cross_layout.bounds())
+                                })
+                            });
+
+                            let message = match on_close {
+                                Some(on_close) => (on_close)(selected),
+                                None => {
+                                    if self.on_reorder.is_some() {
+                                        state.drag = cursor.position().map(|origin| TabDrag {
+                                            from: selected,
+                                            origin,
+                                            dragging: false,
+                                        });
+                                    }
+
+                                    (self.on_select)(selected)
+                                }
+                            };
+
+                            shell.publish(message);
+                        }
+                    }
+                    Status::Ignored
+                }
+                Event::Mouse(mouse::Event::CursorMoved { position })
+                | Event::Touch(touch::Event::FingerMoved { position, .. }) => {
+                    match state.drag.as_mut() {
+                        Some(drag) if !drag.dragging => {
+                            drag.dragging = drag.origin.distance(position) > TabDrag::THRESHOLD;
+                            Status::Ignored
+                        }
+                        Some(_) => Status::Captured,
+                        None => Status::Ignored,
+                    }
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                | Event::Touch(touch::Event::FingerLifted { .. })
+                | Event::Touch(touch::Event::FingerLost { .. })
+                | Event::Mouse(mouse::Event::CursorLeft) => {
+                    let Some(drag) = state.drag.take().filter(|drag| drag.dragging) else {
+                        return Status::Ignored;
+                    };
 
-                        if let Some(selected) = tabs_map.iter().position(|b| *b) {
-                            if self.on_expand.is_some() && selected == tabs_map.len() - 1 {
-                                let on_expand = self.on_expand.as_ref().unwrap();
+                    // Dropping outside the bar cancels the drag
+                    let Some(point) = cursor
+                        .position()
+                        .filter(|point| layout.bounds().contains(*point))
+                    else {
+                        return Status::Captured;
+                    };
 
-                                shell.publish((on_expand)())
-                            } else {
-                                let message = self.on_close.as_ref().filter(|_on_close| {
-                                    let tab_layout = layout.children().nth(selected).expect("TabBar: Layout should have a tab layout at selected index");
-                                    let cross_layout = tab_layout.children().nth(2).expect("TabBar: Layout should have a close layout");
+                    let insertion = self.insertion_index(layout, point.x);
+                    let to = if insertion > drag.from {
+                        insertion - 1
+                    } else {
+                        insertion
+                    };
 
-                                    cursor.position().map_or(false, |point| cross_layout.bounds().contains(point))
-                                }).map_or_else(|| (self.on_select) (selected), |on_close| (on_close)(selected));
+                    if let Some(on_reorder) = self.on_reorder.as_ref().filter(|_| to != drag.from) {
+                        shell.publish((on_reorder)(drag.from, to));
+                    }
 
-                                shell.publish(message);
-                            }
+                    Status::Captured
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                    let Some(on_close) = self.on_close.as_ref() else {
+                        return Status::Ignored;
+                    };
+
+                    match self.hovered_tab(state, layout, cursor) {
+                        Some(idx) => {
+                            shell.publish((on_close)(idx));
+                            Status::Captured
                         }
+                        None => Status::Ignored,
                     }
-                    Status::Ignored
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                    let Some(on_menu) = self.on_menu.as_ref() else {
+                        return Status::Ignored;
+                    };
+
+                    let Some(point) = cursor.position() else {
+                        return Status::Ignored;
+                    };
+
+                    match self.hovered_tab(state, layout, cursor) {
+                        Some(idx) => {
+                            let origin = layout.bounds().position();
+                            let position = Point::new(point.x - origin.x, point.y - origin.y);
+
+                            shell.publish((on_menu)(idx, position));
+                            Status::Captured
+                        }
+                        None => Status::Ignored,
+                    }
+                }
+                Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                    if state.max_scroll_offset <= 0.0 || !cursor.is_over(layout.bounds()) {
+                        return Status::Ignored;
+                    }
+
+                    let (x, y) = match delta {
+                        mouse::ScrollDelta::Lines { x, y } => (x * SCROLL_LINE, y * SCROLL_LINE),
+                        mouse::ScrollDelta::Pixels { x, y } => (x, y),
+                    };
+
+                    // A plain vertical wheel scrolls the tabs sideways too
+                    let delta = if x != 0.0 { x } else { y };
+
+                    state.scroll_offset =
+                        (state.scroll_offset - delta).clamp(0.0, state.max_scroll_offset);
+                    shell.invalidate_layout();
+
+                    Status::Captured
                 }
                 _ => Status::Ignored,
             }
@@ -1569,3 +3229,36 @@ pub mod bar {
         }
     }
 }
+
+#[test]
+fn test_split_panes() {
+    let editor =
+        |content: &str| Tab::Editor(EditorTab::new(EditorTabData::new(None, content.into())));
+    let panes = |state: &TabsState<Theme>| {
+        (
+            state.active_tab_idx(),
+            state.split.map(|split| split.active),
+        )
+    };
+
+    // A single tab has nothing to show beside it
+    let mut state = TabsState::with_tabs(vec![editor("a")].into_iter(), Theme::default());
+    state.update(TabBarMessage::ToggleSplit);
+    assert!(!state.is_split());
+
+    let tabs = vec![editor("a"), editor("b"), editor("c")];
+    let mut state = TabsState::with_tabs(tabs.into_iter(), Theme::default());
+    state.update(TabBarMessage::TabSelected(0));
+    state.update(TabBarMessage::ToggleSplit);
+    assert_eq!(panes(&state), (Some(0), Some(1)));
+
+    // Either pane picking the other's tab swaps them
+    state.update(TabBarMessage::TabSelected(1));
+    assert_eq!(panes(&state), (Some(1), Some(0)));
+    state.update(TabBarMessage::SplitTabSelected(1));
+    assert_eq!(panes(&state), (Some(0), Some(1)));
+
+    // Closing the first pane's tab leaves the panes on different tabs
+    state.update(TabBarMessage::CloseTab(0, true));
+    assert_eq!(panes(&state), (Some(0), Some(1)));
+}
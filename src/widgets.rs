@@ -1,3 +1,4 @@
+pub mod aspect;
 pub mod modal;
 pub mod sidemenu;
 pub mod style;
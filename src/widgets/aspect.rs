@@ -0,0 +1,204 @@
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::advanced::{self, Clipboard, Shell};
+use iced::event;
+use iced::mouse;
+use iced::{Element, Event, Length, Point, Rectangle, Size, Vector};
+
+/// A widget that fills its space and centers its content at a fixed width to
+/// height ratio, leaving the rest empty
+pub struct AspectFrame<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    ratio: Option<f32>,
+}
+
+impl<'a, Message, Theme, Renderer> AspectFrame<'a, Message, Theme, Renderer> {
+    /// Returns a new [`AspectFrame`]. The content fills all the space when
+    /// `ratio` is `None`.
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        ratio: Option<f32>,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            ratio: ratio.filter(|ratio| ratio.is_finite() && *ratio > 0.0),
+        }
+    }
+}
+
+/// Returns the largest size with the width to height `ratio` that fits in
+/// `bounds`
+pub fn fit(bounds: Size, ratio: f32) -> Size {
+    if bounds.width / ratio <= bounds.height {
+        Size::new(bounds.width, bounds.width / ratio)
+    } else {
+        Size::new(bounds.height * ratio, bounds.height)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for AspectFrame<'a, Message, Theme, Renderer>
+where
+    Renderer: advanced::Renderer,
+{
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(&[&self.content]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut widget::Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = limits.max();
+
+        let Some(ratio) = self.ratio else {
+            let child = self
+                .content
+                .as_widget()
+                .layout(&mut tree.children[0], renderer, limits);
+
+            return layout::Node::with_children(size, vec![child]);
+        };
+
+        let fitted = fit(size, ratio);
+        let limits = layout::Limits::new(Size::ZERO, fitted)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let child = self
+            .content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, &limits)
+            .move_to(Point::new(
+                (size.width - fitted.width) * 0.5,
+                (size.height - fitted.height) * 0.5,
+            ));
+
+        layout::Node::with_children(size, vec![child])
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut state.children[0],
+            event,
+            layout.children().next().unwrap(),
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        state: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &state.children[0],
+            renderer,
+            theme,
+            style,
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut state.children[0],
+            layout.children().next().unwrap(),
+            renderer,
+            translation,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &state.children[0],
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        state: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation,
+    ) {
+        self.content.as_widget().operate(
+            &mut state.children[0],
+            layout.children().next().unwrap(),
+            renderer,
+            operation,
+        );
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<AspectFrame<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: advanced::Renderer + 'a,
+{
+    fn from(value: AspectFrame<'a, Message, Theme, Renderer>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[test]
+fn test_fit() {
+    assert_eq!(fit(Size::new(400.0, 200.0), 1.0), Size::new(200.0, 200.0));
+    assert_eq!(fit(Size::new(200.0, 400.0), 1.0), Size::new(200.0, 200.0));
+    assert_eq!(
+        fit(Size::new(320.0, 400.0), 16.0 / 9.0),
+        Size::new(320.0, 180.0)
+    );
+}
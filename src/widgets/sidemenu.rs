@@ -3,11 +3,12 @@ use iced::{
     alignment::{Horizontal, Vertical},
     event, mouse,
     widget::{Space, Text},
-    Background, Border, Color, Element, Event, Font, Length, Padding, Point, Rectangle, Renderer,
-    Size, Theme, Vector,
+    window, Background, Border, Color, Element, Event, Font, Length, Padding, Point, Rectangle,
+    Renderer, Size, Theme, Vector,
 };
 
 use crate::utils::icons;
+use std::time::{Duration, Instant};
 
 pub use context::Context;
 pub use menu::Menu;
@@ -163,6 +164,8 @@ mod menu {
             let state = tree.state.downcast_mut::<State>();
             let bounds = layout.bounds();
 
+            state.hovered = cursor.is_over(bounds);
+
             if event == Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
                 && cursor.is_over(bounds)
             {
@@ -280,11 +283,20 @@ mod menu {
                     viewport,
                 );
             } else {
-                for ((child, state), layout) in self
+                let label_style = advanced::renderer::Style {
+                    text_color: Color {
+                        a: style.text_color.a * state.fade,
+                        ..style.text_color
+                    },
+                };
+                let styles = [*style, label_style];
+
+                for (((child, state), layout), style) in self
                     .children
                     .iter()
                     .zip(&tree.children)
                     .zip(layout.children())
+                    .zip(&styles)
                 {
                     child
                         .as_widget()
@@ -300,13 +312,41 @@ mod menu {
             renderer: &Renderer,
             translation: Vector,
         ) -> Option<advanced::overlay::Element<'b, Message, Theme, Renderer>> {
-            advanced::overlay::from_children(
-                &mut self.children,
-                tree,
-                layout,
-                renderer,
-                translation,
-            )
+            let state = tree.state.downcast_ref::<State>();
+
+            if !(state.collapsed && state.hovered) {
+                return advanced::overlay::from_children(
+                    &mut self.children,
+                    tree,
+                    layout,
+                    renderer,
+                    translation,
+                );
+            }
+
+            // Only the icon is shown, so the label is drawn beside it instead
+            let bounds = layout.bounds();
+            let position = Point::new(bounds.x + bounds.width + LABEL_GAP, bounds.y) + translation;
+
+            let [icon, label] = &mut self.children;
+            let (icon_tree, label_tree) = tree.children.split_at_mut(1);
+            let icon_layout = layout.children().next().expect("Menu missing first child");
+
+            let mut overlays: Vec<_> = icon
+                .as_widget_mut()
+                .overlay(&mut icon_tree[0], icon_layout, renderer, translation)
+                .into_iter()
+                .collect();
+
+            let label = Label {
+                content: label,
+                tree: &mut label_tree[0],
+                position,
+                height: bounds.height,
+            };
+            overlays.push(advanced::overlay::Element::new(Box::new(label)));
+
+            Some(advanced::overlay::Group::with_children(overlays).overlay())
         }
 
         //fn operate(
@@ -342,11 +382,102 @@ mod menu {
     #[derive(Debug, Clone, Copy)]
     pub(super) struct State {
         pub collapsed: bool,
+        /// Opacity of the label, faded out as the menu collapses
+        fade: f32,
+        hovered: bool,
     }
 
     impl State {
         fn new() -> Self {
-            Self { collapsed: false }
+            Self {
+                collapsed: false,
+                fade: 1.0,
+                hovered: false,
+            }
+        }
+
+        /// Collapses the menu once `progress` reaches 0.0, fading the label
+        /// until then.
+        pub(super) fn animate(&mut self, progress: f32) {
+            self.collapsed = progress <= 0.0;
+            self.fade = progress;
+        }
+    }
+
+    /// Space between a collapsed [`Menu`] and its label
+    const LABEL_GAP: f32 = 12.0;
+
+    /// The label of a collapsed [`Menu`], shown as a tooltip while the menu
+    /// is hovered
+    struct Label<'a, 'b, Message> {
+        content: &'b Element<'a, Message, Theme, Renderer>,
+        tree: &'b mut tree::Tree,
+        position: Point,
+        /// Height of the menu, used to center the label beside it
+        height: f32,
+    }
+
+    impl<'a, 'b, Message> advanced::overlay::Overlay<Message, Theme, Renderer>
+        for Label<'a, 'b, Message>
+    {
+        fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+            let padding = Padding::from([4.0, 8.0]);
+            let limits = layout::Limits::new(Size::ZERO, bounds)
+                .shrink(Size::new(padding.horizontal(), padding.vertical()));
+
+            let child = self
+                .content
+                .as_widget()
+                .layout(self.tree, renderer, &limits);
+            let node = layout::Node::container(child, padding);
+
+            let y = self.position.y + (self.height - node.size().height) * 0.5;
+            node.move_to(Point::new(self.position.x, y))
+        }
+
+        fn draw(
+            &self,
+            renderer: &mut Renderer,
+            theme: &Theme,
+            _style: &advanced::renderer::Style,
+            layout: layout::Layout<'_>,
+            cursor: mouse::Cursor,
+        ) {
+            let bounds = layout.bounds();
+            let palette = theme.extended_palette();
+
+            let border = Border::default()
+                .rounded(4.0)
+                .width(1.0)
+                .color(palette.background.strong.color);
+
+            <Renderer as advanced::Renderer>::fill_quad(
+                renderer,
+                Quad {
+                    bounds,
+                    border,
+                    ..Default::default()
+                },
+                Background::Color(palette.background.weak.color),
+            );
+
+            let style = advanced::renderer::Style {
+                text_color: palette.background.weak.text,
+            };
+            let layout = layout.children().next().expect("Label missing content");
+
+            self.content
+                .as_widget()
+                .draw(self.tree, renderer, theme, &style, layout, cursor, &bounds);
+        }
+
+        fn is_over(
+            &self,
+            _layout: layout::Layout<'_>,
+            _renderer: &Renderer,
+            _cursor: Point,
+        ) -> bool {
+            false
         }
     }
 }
@@ -400,10 +531,10 @@ mod sections {
         }
 
         /// Requires `tree` to be a valid [`Section`] widget tree.
-        pub(super) fn collapse(tree: &mut tree::Tree, collapse: bool) {
+        pub(super) fn animate(tree: &mut tree::Tree, progress: f32) {
             tree.children.iter_mut().for_each(|child| {
                 let state = child.state.downcast_mut::<MenuState>();
-                state.collapsed = collapse
+                state.animate(progress)
             });
         }
     }
@@ -559,27 +690,34 @@ mod overlay {
         widget::text::{LineHeight, Shaping, Wrapping},
     };
 
-    pub struct Overlay<'a> {
+    pub struct Overlay<'a, Message> {
         position: Point,
         height: f32,
         width: f32,
         state: &'a mut State,
+        on_toggle: Option<&'a dyn Fn(bool) -> Message>,
     }
 
-    impl<'a> Overlay<'a> {
+    impl<'a, Message> Overlay<'a, Message> {
         /// Creates a new overlay using the widget tree of a
         /// [`SideMenu`].
-        pub fn new(state: &'a mut State, position: Point, width: f32) -> Self {
+        pub fn new(
+            state: &'a mut State,
+            position: Point,
+            width: f32,
+            on_toggle: Option<&'a dyn Fn(bool) -> Message>,
+        ) -> Self {
             Self {
                 width,
                 height: width,
                 position,
                 state,
+                on_toggle,
             }
         }
     }
 
-    impl<'a, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a>
+    impl<'a, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a, Message>
     where
         Message: Clone + 'a,
     {
@@ -590,7 +728,7 @@ mod overlay {
             cursor: advanced::mouse::Cursor,
             _renderer: &Renderer,
             _clipboard: &mut dyn Clipboard,
-            _shell: &mut Shell<'_, Message>,
+            shell: &mut Shell<'_, Message>,
         ) -> event::Status {
             let bounds = layout.bounds();
 
@@ -604,6 +742,10 @@ mod overlay {
             if event == Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) {
                 let collapsed = !self.state.collapsed;
                 self.state.collapsed = collapsed;
+
+                if let Some(on_toggle) = self.on_toggle {
+                    shell.publish(on_toggle(collapsed));
+                }
             }
 
             event::Status::Ignored
@@ -694,7 +836,73 @@ mod overlay {
                 bounds.center(),
                 color,
                 bounds,
-            )
+            );
+
+            if self.state.overlay_hovered {
+                self.draw_tooltip(renderer, theme, bounds);
+            }
+        }
+    }
+
+    impl<'a, Message> Overlay<'a, Message> {
+        /// Draws a bubble beside the button saying what pressing it does
+        fn draw_tooltip(&self, renderer: &mut Renderer, theme: &Theme, button: Rectangle) {
+            let palette = theme.extended_palette();
+            let content = if self.state.collapsed {
+                "Expand menu"
+            } else {
+                "Collapse menu"
+            };
+
+            let text_size = 12.0;
+            let padding = Padding::from([4, 8]);
+            // Text is not measured here, so the width is estimated
+            let size = Size::new(
+                content.chars().count() as f32 * 0.6 * text_size + padding.horizontal(),
+                text_size * 1.3 + padding.vertical(),
+            );
+
+            let bounds = Rectangle::new(
+                Point::new(
+                    button.x + button.width + 6.0,
+                    button.center_y() - 0.5 * size.height,
+                ),
+                size,
+            );
+
+            <Renderer as advanced::Renderer>::fill_quad(
+                renderer,
+                Quad {
+                    bounds,
+                    border: Border {
+                        width: 0.5,
+                        color: palette.secondary.weak.color,
+                        radius: 5.0.into(),
+                    },
+                    ..Default::default()
+                },
+                Background::Color(palette.background.weak.color),
+            );
+
+            let text = Text {
+                content: content.to_string(),
+                size: text_size.into(),
+                bounds: bounds.size(),
+                font: Font::default(),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                line_height: LineHeight::default(),
+                shaping: Shaping::Basic,
+                wrapping: Wrapping::None,
+            };
+
+            <Renderer as advanced::text::Renderer>::fill_text(
+                renderer,
+                text,
+                bounds.center(),
+                palette.background.weak.text,
+                bounds,
+            );
         }
     }
 }
@@ -938,6 +1146,8 @@ where
     width: Length,
     height: Length,
     padding: Padding,
+    collapsed: bool,
+    on_toggle: Option<Box<dyn Fn(bool) -> Message + 'a>>,
     children: Vec<Element<'a, Message, Theme, Renderer>>,
 }
 
@@ -951,6 +1161,8 @@ where
             width: Length::Shrink,
             padding: [20.0, 10.0].into(),
             align: Horizontal::Left,
+            collapsed: false,
+            on_toggle: None,
             kind,
             children,
         }
@@ -1015,53 +1227,67 @@ where
         self
     }
 
+    /// Whether the menu starts out collapsed. Only read when the widget
+    /// state is first created.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Sets the message produced when the menu is collapsed or expanded,
+    /// given the new collapsed state.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) -> Message + 'a) -> Self {
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
     /// Assumes `tree` is a valid [`SideMenu`] widget tree
-    fn collapse(children: &mut [tree::Tree], kind: Kind, collapse: bool) {
+    fn animate(children: &mut [tree::Tree], kind: Kind, progress: f32) {
         match kind {
             Kind::All => {
                 // Header
                 {
                     let header = children[0].state.downcast_mut::<MenuState>();
-                    header.collapsed = collapse;
+                    header.animate(progress);
                 }
 
                 // main
                 {
                     let main = &mut children[2];
-                    MenuSection::<Message>::collapse(main, collapse);
+                    MenuSection::<Message>::animate(main, progress);
                 }
 
                 // footer
                 {
                     let footer = &mut children[4];
-                    MenuSection::<Message>::collapse(footer, collapse);
+                    MenuSection::<Message>::animate(footer, progress);
                 }
             }
             Kind::NoHeader => {
                 {
                     let main = &mut children[1];
-                    MenuSection::<Message>::collapse(main, collapse);
+                    MenuSection::<Message>::animate(main, progress);
                 }
 
                 {
                     let footer = &mut children[3];
-                    MenuSection::<Message>::collapse(footer, collapse);
+                    MenuSection::<Message>::animate(footer, progress);
                 }
             }
             Kind::NoFooter => {
                 {
                     let header = children[0].state.downcast_mut::<MenuState>();
-                    header.collapsed = collapse;
+                    header.animate(progress);
                 }
 
                 {
                     let main = &mut children[2];
-                    MenuSection::<Message>::collapse(main, collapse);
+                    MenuSection::<Message>::animate(main, progress);
                 }
             }
             Kind::OnlyMain => {
                 let main = &mut children[1];
-                MenuSection::<Message>::collapse(main, collapse);
+                MenuSection::<Message>::animate(main, progress);
             }
         }
     }
@@ -1088,7 +1314,7 @@ where
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(State::new())
+        tree::State::new(State::new(self.collapsed))
     }
 
     fn mouse_interaction(
@@ -1148,20 +1374,26 @@ where
             })
             .fold(event::Status::Ignored, event::Status::merge);
 
-        if status == event::Status::Captured {
+        if status == event::Status::Captured && state.collapsed {
             state.collapsed = false;
-        }
 
-        if state.collapsed {
-            Self::collapse(&mut tree.children, self.kind, true);
+            if let Some(on_toggle) = &self.on_toggle {
+                shell.publish(on_toggle(false));
+            }
+        }
 
-            shell.invalidate_layout()
-        } else {
-            Self::collapse(&mut tree.children, self.kind, false);
+        let now = match event {
+            Event::Window(window::Event::RedrawRequested(now)) => now,
+            _ => Instant::now(),
+        };
 
-            shell.invalidate_layout()
+        if state.advance(now) {
+            shell.request_redraw(window::RedrawRequest::NextFrame);
         }
 
+        Self::animate(&mut tree.children, self.kind, state.progress);
+        shell.invalidate_layout();
+
         status
     }
 
@@ -1205,7 +1437,7 @@ where
 
         let hovered = state.hovered;
         let overlay_hovered = state.overlay_hovered;
-        let overlay = Overlay::new(state, position, width);
+        let overlay = Overlay::new(state, position, width, self.on_toggle.as_deref());
 
         let own = advanced::overlay::Element::new(Box::new(overlay));
 
@@ -1222,26 +1454,47 @@ where
         renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        let state = tree.state.downcast_ref::<State>();
+        let progress = tree.state.downcast_ref::<State>().progress;
 
-        let width = if state.collapsed {
-            Length::Shrink
-        } else {
-            self.width
+        let resolve = |width: Length, tree: &mut tree::Tree| {
+            layout::flex::resolve(
+                layout::flex::Axis::Vertical,
+                renderer,
+                limits,
+                width,
+                self.height,
+                self.padding,
+                0.0,
+                self.align.into(),
+                &self.children,
+                &mut tree.children,
+            )
         };
 
-        layout::flex::resolve(
-            layout::flex::Axis::Vertical,
-            renderer,
-            limits,
-            width,
-            self.height,
-            self.padding,
-            0.0,
-            self.align.into(),
-            &self.children,
-            &mut tree.children,
-        )
+        if progress <= 0.0 {
+            return resolve(Length::Shrink, tree);
+        }
+
+        let expanded = resolve(self.width, tree);
+
+        if progress >= 1.0 {
+            return expanded;
+        }
+
+        // The children keep their expanded layout and are clipped to the
+        // interpolated width while animating
+        let collapsed = {
+            Self::animate(&mut tree.children, self.kind, 0.0);
+            let node = resolve(Length::Shrink, tree);
+            Self::animate(&mut tree.children, self.kind, progress);
+
+            node.size().width
+        };
+
+        let size = expanded.size();
+        let width = collapsed + (size.width - collapsed) * ease(progress);
+
+        layout::Node::with_children(Size::new(width, size.height), expanded.children().to_vec())
     }
 
     fn draw(
@@ -1254,19 +1507,32 @@ where
         cursor: iced::advanced::mouse::Cursor,
         viewport: &Rectangle,
     ) {
-        if layout.bounds().intersection(viewport).is_none() {
+        let bounds = layout.bounds();
+
+        if bounds.intersection(viewport).is_none() {
             return;
         }
 
-        for ((child, state), layout) in self
-            .children
-            .iter()
-            .zip(&tree.children)
-            .zip(layout.children())
-        {
-            child
-                .as_widget()
-                .draw(state, renderer, theme, style, layout, cursor, viewport);
+        let draw_children = |renderer: &mut Renderer| {
+            for ((child, state), layout) in self
+                .children
+                .iter()
+                .zip(&tree.children)
+                .zip(layout.children())
+            {
+                child
+                    .as_widget()
+                    .draw(state, renderer, theme, style, layout, cursor, viewport);
+            }
+        };
+
+        let state = tree.state.downcast_ref::<State>();
+
+        // Clips the expanded children to the width of the menu
+        if state.is_animating() {
+            <Renderer as advanced::Renderer>::with_layer(renderer, bounds, draw_children);
+        } else {
+            draw_children(renderer);
         }
     }
 }
@@ -1280,20 +1546,69 @@ where
     }
 }
 
+/// How long the menu takes to collapse or expand
+const ANIMATION_DURATION: Duration = Duration::from_millis(150);
+
+/// Smooths the start and end of the collapse animation
+fn ease(progress: f32) -> f32 {
+    progress * progress * (3.0 - 2.0 * progress)
+}
+
 #[derive(Debug)]
 struct State {
     collapsed: bool,
     hovered: bool,
     overlay_hovered: bool,
+    /// How far the menu is expanded, from 0.0 when collapsed to 1.0
+    progress: f32,
+    /// When the animation last advanced
+    tick: Option<Instant>,
 }
 
 impl State {
-    fn new() -> Self {
+    fn new(collapsed: bool) -> Self {
         Self {
-            collapsed: false,
+            collapsed,
             hovered: false,
             overlay_hovered: false,
+            progress: if collapsed { 0.0 } else { 1.0 },
+            tick: None,
+        }
+    }
+
+    fn target(&self) -> f32 {
+        if self.collapsed {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.progress != self.target()
+    }
+
+    /// Moves the animation towards the current collapsed state, returning
+    /// whether it still has frames left.
+    fn advance(&mut self, now: Instant) -> bool {
+        if !self.is_animating() {
+            self.tick = None;
+            return false;
         }
+
+        let step = self.tick.map_or(0.0, |tick| {
+            now.saturating_duration_since(tick).as_secs_f32() / ANIMATION_DURATION.as_secs_f32()
+        });
+        self.tick = Some(now);
+
+        let target = self.target();
+        self.progress = if target > self.progress {
+            (self.progress + step).min(target)
+        } else {
+            (self.progress - step).max(target)
+        };
+
+        self.is_animating()
     }
 }
 
@@ -1310,3 +1625,26 @@ macro_rules! section {
         MenuSection::from_vec(vec![$($child),*])
     };
     }
+
+#[test]
+fn test_collapse_animation() {
+    let start = Instant::now();
+    let mut state = State::new(false);
+
+    assert!(!state.advance(start));
+
+    state.collapsed = true;
+    assert!(state.advance(start));
+    assert_eq!(state.progress, 1.0);
+
+    assert!(state.advance(start + ANIMATION_DURATION / 2));
+    assert!((state.progress - 0.5).abs() < 0.01);
+
+    assert!(!state.advance(start + ANIMATION_DURATION * 2));
+    assert_eq!(state.progress, 0.0);
+
+    state.collapsed = false;
+    assert!(state.advance(start + ANIMATION_DURATION * 2));
+    assert!(!state.advance(start + ANIMATION_DURATION * 4));
+    assert_eq!(state.progress, 1.0);
+}
@@ -1,38 +1,124 @@
 use iced::{
-    widget::{self, container, Container},
-    Border, Element, Renderer, Theme,
+    widget::{self, container, text, Container},
+    Border, Color, Element, Renderer, Shadow, Theme, Vector,
 };
 
+/// Elevation of dialogs and modals
+pub const DIALOG_LEVEL: u8 = 3;
+/// Elevation of context panels
+pub const CONTEXT_LEVEL: u8 = 2;
+/// Elevation of the side menu
+pub const MENU_LEVEL: u8 = 1;
+
+/// Returns the drop shadow of a surface floating `level` steps above the
+/// content behind it. Level 0 is flat and has no shadow.
+pub fn surface_shadow(theme: &Theme, level: u8) -> Shadow {
+    if level == 0 {
+        return Shadow::default();
+    }
+
+    let alpha = if theme.extended_palette().is_dark {
+        0.4
+    } else {
+        0.18
+    };
+    let level = level as f32;
+
+    Shadow {
+        color: Color {
+            a: alpha,
+            ..Color::BLACK
+        },
+        offset: Vector::new(0.0, 1.5 * level),
+        blur_radius: 6.0 * level,
+    }
+}
+
 pub fn dialog_container<'a, Message>(
     content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    flat: bool,
 ) -> Container<'a, Message, Theme> {
     container(content)
         .padding([20.0, 25.0])
         .width(375.0)
         .height(400.0)
-        .style(|theme| {
+        .style(move |theme| {
             <DialogContainer as widget::container::Catalog>::style(
-                &DialogContainer::default(),
+                &DialogContainer::default().flat(flat),
                 theme,
             )
         })
         .into()
 }
 
+/// A small badge showing the access key of a menu entry or action
+pub fn access_key<'a, Message>(key: char) -> Container<'a, Message, Theme>
+where
+    Message: 'a,
+{
+    container(text(key.to_ascii_uppercase()).size(11.0))
+        .padding([0, 4])
+        .style(|theme| {
+            <AccessKeyBadge as widget::container::Catalog>::style(&AccessKeyBadge, theme)
+        })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AccessKeyBadge;
+
+impl widget::container::Catalog for AccessKeyBadge {
+    type Class<'a> = Theme;
+
+    fn default<'a>() -> Self::Class<'a> {
+        <Theme as std::default::Default>::default()
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> container::Style {
+        let pair = class.extended_palette().primary.weak;
+
+        let border = Border {
+            radius: 3.0.into(),
+            ..Default::default()
+        };
+
+        widget::container::Style {
+            background: Some(pair.color.into()),
+            text_color: Some(pair.text),
+            border,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct DialogContainer {
     pub radius: f32,
+    pub level: u8,
 }
 
 impl Default for DialogContainer {
     fn default() -> Self {
-        Self { radius: 10.0 }
+        Self {
+            radius: 10.0,
+            level: DIALOG_LEVEL,
+        }
     }
 }
 
 impl DialogContainer {
     pub fn new(radius: f32) -> Self {
-        Self { radius }
+        Self {
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Removes the drop shadow if `flat`
+    pub fn flat(mut self, flat: bool) -> Self {
+        if flat {
+            self.level = 0;
+        }
+        self
     }
 }
 
@@ -54,6 +140,47 @@ impl widget::container::Catalog for DialogContainer {
         widget::container::Style {
             background: Some(background.into()),
             border,
+            shadow: surface_shadow(class, self.level),
+            ..Default::default()
+        }
+    }
+}
+
+/// A bordered surface floating above the content behind it, such as the
+/// side menu and context panels
+#[derive(Debug, Clone, Copy)]
+pub struct FloatingSurface {
+    pub level: u8,
+}
+
+impl FloatingSurface {
+    pub fn new(level: u8, flat: bool) -> Self {
+        Self {
+            level: if flat { 0 } else { level },
+        }
+    }
+}
+
+impl widget::container::Catalog for FloatingSurface {
+    type Class<'a> = Theme;
+
+    fn default<'a>() -> Self::Class<'a> {
+        <Theme as std::default::Default>::default()
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> container::Style {
+        let palette = class.extended_palette();
+
+        let border = Border {
+            width: 1.0,
+            radius: 0.0.into(),
+            color: palette.background.strong.color,
+        };
+
+        widget::container::Style {
+            background: Some(palette.background.weak.color.into()),
+            border,
+            shadow: surface_shadow(class, self.level),
             ..Default::default()
         }
     }
@@ -17,7 +17,8 @@ use iced::{
     event::{self, Event},
     mouse,
     widget::{button, column, container, horizontal_space, row, text, Text},
-    window, Alignment, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, Vector,
+    window, Alignment, Border, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme,
+    Vector,
 };
 
 use iced::advanced::Widget;
@@ -76,6 +77,10 @@ impl Status {
     }
 }
 
+impl Status {
+    pub const ALL: [Self; 4] = [Self::Info, Self::Success, Self::Warn, Self::Error];
+}
+
 impl Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -88,10 +93,125 @@ impl Display for Status {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct Toast {
+#[derive(Debug, Clone)]
+pub struct Toast<Message> {
     pub body: String,
     pub status: Status,
+    /// Label of a button on the toast and the message it sends when pressed
+    pub action: Option<(String, Box<Message>)>,
+}
+
+/// Seconds toasts of each status stay before closing on their own. A
+/// timeout of 0 keeps toasts until they are closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    pub info: u64,
+    pub success: u64,
+    pub warn: u64,
+    pub error: u64,
+}
+
+impl Timeouts {
+    /// The longest timeout that can be set, in seconds
+    pub const MAX: u64 = 120;
+
+    /// Parses a timeout typed in by the user. Empty input is no timeout and
+    /// values above [`Self::MAX`] are clamped. Returns `None` if the input
+    /// is not a whole number of seconds.
+    pub fn parse(input: &str) -> Option<u64> {
+        let input = input.trim();
+
+        if input.is_empty() {
+            return Some(0);
+        }
+
+        if !input.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        // Only digits are left, so parsing can only fail by overflowing
+        Some(
+            input
+                .parse()
+                .map_or(Self::MAX, |seconds: u64| seconds.min(Self::MAX)),
+        )
+    }
+
+    /// The same timeout for every status
+    pub fn uniform(seconds: u64) -> Self {
+        Self {
+            info: seconds,
+            success: seconds,
+            warn: seconds,
+            error: seconds,
+        }
+    }
+
+    pub fn get(&self, status: Status) -> u64 {
+        match status {
+            Status::Info => self.info,
+            Status::Success => self.success,
+            Status::Warn => self.warn,
+            Status::Error => self.error,
+        }
+    }
+
+    pub fn set(&mut self, status: Status, seconds: u64) {
+        match status {
+            Status::Info => self.info = seconds,
+            Status::Success => self.success = seconds,
+            Status::Warn => self.warn = seconds,
+            Status::Error => self.error = seconds,
+        }
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            info: 2,
+            success: 2,
+            warn: 5,
+            error: 10,
+        }
+    }
+}
+
+/// Time a toast has been shown. The clock is stopped while the toast is
+/// hovered.
+#[derive(Debug, Clone, Copy)]
+struct Countdown {
+    started: Instant,
+    paused: Option<Instant>,
+}
+
+impl Countdown {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            paused: None,
+        }
+    }
+
+    fn elapsed(&self, now: Instant) -> Duration {
+        self.paused
+            .unwrap_or(now)
+            .saturating_duration_since(self.started)
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    fn pause(&mut self, now: Instant) {
+        self.paused.get_or_insert(now);
+    }
+
+    fn resume(&mut self, now: Instant) {
+        if let Some(paused) = self.paused.take() {
+            self.started += now.saturating_duration_since(paused);
+        }
+    }
 }
 
 pub struct Manager<'a, Message>
@@ -100,7 +220,8 @@ where
 {
     content: Element<'a, Message>,
     toasts: Vec<Element<'a, Message>>,
-    timeout: u64,
+    statuses: Vec<Status>,
+    timeouts: Timeouts,
     on_close: Box<dyn Fn(usize) -> Message + 'a>,
 }
 
@@ -108,12 +229,20 @@ impl<'a, Message> Manager<'a, Message>
 where
     Message: 'a + Clone,
 {
+    /// Pressing a toast's action sends `on_action` with the toast's index
+    /// rather than the action itself, so that the toast can be closed too.
+    /// Error toasts get a button sending `on_copy`, for copying their full
+    /// text which may not fit on the toast.
     pub fn new(
         content: impl Into<Element<'a, Message>>,
-        toasts: &'a [Toast],
+        toasts: &'a [Toast<Message>],
         on_close: impl Fn(usize) -> Message + 'a,
+        on_action: impl Fn(usize) -> Message + 'a,
+        on_copy: impl Fn(usize) -> Message + 'a,
         theme: &'a Theme,
     ) -> Self {
+        let statuses = toasts.iter().map(|toast| toast.status).collect();
+
         let toasts = toasts
             .iter()
             .enumerate()
@@ -146,8 +275,44 @@ where
                 .on_press((on_close)(index))
                 .style(iced::widget::button::text);
 
+                let toast_button = |label: &'a str, message: Message| {
+                    button(text(label).size(14))
+                        .on_press(message)
+                        .padding([4, 8])
+                        .style(move |_, status| {
+                            let background = match status {
+                                button::Status::Hovered | button::Status::Pressed => {
+                                    Some(text_color.scale_alpha(0.15).into())
+                                }
+                                _ => None,
+                            };
+
+                            button::Style {
+                                background,
+                                text_color,
+                                border: Border {
+                                    color: text_color,
+                                    width: 1.0,
+                                    radius: 4.0.into(),
+                                },
+                                ..Default::default()
+                            }
+                        })
+                };
+
+                let action = toast
+                    .action
+                    .as_ref()
+                    .map(|(label, _)| toast_button(label.as_str(), (on_action)(index)));
+
+                let copy =
+                    (toast.status == Status::Error).then(|| toast_button("Copy", (on_copy)(index)));
+
                 container(
-                    row!(status_icon, content, horizontal_space(), close)
+                    row!(status_icon, content, horizontal_space())
+                        .push_maybe(action)
+                        .push_maybe(copy)
+                        .push(close)
                         .align_y(Alignment::Center)
                         .height(Length::Fill)
                         .spacing(16),
@@ -171,14 +336,17 @@ where
 
         Self {
             content: content.into(),
+            statuses,
             toasts,
-            timeout: 5,
+            timeouts: Timeouts::uniform(5),
             on_close: Box::new(on_close),
         }
     }
 
-    pub fn timeout(mut self, seconds: u64) -> Self {
-        self.timeout = seconds;
+    /// Sets how long toasts stay before closing on their own, based on
+    /// their status
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
         self
     }
 }
@@ -205,7 +373,7 @@ impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
     }
 
     fn state(&self) -> widget::tree::State {
-        widget::tree::State::new(Vec::<Option<Instant>>::new())
+        widget::tree::State::new(Vec::<Option<Countdown>>::new())
     }
 
     fn children(&self) -> Vec<Tree> {
@@ -215,7 +383,7 @@ impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
     }
 
     fn diff(&self, tree: &mut Tree) {
-        let instants = tree.state.downcast_mut::<Vec<Option<Instant>>>();
+        let instants = tree.state.downcast_mut::<Vec<Option<Countdown>>>();
 
         // Invalidating removed instants to None allows us to remove
         // them here so that diffing for removed / new toast instants
@@ -227,7 +395,7 @@ impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
                 instants.truncate(new);
             }
             (old, new) if old < new => {
-                instants.extend(std::iter::repeat(Some(Instant::now())).take(new - old));
+                instants.extend((old..new).map(|_| Some(Countdown::new())));
             }
             _ => {}
         }
@@ -321,7 +489,7 @@ impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
         renderer: &Renderer,
         translation: Vector,
     ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
-        let instants = state.state.downcast_mut::<Vec<Option<Instant>>>();
+        let instants = state.state.downcast_mut::<Vec<Option<Countdown>>>();
 
         let (content_state, toasts_state) = state.children.split_at_mut(1);
 
@@ -339,7 +507,11 @@ impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
                 state: toasts_state,
                 instants,
                 on_close: &self.on_close,
-                timeout: self.timeout,
+                timeouts: self
+                    .statuses
+                    .iter()
+                    .map(|status| self.timeouts.get(*status))
+                    .collect(),
             }))
         });
         let overlays = content.into_iter().chain(toasts).collect::<Vec<_>>();
@@ -361,9 +533,10 @@ struct Overlay<'a, 'b, Message> {
     position: Point,
     toasts: &'b mut [Element<'a, Message>],
     state: &'b mut [Tree],
-    instants: &'b mut [Option<Instant>],
+    instants: &'b mut [Option<Countdown>],
     on_close: &'b dyn Fn(usize) -> Message,
-    timeout: u64,
+    /// Timeout of each toast, in seconds
+    timeouts: Vec<u64>,
 }
 
 impl<'a, 'b, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a, 'b, Message> {
@@ -394,16 +567,46 @@ impl<'a, 'b, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a,
         clipboard: &mut dyn Clipboard,
         shell: &mut Shell<'_, Message>,
     ) -> event::Status {
+        if let Event::Mouse(mouse::Event::CursorMoved { .. }) = &event {
+            let now = Instant::now();
+            let mut resumed = false;
+
+            self.instants
+                .iter_mut()
+                .zip(layout.children())
+                .for_each(|(maybe_instant, layout)| {
+                    if let Some(instant) = maybe_instant.as_mut() {
+                        if cursor.is_over(layout.bounds()) {
+                            instant.pause(now);
+                        } else if instant.is_paused() {
+                            instant.resume(now);
+                            resumed = true;
+                        }
+                    }
+                });
+
+            // Resumed toasts need a new redraw scheduled for their timeout
+            if resumed {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+        }
+
         if let Event::Window(window::Event::RedrawRequested(now)) = &event {
             let mut next_redraw: Option<window::RedrawRequest> = None;
 
             self.instants
                 .iter_mut()
+                .zip(&self.timeouts)
                 .enumerate()
-                .for_each(|(index, maybe_instant)| {
+                .for_each(|(index, (maybe_instant, timeout))| {
                     if let Some(instant) = maybe_instant.as_mut() {
+                        // Toasts without a timeout never expire
+                        if instant.is_paused() || *timeout == 0 {
+                            return;
+                        }
+
                         let remaining =
-                            Duration::from_secs(self.timeout).saturating_sub(instant.elapsed());
+                            Duration::from_secs(*timeout).saturating_sub(instant.elapsed(*now));
 
                         if remaining == Duration::ZERO {
                             maybe_instant.take();
@@ -343,6 +343,47 @@ where
                 }
             }
 
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                ..
+            }) => {
+                let state = state.state.downcast_mut::<MenuState>();
+
+                if !state.is_open {
+                    return event::Status::Ignored;
+                }
+
+                let options = self.options.borrow();
+
+                match key {
+                    keyboard::key::Named::ArrowDown => {
+                        state.hovered_option = step(state.hovered_option, options.len(), true);
+                    }
+                    keyboard::key::Named::ArrowUp => {
+                        state.hovered_option = step(state.hovered_option, options.len(), false);
+                    }
+                    keyboard::key::Named::Enter => {
+                        let Some(option) = state.hovered_option.and_then(|idx| options.get(idx))
+                        else {
+                            return event::Status::Ignored;
+                        };
+
+                        state.is_open = false;
+                        shell.publish((self.on_select)(option.clone()));
+                    }
+                    keyboard::key::Named::Escape => {
+                        state.is_open = false;
+
+                        if let Some(on_close) = &self.on_close {
+                            shell.publish(on_close.clone());
+                        }
+                    }
+                    _ => return event::Status::Ignored,
+                }
+
+                event::Status::Captured
+            }
+
             Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
                 let state = state.state.downcast_mut::<MenuState>();
 
@@ -503,6 +544,23 @@ where
     }
 }
 
+/// Moves the hovered option of a list of `len` options one step, stopping at
+/// either end. Starts from the first or last option when nothing is hovered.
+fn step(hovered: Option<usize>, len: usize, forward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    let next = match hovered {
+        Some(idx) if forward => idx.saturating_add(1).min(len - 1),
+        Some(idx) => idx.min(len - 1).saturating_sub(1),
+        None if forward => 0,
+        None => len - 1,
+    };
+
+    Some(next)
+}
+
 struct MenuState {
     keyboard_modifiers: keyboard::Modifiers,
     is_open: bool,
@@ -991,3 +1049,15 @@ where
         Element::new(value)
     }
 }
+
+#[test]
+fn test_step() {
+    assert_eq!(step(None, 0, true), None);
+    assert_eq!(step(None, 3, true), Some(0));
+    assert_eq!(step(None, 3, false), Some(2));
+    assert_eq!(step(Some(0), 3, true), Some(1));
+    assert_eq!(step(Some(2), 3, true), Some(2));
+    assert_eq!(step(Some(1), 3, false), Some(0));
+    assert_eq!(step(Some(0), 3, false), Some(0));
+    assert_eq!(step(Some(7), 3, false), Some(1));
+}
@@ -10,13 +10,21 @@ use iced::{
     },
     Element, Length, Theme,
 };
+use tracing::warn;
+
+use modav_core::repr::sheet::utils::HeaderLabelStrategy;
 
 use crate::views::{
-    BarChartTabData, EditorTabData, FileType, LineTabData, StackedBarChartTabData, View,
+    BarChartTabData, ChartPreview, FileType, SessionTab, StackedBarChartTabData, View,
 };
 
 use crate::styles::FileBorderContainer;
-use crate::utils::{icons, AppError};
+use crate::utils::{
+    csv::{self, ColumnType},
+    icons,
+    sampling::RowLimit,
+    AppError, Location,
+};
 use crate::ViewType;
 
 use super::style::dialog_container;
@@ -36,6 +44,150 @@ mod stacked_barchart;
 use stacked_barchart::StackedBarChartConfig;
 pub use stacked_barchart::StackedBarChartConfigState;
 
+mod boxplot;
+use boxplot::BoxPlotConfig;
+pub use boxplot::BoxPlotConfigState;
+
+mod histogram;
+use histogram::HistogramConfig;
+pub use histogram::HistogramConfigState;
+
+mod radar;
+use radar::RadarConfig;
+pub use radar::RadarConfigState;
+
+/// Fields of a chart configuration that an error can point at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigField {
+    StackColumns,
+    MetricColumns,
+}
+
+/// Why a chart could not be made with the chosen configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    message: String,
+    /// Field at fault, if known. The error is shown beside it.
+    field: Option<ConfigField>,
+}
+
+impl ConfigError {
+    fn field(field: ConfigField, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            field: Some(field),
+        }
+    }
+
+    /// Returns the error if it belongs beside `field`
+    fn at(error: Option<&Self>, field: ConfigField) -> Option<&Self> {
+        error.filter(|error| error.field == Some(field))
+    }
+}
+
+impl From<AppError> for ConfigError {
+    fn from(value: AppError) -> Self {
+        Self {
+            message: value.message(),
+            field: None,
+        }
+    }
+}
+
+struct ErrorBackground;
+
+impl widget::container::Catalog for ErrorBackground {
+    type Class<'a> = Theme;
+
+    fn default<'a>() -> Self::Class<'a> {
+        <Theme as std::default::Default>::default()
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> container::Style {
+        if class.extended_palette().is_dark {
+            let text_color = color!(248, 133, 133);
+            let background = color!(153, 27, 27);
+
+            container::Style {
+                text_color: Some(text_color),
+                background: Some(iced::Background::Color(background)),
+                ..Default::default()
+            }
+        } else {
+            let text_color = color!(75, 20, 20);
+            let background = color!(248, 113, 113);
+
+            container::Style {
+                text_color: Some(text_color),
+                background: Some(iced::Background::Color(background)),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Warns that columns given a numeric role, out of `selected`, hold
+/// something other than numbers
+fn numeric_warning<'a, Message: 'a>(
+    columns: &[(String, ColumnType)],
+    selected: impl IntoIterator<Item = usize>,
+) -> Option<Element<'a, Message>> {
+    let mixed = selected
+        .into_iter()
+        .filter_map(|idx| columns.get(idx).map(|(_, kind)| (idx, *kind)))
+        .filter(|(_, kind)| !kind.is_numeric())
+        .map(|(idx, kind)| format!("{idx} ({kind})"))
+        .collect::<Vec<String>>();
+
+    if mixed.is_empty() {
+        return None;
+    }
+
+    let warning = text(format!(
+        "Expected numbers, but these columns hold other values: {}",
+        mixed.join(", ")
+    ))
+    .size(12.0)
+    .style(text::danger);
+
+    Some(warning.into())
+}
+
+/// Height of the live chart preview beside the model config
+const PREVIEW_HEIGHT: f32 = 320.0;
+
+/// The full text of an error on a red background
+fn error_area<'a, Message: 'a>(message: &str, size: f32) -> Element<'a, Message> {
+    container(text(message.to_string()).size(size))
+        .width(Length::Fill)
+        .padding([4, 6])
+        .style(|theme| <ErrorBackground as container::Catalog>::style(&ErrorBackground, theme))
+        .into()
+}
+
+/// A chart configuration to preview while it is being edited
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewConfig {
+    BarChart(BarChartConfigState),
+    StackedBarChart(StackedBarChartConfigState),
+}
+
+impl PreviewConfig {
+    /// Makes the chart of `file` with this configuration. This reads the
+    /// whole file, so it is best done off the UI thread.
+    pub fn build(self, file: PathBuf) -> Result<View, AppError> {
+        match self {
+            Self::BarChart(config) => BarChartTabData::new(file, config).map(View::BarChart),
+            Self::StackedBarChart(config) => {
+                stacked_barchart::validate_columns(&config.acc_cols_str)
+                    .map_err(AppError::Simple)?;
+
+                StackedBarChartTabData::new(file, config).map(View::StackedBarChart)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum Portal {
     #[default]
@@ -48,12 +200,72 @@ pub enum Portal {
 pub struct Hex {
     model: ViewType,
     current_view: Portal,
-    config: View,
     sheet_config: SheetConfigState,
     line_config: Option<LineConfigState>,
     bar_config: Option<BarChartConfigState>,
     stacked_bar_config: Option<StackedBarChartConfigState>,
-    error: Option<String>,
+    box_config: Option<BoxPlotConfigState>,
+    histogram_config: Option<HistogramConfigState>,
+    radar_config: Option<RadarConfigState>,
+    /// Names and inferred types of the file's columns, read once the sheet
+    /// is configured
+    columns: Vec<(String, ColumnType)>,
+    error: Option<ConfigError>,
+    /// Whether the wizard's initial configuration, if any, was taken up
+    seeded: bool,
+}
+
+impl Hex {
+    /// Starts the wizard on the sheet settings of `session`'s chart, with
+    /// every step filled in from its configuration
+    fn from_session(session: &SessionTab) -> Self {
+        let mut hex = Self {
+            current_view: Portal::SheetConfig,
+            seeded: true,
+            ..Self::default()
+        };
+
+        match session.clone() {
+            SessionTab::LineGraph(_, config) => {
+                hex.model = ViewType::LineGraph;
+                hex.sheet_config = config.sheet_config();
+                hex.line_config = Some(config.submit());
+            }
+            SessionTab::BarChart(_, config) => {
+                hex.model = ViewType::BarChart;
+                hex.sheet_config = config.sheet_config();
+                hex.bar_config = Some(config.submit());
+            }
+            SessionTab::StackedBarChart(_, config) => {
+                hex.model = ViewType::StackedBarChart;
+                hex.sheet_config = config.sheet_config();
+                hex.stacked_bar_config = Some(config.submit());
+            }
+            SessionTab::BoxPlot(_, config) => {
+                hex.model = ViewType::BoxPlot;
+                hex.sheet_config = config.sheet_config();
+                hex.box_config = Some(config.submit());
+            }
+            SessionTab::Histogram(_, config) => {
+                hex.model = ViewType::Histogram;
+                hex.sheet_config = config.sheet_config();
+                hex.histogram_config = Some(config.submit());
+            }
+            SessionTab::Radar(_, config) => {
+                hex.model = ViewType::Radar;
+                hex.sheet_config = config.sheet_config();
+                hex.radar_config = Some(config.submit());
+            }
+            SessionTab::Editor(_) | SessionTab::Table(_) => {
+                return Self {
+                    seeded: true,
+                    ..Self::default()
+                }
+            }
+        }
+
+        hex
+    }
 }
 
 impl Default for Hex {
@@ -61,12 +273,16 @@ impl Default for Hex {
         Self {
             model: ViewType::Editor,
             current_view: Portal::FileSelection,
-            config: View::Editor(EditorTabData::default()),
             sheet_config: SheetConfigState::default(),
             stacked_bar_config: None,
             line_config: None,
             bar_config: None,
+            box_config: None,
+            histogram_config: None,
+            radar_config: None,
+            columns: Vec::new(),
             error: None,
+            seeded: false,
         }
     }
 }
@@ -78,15 +294,22 @@ pub enum Charm {
     ViewConfig,
     ChangeView(Portal),
     Cancel,
-    ConfigSubmit(View),
+    ConfigSubmit(SessionTab),
     SheetSubmit(SheetConfigState),
     SheetPrevious(SheetConfigState),
     LinePrevious(LineConfigState),
     BarChartPrevious(BarChartConfigState),
     StackedBarChartPrevious(StackedBarChartConfigState),
-    Error(AppError),
+    BoxPlotPrevious(BoxPlotConfigState),
+    HistogramPrevious(HistogramConfigState),
+    RadarPrevious(RadarConfigState),
+    BarChartChanged(BarChartConfigState),
+    StackedBarChartChanged(StackedBarChartConfigState),
+    Error(ConfigError),
     Submit,
     ClearError,
+    /// Shows the place in the file the preview failed at
+    Locate(Location),
     #[default]
     None,
 }
@@ -97,27 +320,72 @@ where
 {
     on_reselect_file: Option<Message>,
     on_cancel: Option<Message>,
-    on_error: Box<dyn Fn(AppError) -> Message + 'a>,
-    on_submit: Box<dyn Fn(PathBuf, View) -> Message + 'a>,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_preview: Option<Box<dyn Fn(PathBuf, PreviewConfig) -> Message + 'a>>,
+    preview: Option<&'a Result<ChartPreview, AppError>>,
+    on_locate: Option<Box<dyn Fn(PathBuf, usize) -> Message + 'a>>,
     file: PathBuf,
+    row_limit: RowLimit,
+    flat: bool,
+    /// The open chart being reconfigured, whose configuration fills the
+    /// wizard
+    initial: Option<SessionTab>,
 }
 
 impl<'a, Message> Wizard<'a, Message>
 where
     Message: Debug + Clone,
 {
-    pub fn new<F, E>(file: PathBuf, on_submit: F, on_error: E) -> Self
+    pub fn new<F>(file: PathBuf, on_submit: F) -> Self
     where
-        F: 'a + Fn(PathBuf, View) -> Message,
-        E: 'a + Fn(AppError) -> Message,
+        F: 'a + Fn(SessionTab) -> Message,
     {
         Self {
             file,
             on_reselect_file: None,
             on_submit: Box::new(on_submit),
-            on_error: Box::new(on_error),
+            on_preview: None,
+            preview: None,
+            on_locate: None,
             on_cancel: None,
+            row_limit: RowLimit::default(),
+            flat: false,
+            initial: None,
+        }
+    }
+
+    /// Fills the wizard with the configuration of an open chart, so it can
+    /// be changed without starting over
+    pub fn initial(mut self, session: Option<SessionTab>) -> Self {
+        self.initial = session;
+        self
+    }
+
+    /// Takes up the initial configuration the first time `state` changes
+    fn seed(&self, state: &mut Hex) {
+        if state.seeded {
+            return;
         }
+
+        *state = match &self.initial {
+            Some(session) => Hex::from_session(session),
+            None => Hex {
+                seeded: true,
+                ..state.clone()
+            },
+        };
+    }
+
+    /// Draws the wizard without a drop shadow if `flat`
+    pub fn flat(mut self, flat: bool) -> Self {
+        self.flat = flat;
+        self
+    }
+
+    /// Sets the maximum number of rows charted from the file
+    pub fn row_limit(mut self, row_limit: RowLimit) -> Self {
+        self.row_limit = row_limit;
+        self
     }
 
     pub fn on_reselect(mut self, msg: Message) -> Self {
@@ -130,19 +398,135 @@ where
         self
     }
 
+    /// Asks for a preview of the chart whenever its configuration changes.
+    /// The chart is given back through [`Wizard::preview`].
+    pub fn on_preview<F>(mut self, on_preview: F) -> Self
+    where
+        F: 'a + Fn(PathBuf, PreviewConfig) -> Message,
+    {
+        self.on_preview = Some(Box::new(on_preview));
+        self
+    }
+
+    /// Sets the latest preview, or why it could not be made
+    pub fn preview(mut self, preview: Option<&'a Result<ChartPreview, AppError>>) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Offers to show the file and line a preview failed at, for errors
+    /// that know where they are
+    pub fn on_locate<F>(mut self, on_locate: F) -> Self
+    where
+        F: 'a + Fn(PathBuf, usize) -> Message,
+    {
+        self.on_locate = Some(Box::new(on_locate));
+        self
+    }
+
+    fn preview_message(&self, config: PreviewConfig) -> Option<Message> {
+        self.on_preview
+            .as_ref()
+            .map(|on_preview| (on_preview)(self.file.clone(), config))
+    }
+
+    /// Returns the configuration shown when the model config is first
+    /// opened, if the model can be previewed
+    fn initial_preview(&self, state: &Hex) -> Option<PreviewConfig> {
+        let sheet_config = SheetConfigState {
+            row_limit: self.row_limit,
+            ..state.sheet_config.clone()
+        };
+
+        match state.model {
+            ViewType::BarChart => {
+                let mut config = state.bar_config.clone().unwrap_or_default();
+                config.diff(sheet_config);
+                Some(PreviewConfig::BarChart(config))
+            }
+            ViewType::StackedBarChart => {
+                let mut config = state.stacked_bar_config.clone().unwrap_or_default();
+                config.diff(sheet_config);
+                Some(PreviewConfig::StackedBarChart(config))
+            }
+            _ => None,
+        }
+    }
+
+    fn preview_area(&self) -> Element<'_, Charm> {
+        let content = match self.preview {
+            Some(Ok(preview)) => preview.view(),
+            Some(Err(err)) => {
+                let locate = err
+                    .location()
+                    .filter(|_| self.on_locate.is_some())
+                    .map(|location| {
+                        button(text(format!("Go to line {}", location.line)).size(12.0))
+                            .on_press(Charm::Locate(location.clone()))
+                            .padding([2, 6])
+                    });
+
+                column!(error_area(&err.message(), 13.0))
+                    .push_maybe(locate)
+                    .spacing(6)
+                    .align_x(Alignment::Center)
+                    .into()
+            }
+            None => text("Building preview...").size(13.0).into(),
+        };
+
+        container(content)
+            .center_x(Length::Fill)
+            .center_y(PREVIEW_HEIGHT)
+            .style(|theme| {
+                <FileBorderContainer as container::Catalog>::style(&FileBorderContainer, theme)
+            })
+            .into()
+    }
+
+    /// Lists the columns of the file by number, as the model configs ask
+    /// for them
+    fn columns_hint(&self, state: &Hex) -> Element<'_, Charm> {
+        if state.columns.is_empty() {
+            return Space::new(0, 0).into();
+        }
+
+        let columns = state
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, kind))| {
+                if name.is_empty() {
+                    format!("{idx} ({kind})")
+                } else {
+                    format!("{idx}: {name} ({kind})")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        text(format!("Columns - {columns}")).size(12.0).into()
+    }
+
     fn model_config(&self, state: &Hex) -> Element<'_, Charm> {
+        let sheet_config = SheetConfigState {
+            row_limit: self.row_limit,
+            ..state.sheet_config.clone()
+        };
+
         match &state.model {
             ViewType::Editor => Space::new(0, 0).into(),
             ViewType::LineGraph => {
                 let mut content = LineGraphConfig::new(
                     &self.file,
-                    state.sheet_config.clone(),
+                    sheet_config.clone(),
                     Charm::ConfigSubmit,
                     Charm::LinePrevious,
                     Charm::Cancel,
                     Charm::Error,
                     Charm::ClearError,
-                );
+                )
+                .columns(state.columns.clone());
 
                 if let Some(line_config) = state.line_config.clone() {
                     content = content.previous_state(line_config);
@@ -153,13 +537,14 @@ where
             ViewType::BarChart => {
                 let mut content = BarChartConfig::new(
                     &self.file,
-                    state.sheet_config.clone(),
+                    sheet_config.clone(),
                     Charm::ConfigSubmit,
-                    Charm::Error,
                     Charm::BarChartPrevious,
                     Charm::Cancel,
                     Charm::ClearError,
-                );
+                )
+                .on_change(Charm::BarChartChanged)
+                .columns(state.columns.clone());
 
                 if let Some(barchart_config) = state.bar_config.clone() {
                     content = content.previous_state(barchart_config);
@@ -170,19 +555,76 @@ where
             ViewType::StackedBarChart => {
                 let mut content = StackedBarChartConfig::new(
                     &self.file,
-                    state.sheet_config.clone(),
+                    sheet_config.clone(),
                     Charm::ConfigSubmit,
                     Charm::Error,
                     Charm::StackedBarChartPrevious,
                     Charm::Cancel,
                     Charm::ClearError,
-                );
+                )
+                .on_change(Charm::StackedBarChartChanged)
+                .columns(state.columns.clone())
+                .error(state.error.clone());
                 if let Some(stacked_config) = state.stacked_bar_config.clone() {
                     content = content.previous_state(stacked_config);
                 }
 
                 content.into()
             }
+            ViewType::BoxPlot => {
+                let mut content = BoxPlotConfig::new(
+                    &self.file,
+                    sheet_config.clone(),
+                    Charm::ConfigSubmit,
+                    Charm::BoxPlotPrevious,
+                    Charm::Cancel,
+                    Charm::ClearError,
+                )
+                .columns(state.columns.clone());
+
+                if let Some(box_config) = state.box_config.clone() {
+                    content = content.previous_state(box_config);
+                }
+
+                content.into()
+            }
+            ViewType::Histogram => {
+                let mut content = HistogramConfig::new(
+                    &self.file,
+                    sheet_config.clone(),
+                    Charm::ConfigSubmit,
+                    Charm::HistogramPrevious,
+                    Charm::Cancel,
+                    Charm::ClearError,
+                )
+                .columns(state.columns.clone());
+
+                if let Some(histogram_config) = state.histogram_config.clone() {
+                    content = content.previous_state(histogram_config);
+                }
+
+                content.into()
+            }
+            ViewType::Radar => {
+                let mut content = RadarConfig::new(
+                    &self.file,
+                    sheet_config.clone(),
+                    Charm::ConfigSubmit,
+                    Charm::RadarPrevious,
+                    Charm::Cancel,
+                    Charm::Error,
+                    Charm::ClearError,
+                )
+                .columns(state.columns.clone())
+                .error(state.error.clone());
+
+                if let Some(radar_config) = state.radar_config.clone() {
+                    content = content.previous_state(radar_config);
+                }
+
+                content.into()
+            }
+            ViewType::Table => Space::new(0, 0).into(),
             ViewType::None => Space::new(0, 0).into(),
         }
     }
@@ -193,7 +635,12 @@ where
         let action = if state.model.has_config() {
             row!(button(text("Next").size(13.0)).on_press(Charm::ChangeView(Portal::SheetConfig)))
         } else {
-            row!(button(text("Open").size(13.0)).on_press(Charm::Submit))
+            let label = match state.model {
+                ViewType::Table => "Open as Table",
+                _ => "Open",
+            };
+
+            row!(button(text(label).size(13.0)).on_press(Charm::Submit))
         };
 
         row!(cancel, horizontal_space(), action).into()
@@ -269,12 +716,30 @@ where
     type Event = Charm;
 
     fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<Message> {
+        self.seed(state);
+
         match event {
-            Charm::ConfigSubmit(config) => Some((self.on_submit)(self.file.clone(), config)),
+            Charm::ConfigSubmit(session) => Some((self.on_submit)(session)),
             Charm::SheetSubmit(sheet) => {
                 state.sheet_config = sheet;
+
+                let has_header = matches!(
+                    state.sheet_config.header_labels,
+                    HeaderLabelStrategy::ReadLabels
+                );
+
+                match csv::column_types(&self.file, state.sheet_config.delimiter, has_header) {
+                    Ok(columns) => state.columns = columns,
+                    Err(err) => {
+                        state.error = Some(err.into());
+                        return None;
+                    }
+                }
+
                 state.current_view = Portal::ModelConfig;
-                None
+
+                self.initial_preview(state)
+                    .and_then(|config| self.preview_message(config))
             }
             Charm::SheetPrevious(sheet) => {
                 state.sheet_config = sheet;
@@ -296,58 +761,51 @@ where
                 state.current_view = Portal::SheetConfig;
                 None
             }
+            Charm::BoxPlotPrevious(boxplot) => {
+                state.box_config = Some(boxplot);
+                state.current_view = Portal::SheetConfig;
+                None
+            }
+            Charm::HistogramPrevious(histogram) => {
+                state.histogram_config = Some(histogram);
+                state.current_view = Portal::SheetConfig;
+                None
+            }
+            Charm::RadarPrevious(radar) => {
+                state.radar_config = Some(radar);
+                state.current_view = Portal::SheetConfig;
+                None
+            }
+            Charm::BarChartChanged(config) => {
+                state.error = None;
+                self.preview_message(PreviewConfig::BarChart(config))
+            }
+            Charm::StackedBarChartChanged(config) => {
+                state.error = None;
+                self.preview_message(PreviewConfig::StackedBarChart(config))
+            }
             Charm::Error(err) => {
-                state.error = Some(err.to_string());
-                Some((self.on_error)(err))
+                warn!("Wizard configuration error: {}", err.message);
+                state.error = Some(err);
+                None
             }
             Charm::ClearError => {
                 state.error = None;
                 None
             }
+            Charm::Locate(location) => self
+                .on_locate
+                .as_ref()
+                .map(|on_locate| (on_locate)(location.file, location.line)),
             Charm::ReselectFile => {
                 // Reselecting file means returning to default state
                 state.model = ViewType::Editor;
-
-                let data = EditorTabData::new(Some(self.file.clone()), String::default());
-
-                state.config = View::Editor(data);
-
                 self.on_reselect_file.clone()
             }
             Charm::ModelSelected(model) => {
+                // Charts are only made once their configuration is submitted
                 state.model = model;
-                let config = match state.model {
-                    ViewType::LineGraph => {
-                        LineTabData::new(self.file.clone(), LineConfigState::default())
-                            .and_then(|data| Ok(View::LineGraph(data)))
-                    }
-                    ViewType::BarChart => {
-                        BarChartTabData::new(self.file.clone(), BarChartConfigState::default())
-                            .and_then(|data| Ok(View::BarChart(data)))
-                    }
-                    ViewType::StackedBarChart => StackedBarChartTabData::new(
-                        self.file.clone(),
-                        StackedBarChartConfigState::default(),
-                    )
-                    .and_then(|data| Ok(View::StackedBarChart(data))),
-                    ViewType::Editor => {
-                        let data = EditorTabData::new(Some(self.file.clone()), String::default());
-                        Ok(View::Editor(data))
-                    }
-                    ViewType::None => Ok(View::None),
-                };
-                match config {
-                    Err(_err) => {
-                        // No need to report error at selecting stage. Configuration
-                        // is still not complete
-                        // Some((self.on_error)(err))
-                        None
-                    }
-                    Ok(view) => {
-                        state.config = view;
-                        None
-                    }
-                }
+                None
             }
             Charm::ViewConfig => None,
             Charm::ChangeView(portal) => {
@@ -355,57 +813,40 @@ where
                 None
             }
             Charm::Cancel => self.on_cancel.clone(),
-            Charm::Submit => Some((self.on_submit)(self.file.clone(), state.config.clone())),
+            Charm::Submit => {
+                let session = match state.model {
+                    ViewType::Table => SessionTab::Table(self.file.clone()),
+                    _ => SessionTab::Editor(self.file.clone()),
+                };
+
+                Some((self.on_submit)(session))
+            }
             Charm::None => None,
         }
     }
 
     fn view(&self, state: &Self::State) -> Element<'_, Self::Event> {
+        // Until the first change, the initial configuration is shown in
+        // place of the state
+        let initial;
+        let state = match &self.initial {
+            Some(session) if !state.seeded => {
+                initial = Hex::from_session(session);
+                &initial
+            }
+            _ => state,
+        };
+
         let header = text("File Wizard")
             .size(18.0)
             .width(Length::Fill)
             .align_x(Horizontal::Center);
 
-        let error_section: Element<'_, Self::Event> = match state.error.clone() {
-            Some(msg) => {
-                struct Background;
+        // Errors tied to a field are shown beside it by the model config
+        let general_error = state.error.as_ref().filter(|error| error.field.is_none());
 
-                impl widget::container::Catalog for Background {
-                    type Class<'a> = Theme;
-
-                    fn default<'a>() -> Self::Class<'a> {
-                        <Theme as std::default::Default>::default()
-                    }
-
-                    fn style(&self, class: &Self::Class<'_>) -> container::Style {
-                        if class.extended_palette().is_dark {
-                            let text_color = color!(248, 133, 133);
-                            let background = color!(153, 27, 27);
-
-                            container::Style {
-                                text_color: Some(text_color),
-                                background: Some(iced::Background::Color(background)),
-                                ..Default::default()
-                            }
-                        } else {
-                            let text_color = color!(75, 20, 20);
-                            let background = color!(248, 113, 113);
-
-                            container::Style {
-                                text_color: Some(text_color),
-                                background: Some(iced::Background::Color(background)),
-                                ..Default::default()
-                            }
-                        }
-                    }
-                }
-
-                container(text(msg).size(15.0))
-                    .width(Length::Fill)
-                    .padding([4, 6])
-                    .style(|theme| <Background as container::Catalog>::style(&Background, theme))
-                    .into()
-            }
+        let error_section: Element<'_, Self::Event> = match general_error {
+            Some(error) => error_area(&error.message, 15.0),
             None => Space::new(0, 0).into(),
         };
 
@@ -413,21 +854,38 @@ where
             Portal::ModelConfig => {
                 let content = column!(
                     header,
-                    if state.error.is_some() {
+                    if general_error.is_some() {
                         vertical_space().height(25.0)
                     } else {
                         Space::new(0, 0)
                     },
                     error_section,
                     vertical_space().height(25.0),
-                    self.model_config(state),
+                    self.columns_hint(state),
+                    vertical_space().height(15.0),
                 )
                 .spacing(0);
 
-                dialog_container(content)
-                    .width(450.0)
-                    .height(Length::Shrink)
-                    .into()
+                let has_preview = self.on_preview.is_some()
+                    && matches!(state.model, ViewType::BarChart | ViewType::StackedBarChart);
+
+                if has_preview {
+                    let body = row!(
+                        container(self.model_config(state)).width(400.0),
+                        self.preview_area()
+                    )
+                    .spacing(25.0);
+
+                    dialog_container(content.push(body), self.flat)
+                        .width(900.0)
+                        .height(Length::Shrink)
+                        .into()
+                } else {
+                    dialog_container(content.push(self.model_config(state)), self.flat)
+                        .width(450.0)
+                        .height(Length::Shrink)
+                        .into()
+                }
             }
             Portal::FileSelection => {
                 let content = column!(
@@ -437,7 +895,7 @@ where
                     vertical_space(),
                     self.actions(state)
                 );
-                dialog_container(content).height(250.0).into()
+                dialog_container(content, self.flat).height(250.0).into()
             }
 
             Portal::SheetConfig => {
@@ -451,7 +909,7 @@ where
 
                 let content = column!(
                     header,
-                    if state.error.is_some() {
+                    if general_error.is_some() {
                         vertical_space().height(25.0)
                     } else {
                         Space::new(0, 0)
@@ -462,7 +920,7 @@ where
                 )
                 .spacing(0);
 
-                dialog_container(content)
+                dialog_container(content, self.flat)
                     .width(420.0)
                     .height(Length::Shrink)
                     .into()
@@ -17,11 +17,20 @@ use modav_core::repr::sheet::utils::{
 };
 
 use crate::{
-    utils::{tooltip, AppError},
-    views::{BarChartTabData, View},
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        sampling::RowLimit,
+        stats::Aggregation,
+        tooltip,
+    },
+    views::SessionTab,
 };
 
-use super::sheet::SheetConfigState;
+use super::{
+    numeric_warning,
+    sheet::{indices_json, json_indices, SheetConfigState},
+};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum AxisStrategy {
@@ -99,6 +108,8 @@ pub enum BarChartConfigMessage {
     YLabelChanged(String),
     Order(bool),
     Horizontal(bool),
+    LogScale(bool),
+    Aggregation(Aggregation),
     Previous,
     Cancel,
     Submit,
@@ -115,11 +126,19 @@ pub struct BarChartConfigState {
     pub axis_label: BarChartAxisLabelStrategy,
     pub trim: bool,
     pub flexible: bool,
+    pub delimiter: Delimiter,
     pub header_types: HeaderTypesStrategy,
     pub header_labels: HeaderLabelStrategy,
     pub order: bool,
     pub is_horizontal: bool,
+    /// Spaces the value axis logarithmically
+    pub log_scale: bool,
     pub use_previous: bool,
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
+    /// Combines the rows sharing an X value into one bar
+    pub aggregation: Aggregation,
 }
 
 impl Default for BarChartConfigState {
@@ -134,11 +153,16 @@ impl Default for BarChartConfigState {
             row_exclude: HashSet::default(),
             trim: true,
             flexible: false,
+            delimiter: Delimiter::default(),
             header_types: HeaderTypesStrategy::Infer,
             header_labels: HeaderLabelStrategy::ReadLabels,
             order: false,
             is_horizontal: false,
+            log_scale: false,
             use_previous: true,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
+            aggregation: Aggregation::None,
         }
     }
 }
@@ -148,20 +172,140 @@ impl BarChartConfigState {
         let SheetConfigState {
             trim,
             flexible,
+            delimiter,
             header_type,
             header_labels,
             caption,
+            row_limit,
+            filters,
             ..
         } = sheet_config;
 
         self.trim = trim;
         self.flexible = flexible;
+        self.delimiter = delimiter;
         self.header_labels = header_labels;
         self.header_types = header_type;
         self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        let axis_label = match &self.axis_label {
+            BarChartAxisLabelStrategy::None => json!({ "kind": "none" }),
+            BarChartAxisLabelStrategy::Headers => json!({ "kind": "headers" }),
+            BarChartAxisLabelStrategy::Provided { x, y } => json!({
+                "kind": "provided",
+                "x": x,
+                "y": y,
+            }),
+        };
+
+        // Bar labels are only ever read from a column in the wizard
+        let bar_label = match self.bar_label {
+            BarChartBarLabels::FromColumn(column) => Some(column),
+            _ => None,
+        };
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "x_col": self.x_col,
+            "y_col": self.y_col,
+            "row_exclude": indices_json(&self.row_exclude),
+            "bar_label": bar_label,
+            "axis_label": axis_label,
+            "order": self.order,
+            "horizontal": self.is_horizontal,
+            "log_scale": self.log_scale,
+            "aggregation": self.aggregation.to_string(),
+        }))
+    }
+
+    /// Reads a configuration written by [`BarChartConfigState::to_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        let string = |key: &str| json.get(key).and_then(Value::as_str).map(String::from);
+        let flag = |key: &str| json.get(key).and_then(Value::as_bool).unwrap_or_default();
+
+        if let Some(title) = string("title") {
+            state.title = title;
+        }
+        if let Some(x_col) = json.get("x_col").and_then(json::as_usize) {
+            state.x_col = x_col;
+        }
+        if let Some(y_col) = json.get("y_col").and_then(json::as_usize) {
+            state.y_col = y_col;
+        }
+        state.row_exclude = json_indices(json.get("row_exclude"));
+        state.bar_label = match json.get("bar_label").and_then(json::as_usize) {
+            Some(column) => BarChartBarLabels::FromColumn(column),
+            None => BarChartBarLabels::None,
+        };
+
+        if let Some(axis_label) = json.get("axis_label") {
+            let label = |key: &str| {
+                axis_label
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(String::from)
+                    .unwrap_or_default()
+            };
+
+            match axis_label.get("kind").and_then(Value::as_str) {
+                Some("none") => state.axis_label = BarChartAxisLabelStrategy::None,
+                Some("headers") => state.axis_label = BarChartAxisLabelStrategy::Headers,
+                Some("provided") => {
+                    state.axis_label = BarChartAxisLabelStrategy::Provided {
+                        x: label("x"),
+                        y: label("y"),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        state.order = flag("order");
+        state.is_horizontal = flag("horizontal");
+        state.log_scale = flag("log_scale");
+
+        if let Some(aggregation) =
+            json.get("aggregation")
+                .and_then(Value::as_str)
+                .and_then(|name| {
+                    Aggregation::ALL
+                        .into_iter()
+                        .find(|aggregation| aggregation.to_string() == name)
+                })
+        {
+            state.aggregation = aggregation;
+        }
+
+        state
     }
 
-    fn submit(&self) -> Self {
+    pub(super) fn submit(&self) -> Self {
         Self {
             use_previous: true,
             ..self.clone()
@@ -172,38 +316,39 @@ impl BarChartConfigState {
 pub struct BarChartConfig<'a, Message> {
     file: &'a PathBuf,
     sheet_config: SheetConfigState,
-    on_submit: Box<dyn Fn(View) -> Message + 'a>,
-    on_error: Box<dyn Fn(AppError) -> Message + 'a>,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
     on_previous: Box<dyn Fn(BarChartConfigState) -> Message + 'a>,
     on_cancel: Message,
     on_clear_error: Message,
     previous_state: Option<BarChartConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
+    on_change: Option<Box<dyn Fn(BarChartConfigState) -> Message + 'a>>,
 }
 
 impl<'a, Message> BarChartConfig<'a, Message> {
-    pub fn new<S, P, E>(
+    pub fn new<S, P>(
         file: &'a PathBuf,
         sheet_config: SheetConfigState,
         on_submit: S,
-        on_error: E,
         on_previous: P,
         on_cancel: Message,
         on_clear_error: Message,
     ) -> Self
     where
-        S: 'a + Fn(View) -> Message,
-        E: 'a + Fn(AppError) -> Message,
+        S: 'a + Fn(SessionTab) -> Message,
         P: 'a + Fn(BarChartConfigState) -> Message,
     {
         Self {
             file,
             sheet_config,
             on_submit: Box::new(on_submit),
-            on_error: Box::new(on_error),
             on_previous: Box::new(on_previous),
             on_cancel,
             previous_state: None,
+            columns: Vec::new(),
             on_clear_error,
+            on_change: None,
         }
     }
 
@@ -212,6 +357,35 @@ impl<'a, Message> BarChartConfig<'a, Message> {
         self
     }
 
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sends the configuration, as it would be submitted, whenever a field
+    /// changes. Without it, changes only clear the error.
+    pub fn on_change<F>(mut self, on_change: F) -> Self
+    where
+        F: 'a + Fn(BarChartConfigState) -> Message,
+    {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    fn changed(&self, state: &BarChartConfigState) -> Message
+    where
+        Message: Clone,
+    {
+        match &self.on_change {
+            Some(on_change) => {
+                let mut config = state.submit();
+                config.diff(self.sheet_config.clone());
+                (on_change)(config)
+            }
+            None => self.on_clear_error.clone(),
+        }
+    }
+
     fn update_state(&self, state: &mut BarChartConfigState) {
         if state.use_previous {
             if let Some(previous_state) = self.previous_state.clone() {
@@ -248,7 +422,8 @@ impl<'a, Message> BarChartConfig<'a, Message> {
         };
 
         let title = text_input("Graph Title", state.title.as_str())
-            .on_input(BarChartConfigMessage::TitleChanged);
+            .on_input(BarChartConfigMessage::TitleChanged)
+            .on_submit(BarChartConfigMessage::Submit);
 
         let x_col = {
             let label = text("X column: ");
@@ -258,6 +433,7 @@ impl<'a, Message> BarChartConfig<'a, Message> {
 
                 text_input("", &value)
                     .on_input(BarChartConfigMessage::XCol)
+                    .on_submit(BarChartConfigMessage::Submit)
                     .width(50)
             };
 
@@ -276,14 +452,20 @@ impl<'a, Message> BarChartConfig<'a, Message> {
 
                 text_input("", &value)
                     .on_input(BarChartConfigMessage::YCol)
+                    .on_submit(BarChartConfigMessage::Submit)
                     .width(50)
             };
 
             let tip = tooltip("Columns on the y-axis");
 
-            row!(label, input, tip)
+            let input = row!(label, input, tip)
                 .spacing(15)
-                .align_y(Alignment::Center)
+                .align_y(Alignment::Center);
+
+            match numeric_warning(&self.columns, [state.y_col]) {
+                Some(warning) => column!(input, warning).spacing(6.0),
+                None => column!(input),
+            }
         };
 
         let axis_label = {
@@ -307,10 +489,12 @@ impl<'a, Message> BarChartConfig<'a, Message> {
             let extra: Element<'_, BarChartConfigMessage> = match &state.axis_label {
                 BarChartAxisLabelStrategy::Provided { x, y } => {
                     let x_label = text_input("X axis label", x.as_str())
-                        .on_input(BarChartConfigMessage::XLabelChanged);
+                        .on_input(BarChartConfigMessage::XLabelChanged)
+                        .on_submit(BarChartConfigMessage::Submit);
 
                     let y_label = text_input("Y axis label", y.as_str())
-                        .on_input(BarChartConfigMessage::YLabelChanged);
+                        .on_input(BarChartConfigMessage::YLabelChanged)
+                        .on_submit(BarChartConfigMessage::Submit);
 
                     column!(x_label, y_label).spacing(10.0).into()
                 }
@@ -344,6 +528,7 @@ impl<'a, Message> BarChartConfig<'a, Message> {
 
                 text_input("", &value)
                     .on_input(BarChartConfigMessage::BarLabelColumn)
+                    .on_submit(BarChartConfigMessage::Submit)
                     .width(50.0)
             };
 
@@ -352,6 +537,22 @@ impl<'a, Message> BarChartConfig<'a, Message> {
                 .align_y(Alignment::Center)
         };
 
+        let aggregation = {
+            let label = text("Aggregate: ");
+
+            let list = pick_list(
+                Aggregation::ALL,
+                Some(state.aggregation),
+                BarChartConfigMessage::Aggregation,
+            )
+            .text_size(13.0);
+
+            let tip =
+                tooltip("Group rows with the same X value into one bar, combining their Y values");
+
+            row!(label, list, tip).spacing(8).align_y(Alignment::Center)
+        };
+
         let order = {
             let check = checkbox("Order", state.order).on_toggle(BarChartConfigMessage::Order);
 
@@ -369,9 +570,30 @@ impl<'a, Message> BarChartConfig<'a, Message> {
             row!(check, tip).spacing(25.0)
         };
 
-        column!(title, x_col, y_col, axis_label, bar_labels, order, horizontal)
-            .spacing(20.0)
-            .into()
+        let log_scale = {
+            let check = checkbox("Logarithmic values?", state.log_scale)
+                .on_toggle(BarChartConfigMessage::LogScale);
+
+            let tip = tooltip(
+                "Spaces the value axis by powers of ten. Values that are not positive are left out",
+            );
+
+            row!(check, tip).spacing(25.0)
+        };
+
+        column!(
+            title,
+            x_col,
+            y_col,
+            aggregation,
+            axis_label,
+            bar_labels,
+            order,
+            horizontal,
+            log_scale
+        )
+        .spacing(20.0)
+        .into()
     }
 }
 
@@ -408,19 +630,13 @@ where
                 };
 
                 state.diff(self.sheet_config.clone());
-                let data = BarChartTabData::new(self.file.clone(), state.submit());
-                match data {
-                    Err(error) => Some((self.on_error)(error)),
-                    Ok(data) => {
-                        let view = View::BarChart(data);
-                        Some((self.on_submit)(view))
-                    }
-                }
+                let session = SessionTab::BarChart(self.file.clone(), state.submit());
+                Some((self.on_submit)(session))
             }
             BarChartConfigMessage::TitleChanged(title) => {
                 self.update_state(state);
                 state.title = title;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::AxisLabel(strat) => {
                 self.update_state(state);
@@ -434,7 +650,7 @@ where
                 };
 
                 state.axis_label = strat;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::XLabelChanged(label) => {
                 self.update_state(state);
@@ -447,7 +663,7 @@ where
                     }
                     _ => {}
                 };
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::YLabelChanged(label) => {
                 self.update_state(state);
@@ -460,7 +676,7 @@ where
                     }
                     _ => {}
                 };
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::BarLabel(label) => {
                 self.update_state(state);
@@ -470,7 +686,7 @@ where
                     BarLabels::Provided => BarChartBarLabels::Provided(vec![]),
                 };
                 state.bar_label = strat;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::BarLabelColumn(input) => {
                 self.update_state(state);
@@ -492,7 +708,7 @@ where
                     state.bar_label = BarChartBarLabels::FromColumn(col);
                 }
 
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
 
             BarChartConfigMessage::XCol(input) => {
@@ -511,7 +727,7 @@ where
                 };
 
                 state.x_col = col;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
 
             BarChartConfigMessage::YCol(input) => {
@@ -529,17 +745,27 @@ where
                 };
 
                 state.y_col = col;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::Order(order) => {
                 self.update_state(state);
                 state.order = order;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
             }
             BarChartConfigMessage::Horizontal(is_horizontal) => {
                 self.update_state(state);
                 state.is_horizontal = is_horizontal;
-                Some(self.on_clear_error.clone())
+                Some(self.changed(state))
+            }
+            BarChartConfigMessage::LogScale(log_scale) => {
+                self.update_state(state);
+                state.log_scale = log_scale;
+                Some(self.changed(state))
+            }
+            BarChartConfigMessage::Aggregation(aggregation) => {
+                self.update_state(state);
+                state.aggregation = aggregation;
+                Some(self.changed(state))
             }
         }
     }
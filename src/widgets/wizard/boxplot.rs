@@ -0,0 +1,364 @@
+#![allow(deprecated)]
+use std::{fmt::Debug, path::PathBuf};
+
+use iced::{
+    widget::{
+        button, column, component, container, horizontal_space, row, text, text_input,
+        vertical_space, Component,
+    },
+    Alignment, Element, Renderer, Theme,
+};
+
+use modav_core::repr::sheet::utils::{HeaderLabelStrategy, HeaderTypesStrategy};
+
+use crate::{
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        sampling::RowLimit,
+        tooltip,
+    },
+    views::SessionTab,
+};
+
+use super::{numeric_warning, sheet::SheetConfigState};
+
+#[derive(Debug, Clone)]
+pub enum BoxPlotConfigMessage {
+    TitleChanged(String),
+    CategoryColumn(String),
+    ValueColumn(String),
+    Cancel,
+    Previous,
+    Submit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlotConfigState {
+    pub title: String,
+    /// Column whose values name the box each row belongs to
+    pub category_col: usize,
+    /// Column of the numbers summarized by each box
+    pub value_col: usize,
+    pub caption: Option<String>,
+    pub trim: bool,
+    pub flexible: bool,
+    pub delimiter: Delimiter,
+    pub header_types: HeaderTypesStrategy,
+    pub header_labels: HeaderLabelStrategy,
+    pub use_previous: bool,
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
+}
+
+impl Default for BoxPlotConfigState {
+    fn default() -> Self {
+        Self {
+            title: "Untitled".into(),
+            category_col: 0,
+            value_col: 1,
+            caption: None,
+            trim: true,
+            flexible: false,
+            delimiter: Delimiter::default(),
+            header_types: HeaderTypesStrategy::Infer,
+            header_labels: HeaderLabelStrategy::ReadLabels,
+            use_previous: true,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
+        }
+    }
+}
+
+impl BoxPlotConfigState {
+    pub fn diff(&mut self, sheet_config: SheetConfigState) {
+        let SheetConfigState {
+            trim,
+            flexible,
+            delimiter,
+            header_type,
+            header_labels,
+            caption,
+            row_limit,
+            filters,
+            ..
+        } = sheet_config;
+
+        self.trim = trim;
+        self.flexible = flexible;
+        self.delimiter = delimiter;
+        self.header_labels = header_labels;
+        self.header_types = header_type;
+        self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "category_col": self.category_col,
+            "value_col": self.value_col,
+        }))
+    }
+
+    /// Reads a configuration written by [`BoxPlotConfigState::to_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        if let Some(title) = json.get("title").and_then(Value::as_str) {
+            state.title = title.to_string();
+        }
+        if let Some(column) = json.get("category_col").and_then(json::as_usize) {
+            state.category_col = column;
+        }
+        if let Some(column) = json.get("value_col").and_then(json::as_usize) {
+            state.value_col = column;
+        }
+
+        state
+    }
+
+    pub(super) fn submit(&self) -> Self {
+        Self {
+            use_previous: true,
+            ..self.clone()
+        }
+    }
+}
+
+pub struct BoxPlotConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    file: &'a PathBuf,
+    sheet_config: SheetConfigState,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_previous: Box<dyn Fn(BoxPlotConfigState) -> Message + 'a>,
+    on_cancel: Message,
+    previous_state: Option<BoxPlotConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
+    on_clear_error: Message,
+}
+
+impl<'a, Message> BoxPlotConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    pub fn new<S, P>(
+        file: &'a PathBuf,
+        sheet_config: SheetConfigState,
+        on_submit: S,
+        on_previous: P,
+        on_cancel: Message,
+        on_clear_error: Message,
+    ) -> Self
+    where
+        S: 'a + Fn(SessionTab) -> Message,
+        P: 'a + Fn(BoxPlotConfigState) -> Message,
+    {
+        Self {
+            file,
+            sheet_config,
+            on_submit: Box::new(on_submit),
+            on_previous: Box::new(on_previous),
+            on_cancel,
+            previous_state: None,
+            columns: Vec::new(),
+            on_clear_error,
+        }
+    }
+
+    pub fn previous_state(mut self, state: BoxPlotConfigState) -> Self {
+        self.previous_state = Some(state);
+        self
+    }
+
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    fn update_state(&self, state: &mut BoxPlotConfigState) {
+        if state.use_previous {
+            if let Some(previous_state) = self.previous_state.clone() {
+                *state = previous_state;
+            }
+            state.use_previous = false;
+        }
+    }
+
+    fn actions(&self) -> Element<'_, BoxPlotConfigMessage> {
+        let cancel_btn = button(text("Cancel").size(13.0)).on_press(BoxPlotConfigMessage::Cancel);
+
+        let prev_btn = button(text("Back").size(13.0)).on_press(BoxPlotConfigMessage::Previous);
+
+        let submit = button(text("Open").size(13.0)).on_press(BoxPlotConfigMessage::Submit);
+
+        let actions = row!(
+            cancel_btn,
+            horizontal_space(),
+            row!(prev_btn, submit).spacing(10.0)
+        );
+
+        actions.into()
+    }
+
+    fn boxplot_config(&self, state: &BoxPlotConfigState) -> Element<'_, BoxPlotConfigMessage> {
+        let state = if state.use_previous {
+            match &self.previous_state {
+                Some(prev_state) => prev_state,
+                None => state,
+            }
+        } else {
+            state
+        };
+
+        let title = text_input("Graph Title", state.title.as_str())
+            .on_input(BoxPlotConfigMessage::TitleChanged)
+            .on_submit(BoxPlotConfigMessage::Submit);
+
+        let category = {
+            let label = text("Category column: ");
+
+            let input = text_input("", &state.category_col.to_string())
+                .on_input(BoxPlotConfigMessage::CategoryColumn)
+                .on_submit(BoxPlotConfigMessage::Submit)
+                .width(50);
+
+            let tip = tooltip("Rows with the same value in this column share a box");
+
+            row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center)
+        };
+
+        let value = {
+            let label = text("Value column: ");
+
+            let input = text_input("", &state.value_col.to_string())
+                .on_input(BoxPlotConfigMessage::ValueColumn)
+                .on_submit(BoxPlotConfigMessage::Submit)
+                .width(50);
+
+            let tip = tooltip(
+                "The numbers whose quartiles, median and outliers each box shows. Other values are left out",
+            );
+
+            let input = row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center);
+
+            match numeric_warning(&self.columns, [state.value_col]) {
+                Some(warning) => column!(input, warning).spacing(6.0),
+                None => column!(input),
+            }
+        };
+
+        column!(title, category, value).spacing(20.0).into()
+    }
+}
+
+/// Returns the column typed into `input`, or `current` if it is not a number
+fn parse_column(input: &str, current: usize) -> usize {
+    let input = input.trim();
+
+    if input.is_empty() {
+        0
+    } else {
+        input.parse().unwrap_or(current)
+    }
+}
+
+impl<'a, Message> Component<Message> for BoxPlotConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    type State = BoxPlotConfigState;
+    type Event = BoxPlotConfigMessage;
+
+    fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<Message> {
+        match event {
+            BoxPlotConfigMessage::Cancel => Some(self.on_cancel.clone()),
+            BoxPlotConfigMessage::Previous => {
+                let submit_state = if state.use_previous {
+                    match &self.previous_state {
+                        Some(prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                Some((self.on_previous)(submit_state.submit()))
+            }
+            BoxPlotConfigMessage::TitleChanged(title) => {
+                self.update_state(state);
+                state.title = title;
+                Some(self.on_clear_error.clone())
+            }
+            BoxPlotConfigMessage::CategoryColumn(input) => {
+                self.update_state(state);
+                state.category_col = parse_column(&input, state.category_col);
+                Some(self.on_clear_error.clone())
+            }
+            BoxPlotConfigMessage::ValueColumn(input) => {
+                self.update_state(state);
+                state.value_col = parse_column(&input, state.value_col);
+                Some(self.on_clear_error.clone())
+            }
+            BoxPlotConfigMessage::Submit => {
+                let state = if state.use_previous {
+                    match self.previous_state {
+                        Some(ref mut prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                state.diff(self.sheet_config.clone());
+                let session = SessionTab::BoxPlot(self.file.clone(), state.submit());
+                Some((self.on_submit)(session))
+            }
+        }
+    }
+
+    fn view(&self, state: &Self::State) -> Element<'_, Self::Event, Theme, Renderer> {
+        let config = self.boxplot_config(state);
+        let content = column!(config, vertical_space().height(50.0), self.actions()).spacing(10.0);
+
+        container(content).into()
+    }
+}
+
+impl<'a, Message> From<BoxPlotConfig<'a, Message>> for Element<'a, Message>
+where
+    Message: 'a + Debug + Clone,
+{
+    fn from(value: BoxPlotConfig<'a, Message>) -> Self {
+        component(value)
+    }
+}
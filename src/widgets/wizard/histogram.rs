@@ -0,0 +1,373 @@
+#![allow(deprecated)]
+use std::{fmt::Debug, path::PathBuf};
+
+use iced::{
+    widget::{
+        button, column, component, container, horizontal_space, row, text, text_input,
+        vertical_space, Component,
+    },
+    Alignment, Element, Renderer, Theme,
+};
+
+use modav_core::repr::sheet::utils::{HeaderLabelStrategy, HeaderTypesStrategy};
+
+use crate::{
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        sampling::RowLimit,
+        tooltip,
+    },
+    views::SessionTab,
+};
+
+use super::{numeric_warning, sheet::SheetConfigState};
+
+#[derive(Debug, Clone)]
+pub enum HistogramConfigMessage {
+    TitleChanged(String),
+    Column(String),
+    Bins(String),
+    Cancel,
+    Previous,
+    Submit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramConfigState {
+    pub title: String,
+    /// Column of the numbers counted into bins
+    pub column: usize,
+    /// Number of bins, or `None` to pick one from the spread of the values
+    pub bins: Option<usize>,
+    pub caption: Option<String>,
+    pub trim: bool,
+    pub flexible: bool,
+    pub delimiter: Delimiter,
+    pub header_types: HeaderTypesStrategy,
+    pub header_labels: HeaderLabelStrategy,
+    pub use_previous: bool,
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
+}
+
+impl Default for HistogramConfigState {
+    fn default() -> Self {
+        Self {
+            title: "Untitled".into(),
+            column: 0,
+            bins: None,
+            caption: None,
+            trim: true,
+            flexible: false,
+            delimiter: Delimiter::default(),
+            header_types: HeaderTypesStrategy::Infer,
+            header_labels: HeaderLabelStrategy::ReadLabels,
+            use_previous: true,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
+        }
+    }
+}
+
+impl HistogramConfigState {
+    pub fn diff(&mut self, sheet_config: SheetConfigState) {
+        let SheetConfigState {
+            trim,
+            flexible,
+            delimiter,
+            header_type,
+            header_labels,
+            caption,
+            row_limit,
+            filters,
+            ..
+        } = sheet_config;
+
+        self.trim = trim;
+        self.flexible = flexible;
+        self.delimiter = delimiter;
+        self.header_labels = header_labels;
+        self.header_types = header_type;
+        self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "column": self.column,
+            "bins": self.bins,
+        }))
+    }
+
+    /// Reads a configuration written by [`HistogramConfigState::to_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        if let Some(title) = json.get("title").and_then(Value::as_str) {
+            state.title = title.to_string();
+        }
+        if let Some(column) = json.get("column").and_then(json::as_usize) {
+            state.column = column;
+        }
+        state.bins = json
+            .get("bins")
+            .and_then(json::as_usize)
+            .filter(|bins| *bins > 0);
+
+        state
+    }
+
+    pub(super) fn submit(&self) -> Self {
+        Self {
+            use_previous: true,
+            ..self.clone()
+        }
+    }
+}
+
+pub struct HistogramConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    file: &'a PathBuf,
+    sheet_config: SheetConfigState,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_previous: Box<dyn Fn(HistogramConfigState) -> Message + 'a>,
+    on_cancel: Message,
+    previous_state: Option<HistogramConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
+    on_clear_error: Message,
+}
+
+impl<'a, Message> HistogramConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    pub fn new<S, P>(
+        file: &'a PathBuf,
+        sheet_config: SheetConfigState,
+        on_submit: S,
+        on_previous: P,
+        on_cancel: Message,
+        on_clear_error: Message,
+    ) -> Self
+    where
+        S: 'a + Fn(SessionTab) -> Message,
+        P: 'a + Fn(HistogramConfigState) -> Message,
+    {
+        Self {
+            file,
+            sheet_config,
+            on_submit: Box::new(on_submit),
+            on_previous: Box::new(on_previous),
+            on_cancel,
+            previous_state: None,
+            columns: Vec::new(),
+            on_clear_error,
+        }
+    }
+
+    pub fn previous_state(mut self, state: HistogramConfigState) -> Self {
+        self.previous_state = Some(state);
+        self
+    }
+
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    fn update_state(&self, state: &mut HistogramConfigState) {
+        if state.use_previous {
+            if let Some(previous_state) = self.previous_state.clone() {
+                *state = previous_state;
+            }
+            state.use_previous = false;
+        }
+    }
+
+    fn actions(&self) -> Element<'_, HistogramConfigMessage> {
+        let cancel_btn = button(text("Cancel").size(13.0)).on_press(HistogramConfigMessage::Cancel);
+
+        let prev_btn = button(text("Back").size(13.0)).on_press(HistogramConfigMessage::Previous);
+
+        let submit = button(text("Open").size(13.0)).on_press(HistogramConfigMessage::Submit);
+
+        let actions = row!(
+            cancel_btn,
+            horizontal_space(),
+            row!(prev_btn, submit).spacing(10.0)
+        );
+
+        actions.into()
+    }
+
+    fn histogram_config(
+        &self,
+        state: &HistogramConfigState,
+    ) -> Element<'_, HistogramConfigMessage> {
+        let state = if state.use_previous {
+            match &self.previous_state {
+                Some(prev_state) => prev_state,
+                None => state,
+            }
+        } else {
+            state
+        };
+
+        let title = text_input("Graph Title", state.title.as_str())
+            .on_input(HistogramConfigMessage::TitleChanged)
+            .on_submit(HistogramConfigMessage::Submit);
+
+        let values = {
+            let label = text("Column: ");
+
+            let input = text_input("", &state.column.to_string())
+                .on_input(HistogramConfigMessage::Column)
+                .on_submit(HistogramConfigMessage::Submit)
+                .width(50);
+
+            let tip = tooltip("The numbers counted into bins. Other values are left out");
+
+            let input = row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center);
+
+            match numeric_warning(&self.columns, [state.column]) {
+                Some(warning) => column!(input, warning).spacing(6.0),
+                None => column!(input),
+            }
+        };
+
+        let bins = {
+            let label = text("Bins: ");
+
+            let value = state.bins.map(|bins| bins.to_string()).unwrap_or_default();
+
+            let input = text_input("Auto", &value)
+                .on_input(HistogramConfigMessage::Bins)
+                .on_submit(HistogramConfigMessage::Submit)
+                .width(60.0);
+
+            let tip = tooltip(
+                "How many equal ranges the values are split into. Leave empty to pick a count from the spread of the values",
+            );
+
+            row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center)
+        };
+
+        column!(title, values, bins).spacing(20.0).into()
+    }
+}
+
+impl<'a, Message> Component<Message> for HistogramConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    type State = HistogramConfigState;
+    type Event = HistogramConfigMessage;
+
+    fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<Message> {
+        match event {
+            HistogramConfigMessage::Cancel => Some(self.on_cancel.clone()),
+            HistogramConfigMessage::Previous => {
+                let submit_state = if state.use_previous {
+                    match &self.previous_state {
+                        Some(prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                Some((self.on_previous)(submit_state.submit()))
+            }
+            HistogramConfigMessage::TitleChanged(title) => {
+                self.update_state(state);
+                state.title = title;
+                Some(self.on_clear_error.clone())
+            }
+            HistogramConfigMessage::Column(input) => {
+                self.update_state(state);
+                let input = input.trim();
+
+                if input.is_empty() {
+                    state.column = 0;
+                } else if let Ok(col) = input.parse() {
+                    state.column = col;
+                }
+
+                Some(self.on_clear_error.clone())
+            }
+            HistogramConfigMessage::Bins(input) => {
+                self.update_state(state);
+                let input = input.trim();
+
+                if input.is_empty() {
+                    state.bins = None;
+                } else if let Ok(bins) = input.parse::<usize>() {
+                    state.bins = Some(bins.max(1));
+                }
+
+                Some(self.on_clear_error.clone())
+            }
+            HistogramConfigMessage::Submit => {
+                let state = if state.use_previous {
+                    match self.previous_state {
+                        Some(ref mut prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                state.diff(self.sheet_config.clone());
+                let session = SessionTab::Histogram(self.file.clone(), state.submit());
+                Some((self.on_submit)(session))
+            }
+        }
+    }
+
+    fn view(&self, state: &Self::State) -> Element<'_, Self::Event, Theme, Renderer> {
+        let config = self.histogram_config(state);
+        let content = column!(config, vertical_space().height(50.0), self.actions()).spacing(10.0);
+
+        container(content).into()
+    }
+}
+
+impl<'a, Message> From<HistogramConfig<'a, Message>> for Element<'a, Message>
+where
+    Message: 'a + Debug + Clone,
+{
+    fn from(value: HistogramConfig<'a, Message>) -> Self {
+        component(value)
+    }
+}
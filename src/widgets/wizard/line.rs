@@ -3,8 +3,8 @@ use std::{collections::HashSet, fmt::Debug, path::PathBuf};
 
 use iced::{
     widget::{
-        button, column, component, container, horizontal_space, pick_list, row, text, text_input,
-        vertical_space, Component,
+        button, checkbox, column, component, container, horizontal_space, pick_list, row, text,
+        text_input, vertical_space, Component,
     },
     Alignment, Element, Renderer, Theme,
 };
@@ -12,11 +12,20 @@ use iced::{
 use modav_core::repr::sheet::utils::{HeaderLabelStrategy, HeaderTypesStrategy, LineLabelStrategy};
 
 use crate::{
-    utils::{tooltip, AppError},
-    views::{LineTabData, View},
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        sampling::RowLimit,
+        tooltip, AppError,
+    },
+    views::SessionTab,
 };
 
-use super::sheet::SheetConfigState;
+use super::{
+    numeric_warning,
+    sheet::{indices_json, json_indices, SheetConfigState},
+    ConfigError,
+};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 
@@ -57,6 +66,12 @@ pub enum ConfigMessage {
     Error(AppError),
     LineLabelOption(LineLabelOptions),
     LineLabelColumn(String),
+    FacetColumn(String),
+    SizeColumn(String),
+    LogX(bool),
+    LogY(bool),
+    SecondaryLines(String),
+    SecondaryLabel(String),
     Cancel,
     Previous,
     Submit,
@@ -73,9 +88,25 @@ pub struct LineConfigState {
     pub col_exclude: HashSet<usize>,
     pub trim: bool,
     pub flexible: bool,
+    pub delimiter: Delimiter,
     pub header_types: HeaderTypesStrategy,
     pub header_labels: HeaderLabelStrategy,
     pub use_previous: bool,
+    pub row_limit: RowLimit,
+    /// Column whose values split the lines into separate small charts
+    pub facet: Option<usize>,
+    /// Column whose numbers size each line's points in scatter plots
+    pub size_column: Option<usize>,
+    /// Spaces the X axis logarithmically
+    pub log_x: bool,
+    /// Spaces the Y axis logarithmically
+    pub log_y: bool,
+    /// Labels of the lines plotted against a second Y axis on the right
+    pub secondary: Vec<String>,
+    /// Label of the second Y axis
+    pub secondary_label: String,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
 }
 
 impl Default for LineConfigState {
@@ -90,9 +121,18 @@ impl Default for LineConfigState {
             col_exclude: HashSet::default(),
             trim: true,
             flexible: false,
+            delimiter: Delimiter::default(),
             header_types: HeaderTypesStrategy::Infer,
             header_labels: HeaderLabelStrategy::ReadLabels,
             use_previous: true,
+            row_limit: RowLimit::default(),
+            facet: None,
+            size_column: None,
+            log_x: false,
+            log_y: false,
+            secondary: Vec::default(),
+            secondary_label: String::default(),
+            filters: Vec::default(),
         }
     }
 }
@@ -102,20 +142,113 @@ impl LineConfigState {
         let SheetConfigState {
             trim,
             flexible,
+            delimiter,
             header_type,
             header_labels,
             caption,
+            row_limit,
+            filters,
             ..
         } = sheet_config;
 
         self.trim = trim;
         self.flexible = flexible;
+        self.delimiter = delimiter;
         self.header_labels = header_labels;
         self.header_types = header_type;
         self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
     }
 
-    fn submit(&self) -> Self {
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        // Labels are only ever read from a column in the wizard
+        let label_column = match self.label_strat {
+            LineLabelStrategy::FromCell(column) => Some(column),
+            _ => None,
+        };
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "x_label": self.x_label,
+            "y_label": self.y_label,
+            "label_column": label_column,
+            "row_exclude": indices_json(&self.row_exclude),
+            "col_exclude": indices_json(&self.col_exclude),
+            "facet": self.facet,
+            "size_column": self.size_column,
+            "log_x": self.log_x,
+            "log_y": self.log_y,
+            "secondary": self.secondary,
+            "secondary_label": self.secondary_label,
+        }))
+    }
+
+    /// Reads a configuration written by [`LineConfigState::to_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        let string = |key: &str| json.get(key).and_then(Value::as_str).map(String::from);
+        let flag = |key: &str| json.get(key).and_then(Value::as_bool);
+
+        if let Some(title) = string("title") {
+            state.title = title;
+        }
+        if let Some(x_label) = string("x_label") {
+            state.x_label = x_label;
+        }
+        if let Some(y_label) = string("y_label") {
+            state.y_label = y_label;
+        }
+        match json.get("label_column") {
+            Some(Value::Null) => state.label_strat = LineLabelStrategy::None,
+            Some(column) => {
+                if let Some(column) = json::as_usize(column) {
+                    state.label_strat = LineLabelStrategy::FromCell(column);
+                }
+            }
+            None => {}
+        }
+        state.row_exclude = json_indices(json.get("row_exclude"));
+        state.col_exclude = json_indices(json.get("col_exclude"));
+        state.facet = json.get("facet").and_then(json::as_usize);
+        state.size_column = json.get("size_column").and_then(json::as_usize);
+        state.log_x = flag("log_x").unwrap_or_default();
+        state.log_y = flag("log_y").unwrap_or_default();
+        state.secondary = json
+            .get("secondary")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect();
+        state.secondary_label = string("secondary_label").unwrap_or_default();
+
+        state
+    }
+
+    pub(super) fn submit(&self) -> Self {
         Self {
             use_previous: true,
             ..self.clone()
@@ -129,11 +262,13 @@ where
 {
     file: &'a PathBuf,
     sheet_config: SheetConfigState,
-    on_submit: Box<dyn Fn(View) -> Message + 'a>,
-    on_error: Box<dyn Fn(AppError) -> Message + 'a>,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_error: Box<dyn Fn(ConfigError) -> Message + 'a>,
     on_previous: Box<dyn Fn(LineConfigState) -> Message + 'a>,
     on_cancel: Message,
     previous_state: Option<LineConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
     on_clear_error: Message,
 }
 
@@ -151,9 +286,9 @@ where
         on_clear_error: Message,
     ) -> Self
     where
-        S: 'a + Fn(View) -> Message,
+        S: 'a + Fn(SessionTab) -> Message,
         P: 'a + Fn(LineConfigState) -> Message,
-        E: 'a + Fn(AppError) -> Message,
+        E: 'a + Fn(ConfigError) -> Message,
     {
         Self {
             file,
@@ -163,6 +298,7 @@ where
             on_previous: Box::new(on_previous),
             on_cancel,
             previous_state: None,
+            columns: Vec::new(),
             on_clear_error,
         }
     }
@@ -172,6 +308,11 @@ where
         self
     }
 
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
     fn update_state(&self, state: &mut LineConfigState) {
         if state.use_previous {
             if let Some(previous_state) = self.previous_state.clone() {
@@ -207,14 +348,17 @@ where
             state
         };
 
-        let title =
-            text_input("Graph Title", state.title.as_str()).on_input(ConfigMessage::TitleChanged);
+        let title = text_input("Graph Title", state.title.as_str())
+            .on_input(ConfigMessage::TitleChanged)
+            .on_submit(ConfigMessage::Submit);
 
         let x_label = text_input("X axis label", state.x_label.as_str())
-            .on_input(ConfigMessage::XLabelChanged);
+            .on_input(ConfigMessage::XLabelChanged)
+            .on_submit(ConfigMessage::Submit);
 
         let y_label = text_input("Y axis label", state.y_label.as_str())
-            .on_input(ConfigMessage::YLabelChanged);
+            .on_input(ConfigMessage::YLabelChanged)
+            .on_submit(ConfigMessage::Submit);
 
         let line_labels = {
             let label = text("Line Labels: ");
@@ -236,6 +380,7 @@ where
 
                 text_input("", &value)
                     .on_input(ConfigMessage::LineLabelColumn)
+                    .on_submit(ConfigMessage::Submit)
                     .width(40.0)
             };
 
@@ -244,9 +389,99 @@ where
                 .align_y(Alignment::Center)
         };
 
-        column!(title, x_label, y_label, line_labels)
-            .spacing(20.0)
-            .into()
+        let facet = {
+            let label = text("Facet by column: ");
+
+            let value = state.facet.map(|col| col.to_string()).unwrap_or_default();
+
+            let input = text_input("None", &value)
+                .on_input(ConfigMessage::FacetColumn)
+                .on_submit(ConfigMessage::Submit)
+                .width(60.0);
+
+            let tip = tooltip(
+                "Splits the lines into a grid of small charts, one for each value in this column",
+            );
+
+            row!(label, input, tip)
+                .spacing(8)
+                .align_y(Alignment::Center)
+        };
+
+        let size = {
+            let label = text("Point size column: ");
+
+            let value = state
+                .size_column
+                .map(|col| col.to_string())
+                .unwrap_or_default();
+
+            let input = text_input("None", &value)
+                .on_input(ConfigMessage::SizeColumn)
+                .on_submit(ConfigMessage::Submit)
+                .width(60.0);
+
+            let tip = tooltip(
+                "In scatter plots, sizes the points of each line by the number in this column",
+            );
+
+            let input = row!(label, input, tip)
+                .spacing(8)
+                .align_y(Alignment::Center);
+
+            match numeric_warning(&self.columns, state.size_column) {
+                Some(warning) => column!(input, warning).spacing(6.0),
+                None => column!(input),
+            }
+        };
+
+        let log_scale = {
+            let log_x = checkbox("Logarithmic X axis", state.log_x).on_toggle(ConfigMessage::LogX);
+            let log_y = checkbox("Logarithmic Y axis", state.log_y).on_toggle(ConfigMessage::LogY);
+
+            let tip = tooltip(
+                "Spaces a numeric axis by powers of ten. Points that are not positive are left out",
+            );
+
+            row!(log_x, log_y, tip)
+                .spacing(15)
+                .align_y(Alignment::Center)
+        };
+
+        let secondary = {
+            let label = text("Right axis lines: ");
+
+            let lines = text_input("None", &state.secondary.join(","))
+                .on_input(ConfigMessage::SecondaryLines)
+                .on_submit(ConfigMessage::Submit)
+                .width(200.0);
+
+            let axis_label = text_input("Right axis label", &state.secondary_label)
+                .on_input(ConfigMessage::SecondaryLabel)
+                .on_submit(ConfigMessage::Submit)
+                .width(150.0);
+
+            let tip = tooltip(
+                "Comma separated labels of the lines plotted against a second Y axis on the right",
+            );
+
+            row!(label, lines, axis_label, tip)
+                .spacing(8)
+                .align_y(Alignment::Center)
+        };
+
+        column!(
+            title,
+            x_label,
+            y_label,
+            line_labels,
+            facet,
+            size,
+            log_scale,
+            secondary
+        )
+        .spacing(20.0)
+        .into()
     }
 }
 
@@ -259,7 +494,7 @@ where
 
     fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<Message> {
         match event {
-            ConfigMessage::Error(err) => Some((self.on_error)(err)),
+            ConfigMessage::Error(err) => Some((self.on_error)(err.into())),
             ConfigMessage::TitleChanged(title) => {
                 self.update_state(state);
                 state.title = title;
@@ -321,6 +556,54 @@ where
                 };
                 Some(self.on_clear_error.clone())
             }
+            ConfigMessage::FacetColumn(input) => {
+                self.update_state(state);
+                let input = input.trim();
+
+                if input.is_empty() {
+                    state.facet = None;
+                } else if let Ok(col) = input.parse() {
+                    state.facet = Some(col);
+                }
+
+                Some(self.on_clear_error.clone())
+            }
+            ConfigMessage::SizeColumn(input) => {
+                self.update_state(state);
+                let input = input.trim();
+
+                if input.is_empty() {
+                    state.size_column = None;
+                } else if let Ok(col) = input.parse() {
+                    state.size_column = Some(col);
+                }
+
+                Some(self.on_clear_error.clone())
+            }
+            ConfigMessage::LogX(log) => {
+                self.update_state(state);
+                state.log_x = log;
+                Some(self.on_clear_error.clone())
+            }
+            ConfigMessage::LogY(log) => {
+                self.update_state(state);
+                state.log_y = log;
+                Some(self.on_clear_error.clone())
+            }
+            ConfigMessage::SecondaryLines(input) => {
+                self.update_state(state);
+                state.secondary = if input.is_empty() {
+                    Vec::new()
+                } else {
+                    input.split(',').map(String::from).collect()
+                };
+                Some(self.on_clear_error.clone())
+            }
+            ConfigMessage::SecondaryLabel(label) => {
+                self.update_state(state);
+                state.secondary_label = label;
+                Some(self.on_clear_error.clone())
+            }
             ConfigMessage::Submit => {
                 let state = if state.use_previous {
                     match self.previous_state {
@@ -332,15 +615,8 @@ where
                 };
 
                 state.diff(self.sheet_config.clone());
-                let data = LineTabData::new(self.file.clone(), state.submit());
-                match data {
-                    Err(err) => Some((self.on_error)(err)),
-                    Ok(data) => {
-                        let view = View::LineGraph(data);
-
-                        Some((self.on_submit)(view))
-                    }
-                }
+                let session = SessionTab::LineGraph(self.file.clone(), state.submit());
+                Some((self.on_submit)(session))
             }
         }
     }
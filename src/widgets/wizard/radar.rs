@@ -0,0 +1,424 @@
+#![allow(deprecated)]
+use std::{fmt::Debug, path::PathBuf};
+
+use iced::{
+    widget::{
+        button, checkbox, column, component, container, horizontal_space, row, text, text_input,
+        vertical_space, Component,
+    },
+    Alignment, Element, Renderer, Theme,
+};
+
+use modav_core::repr::sheet::utils::{HeaderLabelStrategy, HeaderTypesStrategy};
+
+use crate::{
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        parse_ints,
+        sampling::RowLimit,
+        tooltip, Selection,
+    },
+    views::SessionTab,
+};
+
+use super::{
+    error_area, numeric_warning, sheet::SheetConfigState, stacked_barchart::validate_columns,
+    ConfigError, ConfigField,
+};
+
+#[derive(Debug, Clone)]
+pub enum RadarConfigMessage {
+    TitleChanged(String),
+    LabelCol(String),
+    Metrics(String),
+    PerAxis(bool),
+    Cancel,
+    Previous,
+    Submit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarConfigState {
+    pub title: String,
+    /// Column whose values name the polygon of each row
+    pub label_col: usize,
+    /// Columns plotted on the radial axes, as entered. Empty for every
+    /// numeric column but the labels
+    pub metric_cols_str: String,
+    /// Scales each axis to its own range instead of the range of every axis
+    pub per_axis: bool,
+    pub caption: Option<String>,
+    pub trim: bool,
+    pub flexible: bool,
+    pub delimiter: Delimiter,
+    pub header_types: HeaderTypesStrategy,
+    pub header_labels: HeaderLabelStrategy,
+    pub use_previous: bool,
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
+}
+
+impl Default for RadarConfigState {
+    fn default() -> Self {
+        Self {
+            title: "Untitled".into(),
+            label_col: 0,
+            metric_cols_str: String::default(),
+            per_axis: false,
+            caption: None,
+            trim: true,
+            flexible: false,
+            delimiter: Delimiter::default(),
+            header_types: HeaderTypesStrategy::Infer,
+            header_labels: HeaderLabelStrategy::ReadLabels,
+            use_previous: true,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
+        }
+    }
+}
+
+/// Checks that `input` lists the metric columns of a radar chart. Empty
+/// input picks every numeric column.
+pub(super) fn validate_metrics(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+
+    validate_columns(input)
+}
+
+impl RadarConfigState {
+    pub fn diff(&mut self, sheet_config: SheetConfigState) {
+        let SheetConfigState {
+            trim,
+            flexible,
+            delimiter,
+            header_type,
+            header_labels,
+            caption,
+            row_limit,
+            filters,
+            ..
+        } = sheet_config;
+
+        self.trim = trim;
+        self.flexible = flexible;
+        self.delimiter = delimiter;
+        self.header_labels = header_labels;
+        self.header_types = header_type;
+        self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "label_column": self.label_col,
+            "metric_columns": self.metric_cols_str,
+            "per_axis": self.per_axis,
+        }))
+    }
+
+    /// Reads a configuration written by [`RadarConfigState::to_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        if let Some(title) = json.get("title").and_then(Value::as_str) {
+            state.title = title.to_string();
+        }
+        if let Some(column) = json.get("label_column").and_then(json::as_usize) {
+            state.label_col = column;
+        }
+        if let Some(columns) = json.get("metric_columns").and_then(Value::as_str) {
+            state.metric_cols_str = columns.to_string();
+        }
+        if let Some(per_axis) = json.get("per_axis").and_then(Value::as_bool) {
+            state.per_axis = per_axis;
+        }
+
+        state
+    }
+
+    pub(super) fn submit(&self) -> Self {
+        Self {
+            use_previous: true,
+            ..self.clone()
+        }
+    }
+}
+
+pub struct RadarConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    file: &'a PathBuf,
+    sheet_config: SheetConfigState,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_error: Box<dyn Fn(ConfigError) -> Message + 'a>,
+    on_previous: Box<dyn Fn(RadarConfigState) -> Message + 'a>,
+    on_cancel: Message,
+    previous_state: Option<RadarConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
+    on_clear_error: Message,
+    error: Option<ConfigError>,
+}
+
+impl<'a, Message> RadarConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    pub fn new<S, P, E>(
+        file: &'a PathBuf,
+        sheet_config: SheetConfigState,
+        on_submit: S,
+        on_previous: P,
+        on_cancel: Message,
+        on_error: E,
+        on_clear_error: Message,
+    ) -> Self
+    where
+        S: 'a + Fn(SessionTab) -> Message,
+        P: 'a + Fn(RadarConfigState) -> Message,
+        E: 'a + Fn(ConfigError) -> Message,
+    {
+        Self {
+            file,
+            sheet_config,
+            on_submit: Box::new(on_submit),
+            on_error: Box::new(on_error),
+            on_previous: Box::new(on_previous),
+            on_cancel,
+            previous_state: None,
+            columns: Vec::new(),
+            on_clear_error,
+            error: None,
+        }
+    }
+
+    pub fn previous_state(mut self, state: RadarConfigState) -> Self {
+        self.previous_state = Some(state);
+        self
+    }
+
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the wizard's current error, shown beside its field if it has one
+    pub fn error(mut self, error: Option<ConfigError>) -> Self {
+        self.error = error;
+        self
+    }
+
+    fn update_state(&self, state: &mut RadarConfigState) {
+        if state.use_previous {
+            if let Some(previous_state) = self.previous_state.clone() {
+                *state = previous_state;
+            }
+            state.use_previous = false;
+        }
+    }
+
+    fn actions(&self) -> Element<'_, RadarConfigMessage> {
+        let cancel_btn = button(text("Cancel").size(13.0)).on_press(RadarConfigMessage::Cancel);
+
+        let prev_btn = button(text("Back").size(13.0)).on_press(RadarConfigMessage::Previous);
+
+        let submit = button(text("Open").size(13.0)).on_press(RadarConfigMessage::Submit);
+
+        let actions = row!(
+            cancel_btn,
+            horizontal_space(),
+            row!(prev_btn, submit).spacing(10.0)
+        );
+
+        actions.into()
+    }
+
+    fn radar_config(&self, state: &RadarConfigState) -> Element<'_, RadarConfigMessage> {
+        let state = if state.use_previous {
+            match &self.previous_state {
+                Some(prev_state) => prev_state,
+                None => state,
+            }
+        } else {
+            state
+        };
+
+        let title = text_input("Graph Title", state.title.as_str())
+            .on_input(RadarConfigMessage::TitleChanged)
+            .on_submit(RadarConfigMessage::Submit);
+
+        let label_col = {
+            let label = text("Label column: ");
+
+            let input = text_input("", &state.label_col.to_string())
+                .on_input(RadarConfigMessage::LabelCol)
+                .on_submit(RadarConfigMessage::Submit)
+                .width(50);
+
+            let tip = tooltip("Column naming the polygon of each row in the legend");
+
+            row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center)
+        };
+
+        let metrics = {
+            let label = text("Metric columns: ");
+
+            let input = text_input("All numeric", &state.metric_cols_str)
+                .on_input(RadarConfigMessage::Metrics)
+                .on_submit(RadarConfigMessage::Submit)
+                .width(100);
+
+            let tip = tooltip("Columns given an axis each, separated by `,`. You can also use `:` to denote a range of columns. At least 3 are needed. Leave empty to use every numeric column");
+
+            let input = row!(label, input, tip)
+                .spacing(15)
+                .align_y(Alignment::Center);
+
+            let metrics = Selection::to_vec(
+                parse_ints(&state.metric_cols_str),
+                self.columns.len().saturating_sub(1),
+            );
+
+            match ConfigError::at(self.error.as_ref(), ConfigField::MetricColumns) {
+                Some(error) => column!(input, error_area(&error.message, 13.0)).spacing(6.0),
+                None => match numeric_warning(&self.columns, metrics) {
+                    Some(warning) => column!(input, warning).spacing(6.0),
+                    None => column!(input),
+                },
+            }
+        };
+
+        let per_axis = {
+            let check = checkbox("Scale each axis separately", state.per_axis)
+                .on_toggle(RadarConfigMessage::PerAxis);
+
+            let tip = tooltip(
+                "Stretches every axis over its own range of values instead of the range of all of them. Useful when metrics are measured in different units",
+            );
+
+            row!(check, tip).spacing(15).align_y(Alignment::Center)
+        };
+
+        column!(title, label_col, metrics, per_axis)
+            .spacing(20.0)
+            .into()
+    }
+}
+
+impl<'a, Message> Component<Message> for RadarConfig<'a, Message>
+where
+    Message: Debug + Clone,
+{
+    type State = RadarConfigState;
+    type Event = RadarConfigMessage;
+
+    fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<Message> {
+        match event {
+            RadarConfigMessage::Cancel => Some(self.on_cancel.clone()),
+            RadarConfigMessage::Previous => {
+                let submit_state = if state.use_previous {
+                    match &self.previous_state {
+                        Some(prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                Some((self.on_previous)(submit_state.submit()))
+            }
+            RadarConfigMessage::TitleChanged(title) => {
+                self.update_state(state);
+                state.title = title;
+                Some(self.on_clear_error.clone())
+            }
+            RadarConfigMessage::LabelCol(input) => {
+                self.update_state(state);
+                let input = input.trim();
+
+                if input.is_empty() {
+                    state.label_col = 0;
+                } else if let Ok(col) = input.parse() {
+                    state.label_col = col;
+                }
+
+                Some(self.on_clear_error.clone())
+            }
+            RadarConfigMessage::Metrics(input) => {
+                self.update_state(state);
+                state.metric_cols_str = input;
+                Some(self.on_clear_error.clone())
+            }
+            RadarConfigMessage::PerAxis(per_axis) => {
+                self.update_state(state);
+                state.per_axis = per_axis;
+                Some(self.on_clear_error.clone())
+            }
+            RadarConfigMessage::Submit => {
+                let state = if state.use_previous {
+                    match self.previous_state {
+                        Some(ref mut prev_state) => prev_state,
+                        None => state,
+                    }
+                } else {
+                    state
+                };
+
+                if let Err(message) = validate_metrics(&state.metric_cols_str) {
+                    let error = ConfigError::field(ConfigField::MetricColumns, message);
+                    return Some((self.on_error)(error));
+                }
+
+                state.diff(self.sheet_config.clone());
+                let session = SessionTab::Radar(self.file.clone(), state.submit());
+                Some((self.on_submit)(session))
+            }
+        }
+    }
+
+    fn view(&self, state: &Self::State) -> Element<'_, Self::Event, Theme, Renderer> {
+        let config = self.radar_config(state);
+        let content = column!(config, vertical_space().height(50.0), self.actions()).spacing(10.0);
+
+        container(content).into()
+    }
+}
+
+impl<'a, Message> From<RadarConfig<'a, Message>> for Element<'a, Message>
+where
+    Message: 'a + Debug + Clone,
+{
+    fn from(value: RadarConfig<'a, Message>) -> Self {
+        component(value)
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(deprecated)]
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 
 use iced::{
     widget::{
@@ -11,40 +11,210 @@ use iced::{
 
 use modav_core::repr::sheet::utils::{HeaderLabelStrategy, HeaderTypesStrategy};
 
-use crate::utils::tooltip;
+use crate::utils::{
+    csv::{Delimiter, FilterOp, RowFilter},
+    json::{self, json, Value},
+    sampling::{RowLimit, SamplingStrategy},
+    tooltip,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SheetConfigState {
     pub trim: bool,
     pub flexible: bool,
+    /// Character separating the cells of each row
+    pub delimiter: Delimiter,
     pub header_type: HeaderTypesStrategy,
     pub header_labels: HeaderLabelStrategy,
     pub caption: Option<String>,
     pub use_previous: bool,
+    /// Maximum rows charted from the sheet, taken from the settings
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
+    /// The filter being written, added to `filters` once complete
+    pub draft: FilterDraft,
+}
+
+/// The inputs of a [`RowFilter`] not yet added
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FilterDraft {
+    pub column: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl FilterDraft {
+    /// Returns the filter written, if its column is a number
+    fn filter(&self) -> Option<RowFilter> {
+        let column = self.column.trim().parse().ok()?;
+
+        Some(RowFilter {
+            column,
+            op: self.op,
+            value: self.value.clone(),
+        })
+    }
 }
 
 impl SheetConfigState {
+    /// Adds the fields shared by every chart configuration to the session
+    /// object `config`
+    pub(super) fn extend_json(&self, mut config: Value) -> Value {
+        let filters = self
+            .filters
+            .iter()
+            .map(|filter| {
+                json!({
+                    "column": filter.column,
+                    "op": filter.op.to_string(),
+                    "value": filter.value,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let shared = json!({
+            "trim": self.trim,
+            "flexible": self.flexible,
+            "delimiter": self.delimiter.to_string(),
+            "header_types": self.header_type.to_string(),
+            "header_labels": self.header_labels.to_string(),
+            "caption": self.caption,
+            "max_rows": self.row_limit.max_rows,
+            "sampling": self.row_limit.strategy.to_string(),
+            // Seeds do not fit in a JSON number
+            "seed": self.row_limit.seed.to_string(),
+            "filters": filters,
+        });
+
+        if let (Value::Object(fields), Value::Object(shared)) = (&mut config, shared) {
+            fields.extend(shared);
+        }
+
+        config
+    }
+
+    /// Reads the fields written by [`SheetConfigState::extend_json`].
+    /// Missing or invalid fields are left as their defaults.
+    pub(super) fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        let flag = |key: &str| json.get(key).and_then(Value::as_bool);
+        let name = |key: &str| json.get(key).and_then(Value::as_str);
+
+        if let Some(trim) = flag("trim") {
+            state.trim = trim;
+        }
+        if let Some(flexible) = flag("flexible") {
+            state.flexible = flexible;
+        }
+        if let Some(delimiter) = name("delimiter").and_then(|name| {
+            Delimiter::ALL
+                .into_iter()
+                .find(|delimiter| delimiter.to_string() == name)
+        }) {
+            state.delimiter = delimiter;
+        }
+        if let Some(header_type) = name("header_types").and_then(|name| {
+            [HeaderTypesStrategy::None, HeaderTypesStrategy::Infer]
+                .into_iter()
+                .find(|strategy| strategy.to_string() == name)
+        }) {
+            state.header_type = header_type;
+        }
+        if let Some(header_labels) = name("header_labels").and_then(|name| {
+            [
+                HeaderLabelStrategy::NoLabels,
+                HeaderLabelStrategy::ReadLabels,
+            ]
+            .into_iter()
+            .find(|strategy| strategy.to_string() == name)
+        }) {
+            state.header_labels = header_labels;
+        }
+        state.caption = name("caption").map(String::from);
+
+        if let Some(max_rows) = json.get("max_rows").and_then(json::as_usize) {
+            state.row_limit.max_rows = max_rows;
+        }
+        if let Some(strategy) = name("sampling").and_then(|name| {
+            SamplingStrategy::ALL
+                .into_iter()
+                .find(|strategy| strategy.to_string() == name)
+        }) {
+            state.row_limit.strategy = strategy;
+        }
+        if let Some(seed) = name("seed").and_then(|seed| seed.parse().ok()) {
+            state.row_limit.seed = seed;
+        }
+
+        state.filters = json
+            .get("filters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|filter| {
+                let op = filter.get("op").and_then(Value::as_str)?;
+
+                Some(RowFilter {
+                    column: filter.get("column").and_then(json::as_usize)?,
+                    op: FilterOp::ALL
+                        .into_iter()
+                        .find(|kind| kind.to_string() == op)?,
+                    value: filter.get("value").and_then(Value::as_str)?.to_string(),
+                })
+            })
+            .collect();
+
+        state
+    }
+
     fn submit(&self) -> Self {
         Self {
             trim: self.trim,
             flexible: self.flexible,
+            delimiter: self.delimiter,
             header_labels: self.header_labels.clone(),
             header_type: self.header_type.clone(),
             caption: self.caption.clone(),
             use_previous: true,
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            draft: FilterDraft::default(),
         }
     }
 }
 
+/// Writes column or row indices as a sorted JSON array
+pub(super) fn indices_json(indices: &HashSet<usize>) -> Value {
+    let mut indices = indices.iter().copied().collect::<Vec<usize>>();
+    indices.sort_unstable();
+
+    json!(indices)
+}
+
+/// Reads indices written by [`indices_json`], skipping invalid entries
+pub(super) fn json_indices(value: Option<&Value>) -> HashSet<usize> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(json::as_usize)
+        .collect()
+}
+
 impl Default for SheetConfigState {
     fn default() -> Self {
         Self {
             trim: true,
             flexible: false,
+            delimiter: Delimiter::default(),
             header_labels: HeaderLabelStrategy::ReadLabels,
             header_type: HeaderTypesStrategy::Infer,
             caption: None,
             use_previous: true,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
+            draft: FilterDraft::default(),
         }
     }
 }
@@ -56,9 +226,15 @@ pub enum SheetConfigMessage {
     Submit,
     TrimToggled(bool),
     FlexibleToggled(bool),
+    DelimiterChanged(Delimiter),
     HeaderTypeChanged(HeaderTypesStrategy),
     HeaderLabelChanged(HeaderLabelStrategy),
     CaptionChange(String),
+    FilterColumn(String),
+    FilterOp(FilterOp),
+    FilterValue(String),
+    AddFilter,
+    RemoveFilter(usize),
 }
 
 pub struct SheetConfig<'a, Message> {
@@ -119,6 +295,52 @@ impl<'a, Message> SheetConfig<'a, Message> {
         actions.into()
     }
 
+    fn filters(&self, state: &SheetConfigState) -> Element<'_, SheetConfigMessage> {
+        let builder = {
+            let label = text("Filter rows:");
+
+            let column = text_input("Column", &state.draft.column)
+                .on_input(SheetConfigMessage::FilterColumn)
+                .on_submit(SheetConfigMessage::Submit)
+                .width(80.0);
+
+            let op = pick_list(
+                FilterOp::ALL,
+                Some(state.draft.op),
+                SheetConfigMessage::FilterOp,
+            )
+            .text_size(13.0);
+
+            let value = text_input("Value", &state.draft.value)
+                .on_input(SheetConfigMessage::FilterValue)
+                .on_submit(SheetConfigMessage::Submit)
+                .width(120.0);
+
+            let add = button(text("Add").size(13.0))
+                .on_press_maybe(state.draft.filter().map(|_| SheetConfigMessage::AddFilter));
+
+            let tip =
+                tooltip("Chart only the rows matching every filter. Columns are numbered from 0");
+
+            row!(label, column, op, value, add, tip)
+                .spacing(8)
+                .align_y(Alignment::Center)
+        };
+
+        let filters = state.filters.iter().enumerate().map(|(idx, filter)| {
+            let remove = button(text("Remove").size(12.0))
+                .on_press(SheetConfigMessage::RemoveFilter(idx))
+                .padding([2, 6]);
+
+            row!(text(filter.to_string()).size(13.0), remove)
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into()
+        });
+
+        column!(builder).extend(filters).spacing(8).into()
+    }
+
     fn sheet_config(&self, state: &SheetConfigState) -> Element<'_, SheetConfigMessage> {
         let state = if state.use_previous {
             match &self.previous_state {
@@ -146,6 +368,21 @@ impl<'a, Message> SheetConfig<'a, Message> {
             row!(check, tip).spacing(25.0)
         };
 
+        let delimiter = {
+            let label = text("Delimiter:");
+
+            let list = pick_list(
+                Delimiter::ALL,
+                Some(state.delimiter),
+                SheetConfigMessage::DelimiterChanged,
+            )
+            .text_size(13.0);
+
+            let tip = tooltip("The character separating cells. Auto uses tabs for .tsv files");
+
+            row!(label, list, tip).spacing(8).align_y(Alignment::Center)
+        };
+
         let header_types = {
             let label = text("Column Types:");
 
@@ -191,12 +428,21 @@ impl<'a, Message> SheetConfig<'a, Message> {
                 .map(|s| s.as_str())
                 .unwrap_or_default(),
         )
-        .on_input(SheetConfigMessage::CaptionChange);
-
-        column!(trim, flexible, header_labels, header_types, caption)
-            .align_x(Alignment::Start)
-            .spacing(30.0)
-            .into()
+        .on_input(SheetConfigMessage::CaptionChange)
+        .on_submit(SheetConfigMessage::Submit);
+
+        column!(
+            trim,
+            flexible,
+            delimiter,
+            header_labels,
+            header_types,
+            self.filters(state),
+            caption
+        )
+        .align_x(Alignment::Start)
+        .spacing(30.0)
+        .into()
     }
 }
 
@@ -242,6 +488,11 @@ where
                 state.flexible = flexible;
                 Some(self.on_clear_error.clone())
             }
+            SheetConfigMessage::DelimiterChanged(delimiter) => {
+                self.update_state(state);
+                state.delimiter = delimiter;
+                Some(self.on_clear_error.clone())
+            }
             SheetConfigMessage::HeaderTypeChanged(ht) => {
                 self.update_state(state);
                 state.header_type = ht;
@@ -262,6 +513,36 @@ where
 
                 Some(self.on_clear_error.clone())
             }
+            SheetConfigMessage::FilterColumn(column) => {
+                self.update_state(state);
+                state.draft.column = column;
+                None
+            }
+            SheetConfigMessage::FilterOp(op) => {
+                self.update_state(state);
+                state.draft.op = op;
+                None
+            }
+            SheetConfigMessage::FilterValue(value) => {
+                self.update_state(state);
+                state.draft.value = value;
+                None
+            }
+            SheetConfigMessage::AddFilter => {
+                self.update_state(state);
+                if let Some(filter) = state.draft.filter() {
+                    state.filters.push(filter);
+                    state.draft = FilterDraft::default();
+                }
+                Some(self.on_clear_error.clone())
+            }
+            SheetConfigMessage::RemoveFilter(idx) => {
+                self.update_state(state);
+                if idx < state.filters.len() {
+                    state.filters.remove(idx);
+                }
+                Some(self.on_clear_error.clone())
+            }
         }
     }
 
@@ -4,16 +4,22 @@ use std::{
     path::PathBuf,
 };
 
-use super::sheet::SheetConfigState;
+use super::{error_area, numeric_warning, sheet::SheetConfigState, ConfigError, ConfigField};
 
 use crate::{
-    utils::{tooltip, AppError},
-    views::{StackedBarChartTabData, View},
+    utils::{
+        csv::{ColumnType, Delimiter, RowFilter},
+        json::{self, json, Value},
+        parse_ints,
+        sampling::RowLimit,
+        tooltip, Selection,
+    },
+    views::SessionTab,
 };
 use iced::{
     widget::{
-        button, checkbox, column, component, container, horizontal_space, pick_list, row, text,
-        text_input, vertical_space, Component, Space,
+        button, checkbox, column, component, container, horizontal_space, pick_list, row,
+        scrollable, text, text_input, vertical_space, Column, Component, Space,
     },
     Alignment, Element, Renderer, Theme,
 };
@@ -62,9 +68,11 @@ pub(super) enum StackedBarChartConfigMessage {
     TitleChanged(String),
     XCol(String),
     YCol(String),
+    ToggleColumn(usize, bool),
     AxisLabel(AxisStrategy),
     Order(bool),
     Horizontal(bool),
+    Percent(bool),
     XLabelChanged(String),
     YLabelChanged(String),
 }
@@ -77,12 +85,18 @@ pub struct StackedBarChartConfigState {
     pub is_horizontal: bool,
     pub use_previous: bool,
     pub order: bool,
+    /// Stretches every bar to 100%, so segments show their share of it
+    pub percent: bool,
     pub axis_label: StackedBarChartAxisLabelStrategy,
     pub trim: bool,
     pub flexible: bool,
+    pub delimiter: Delimiter,
     pub header_types: HeaderTypesStrategy,
     pub header_labels: HeaderLabelStrategy,
     pub caption: Option<String>,
+    pub row_limit: RowLimit,
+    /// Only rows matching every filter are charted
+    pub filters: Vec<RowFilter>,
 }
 
 impl StackedBarChartConfigState {
@@ -90,20 +104,120 @@ impl StackedBarChartConfigState {
         let SheetConfigState {
             trim,
             flexible,
+            delimiter,
             header_type,
             header_labels,
             caption,
+            row_limit,
+            filters,
             ..
         } = sheet_config;
 
         self.trim = trim;
         self.flexible = flexible;
+        self.delimiter = delimiter;
         self.header_labels = header_labels;
         self.header_types = header_type;
         self.caption = caption;
+        self.row_limit = row_limit;
+        self.filters = filters;
+    }
+
+    /// The sheet settings the chart was configured with
+    pub(super) fn sheet_config(&self) -> SheetConfigState {
+        SheetConfigState {
+            trim: self.trim,
+            flexible: self.flexible,
+            delimiter: self.delimiter,
+            header_type: self.header_types.clone(),
+            header_labels: self.header_labels.clone(),
+            caption: self.caption.clone(),
+            row_limit: self.row_limit,
+            filters: self.filters.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Serializes self so the chart can be reopened in a later session
+    pub fn to_json(&self) -> Value {
+        let sheet = self.sheet_config();
+
+        let axis_label = match &self.axis_label {
+            StackedBarChartAxisLabelStrategy::None => json!({ "kind": "none" }),
+            StackedBarChartAxisLabelStrategy::Header(y) => json!({
+                "kind": "header",
+                "y": y,
+            }),
+            StackedBarChartAxisLabelStrategy::Provided { x, y } => json!({
+                "kind": "provided",
+                "x": x,
+                "y": y,
+            }),
+        };
+
+        sheet.extend_json(json!({
+            "title": self.title,
+            "x_col": self.x_col,
+            "stack_columns": self.acc_cols_str,
+            "axis_label": axis_label,
+            "order": self.order,
+            "horizontal": self.is_horizontal,
+            "percent": self.percent,
+        }))
+    }
+
+    /// Reads a configuration written by
+    /// [`StackedBarChartConfigState::to_json`]. Missing or invalid fields
+    /// are left as their defaults.
+    pub fn from_json(json: &Value) -> Self {
+        let mut state = Self::default();
+        state.diff(SheetConfigState::from_json(json));
+
+        let string = |key: &str| json.get(key).and_then(Value::as_str).map(String::from);
+        let flag = |key: &str| json.get(key).and_then(Value::as_bool).unwrap_or_default();
+
+        if let Some(title) = string("title") {
+            state.title = title;
+        }
+        if let Some(x_col) = json.get("x_col").and_then(json::as_usize) {
+            state.x_col = x_col;
+        }
+        if let Some(columns) = string("stack_columns") {
+            state.acc_cols_str = columns;
+        }
+
+        if let Some(axis_label) = json.get("axis_label") {
+            let label = |key: &str| {
+                axis_label
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(String::from)
+                    .unwrap_or_default()
+            };
+
+            match axis_label.get("kind").and_then(Value::as_str) {
+                Some("none") => state.axis_label = StackedBarChartAxisLabelStrategy::None,
+                Some("header") => {
+                    state.axis_label = StackedBarChartAxisLabelStrategy::Header(label("y"))
+                }
+                Some("provided") => {
+                    state.axis_label = StackedBarChartAxisLabelStrategy::Provided {
+                        x: label("x"),
+                        y: label("y"),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        state.order = flag("order");
+        state.is_horizontal = flag("horizontal");
+        state.percent = flag("percent");
+
+        state
     }
 
-    fn submit(&self) -> Self {
+    pub(super) fn submit(&self) -> Self {
         Self {
             use_previous: true,
             ..self.clone()
@@ -111,6 +225,52 @@ impl StackedBarChartConfigState {
     }
 }
 
+/// Checks that `input` lists columns or ranges of columns to stack
+pub(super) fn validate_columns(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Err(String::from(
+            "Enter the columns to stack, such as `1,2` or `1:4`",
+        ));
+    }
+
+    for part in input.trim().split(',').map(str::trim) {
+        match Selection::from_str(part) {
+            Selection::None => {
+                return Err(format!("`{part}` is not a column or a range of columns"))
+            }
+            Selection::Range { x, y } if x >= y => {
+                return Err(format!(
+                    "`{part}` has no columns. A range `x:y` covers x up to but not including y"
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds or removes `column` from the stacked columns in `input`, resolving
+/// any ranges into single indices. `last` is the index of the file's last
+/// column.
+fn toggle_column(input: &str, column: usize, on: bool, last: usize) -> String {
+    let mut columns = Selection::to_vec(parse_ints(input), last);
+    columns.retain(|col| *col != column);
+
+    if on {
+        columns.push(column);
+    }
+
+    columns.sort_unstable();
+    columns.dedup();
+
+    columns
+        .into_iter()
+        .map(|col| col.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 impl Default for StackedBarChartConfigState {
     fn default() -> Self {
         Self {
@@ -120,12 +280,16 @@ impl Default for StackedBarChartConfigState {
             is_horizontal: false,
             use_previous: true,
             order: false,
+            percent: false,
             axis_label: StackedBarChartAxisLabelStrategy::default(),
             trim: true,
             flexible: false,
+            delimiter: Delimiter::default(),
             header_labels: HeaderLabelStrategy::ReadLabels,
             header_types: HeaderTypesStrategy::Infer,
             caption: None,
+            row_limit: RowLimit::default(),
+            filters: Vec::default(),
         }
     }
 }
@@ -133,12 +297,16 @@ impl Default for StackedBarChartConfigState {
 pub(super) struct StackedBarChartConfig<'a, Message> {
     file: &'a PathBuf,
     sheet_config: SheetConfigState,
-    on_submit: Box<dyn Fn(View) -> Message + 'a>,
-    on_error: Box<dyn Fn(AppError) -> Message + 'a>,
+    on_submit: Box<dyn Fn(SessionTab) -> Message + 'a>,
+    on_error: Box<dyn Fn(ConfigError) -> Message + 'a>,
     on_previous: Box<dyn Fn(StackedBarChartConfigState) -> Message + 'a>,
     on_cancel: Message,
     on_clear_error: Message,
     previous_state: Option<StackedBarChartConfigState>,
+    /// Names and inferred types of the file's columns
+    columns: Vec<(String, ColumnType)>,
+    error: Option<ConfigError>,
+    on_change: Option<Box<dyn Fn(StackedBarChartConfigState) -> Message + 'a>>,
 }
 
 impl<'a, Message> StackedBarChartConfig<'a, Message> {
@@ -152,8 +320,8 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
         on_clear_error: Message,
     ) -> Self
     where
-        S: 'a + Fn(View) -> Message,
-        E: 'a + Fn(AppError) -> Message,
+        S: 'a + Fn(SessionTab) -> Message,
+        E: 'a + Fn(ConfigError) -> Message,
         P: 'a + Fn(StackedBarChartConfigState) -> Message,
     {
         Self {
@@ -164,7 +332,10 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
             on_previous: Box::new(on_previous),
             on_cancel,
             previous_state: None,
+            columns: Vec::new(),
             on_clear_error,
+            error: None,
+            on_change: None,
         }
     }
 
@@ -173,6 +344,27 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
         self
     }
 
+    pub fn columns(mut self, columns: Vec<(String, ColumnType)>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sends the configuration, as it would be submitted, whenever a field
+    /// changes. Without it, changes only clear the error.
+    pub fn on_change<F>(mut self, on_change: F) -> Self
+    where
+        F: 'a + Fn(StackedBarChartConfigState) -> Message,
+    {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    /// Sets the wizard's current error, shown beside its field if it has one
+    pub fn error(mut self, error: Option<ConfigError>) -> Self {
+        self.error = error;
+        self
+    }
+
     fn actions(&self) -> Element<'_, StackedBarChartConfigMessage> {
         let cancel_btn =
             button(text("Cancel").size(13.0)).on_press(StackedBarChartConfigMessage::Cancel);
@@ -205,7 +397,8 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
         };
 
         let title = text_input("Graph Title", state.title.as_str())
-            .on_input(StackedBarChartConfigMessage::TitleChanged);
+            .on_input(StackedBarChartConfigMessage::TitleChanged)
+            .on_submit(StackedBarChartConfigMessage::Submit);
 
         let x_col = {
             let label = text("X column: ");
@@ -215,6 +408,7 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
 
                 text_input("", &value)
                     .on_input(StackedBarChartConfigMessage::XCol)
+                    .on_submit(StackedBarChartConfigMessage::Submit)
                     .width(50)
             };
 
@@ -231,14 +425,47 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
             let input = {
                 text_input("", &state.acc_cols_str)
                     .on_input(StackedBarChartConfigMessage::YCol)
+                    .on_submit(StackedBarChartConfigMessage::Submit)
                     .width(100)
             };
 
-            let tip = tooltip("Columns to use for the stack, separated by `,`. You can also use `:` to denote a range of columns");
+            let tip = tooltip("Columns to use for the stack, picked from the list below or typed, separated by `,`. You can also use `:` to denote a range of columns");
 
-            row!(label, input, tip)
+            let input = row!(label, input, tip)
                 .spacing(15)
-                .align_y(Alignment::Center)
+                .align_y(Alignment::Center);
+
+            let stacked = Selection::to_vec(
+                parse_ints(&state.acc_cols_str),
+                self.columns.len().saturating_sub(1),
+            );
+
+            let checklist = self
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != state.x_col)
+                .fold(Column::new().spacing(4.0), |list, (idx, (name, _))| {
+                    list.push(
+                        checkbox(name.as_str(), stacked.contains(&idx))
+                            .on_toggle(move |on| {
+                                StackedBarChartConfigMessage::ToggleColumn(idx, on)
+                            })
+                            .text_size(13.0)
+                            .size(14.0),
+                    )
+                });
+
+            let input =
+                column!(input, container(scrollable(checklist)).max_height(120.0)).spacing(8.0);
+
+            match ConfigError::at(self.error.as_ref(), ConfigField::StackColumns) {
+                Some(error) => column!(input, error_area(&error.message, 13.0)).spacing(6.0),
+                None => match numeric_warning(&self.columns, stacked) {
+                    Some(warning) => column!(input, warning).spacing(6.0),
+                    None => column!(input),
+                },
+            }
         };
 
         let axis_label = {
@@ -266,16 +493,19 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
             let extra: Element<'_, StackedBarChartConfigMessage> = match &state.axis_label {
                 StackedBarChartAxisLabelStrategy::Provided { x, y } => {
                     let x_label = text_input("X axis label", x.as_str())
-                        .on_input(StackedBarChartConfigMessage::XLabelChanged);
+                        .on_input(StackedBarChartConfigMessage::XLabelChanged)
+                        .on_submit(StackedBarChartConfigMessage::Submit);
 
                     let y_label = text_input("Y axis label", y.as_str())
-                        .on_input(StackedBarChartConfigMessage::YLabelChanged);
+                        .on_input(StackedBarChartConfigMessage::YLabelChanged)
+                        .on_submit(StackedBarChartConfigMessage::Submit);
 
                     column!(x_label, y_label).spacing(10.0).into()
                 }
                 StackedBarChartAxisLabelStrategy::Header(y) => {
                     let y_label = text_input("Y axis label", y.as_str())
-                        .on_input(StackedBarChartConfigMessage::YLabelChanged);
+                        .on_input(StackedBarChartConfigMessage::YLabelChanged)
+                        .on_submit(StackedBarChartConfigMessage::Submit);
                     y_label.into()
                 }
                 StackedBarChartAxisLabelStrategy::None => Space::new(0.0, 0.0).into(),
@@ -302,11 +532,34 @@ impl<'a, Message> StackedBarChartConfig<'a, Message> {
             row!(check, tip).spacing(25.0)
         };
 
-        column!(title, x_col, y_col, axis_label, order, horizontal)
+        let percent = {
+            let check = checkbox("100% stacked?", state.percent)
+                .on_toggle(StackedBarChartConfigMessage::Percent);
+
+            let tip = tooltip("Stretch every bar to 100% so each segment shows its share of it");
+
+            row!(check, tip).spacing(25.0)
+        };
+
+        column!(title, x_col, y_col, axis_label, order, horizontal, percent)
             .spacing(20.0)
             .into()
     }
 
+    fn changed(&self, state: &StackedBarChartConfigState) -> Message
+    where
+        Message: Clone,
+    {
+        match &self.on_change {
+            Some(on_change) => {
+                let mut config = state.submit();
+                config.diff(self.sheet_config.clone());
+                (on_change)(config)
+            }
+            None => self.on_clear_error.clone(),
+        }
+    }
+
     fn update_state(&self, state: &mut StackedBarChartConfigState) {
         if state.use_previous {
             if let Some(prevous_state) = self.previous_state.clone() {
@@ -351,17 +604,14 @@ where
                     state
                 };
 
-                state.diff(self.sheet_config.clone());
-                let data = StackedBarChartTabData::new(self.file.clone(), state.submit());
-                match data {
-                    Err(error) => {
-                        return Some((self.on_error)(error));
-                    }
-                    Ok(data) => {
-                        let view = View::StackedBarChart(data);
-                        return Some((self.on_submit)(view));
-                    }
+                if let Err(message) = validate_columns(&state.acc_cols_str) {
+                    let error = ConfigError::field(ConfigField::StackColumns, message);
+                    return Some((self.on_error)(error));
                 }
+
+                state.diff(self.sheet_config.clone());
+                let session = SessionTab::StackedBarChart(self.file.clone(), state.submit());
+                return Some((self.on_submit)(session));
             }
             StackedBarChartConfigMessage::TitleChanged(title) => {
                 self.update_state(state);
@@ -431,6 +681,12 @@ where
 
                 state.acc_cols_str = input;
             }
+            StackedBarChartConfigMessage::ToggleColumn(column, on) => {
+                self.update_state(state);
+
+                let last = self.columns.len().saturating_sub(1);
+                state.acc_cols_str = toggle_column(&state.acc_cols_str, column, on, last);
+            }
             StackedBarChartConfigMessage::Order(order) => {
                 self.update_state(state);
                 state.order = order;
@@ -439,8 +695,12 @@ where
                 self.update_state(state);
                 state.is_horizontal = is_horizontal;
             }
+            StackedBarChartConfigMessage::Percent(percent) => {
+                self.update_state(state);
+                state.percent = percent;
+            }
         };
-        Some(self.on_clear_error.clone())
+        Some(self.changed(state))
     }
 
     fn view(&self, state: &Self::State) -> Element<'_, Self::Event, Theme, Renderer> {
@@ -460,3 +720,25 @@ where
         component(value)
     }
 }
+
+#[test]
+fn test_validate_columns() {
+    assert!(validate_columns("1,2").is_ok());
+    assert!(validate_columns(" 1:4 , 6 ").is_ok());
+    assert!(validate_columns("2:").is_ok());
+
+    assert!(validate_columns("").is_err());
+    assert!(validate_columns("1,,2").is_err());
+    assert!(validate_columns("a").is_err());
+    assert!(validate_columns("4:2").is_err());
+}
+
+#[test]
+fn test_toggle_column() {
+    assert_eq!(toggle_column("", 2, true, 5), "2");
+    assert_eq!(toggle_column("3,1", 2, true, 5), "1,2,3");
+    assert_eq!(toggle_column("1:4", 2, false, 5), "1,3");
+    assert_eq!(toggle_column("4:", 1, true, 5), "1,4,5");
+    assert_eq!(toggle_column("2", 2, false, 5), "");
+    assert_eq!(toggle_column("2", 2, true, 5), "2");
+}